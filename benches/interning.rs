@@ -0,0 +1,84 @@
+//! Compares the memory used by a large model with and without interning its
+//! type and field names.
+//!
+//! Run with `cargo bench --bench interning`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use simple_codegen::{Field, Interner, Scope, Struct, Type};
+
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const STRUCTS: usize = 10_000;
+
+const FIELDS: [(&str, &str); 6] = [
+    ("id", "u64"),
+    ("name", "String"),
+    ("created_at", "chrono::DateTime<chrono::Utc>"),
+    ("updated_at", "chrono::DateTime<chrono::Utc>"),
+    ("owner_id", "u64"),
+    ("tags", "Vec<String>"),
+];
+
+fn build(mut field: impl FnMut(&str, &str) -> Field) -> Scope {
+    let mut scope = Scope::new();
+
+    for i in 0..STRUCTS {
+        let mut s = Struct::new(format!("Model{}", i));
+        for (name, ty) in FIELDS {
+            s.push_named_field(field(name, ty));
+        }
+        scope.push_struct(s);
+    }
+
+    scope
+}
+
+fn measure(label: &str, f: impl FnOnce() -> Scope) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let scope = f();
+    let elapsed = start.elapsed();
+    let used = ALLOCATED.load(Ordering::Relaxed) - before;
+
+    let start = Instant::now();
+    let rendered = scope.to_string();
+    let render = start.elapsed();
+
+    println!(
+        "{:<10} {:>8} KiB  build {:>8.2?}  render {:>8.2?}  ({} bytes of code)",
+        label,
+        used / 1024,
+        elapsed,
+        render,
+        rendered.len()
+    );
+}
+
+fn main() {
+    measure("owned", || build(|name, ty| Field::new(name, ty)));
+
+    measure("interned", || {
+        let mut interner = Interner::new();
+        build(|name, ty| Field::new(interner.intern(name), Type::new(interner.intern(ty))))
+    });
+}
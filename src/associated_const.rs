@@ -1,11 +1,14 @@
 use crate::generic_parameter::GenericParameter;
+use crate::prelude::*;
+use crate::r#type::Type;
 use crate::visibility::Vis;
 
 /// Defines an [associated constant](https://doc.rust-lang.org/reference/items/associated-items.html#associated-constants).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssociatedConst {
     name: String,
-    ty: String,
+    ty: Type,
     generics: Vec<GenericParameter>,
     concrete_vis: Vis,
     concrete_value: Option<String>,
@@ -13,7 +16,7 @@ pub struct AssociatedConst {
 
 impl AssociatedConst {
     /// Creates a new associated const.
-    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+    pub fn new(name: impl Into<String>, ty: impl Into<Type>) -> Self {
         Self {
             name: name.into(),
             ty: ty.into(),
@@ -46,24 +49,24 @@ impl AssociatedConst {
     }
 
     /// Gets the type for this associated const.
-    pub fn ty(&self) -> &str {
+    pub fn ty(&self) -> &Type {
         &self.ty
     }
 
     /// Sets the type for this associated const.
-    pub fn set_ty(&mut self, ty: impl Into<String>) -> &Self {
+    pub fn set_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
         self.ty = ty.into();
         self
     }
 
     /// Sets the type for this associated const.
-    pub fn with_ty(&mut self, ty: impl Into<String>) -> &Self {
+    pub fn with_ty(mut self, ty: impl Into<Type>) -> Self {
         self.set_ty(ty);
         self
     }
 
     /// Gets a mutable reference to the type of the associated const.
-    pub fn ty_mut(&mut self) -> &mut String {
+    pub fn ty_mut(&mut self) -> &mut Type {
         &mut self.ty
     }
 
@@ -82,7 +85,7 @@ impl AssociatedConst {
     }
 
     /// Sets the generics for the associated const.
-    pub fn with_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
+    pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
     {
@@ -118,11 +121,11 @@ impl AssociatedConst {
     }
 
     /// Gets the concrete value of the associated const, if any.
-    pub fn concrete_value(&self) -> Option<&String> {
-        self.concrete_value.as_ref()
+    pub fn concrete_value(&self) -> Option<&str> {
+        self.concrete_value.as_deref()
     }
 
-    /// Gets the concrete value for this associated const.
+    /// Sets the concrete value for this associated const.
     pub fn set_concrete_value(&mut self, value: impl Into<String>) -> &mut Self {
         self.concrete_value = Some(value.into());
         self
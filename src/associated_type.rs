@@ -1,12 +1,15 @@
 use crate::bound::Bound;
+use crate::prelude::*;
+use crate::r#type::Type;
 
 /// Defines an associated type.
 ///
 /// https://doc.rust-lang.org/rust-by-example/generics/assoc_items/types.html
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssociatedType {
     ty: Bound,
-    concrete_ty: Option<(String, Vec<String>)>,
+    concrete_ty: Option<Type>,
 }
 
 impl AssociatedType {
@@ -30,10 +33,10 @@ impl AssociatedType {
     }
 
     /// Creates a new associated type with the provided name and concrete type.
-    pub fn new_with_concrete_ty(name: impl Into<String>, concrete_ty: impl Into<String>) -> Self {
+    pub fn new_with_concrete_ty(name: impl Into<String>, concrete_ty: impl Into<Type>) -> Self {
         Self {
             ty: Bound::new(name, Vec::<String>::new()),
-            concrete_ty: Some((concrete_ty.into(), Vec::<String>::new())),
+            concrete_ty: Some(concrete_ty.into()),
         }
     }
 
@@ -74,7 +77,7 @@ impl AssociatedType {
     }
 
     /// Sets the associated type's bounds.
-    pub fn with_trait_bounds<S>(&mut self, traits: impl IntoIterator<Item = S>) -> &mut Self
+    pub fn with_trait_bounds<S>(mut self, traits: impl IntoIterator<Item = S>) -> Self
     where
         S: Into<String>,
     {
@@ -100,38 +103,24 @@ impl AssociatedType {
     }
 
     /// Gets the concrete type associated with this associated type, if any.
-    pub fn concrete_ty(&self) -> Option<&(String, Vec<String>)> {
+    pub fn concrete_ty(&self) -> Option<&Type> {
         self.concrete_ty.as_ref()
     }
 
     /// Sets the concrete type for this associated type.
-    pub fn set_concrete_ty<S>(
-        &mut self,
-        name: impl Into<String>,
-        generics: impl IntoIterator<Item = S>,
-    ) -> &mut Self
-    where
-        S: Into<String>,
-    {
-        self.concrete_ty = Some((name.into(), generics.into_iter().map(Into::into).collect()));
+    pub fn set_concrete_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
+        self.concrete_ty = Some(ty.into());
         self
     }
 
     /// Sets the concrete type for this associated type.
-    pub fn with_concrete_ty<S>(
-        mut self,
-        name: impl Into<String>,
-        generics: impl IntoIterator<Item = S>,
-    ) -> Self
-    where
-        S: Into<String>,
-    {
-        self.set_concrete_ty(name, generics);
+    pub fn with_concrete_ty(mut self, ty: impl Into<Type>) -> Self {
+        self.set_concrete_ty(ty);
         self
     }
 
     /// Gets a mutable reference to the concrete type for this associated type.
-    pub fn concrete_ty_mut(&mut self) -> Option<&mut (String, Vec<String>)> {
+    pub fn concrete_ty_mut(&mut self) -> Option<&mut Type> {
         self.concrete_ty.as_mut()
     }
 }
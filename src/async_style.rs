@@ -0,0 +1,106 @@
+use crate::body::Body;
+use crate::formatter::Formatter;
+use crate::function::{Function, SelfArg};
+use crate::prelude::*;
+use crate::r#type::Type;
+
+/// Defines how the async functions of a trait, and of the impl blocks
+/// implementing it, are emitted.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AsyncTraitStyle {
+    /// Native `async fn`, which needs Rust 1.75 or later.
+    #[default]
+    Native,
+    /// `fn f() -> impl Future<Output = T> + Send`, with bodies wrapped in an
+    /// `async move` block. Like native `async fn`, this needs Rust 1.75 or
+    /// later, but lets callers rely on the future being `Send`.
+    ImplFuture,
+    /// `fn f() -> Pin<Box<dyn Future<Output = T> + Send + '_>>`, with bodies
+    /// wrapped in `Box::pin(async move { ... })`, for older toolchains.
+    BoxFuture,
+    /// `async fn` in a trait or impl block annotated with
+    /// `#[async_trait::async_trait]`.
+    AsyncTrait,
+}
+
+impl AsyncTraitStyle {
+    /// The attribute put on traits and trait impls by this style, if any.
+    pub(crate) fn attribute(&self) -> Option<&'static str> {
+        match self {
+            AsyncTraitStyle::AsyncTrait => Some("#[async_trait::async_trait]"),
+            _ => None,
+        }
+    }
+}
+
+impl Function {
+    /// Returns the function as emitted in a trait or trait impl with the
+    /// given async style.
+    ///
+    /// Functions that are not async, and styles keeping `async fn`, return
+    /// the function unchanged. Otherwise the function is no longer `async`,
+    /// returns a future of its return type and its body, if any, is wrapped
+    /// to produce that future.
+    pub fn desugar_async(&self, style: AsyncTraitStyle) -> Function {
+        let mut dst = String::new();
+        self.desugar_async_with(style, &Formatter::new(&mut dst))
+    }
+
+    /// Desugars the function as [`desugar_async`](Function::desugar_async)
+    /// does, rendering the return type and body with the settings of `fmt`.
+    pub(crate) fn desugar_async_with(
+        &self,
+        style: AsyncTraitStyle,
+        fmt: &Formatter<'_>,
+    ) -> Function {
+        let mut ret = self.clone();
+        if !self.is_async()
+            || matches!(style, AsyncTraitStyle::Native | AsyncTraitStyle::AsyncTrait)
+        {
+            return ret;
+        }
+
+        let output = self
+            .ret()
+            .map_or_else(|| "()".to_string(), |ty| fmt.render_type(ty));
+        let future = format!("std::future::Future<Output = {}>", output);
+        ret.set_async(false);
+
+        let (open, close) = if style == AsyncTraitStyle::ImplFuture {
+            ret.set_ret(Type::impl_trait([future.as_str(), "Send"]));
+            ("async move {", "}")
+        } else {
+            let borrows = matches!(
+                self.self_arg(),
+                SelfArg::WithSelfRef
+                    | SelfArg::WithMutSelfRef
+                    | SelfArg::WithSelfRefLifetime(_)
+                    | SelfArg::WithMutSelfRefLifetime(_)
+            );
+            let bounds = if borrows { " + Send + '_" } else { " + Send" };
+            ret.set_ret(
+                Type::new("std::pin::Pin").with_generic(format!("Box<dyn {}{}>", future, bounds)),
+            );
+            ("Box::pin(async move {", "})")
+        };
+
+        if !self.body().is_empty() {
+            let mut body = String::new();
+            let mut body_fmt = fmt.nested(&mut body);
+            for b in self.body() {
+                b.fmt(&mut body_fmt).unwrap();
+            }
+
+            let mut lines = vec![Body::String(open.to_string())];
+            lines.extend(body.lines().map(|line| match line {
+                "" => Body::String(String::new()),
+                line => Body::String(format!("    {}", line)),
+            }));
+            lines.push(Body::String(close.to_string()));
+            ret.set_body(lines);
+        }
+
+        ret
+    }
+}
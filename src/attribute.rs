@@ -0,0 +1,222 @@
+use core::fmt;
+
+use crate::prelude::*;
+
+/// Defines an [attribute](https://doc.rust-lang.org/reference/attributes.html),
+/// without the surrounding `#[...]`.
+///
+/// Attributes convert from strings such as `"allow(dead_code)"` or
+/// `"path = \"foo.rs\""`, which are split into their path and arguments, so
+/// every `push_attribute` method keeps accepting plain strings. The
+/// surrounding `#[...]` may be written too, e.g. `"#[serde(default)]"`, and
+/// several attributes written on separate lines, e.g.
+/// `"#[serde(default)]\n#[serde(skip)]"`, are pushed one by one.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attribute {
+    /// Attribute path, e.g. `serde` or `rustfmt::skip`
+    path: String,
+
+    /// Attribute arguments
+    args: AttributeArgs,
+}
+
+/// Defines the arguments of an [`Attribute`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttributeArgs {
+    /// No arguments, e.g. `#[test]`.
+    Empty,
+    /// Delimited tokens, e.g. `#[allow(dead_code)]`.
+    List(String),
+    /// A value, e.g. `#[path = "foo.rs"]`.
+    Value(String),
+}
+
+impl Attribute {
+    /// Creates an attribute without arguments, e.g. `#[test]`.
+    pub fn new(path: impl Into<String>) -> Self {
+        Attribute {
+            path: path.into(),
+            args: AttributeArgs::Empty,
+        }
+    }
+
+    /// Creates an attribute with delimited arguments, e.g.
+    /// `#[allow(dead_code)]`.
+    pub fn list(path: impl Into<String>, args: impl Into<String>) -> Self {
+        Attribute {
+            path: path.into(),
+            args: AttributeArgs::List(args.into()),
+        }
+    }
+
+    /// Creates an attribute with a value, e.g. `#[path = "foo.rs"]`.
+    ///
+    /// The value is used verbatim, so string values must be quoted.
+    pub fn value(path: impl Into<String>, value: impl Into<String>) -> Self {
+        Attribute {
+            path: path.into(),
+            args: AttributeArgs::Value(value.into()),
+        }
+    }
+
+    /// Creates a derive helper attribute setting a string option, e.g.
+    /// `#[serde(rename_all = "camelCase")]`.
+    pub fn derive_helper(
+        helper: impl Into<String>,
+        key: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> Self {
+        Attribute::list(helper, format!("{} = {:?}", key.as_ref(), value.as_ref()))
+    }
+
+    /// Gets the path of the attribute.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Sets the path of the attribute.
+    pub fn set_path(&mut self, path: impl Into<String>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the path of the attribute.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Gets a mutable reference to the path of the attribute.
+    pub fn path_mut(&mut self) -> &mut String {
+        &mut self.path
+    }
+
+    /// Gets the arguments of the attribute.
+    pub fn args(&self) -> &AttributeArgs {
+        &self.args
+    }
+
+    /// Sets the arguments of the attribute.
+    pub fn set_args(&mut self, args: AttributeArgs) -> &mut Self {
+        self.args = args;
+        self
+    }
+
+    /// Sets the arguments of the attribute.
+    pub fn with_args(mut self, args: AttributeArgs) -> Self {
+        self.set_args(args);
+        self
+    }
+
+    /// Gets a mutable reference to the arguments of the attribute.
+    pub fn args_mut(&mut self) -> &mut AttributeArgs {
+        &mut self.args
+    }
+
+    /// Parses an attribute from its source, with or without the surrounding
+    /// `#[...]`.
+    ///
+    /// Sources that are neither a path, a path followed by delimited tokens,
+    /// nor a path followed by `= value` are kept verbatim as the path.
+    fn parse(src: String) -> Self {
+        if let Some(inner) = strip_outer(src.trim()) {
+            return Attribute::parse(inner.to_string());
+        }
+
+        let src_trim = src.trim();
+        let end = src_trim
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+            .unwrap_or(src_trim.len());
+        let (path, rest) = src_trim.split_at(end);
+        let rest = rest.trim_start();
+
+        let args = if rest.is_empty() {
+            AttributeArgs::Empty
+        } else if let Some(value) = rest.strip_prefix('=') {
+            AttributeArgs::Value(value.trim().to_string())
+        } else if let Some(list) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+            AttributeArgs::List(list.to_string())
+        } else {
+            return Attribute::new(src);
+        };
+
+        if path.is_empty() {
+            return Attribute::new(src);
+        }
+
+        Attribute {
+            path: path.to_string(),
+            args,
+        }
+    }
+}
+
+impl<S: Into<String>> From<S> for Attribute {
+    fn from(src: S) -> Self {
+        Attribute::parse(src.into())
+    }
+}
+
+impl PartialEq<str> for Attribute {
+    fn eq(&self, other: &str) -> bool {
+        *self == Attribute::parse(other.to_string())
+    }
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)?;
+        match &self.args {
+            AttributeArgs::Empty => Ok(()),
+            AttributeArgs::List(args) => write!(f, "({})", args),
+            AttributeArgs::Value(value) => write!(f, " = {}", value),
+        }
+    }
+}
+
+/// Pushes an attribute unless an equal one is already present, keeping the
+/// order in which the attributes were first pushed.
+///
+/// Source holding several attributes on separate lines, which is kept
+/// verbatim as the path, is split into one attribute per line.
+pub(crate) fn push_unique(attributes: &mut Vec<Attribute>, attribute: Attribute) {
+    let split = match attribute.args {
+        AttributeArgs::Empty if attribute.path.contains('\n') => attribute
+            .path
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| Attribute::parse(line.to_string()))
+            .collect(),
+        _ => vec![attribute],
+    };
+
+    for attribute in split {
+        if !attributes.contains(&attribute) {
+            attributes.push(attribute);
+        }
+    }
+}
+
+/// Strips the `#[...]` around a single attribute written as source.
+fn strip_outer(src: &str) -> Option<&str> {
+    let inner = src.strip_prefix("#[")?.strip_suffix(']')?;
+
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in inner.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '[' if !quoted => depth += 1,
+            ']' if !quoted => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+
+    Some(inner)
+}
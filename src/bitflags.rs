@@ -0,0 +1,211 @@
+use thiserror::Error;
+
+use crate::associated_type::AssociatedType;
+use crate::const_item::ConstItem;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::r#struct::Struct;
+use crate::visibility::Vis;
+
+/// Generates a set of bit flags, like the `bitflags` macro does.
+///
+/// The flags are a newtype over an integer, with an associated const per
+/// flag, methods to query and update the set, and the `|` and `&`
+/// operators. The generated code has no dependencies.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bitflags {
+    /// Name of the struct
+    name: String,
+
+    /// Integer type holding the bits
+    repr: String,
+
+    /// Visibility of the struct and its methods
+    vis: Vis,
+
+    /// Names of the flags and the expressions of their bits
+    flags: Vec<(String, String)>,
+}
+
+impl Bitflags {
+    /// Creates a set of flags stored in the `repr` integer type.
+    pub fn new(name: impl Into<String>, repr: impl Into<String>) -> Self {
+        Bitflags {
+            name: name.into(),
+            repr: repr.into(),
+            vis: Vis::Private,
+            flags: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the integer type holding the bits.
+    pub fn repr(&self) -> &str {
+        &self.repr
+    }
+
+    /// Gets the visibility of the struct and its methods.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility of the struct and its methods.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility of the struct and its methods.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets the names of the flags and the expressions of their bits.
+    pub fn flags(&self) -> &[(String, String)] {
+        &self.flags
+    }
+
+    /// Pushes a flag, with its bits given as an expression, e.g. `1 << 2`.
+    pub fn push_flag(&mut self, name: impl Into<String>, bits: impl Into<String>) -> &mut Self {
+        self.flags.push((name.into(), bits.into()));
+        self
+    }
+
+    /// Pushes a flag, with its bits given as an expression, e.g. `1 << 2`.
+    pub fn with_flag(mut self, name: impl Into<String>, bits: impl Into<String>) -> Self {
+        self.push_flag(name, bits);
+        self
+    }
+
+    /// Generates the struct, its flags and methods, and the `BitOr` and
+    /// `BitAnd` impls.
+    ///
+    /// Fails if the representation is not an integer type or if a flag is
+    /// pushed twice.
+    pub fn generate(&self) -> Result<Vec<Item>, BitflagsError> {
+        if !INTEGERS.contains(&self.repr.as_str()) {
+            return Err(BitflagsError::UnsupportedRepr(self.repr.clone()));
+        }
+        for (i, (name, _)) in self.flags.iter().enumerate() {
+            if self.flags[..i].iter().any(|(n, _)| n == name) {
+                return Err(BitflagsError::DuplicateFlag(name.clone()));
+            }
+        }
+
+        let r#struct = Struct::new(self.name.as_str())
+            .with_vis(self.vis.clone())
+            .with_derives([
+                "Clone",
+                "Copy",
+                "PartialEq",
+                "Eq",
+                "Hash",
+                "Debug",
+                "Default",
+            ])
+            .with_tuple_field(self.repr.as_str());
+
+        Ok(vec![
+            r#struct.into(),
+            self.methods().into(),
+            self.operator("BitOr", "bitor", "|").into(),
+            self.operator("BitAnd", "bitand", "&").into(),
+        ])
+    }
+
+    /// Generates the impl block with the flags and the methods.
+    fn methods(&self) -> Impl {
+        let mut ret = Impl::new(self.name.as_str());
+        for (name, bits) in &self.flags {
+            ret.push_const(
+                ConstItem::new(name.as_str(), "Self", format!("Self({})", bits))
+                    .with_vis(self.vis.clone())
+                    .with_compact(true),
+            );
+        }
+
+        let method =
+            |name: &str, doc: &str| Function::new(name).with_vis(self.vis.clone()).with_doc(doc);
+        ret.push_function(
+            method("empty", "Returns a set without any flag.")
+                .with_ret("Self")
+                .with_line("Self(0)"),
+        )
+        .push_function(
+            method("bits", "Returns the raw bits of the set.")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret(self.repr.as_str())
+                .with_line("self.0"),
+        )
+        .push_function(
+            method(
+                "from_bits_retain",
+                "Creates a set from raw bits, keeping unknown bits.",
+            )
+            .with_arg("bits", self.repr.as_str())
+            .with_ret("Self")
+            .with_line("Self(bits)"),
+        )
+        .push_function(
+            method(
+                "contains",
+                "Returns whether all the flags of `other` are set.",
+            )
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("other", "Self")
+            .with_ret("bool")
+            .with_line("self.0 & other.0 == other.0"),
+        )
+        .push_function(
+            method("insert", "Sets the flags of `other`.")
+                .with_self_arg(SelfArg::WithMutSelfRef)
+                .with_arg("other", "Self")
+                .with_line("self.0 |= other.0;"),
+        )
+        .push_function(
+            method("remove", "Clears the flags of `other`.")
+                .with_self_arg(SelfArg::WithMutSelfRef)
+                .with_arg("other", "Self")
+                .with_line("self.0 &= !other.0;"),
+        );
+        ret
+    }
+
+    /// Generates the impl of a binary operator trait.
+    fn operator(&self, r#trait: &str, method: &str, op: &str) -> Impl {
+        Impl::new(self.name.as_str())
+            .with_impl_trait(format!("std::ops::{}", r#trait))
+            .with_associated_type(AssociatedType::new_with_concrete_ty("Output", "Self"))
+            .with_function(
+                Function::new(method)
+                    .with_self_arg(SelfArg::WithSelf)
+                    .with_arg("rhs", "Self")
+                    .with_ret("Self")
+                    .with_line(format!("Self(self.0 {} rhs.0)", op)),
+            )
+    }
+}
+
+/// Integer types bit flags can be stored in.
+const INTEGERS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Error returned when [`Bitflags`] cannot be generated.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum BitflagsError {
+    /// The representation is not an integer type.
+    #[error("bit flags cannot be stored in `{0}`")]
+    UnsupportedRepr(String),
+    /// A flag is pushed twice.
+    #[error("duplicate flag: {0}")]
+    DuplicateFlag(String),
+}
@@ -0,0 +1,137 @@
+use crate::item::Item;
+use crate::item_kind::ItemKind;
+use crate::prelude::*;
+
+/// Configures the blank lines written between the items of a scope.
+///
+/// By default, items are separated by one blank line and imports form a
+/// single block.
+///
+/// ```rust
+/// use simple_codegen::{BlankLines, Formatter, ItemKind, Scope};
+///
+/// let mut scope = Scope::new();
+/// scope.raw("const A: u8 = 1;");
+/// scope.raw("const B: u8 = 2;");
+///
+/// let mut ret = String::new();
+/// let blank_lines = BlankLines::new().with_between_kind(ItemKind::Raw, 0);
+/// scope
+///     .fmt(&mut Formatter::new(&mut ret).with_blank_lines(blank_lines))
+///     .unwrap();
+///
+/// assert_eq!(ret, "const A: u8 = 1;\nconst B: u8 = 2;\n");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlankLines {
+    /// Blank lines between items
+    between_items: usize,
+
+    /// Blank lines between two consecutive items of the same kind
+    between_kinds: Vec<(ItemKind, usize)>,
+
+    /// Whether imports are grouped by origin
+    grouped_imports: bool,
+}
+
+impl Default for BlankLines {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlankLines {
+    /// Creates the default policy: one blank line between items.
+    pub fn new() -> Self {
+        BlankLines {
+            between_items: 1,
+            between_kinds: Vec::new(),
+            grouped_imports: false,
+        }
+    }
+
+    /// Gets the number of blank lines between items.
+    pub fn between_items(&self) -> usize {
+        self.between_items
+    }
+
+    /// Sets the number of blank lines between items.
+    pub fn set_between_items(&mut self, lines: usize) -> &mut Self {
+        self.between_items = lines;
+        self
+    }
+
+    /// Sets the number of blank lines between items.
+    pub fn with_between_items(mut self, lines: usize) -> Self {
+        self.set_between_items(lines);
+        self
+    }
+
+    /// Gets the number of blank lines between two consecutive items of the
+    /// given kind.
+    pub fn between_kind(&self, kind: ItemKind) -> usize {
+        self.between_kinds
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map_or(self.between_items, |(_, lines)| *lines)
+    }
+
+    /// Sets the number of blank lines between two consecutive items of the
+    /// given kind, overriding [`between_items`](Self::between_items).
+    pub fn set_between_kind(&mut self, kind: ItemKind, lines: usize) -> &mut Self {
+        match self.between_kinds.iter_mut().find(|(k, _)| *k == kind) {
+            Some((_, v)) => *v = lines,
+            None => self.between_kinds.push((kind, lines)),
+        }
+        self
+    }
+
+    /// Sets the number of blank lines between two consecutive items of the
+    /// given kind.
+    pub fn with_between_kind(mut self, kind: ItemKind, lines: usize) -> Self {
+        self.set_between_kind(kind, lines);
+        self
+    }
+
+    /// Returns whether imports are grouped by origin.
+    pub fn has_grouped_imports(&self) -> bool {
+        self.grouped_imports
+    }
+
+    /// Sets whether imports are grouped by [origin](crate::ImportGroup):
+    /// `std`, `core` and `alloc` first, then external crates, then `crate`,
+    /// `self` and `super`, with a blank line between groups.
+    ///
+    /// Grouped imports are also sorted by path, then by imported name, so the
+    /// output matches rustfmt's `group_imports = "StdExternalCrate"`.
+    pub fn set_grouped_imports(&mut self, grouped_imports: bool) -> &mut Self {
+        self.grouped_imports = grouped_imports;
+        self
+    }
+
+    /// Sets whether imports are grouped by origin.
+    pub fn with_grouped_imports(mut self, grouped_imports: bool) -> Self {
+        self.set_grouped_imports(grouped_imports);
+        self
+    }
+
+    /// Gets the number of blank lines written between two consecutive items
+    /// of a scope: none before a compact item or after a comment.
+    pub(crate) fn separating(&self, prev: &Item, next: &Item) -> usize {
+        if next.is_compact() || matches!(prev, Item::Comment(_)) {
+            0
+        } else {
+            self.between(prev.kind(), next.kind())
+        }
+    }
+
+    /// Gets the number of blank lines between the given consecutive items.
+    pub(crate) fn between(&self, prev: ItemKind, next: ItemKind) -> usize {
+        if prev == next {
+            self.between_kind(next)
+        } else {
+            self.between_items
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use thiserror::Error;
+
+use crate::generic_parameter::generic_name;
+use crate::r#impl::Impl;
+use crate::prelude::*;
+use crate::r#type::Type;
+
+/// Error returned when the generics declared on an impl block do not match
+/// its target and trait.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ImplGenericsError {
+    /// A lifetime of the target is not declared on the impl.
+    #[error("lifetime `{lifetime}` of impl target `{target}` is not declared on the impl")]
+    UndeclaredLifetime {
+        /// The undeclared lifetime, e.g. `'a`
+        lifetime: String,
+        /// The target type of the impl block
+        target: String,
+    },
+    /// A type or const generic declared on the impl is used by neither the
+    /// target nor the trait, so the compiler cannot infer it.
+    #[error("generic `{generic}` is not used by impl target `{target}` or its trait")]
+    UnusedGeneric {
+        /// The unused generic, e.g. `T`
+        generic: String,
+        /// The target type of the impl block
+        target: String,
+    },
+}
+
+impl Impl {
+    /// Creates a blanket impl of `impl_trait` for every type matching
+    /// `target_pattern`.
+    ///
+    /// The pattern is a generic with its bounds, e.g. `T: Display + ?Sized`.
+    /// It is declared on the impl block and its name becomes the target, so
+    /// `Impl::new_blanket("ToJson", "T: Serialize")` generates
+    /// `impl<T: Serialize> ToJson for T`.
+    pub fn new_blanket(impl_trait: impl Into<Type>, target_pattern: impl Into<String>) -> Self {
+        let target_pattern = target_pattern.into();
+        let target = generic_name(&target_pattern).to_string();
+        Impl::new(target)
+            .with_generic(target_pattern)
+            .with_impl_trait(impl_trait)
+    }
+
+    /// Creates a blanket impl of `impl_trait` for a generic `target`, e.g.
+    /// `Wrapper<T>`, declaring the given `generics` on the impl block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generics do not match the target, see
+    /// [`Impl::check_generics`].
+    pub fn new_blanket_for<G>(
+        impl_trait: impl Into<Type>,
+        target: impl Into<Type>,
+        generics: impl IntoIterator<Item = G>,
+    ) -> Self
+    where
+        G: Into<String>,
+    {
+        let ret = Impl::new(target)
+            .with_generics(generics)
+            .with_impl_trait(impl_trait);
+        if let Err(e) = ret.check_generics() {
+            panic!("{}", e);
+        }
+        ret
+    }
+
+    /// Checks the generics declared on the impl block against its target
+    /// and trait.
+    ///
+    /// Lifetimes of the target, other than `'static` and `'_`, must be
+    /// declared. Type and const generics must be declared too, but they
+    /// cannot be told apart from type names, so the check goes the other
+    /// way: each declared type or const generic must be used by the target
+    /// or the trait, as the compiler requires.
+    pub fn check_generics(&self) -> Result<(), ImplGenericsError> {
+        let target = self.target().to_source();
+        let declared: Vec<&str> = self.generics().iter().map(|g| generic_name(g)).collect();
+
+        let used = tokens(&target);
+        if let Some(lifetime) = used
+            .iter()
+            .find(|t| is_lifetime(t) && !declared.contains(t))
+        {
+            return Err(ImplGenericsError::UndeclaredLifetime {
+                lifetime: lifetime.to_string(),
+                target,
+            });
+        }
+
+        let r#trait = self.impl_trait().map(Type::to_source).unwrap_or_default();
+        let trait_tokens = tokens(&r#trait);
+        if let Some(generic) = declared
+            .iter()
+            .find(|g| !g.starts_with('\'') && !used.contains(g) && !trait_tokens.contains(g))
+        {
+            return Err(ImplGenericsError::UnusedGeneric {
+                generic: generic.to_string(),
+                target,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits source into its identifiers and lifetimes.
+fn tokens(src: &str) -> Vec<&str> {
+    let mut ret = Vec::new();
+    let mut chars = src.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !(c.is_alphanumeric() || c == '_' || c == '\'') {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, c)) = chars.peek() {
+            if !(c.is_alphanumeric() || c == '_') {
+                break;
+            }
+            end = i + c.len_utf8();
+            chars.next();
+        }
+        ret.push(&src[start..end]);
+    }
+    ret
+}
+
+/// Returns whether the token is a named lifetime, other than `'static` and
+/// `'_`.
+fn is_lifetime(token: &str) -> bool {
+    token
+        .strip_prefix('\'')
+        .is_some_and(|lifetime| !lifetime.is_empty() && lifetime != "static" && lifetime != "_")
+}
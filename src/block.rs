@@ -1,10 +1,14 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
-use crate::body::Body;
+use crate::attribute::Attribute;
+use crate::body::{Body, LetElse, dedent};
+use crate::comment::Comment;
 use crate::formatter::Formatter;
+use crate::prelude::*;
 
 /// Defines a code block. This is used to define a function body.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Block {
     body: Vec<Body>,
 }
@@ -61,6 +65,22 @@ impl Block {
         self
     }
 
+    /// Push a multi-line snippet to the code block.
+    ///
+    /// The blank lines around the snippet and the indentation common to all of
+    /// its lines are removed, so that the snippet is indented like the
+    /// surrounding code, whatever its indentation in the source.
+    pub fn push_raw_indented(&mut self, snippet: impl AsRef<str>) -> &mut Self {
+        self.body.push(Body::String(dedent(snippet.as_ref())));
+        self
+    }
+
+    /// Push a multi-line snippet to the code block.
+    pub fn with_raw_indented(mut self, snippet: impl AsRef<str>) -> Self {
+        self.push_raw_indented(snippet);
+        self
+    }
+
     /// Push a nested block to this block.
     pub fn push_block(&mut self, block: impl Into<Block>) -> &mut Self {
         self.body.push(Body::Block(block.into()));
@@ -73,6 +93,94 @@ impl Block {
         self
     }
 
+    /// Push a comment to the code block.
+    pub fn push_comment(&mut self, comment: impl Into<Comment>) -> &mut Self {
+        self.body.push(Body::Comment(comment.into()));
+        self
+    }
+
+    /// Push a comment to the code block.
+    pub fn with_comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.push_comment(comment);
+        self
+    }
+
+    /// Push a line with an attribute, e.g. `allow(unused_variables)`, written
+    /// on the line before it.
+    pub fn push_attributed_line(
+        &mut self,
+        attribute: impl Into<Attribute>,
+        line: impl Into<String>,
+    ) -> &mut Self {
+        self.body.push(Body::Attributed(
+            attribute.into(),
+            Box::new(Body::String(line.into())),
+        ));
+        self
+    }
+
+    /// Push a line with an attribute, written on the line before it.
+    pub fn with_attributed_line(
+        mut self,
+        attribute: impl Into<Attribute>,
+        line: impl Into<String>,
+    ) -> Self {
+        self.push_attributed_line(attribute, line);
+        self
+    }
+
+    /// Push a nested block with an attribute, e.g. `cfg(unix)`, written on
+    /// the line before it.
+    pub fn push_attributed_block(
+        &mut self,
+        attribute: impl Into<Attribute>,
+        block: impl Into<Block>,
+    ) -> &mut Self {
+        self.body.push(Body::Attributed(
+            attribute.into(),
+            Box::new(Body::Block(block.into())),
+        ));
+        self
+    }
+
+    /// Push a nested block with an attribute, written on the line before it.
+    pub fn with_attributed_block(
+        mut self,
+        attribute: impl Into<Attribute>,
+        block: impl Into<Block>,
+    ) -> Self {
+        self.push_attributed_block(attribute, block);
+        self
+    }
+
+    /// Push a `let ... else` statement, given the diverging expression of
+    /// its `else` block without the `;`, e.g. `return None`.
+    ///
+    /// For toolchains older than Rust 1.65 the statement is written as a
+    /// `match` binding the variables of the pattern instead, see
+    /// [`Formatter::set_rust_version`].
+    pub fn push_let_else(
+        &mut self,
+        pattern: impl Into<String>,
+        expr: impl Into<String>,
+        diverge: impl Into<String>,
+    ) -> &mut Self {
+        self.body
+            .push(Body::LetElse(LetElse::new(pattern, expr, diverge)));
+        self
+    }
+
+    /// Push a `let ... else` statement.
+    pub fn with_let_else(
+        mut self,
+        pattern: impl Into<String>,
+        expr: impl Into<String>,
+        diverge: impl Into<String>,
+    ) -> Self {
+        self.push_let_else(pattern, expr, diverge);
+        self
+    }
+
     /// Formats the block using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         // Inlined `Formatter::fmt`
@@ -1,12 +1,70 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::attribute::Attribute;
 use crate::block::Block;
+use crate::comment::Comment;
 use crate::formatter::Formatter;
+use crate::prelude::*;
+use crate::toolchain::RustVersion;
 
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Body {
     String(String),
     Block(Block),
+    Comment(Comment),
+    Attributed(Attribute, Box<Body>),
+    LetElse(LetElse),
+}
+
+/// A `let ... else` statement, written as a `match` for toolchains without
+/// it.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LetElse {
+    pattern: String,
+    expr: String,
+    diverge: String,
+}
+
+impl LetElse {
+    pub(crate) fn new(
+        pattern: impl Into<String>,
+        expr: impl Into<String>,
+        diverge: impl Into<String>,
+    ) -> Self {
+        LetElse {
+            pattern: pattern.into(),
+            expr: expr.into(),
+            diverge: diverge.into(),
+        }
+    }
+
+    /// Gets mutable references to the pattern, expression and diverging
+    /// expression.
+    pub(crate) fn parts_mut(&mut self) -> [&mut String; 3] {
+        [&mut self.pattern, &mut self.expr, &mut self.diverge]
+    }
+
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if fmt.supports(RustVersion::LET_ELSE) {
+            writeln!(fmt, "let {} = {} else {{", self.pattern, self.expr)?;
+            fmt.indent(|fmt| writeln!(fmt, "{};", self.diverge))?;
+            return writeln!(fmt, "}};");
+        }
+
+        let bindings = bindings(&self.pattern);
+        let bound = match bindings.as_slice() {
+            [binding] => binding.to_string(),
+            _ => format!("({})", bindings.join(", ")),
+        };
+        writeln!(fmt, "let {} = match {} {{", bound, self.expr)?;
+        fmt.indent(|fmt| {
+            writeln!(fmt, "{} => {},", self.pattern, bound)?;
+            writeln!(fmt, "_ => {},", self.diverge)
+        })?;
+        writeln!(fmt, "}};")
+    }
 }
 
 impl Body {
@@ -14,6 +72,65 @@ impl Body {
         match &self {
             Body::String(s) => writeln!(fmt, "{}", s),
             Body::Block(b) => b.fmt(fmt),
+            Body::Comment(c) => c.fmt(fmt),
+            Body::Attributed(attribute, body) => {
+                writeln!(fmt, "#[{}]", attribute)?;
+                body.fmt(fmt)
+            }
+            Body::LetElse(v) => v.fmt(fmt),
+        }
+    }
+}
+
+/// Removes the blank lines around a snippet and the indentation common to
+/// all of its lines.
+pub(crate) fn dedent(snippet: &str) -> String {
+    let lines: Vec<&str> = snippet.lines().collect();
+    let start = lines.iter().position(|l| !l.trim().is_empty());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty());
+    let (Some(start), Some(end)) = (start, end) else {
+        return String::new();
+    };
+    let lines = &lines[start..=end];
+
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the variables bound by a pattern, e.g. `a` and `b` for
+/// `Foo { a, b: Some(b) }`.
+fn bindings(pattern: &str) -> Vec<&str> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut ret = Vec::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find(is_ident) {
+        let before = rest[..start].trim_end();
+        let len = rest[start..]
+            .find(|c| !is_ident(c))
+            .unwrap_or(rest.len() - start);
+        let ident = &rest[start..start + len];
+        rest = &rest[start + len..];
+
+        let after = rest.trim_start();
+        let is_path = before.ends_with("::") || after.starts_with("::");
+        let is_binding = ident.starts_with(|c: char| c.is_lowercase() || c == '_')
+            && !matches!(ident, "_" | "mut" | "ref" | "true" | "false")
+            && !before.ends_with(['\'', '"'])
+            && !is_path
+            && !after.starts_with(['(', '{', ':', '!']);
+        if is_binding {
+            ret.push(ident);
         }
     }
+    ret
 }
@@ -1,7 +1,14 @@
+use crate::prelude::*;
+use crate::r#type::Type;
+
 /// Defines a bound for a type in the `where` clause.
 ///
+/// The bounded type is rendered verbatim, so it can be any type rather than
+/// only a generic parameter, e.g. `Self: Sized` or `Vec<T>: Debug`.
+///
 /// Note that [`GenericParameter`] also allows setting bounds right next to the generic parmaeters.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Bound {
     name: String,
     traits: Vec<String>,
@@ -19,6 +26,20 @@ impl Bound {
         }
     }
 
+    /// Creates a new bound on an arbitrary type, e.g. `Vec<T>: Debug`.
+    pub fn for_type<S>(ty: impl Into<Type>, traits: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        Bound::new(ty.into().to_source(), traits)
+    }
+
+    /// Creates the `Self: Sized` bound, e.g. to exclude a trait method from
+    /// trait objects.
+    pub fn self_sized() -> Self {
+        Bound::new("Self", ["Sized"])
+    }
+
     /// Gets the name of the bound type.
     pub fn name(&self) -> &str {
         &self.name
@@ -0,0 +1,125 @@
+//! Helpers for generating code from a `build.rs` script.
+//!
+//! Cargo passes the directory build scripts should write to in the `OUT_DIR`
+//! environment variable. [`BuildScriptOutput`] writes files and libraries
+//! there, leaving unchanged files untouched so their timestamps do not
+//! trigger a recompile of the crate.
+//!
+//! ```rust,no_run
+//! use simple_codegen::File;
+//! use simple_codegen::build_script::{self, BuildScriptOutput};
+//!
+//! build_script::emit_rerun_if_changed(["schema.json"]);
+//!
+//! let mut file = File::new("generated.rs");
+//! file.scope_mut().new_struct("Foo");
+//!
+//! BuildScriptOutput::from_env()
+//!     .expect("not running in a build script")
+//!     .write_file(&file)
+//!     .expect("failed to write generated code");
+//! ```
+//!
+//! The generated file is then pulled into the crate with
+//! `include!(concat!(env!("OUT_DIR"), "/generated.rs"));`.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::files::{File, Library};
+
+const OUT_DIR: &str = "OUT_DIR";
+
+/// Tells Cargo to rerun the build script when any of the given paths change.
+pub fn emit_rerun_if_changed<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) {
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.as_ref().display());
+    }
+}
+
+/// Gets the output directory of the running build script.
+pub fn out_dir() -> Result<PathBuf, BuildScriptError> {
+    env::var_os(OUT_DIR)
+        .map(PathBuf::from)
+        .ok_or(BuildScriptError::MissingOutDir)
+}
+
+/// Writes generated files into the output directory of a build script.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BuildScriptOutput {
+    /// Directory files are written to
+    dir: PathBuf,
+}
+
+impl BuildScriptOutput {
+    /// Creates an output writing to the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        BuildScriptOutput { dir: dir.into() }
+    }
+
+    /// Creates an output writing to `OUT_DIR`.
+    pub fn from_env() -> Result<Self, BuildScriptError> {
+        out_dir().map(Self::new)
+    }
+
+    /// Gets the directory files are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Gets the path a file is written to.
+    pub fn path_of(&self, file: &File) -> PathBuf {
+        self.dir.join(file.path())
+    }
+
+    /// Writes a file relative to the output directory.
+    ///
+    /// Existing files are only overwritten if their contents differ.
+    /// Returns whether the file was written.
+    pub fn write_file(&self, file: &File) -> io::Result<bool> {
+        let path = self.path_of(file);
+        let contents = file.contents()?;
+
+        if fs::read(&path).is_ok_and(|existing| existing == contents) {
+            return Ok(false);
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(true)
+    }
+
+    /// Writes all files of a library relative to the output directory,
+    /// ignoring the library's own root directory.
+    ///
+    /// As with [`Library::generate`], `use` statements are inserted for
+    /// references to registered types. Returns the number of files written.
+    pub fn write_library(&self, library: &Library) -> io::Result<usize> {
+        let registry = library.resolved_registry();
+        let mut written = 0;
+
+        for file in library.files() {
+            let file = library.resolve_imports_with(&registry, file);
+            if self.write_file(&file)? {
+                written += 1;
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+/// Error returned when the build script environment is incomplete.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum BuildScriptError {
+    /// `OUT_DIR` is not set, e.g. because the code does not run in a build
+    /// script.
+    #[error("OUT_DIR is not set; is this running from a build script?")]
+    MissingOutDir,
+}
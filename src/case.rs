@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+/// Naming conventions used for Rust identifiers.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Case {
+    /// `snake_case`, used for fields, functions and modules.
+    Snake,
+    /// `CamelCase`, used for types, traits and enum variants.
+    Camel,
+    /// `SCREAMING_SNAKE_CASE`, used for constants and statics.
+    ScreamingSnake,
+}
+
+impl Case {
+    /// Converts the given name to this case.
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            Case::Snake => to_snake_case(name),
+            Case::Camel => to_camel_case(name),
+            Case::ScreamingSnake => to_screaming_snake_case(name),
+        }
+    }
+}
+
+/// Converts the given name to `snake_case`.
+pub fn to_snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Converts the given name to `CamelCase`.
+pub fn to_camel_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Converts the given name to `SCREAMING_SNAKE_CASE`.
+pub fn to_screaming_snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_uppercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Splits a name into words on separators and case boundaries.
+///
+/// `"HTTPServer"`, `"http_server"`, `"http-server"` and `"httpServer"` all split into
+/// `["http", "server"]` (modulo case).
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = name.chars().collect();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(core::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            // Split on `fooBar` and on the last capital of an acronym (`HTTPServer`).
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                words.push(core::mem::take(&mut current));
+            }
+        }
+
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
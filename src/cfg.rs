@@ -0,0 +1,170 @@
+use core::fmt;
+
+use crate::attribute::Attribute;
+use crate::prelude::*;
+
+/// Defines a [conditional compilation](https://doc.rust-lang.org/reference/conditional-compilation.html)
+/// predicate.
+///
+/// A `Cfg` converts into a `cfg(...)` attribute, so it can be passed to any
+/// `push_attribute` method.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Cfg {
+    /// `feature = "name"`
+    Feature(String),
+    /// Any other predicate, used verbatim, e.g. `unix` or `target_os = "linux"`
+    Raw(String),
+    /// `not(cfg)`
+    Not(Box<Cfg>),
+    /// `all(cfg, ...)`
+    All(Vec<Cfg>),
+    /// `any(cfg, ...)`
+    Any(Vec<Cfg>),
+}
+
+impl Cfg {
+    /// Creates a predicate on a Cargo feature.
+    pub fn feature(name: impl Into<String>) -> Self {
+        Cfg::Feature(name.into())
+    }
+
+    /// Creates a predicate used verbatim.
+    pub fn raw(predicate: impl Into<String>) -> Self {
+        Cfg::Raw(predicate.into())
+    }
+
+    /// Negates a predicate.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(cfg: Cfg) -> Self {
+        Cfg::Not(Box::new(cfg))
+    }
+
+    /// Creates a predicate that holds if all the given predicates hold.
+    pub fn all(cfgs: impl IntoIterator<Item = Cfg>) -> Self {
+        Cfg::All(cfgs.into_iter().collect())
+    }
+
+    /// Creates a predicate that holds if any of the given predicates holds.
+    pub fn any(cfgs: impl IntoIterator<Item = Cfg>) -> Self {
+        Cfg::Any(cfgs.into_iter().collect())
+    }
+
+    /// Gets the names of the features referenced by the predicate.
+    pub fn features(&self) -> Vec<&str> {
+        match self {
+            Cfg::Feature(name) => vec![name.as_str()],
+            Cfg::Raw(_) => Vec::new(),
+            Cfg::Not(cfg) => cfg.features(),
+            Cfg::All(cfgs) | Cfg::Any(cfgs) => cfgs.iter().flat_map(Cfg::features).collect(),
+        }
+    }
+
+    /// Returns the predicate as a `cfg(...)` attribute.
+    pub fn to_attribute(&self) -> Attribute {
+        Attribute::list("cfg", self.to_string())
+    }
+
+    /// Gets the predicate of a `cfg(...)` or `cfg_attr(...)` attribute.
+    #[cfg(feature = "std")]
+    pub(crate) fn from_attribute(attr: &Attribute) -> Option<Cfg> {
+        let crate::attribute::AttributeArgs::List(args) = attr.args() else {
+            return None;
+        };
+        match attr.path() {
+            "cfg" => Some(Cfg::parse(args)),
+            "cfg_attr" => split_args(args)
+                .first()
+                .map(|predicate| Cfg::parse(predicate)),
+            _ => None,
+        }
+    }
+
+    /// Parses a predicate, keeping any predicate other than a feature, `not`,
+    /// `all` or `any` verbatim.
+    #[cfg(feature = "std")]
+    fn parse(src: &str) -> Cfg {
+        let src = src.trim();
+        let list = |op: &str| {
+            src.strip_prefix(op)?
+                .trim_start()
+                .strip_prefix('(')?
+                .strip_suffix(')')
+        };
+
+        if let Some(args) = list("not") {
+            return Cfg::not(Cfg::parse(args));
+        }
+        if let Some(args) = list("all") {
+            return Cfg::all(split_args(args).into_iter().map(Cfg::parse));
+        }
+        if let Some(args) = list("any") {
+            return Cfg::any(split_args(args).into_iter().map(Cfg::parse));
+        }
+
+        let feature = src
+            .strip_prefix("feature")
+            .and_then(|rest| rest.trim_start().strip_prefix('='))
+            .and_then(|rest| rest.trim().strip_prefix('"')?.strip_suffix('"'));
+        match feature {
+            Some(name) => Cfg::feature(name),
+            None => Cfg::raw(src),
+        }
+    }
+}
+
+/// Splits the comma separated predicates of a `cfg` list.
+#[cfg(feature = "std")]
+fn split_args(args: &str) -> Vec<&str> {
+    let mut ret = Vec::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (i, c) in args.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            ',' if !quoted && depth == 0 => {
+                ret.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    ret.push(&args[start..]);
+    ret.retain(|arg| !arg.trim().is_empty());
+    ret
+}
+
+impl fmt::Display for Cfg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cfg::Feature(name) => write!(f, "feature = \"{}\"", name),
+            Cfg::Raw(predicate) => write!(f, "{}", predicate),
+            Cfg::Not(cfg) => write!(f, "not({})", cfg),
+            Cfg::All(cfgs) | Cfg::Any(cfgs) => {
+                let op = if matches!(self, Cfg::All(_)) {
+                    "all"
+                } else {
+                    "any"
+                };
+                write!(f, "{}(", op)?;
+                for (i, cfg) in cfgs.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", cfg)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl From<Cfg> for String {
+    fn from(cfg: Cfg) -> Self {
+        cfg.to_attribute().to_string()
+    }
+}
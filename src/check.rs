@@ -0,0 +1,232 @@
+use alloc::collections::BTreeSet;
+
+use thiserror::Error;
+
+use crate::r#enum::Enum;
+use crate::fields::Fields;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::repr::{Repr, ReprError};
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::visibility::Vis;
+
+/// Defines a problem found by [`Scope::check`] that makes the generated code
+/// fail to compile.
+///
+/// The `module` of each diagnostic is the path of the module the problem is
+/// in, relative to the scope, e.g. `a::b`, and empty for the scope itself.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum Diagnostic {
+    /// Two items of the same namespace have the same name.
+    #[error("duplicate {kind} `{name}`{}", location(module))]
+    DuplicateName {
+        /// Module of the items
+        module: String,
+        /// Keyword of the second item, e.g. `struct`
+        kind: &'static str,
+        /// Name of the items
+        name: String,
+    },
+    /// Two variants of an enum have the same name.
+    #[error(
+        "duplicate variant `{variant}` in enum `{enum_name}`{}",
+        location(module)
+    )]
+    DuplicateVariant {
+        /// Module of the enum
+        module: String,
+        /// Name of the enum
+        enum_name: String,
+        /// Name of the variants
+        variant: String,
+    },
+    /// Two fields of a struct have the same name.
+    #[error(
+        "duplicate field `{field}` in struct `{struct_name}`{}",
+        location(module)
+    )]
+    DuplicateField {
+        /// Module of the struct
+        module: String,
+        /// Name of the struct
+        struct_name: String,
+        /// Name of the fields
+        field: String,
+    },
+    /// An enum without variants has a representation hint.
+    #[error("enum `{name}` has a repr but no variants{}", location(module))]
+    EmptyEnumWithRepr {
+        /// Module of the enum
+        module: String,
+        /// Name of the enum
+        name: String,
+    },
+    /// The representation hints of a struct or enum conflict or are not
+    /// supported on it.
+    #[error("invalid repr on `{name}`{}: {error}", location(module))]
+    InvalidRepr {
+        /// Module of the item
+        module: String,
+        /// Name of the item
+        name: String,
+        /// Problem with the hints
+        error: ReprError,
+    },
+    /// A function of a trait has a visibility.
+    #[error(
+        "function `{function}` of trait `{trait_name}` has a visibility{}",
+        location(module)
+    )]
+    TraitFunctionWithVis {
+        /// Module of the trait
+        module: String,
+        /// Name of the trait
+        trait_name: String,
+        /// Name of the function
+        function: String,
+    },
+}
+
+impl Scope {
+    /// Checks the scope and its nested modules for code that would not
+    /// compile, returning the problems found.
+    ///
+    /// This catches duplicate item names in the same namespace, duplicate
+    /// variants and fields, empty enums with a `repr`, invalid `repr` hints,
+    /// and trait functions with a visibility.
+    pub fn check(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        check_scope(self, "", &mut diagnostics);
+        diagnostics
+    }
+}
+
+fn check_scope(scope: &Scope, module: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut types = BTreeSet::new();
+    let mut values = BTreeSet::new();
+
+    for item in scope.items() {
+        let (Some(name), Some(namespaces)) = (item.name(), namespaces(item)) else {
+            continue;
+        };
+        let (is_type, is_value) = namespaces;
+        let duplicate = (is_type && !types.insert(name)) | (is_value && !values.insert(name));
+        if duplicate {
+            diagnostics.push(Diagnostic::DuplicateName {
+                module: module.to_string(),
+                kind: item.kind_name(),
+                name: name.to_string(),
+            });
+        }
+
+        match item {
+            Item::Module(v) => {
+                let path = match module {
+                    "" => v.name().to_string(),
+                    _ => format!("{}::{}", module, v.name()),
+                };
+                check_scope(v.scope(), &path, diagnostics);
+            }
+            Item::Struct(v) => check_struct(v, module, diagnostics),
+            Item::Enum(v) => check_enum(v, module, diagnostics),
+            Item::Trait(v) => check_trait(v, module, diagnostics),
+            _ => {}
+        }
+    }
+}
+
+/// Returns whether the item is in the type namespace, then whether it is in
+/// the value namespace, or `None` if it does not define a name.
+fn namespaces(item: &Item) -> Option<(bool, bool)> {
+    match item {
+        Item::Module(_) | Item::ModDecl(_) | Item::Enum(_) | Item::Trait(_) => Some((true, false)),
+        Item::TypeAlias(_) => Some((true, false)),
+        Item::Struct(v) => Some((true, !matches!(v.fields(), Fields::Named(_)))),
+        Item::Function(_) | Item::Const(_) => Some((false, true)),
+        Item::Impl(_) | Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => None,
+    }
+}
+
+fn check_struct(r#struct: &Struct, module: &str, diagnostics: &mut Vec<Diagnostic>) {
+    check_repr(
+        r#struct.name(),
+        r#struct.repr(),
+        "struct",
+        module,
+        diagnostics,
+    );
+
+    let Fields::Named(fields) = r#struct.fields() else {
+        return;
+    };
+    let mut names = BTreeSet::new();
+    for field in fields {
+        if !names.insert(field.name()) {
+            diagnostics.push(Diagnostic::DuplicateField {
+                module: module.to_string(),
+                struct_name: r#struct.name().to_string(),
+                field: field.name().to_string(),
+            });
+        }
+    }
+}
+
+fn check_enum(r#enum: &Enum, module: &str, diagnostics: &mut Vec<Diagnostic>) {
+    check_repr(r#enum.name(), r#enum.repr(), "enum", module, diagnostics);
+
+    if r#enum.variants().is_empty() && !r#enum.repr().is_empty() {
+        diagnostics.push(Diagnostic::EmptyEnumWithRepr {
+            module: module.to_string(),
+            name: r#enum.name().to_string(),
+        });
+    }
+
+    let mut names = BTreeSet::new();
+    for variant in r#enum.variants() {
+        if !names.insert(variant.name()) {
+            diagnostics.push(Diagnostic::DuplicateVariant {
+                module: module.to_string(),
+                enum_name: r#enum.name().to_string(),
+                variant: variant.name().to_string(),
+            });
+        }
+    }
+}
+
+fn check_repr(
+    name: &str,
+    repr: &[Repr],
+    kind: &str,
+    module: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let Err(error) = Repr::validate(repr, kind) {
+        diagnostics.push(Diagnostic::InvalidRepr {
+            module: module.to_string(),
+            name: name.to_string(),
+            error,
+        });
+    }
+}
+
+fn check_trait(r#trait: &Trait, module: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for function in r#trait.functions() {
+        if *function.vis() != Vis::Private {
+            diagnostics.push(Diagnostic::TraitFunctionWithVis {
+                module: module.to_string(),
+                trait_name: r#trait.name().to_string(),
+                function: function.name().to_string(),
+            });
+        }
+    }
+}
+
+/// Renders the module of a diagnostic, if any.
+fn location(module: &str) -> String {
+    match module {
+        "" => String::new(),
+        _ => format!(" in module `{}`", module),
+    }
+}
@@ -0,0 +1,143 @@
+use core::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+use crate::prelude::*;
+
+/// Defines the delimiters of a [`Comment`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentStyle {
+    /// `// text`, repeated on each line.
+    #[default]
+    Line,
+    /// `/* text */`, spread over several lines if the text is:
+    ///
+    /// ```text
+    /// /*
+    ///  * first
+    ///  * second
+    ///  */
+    /// ```
+    ///
+    /// Text containing `*/` would end the comment early, so it is written
+    /// as line comments instead.
+    Block,
+}
+
+/// Defines a regular, non-documentation comment.
+///
+/// The text is given without delimiters; each of its lines is prefixed when
+/// formatting.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Comment {
+    /// Comment text, without delimiters
+    text: String,
+
+    /// Comment delimiters
+    style: CommentStyle,
+}
+
+impl Comment {
+    /// Creates a new line comment.
+    pub fn new(text: impl Into<String>) -> Self {
+        Comment {
+            text: text.into(),
+            style: CommentStyle::Line,
+        }
+    }
+
+    /// Creates a new line comment.
+    pub fn line(text: impl Into<String>) -> Self {
+        Comment::new(text)
+    }
+
+    /// Creates a new block comment.
+    pub fn block(text: impl Into<String>) -> Self {
+        Comment::new(text).with_style(CommentStyle::Block)
+    }
+
+    /// Gets the comment text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Sets the comment text.
+    pub fn set_text(&mut self, text: impl Into<String>) -> &mut Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Sets the comment text.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Gets a mutable reference to the comment text.
+    pub fn text_mut(&mut self) -> &mut String {
+        &mut self.text
+    }
+
+    /// Gets the comment delimiters.
+    pub fn style(&self) -> CommentStyle {
+        self.style
+    }
+
+    /// Sets the comment delimiters.
+    pub fn set_style(&mut self, style: CommentStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the comment delimiters.
+    pub fn with_style(mut self, style: CommentStyle) -> Self {
+        self.set_style(style);
+        self
+    }
+
+    /// Gets a mutable reference to the comment delimiters.
+    pub fn style_mut(&mut self) -> &mut CommentStyle {
+        &mut self.style
+    }
+
+    /// Formats the comment using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self.style {
+            CommentStyle::Line if self.text.is_empty() => writeln!(fmt, "//"),
+            CommentStyle::Block if self.text.contains("*/") => {
+                Comment::line(self.text.as_str()).fmt(fmt)
+            }
+            CommentStyle::Line => {
+                for line in self.text.lines() {
+                    write!(fmt, "//")?;
+                    if !line.is_empty() {
+                        write!(fmt, " {}", line)?;
+                    }
+                    writeln!(fmt)?;
+                }
+                Ok(())
+            }
+            CommentStyle::Block if !self.text.contains('\n') => {
+                writeln!(fmt, "/* {} */", self.text)
+            }
+            CommentStyle::Block => {
+                writeln!(fmt, "/*")?;
+                for line in self.text.lines() {
+                    write!(fmt, " *")?;
+                    if !line.is_empty() {
+                        write!(fmt, " {}", line)?;
+                    }
+                    writeln!(fmt)?;
+                }
+                writeln!(fmt, " */")
+            }
+        }
+    }
+}
+
+impl<S: Into<String>> From<S> for Comment {
+    fn from(text: S) -> Self {
+        Comment::new(text)
+    }
+}
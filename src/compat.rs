@@ -0,0 +1,391 @@
+use core::fmt;
+
+use crate::formatter::Formatter;
+use crate::function::{Function, SelfArg};
+use crate::item::Item;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// A snapshot of the public function signatures of a scope.
+///
+/// Snapshots taken from successive versions of a generator can be compared
+/// with [`breaking_changes`] to detect changes that break callers. With the
+/// `serde` feature enabled, snapshots can be saved and loaded, e.g. as JSON.
+///
+/// [`breaking_changes`]: #method.breaking_changes
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiSnapshot {
+    functions: Vec<FunctionSignature>,
+}
+
+impl ApiSnapshot {
+    /// Creates a new, empty snapshot.
+    pub fn new() -> Self {
+        ApiSnapshot::default()
+    }
+
+    /// Takes a snapshot of the public functions of the given scope.
+    ///
+    /// This includes `pub` free functions and `pub` methods of inherent
+    /// `impl` blocks, in `pub` modules at any depth.
+    pub fn from_scope(scope: &Scope) -> Self {
+        let mut snapshot = ApiSnapshot::new();
+        snapshot.collect(scope, "");
+        snapshot
+    }
+
+    fn collect(&mut self, scope: &Scope, prefix: &str) {
+        for item in scope.items() {
+            match item {
+                Item::Module(v) if *v.vis() == Vis::Pub => {
+                    self.collect(v.scope(), &format!("{}{}::", prefix, v.name()));
+                }
+                Item::Function(v) if *v.vis() == Vis::Pub => {
+                    self.push_function(FunctionSignature::from_function(prefix, v));
+                }
+                Item::Impl(v) if v.impl_trait().is_none() => {
+                    let prefix = format!("{}{}::", prefix, v.target().name());
+                    for function in v.functions() {
+                        if *function.vis() == Vis::Pub {
+                            self.push_function(FunctionSignature::from_function(
+                                &prefix, function,
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Gets the function signatures of the snapshot.
+    pub fn functions(&self) -> &[FunctionSignature] {
+        &self.functions
+    }
+
+    /// Sets the function signatures of the snapshot.
+    pub fn set_functions<F>(&mut self, functions: impl IntoIterator<Item = F>) -> &mut Self
+    where
+        F: Into<FunctionSignature>,
+    {
+        self.functions = functions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the function signatures of the snapshot.
+    pub fn with_functions<F>(mut self, functions: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Into<FunctionSignature>,
+    {
+        self.set_functions(functions);
+        self
+    }
+
+    /// Gets a mutable reference to the function signatures of the snapshot.
+    pub fn functions_mut(&mut self) -> &mut Vec<FunctionSignature> {
+        &mut self.functions
+    }
+
+    /// Pushes a function signature.
+    pub fn push_function(&mut self, function: impl Into<FunctionSignature>) -> &mut Self {
+        self.functions.push(function.into());
+        self
+    }
+
+    /// Pushes a function signature.
+    pub fn with_function(mut self, function: impl Into<FunctionSignature>) -> Self {
+        self.push_function(function);
+        self
+    }
+
+    /// Gets the signature of the function with the given path, if any.
+    pub fn get_function(&self, path: &str) -> Option<&FunctionSignature> {
+        self.functions.iter().find(|f| f.path() == path)
+    }
+
+    /// Lists the changes from this snapshot to `current` that break callers.
+    ///
+    /// Removed functions, changed receivers, removed or added arguments, and
+    /// changed argument or return types are reported. Arguments are compared
+    /// by position, so renaming one is not reported. Newly added functions
+    /// are not reported either.
+    pub fn breaking_changes(&self, current: &ApiSnapshot) -> Vec<BreakingChange> {
+        let mut changes = Vec::new();
+
+        for old in &self.functions {
+            let Some(new) = current.get_function(old.path()) else {
+                changes.push(BreakingChange::FunctionRemoved {
+                    path: old.path.clone(),
+                });
+                continue;
+            };
+
+            if old.receiver != new.receiver {
+                changes.push(BreakingChange::ReceiverChanged {
+                    path: old.path.clone(),
+                    old: old.receiver.clone(),
+                    new: new.receiver.clone(),
+                });
+            }
+
+            // Arguments are passed by position, so a renamed argument is
+            // compatible while a reordered one is not.
+            for i in 0..old.args.len().max(new.args.len()) {
+                match (old.args.get(i), new.args.get(i)) {
+                    (Some((name, _)), None) => changes.push(BreakingChange::ArgRemoved {
+                        path: old.path.clone(),
+                        name: name.clone(),
+                    }),
+                    (None, Some((name, _))) => changes.push(BreakingChange::ArgAdded {
+                        path: old.path.clone(),
+                        name: name.clone(),
+                    }),
+                    (Some((name, ty)), Some((_, new_ty))) if new_ty != ty => {
+                        changes.push(BreakingChange::ArgTypeChanged {
+                            path: old.path.clone(),
+                            name: name.clone(),
+                            old: ty.clone(),
+                            new: new_ty.clone(),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+
+            if old.ret != new.ret {
+                changes.push(BreakingChange::ReturnTypeChanged {
+                    path: old.path.clone(),
+                    old: old.ret.clone(),
+                    new: new.ret.clone(),
+                });
+            }
+        }
+
+        changes
+    }
+
+    /// Returns whether `current` is compatible with this snapshot, i.e. has
+    /// no breaking changes.
+    pub fn is_compatible(&self, current: &ApiSnapshot) -> bool {
+        self.breaking_changes(current).is_empty()
+    }
+}
+
+/// The signature of a public function, as recorded in an [`ApiSnapshot`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionSignature {
+    /// Path of the function, e.g. `module::Type::name`
+    path: String,
+
+    /// Rendered `self` argument, e.g. `&self`, if the function has one
+    receiver: Option<String>,
+
+    /// Argument names and rendered types
+    args: Vec<(String, String)>,
+
+    /// Rendered return type
+    ret: Option<String>,
+}
+
+impl FunctionSignature {
+    /// Creates a new signature with no arguments and no return type.
+    pub fn new(path: impl Into<String>) -> Self {
+        FunctionSignature {
+            path: path.into(),
+            receiver: None,
+            args: Vec::new(),
+            ret: None,
+        }
+    }
+
+    /// Creates the signature of the given function, prefixing its name with
+    /// `prefix`.
+    pub fn from_function(prefix: &str, function: &Function) -> Self {
+        FunctionSignature {
+            path: format!("{}{}", prefix, function.name()),
+            receiver: render_receiver(function.self_arg()),
+            args: function
+                .args()
+                .iter()
+                .map(|arg| (arg.name().to_string(), arg.ty().to_source()))
+                .collect(),
+            ret: function.ret().map(Type::to_source),
+        }
+    }
+
+    /// Gets the path of the function.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Gets the rendered `self` argument, if any.
+    pub fn receiver(&self) -> Option<&str> {
+        self.receiver.as_deref()
+    }
+
+    /// Sets the rendered `self` argument.
+    pub fn set_receiver<S>(&mut self, receiver: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.receiver = receiver.into().map(Into::into);
+        self
+    }
+
+    /// Sets the rendered `self` argument.
+    pub fn with_receiver<S>(mut self, receiver: impl Into<Option<S>>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_receiver(receiver);
+        self
+    }
+
+    /// Gets the argument names and types.
+    pub fn args(&self) -> &[(String, String)] {
+        &self.args
+    }
+
+    /// Pushes an argument.
+    pub fn push_arg(&mut self, name: impl Into<String>, ty: impl Into<String>) -> &mut Self {
+        self.args.push((name.into(), ty.into()));
+        self
+    }
+
+    /// Pushes an argument.
+    pub fn with_arg(mut self, name: impl Into<String>, ty: impl Into<String>) -> Self {
+        self.push_arg(name, ty);
+        self
+    }
+
+    /// Gets the return type.
+    pub fn ret(&self) -> Option<&str> {
+        self.ret.as_deref()
+    }
+
+    /// Sets the return type.
+    pub fn set_ret<S>(&mut self, ret: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.ret = ret.into().map(Into::into);
+        self
+    }
+
+    /// Sets the return type.
+    pub fn with_ret<S>(mut self, ret: impl Into<Option<S>>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_ret(ret);
+        self
+    }
+}
+
+/// A change between two [`ApiSnapshot`]s that breaks callers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BreakingChange {
+    /// A function was removed.
+    FunctionRemoved {
+        /// Path of the function
+        path: String,
+    },
+    /// The `self` argument was added, removed or changed.
+    ReceiverChanged {
+        /// Path of the function
+        path: String,
+        /// Previous `self` argument
+        old: Option<String>,
+        /// Current `self` argument
+        new: Option<String>,
+    },
+    /// An argument was removed.
+    ArgRemoved {
+        /// Path of the function
+        path: String,
+        /// Name of the argument
+        name: String,
+    },
+    /// An argument was added.
+    ArgAdded {
+        /// Path of the function
+        path: String,
+        /// Name of the argument
+        name: String,
+    },
+    /// The type of an argument changed.
+    ArgTypeChanged {
+        /// Path of the function
+        path: String,
+        /// Name of the argument
+        name: String,
+        /// Previous type
+        old: String,
+        /// Current type
+        new: String,
+    },
+    /// The return type changed.
+    ReturnTypeChanged {
+        /// Path of the function
+        path: String,
+        /// Previous return type
+        old: Option<String>,
+        /// Current return type
+        new: Option<String>,
+    },
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakingChange::FunctionRemoved { path } => write!(f, "`{}` was removed", path),
+            BreakingChange::ReceiverChanged { path, old, new } => write!(
+                f,
+                "receiver of `{}` changed from `{}` to `{}`",
+                path,
+                old.as_deref().unwrap_or("none"),
+                new.as_deref().unwrap_or("none")
+            ),
+            BreakingChange::ArgRemoved { path, name } => {
+                write!(f, "argument `{}` of `{}` was removed", name, path)
+            }
+            BreakingChange::ArgAdded { path, name } => {
+                write!(f, "argument `{}` was added to `{}`", name, path)
+            }
+            BreakingChange::ArgTypeChanged {
+                path,
+                name,
+                old,
+                new,
+            } => write!(
+                f,
+                "argument `{}` of `{}` changed type from `{}` to `{}`",
+                name, path, old, new
+            ),
+            BreakingChange::ReturnTypeChanged { path, old, new } => write!(
+                f,
+                "return type of `{}` changed from `{}` to `{}`",
+                path,
+                old.as_deref().unwrap_or("()"),
+                new.as_deref().unwrap_or("()")
+            ),
+        }
+    }
+}
+
+fn render_receiver(self_arg: &SelfArg) -> Option<String> {
+    let self_arg = match self_arg {
+        SelfArg::None => return None,
+        // `mut self` only makes the binding mutable; callers see `self`.
+        SelfArg::WithMutSelf => &SelfArg::WithSelf,
+        self_arg => self_arg,
+    };
+    let mut ret = String::new();
+    self_arg.fmt(&mut Formatter::new(&mut ret)).unwrap();
+    Some(ret)
+}
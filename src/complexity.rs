@@ -0,0 +1,286 @@
+use core::fmt;
+
+use crate::body::Body;
+use crate::r#enum::Enum;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::module::Module;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::visit::{Visitor, walk_enum, walk_impl, walk_module, walk_struct, walk_trait};
+
+const DEFAULT_MAX_ARGS: usize = 7;
+const DEFAULT_MAX_LINES: usize = 100;
+const DEFAULT_MAX_DEPTH: usize = 4;
+
+/// Flags generated functions that exceed complexity thresholds.
+///
+/// Arguments include `self`. Body lines count every line of the body,
+/// including nested blocks, and nesting depth counts the blocks pushed into
+/// the body, so a flat body has a depth of zero.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ComplexityLimits {
+    /// Maximum number of arguments
+    max_args: usize,
+
+    /// Maximum number of body lines
+    max_lines: usize,
+
+    /// Maximum nesting depth of blocks
+    max_depth: usize,
+}
+
+impl Default for ComplexityLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ComplexityLimits {
+    /// Creates limits of 7 arguments, 100 body lines and a nesting depth of 4.
+    pub fn new() -> Self {
+        ComplexityLimits {
+            max_args: DEFAULT_MAX_ARGS,
+            max_lines: DEFAULT_MAX_LINES,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Gets the maximum number of arguments.
+    pub fn max_args(&self) -> usize {
+        self.max_args
+    }
+
+    /// Sets the maximum number of arguments.
+    pub fn set_max_args(&mut self, max_args: usize) -> &mut Self {
+        self.max_args = max_args;
+        self
+    }
+
+    /// Sets the maximum number of arguments.
+    pub fn with_max_args(mut self, max_args: usize) -> Self {
+        self.set_max_args(max_args);
+        self
+    }
+
+    /// Gets the maximum number of body lines.
+    pub fn max_lines(&self) -> usize {
+        self.max_lines
+    }
+
+    /// Sets the maximum number of body lines.
+    pub fn set_max_lines(&mut self, max_lines: usize) -> &mut Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Sets the maximum number of body lines.
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.set_max_lines(max_lines);
+        self
+    }
+
+    /// Gets the maximum nesting depth of blocks.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Sets the maximum nesting depth of blocks.
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum nesting depth of blocks.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.set_max_depth(max_depth);
+        self
+    }
+
+    /// Checks every function of the scope, in nested modules, traits, `impl`
+    /// blocks and generated tests too.
+    ///
+    /// Functions are reported by path, e.g. `models::User::new`.
+    pub fn check(&self, scope: &Scope) -> Vec<ComplexityIssue> {
+        let mut checker = Checker {
+            limits: self,
+            path: Vec::new(),
+            issues: Vec::new(),
+        };
+        checker.visit_scope(scope);
+        checker.issues
+    }
+
+    /// Checks a single function.
+    pub fn check_function(&self, function: &Function) -> Vec<ComplexityIssue> {
+        let mut checker = Checker {
+            limits: self,
+            path: Vec::new(),
+            issues: Vec::new(),
+        };
+        checker.visit_function(function);
+        checker.issues
+    }
+}
+
+/// A function exceeding a complexity limit.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ComplexityIssue {
+    /// The function takes too many arguments.
+    TooManyArgs {
+        /// Path of the function
+        path: String,
+        /// Number of arguments, including `self`
+        count: usize,
+        /// Configured limit
+        limit: usize,
+    },
+    /// The function body has too many lines.
+    TooManyLines {
+        /// Path of the function
+        path: String,
+        /// Number of body lines
+        count: usize,
+        /// Configured limit
+        limit: usize,
+    },
+    /// The function body nests blocks too deeply.
+    TooDeep {
+        /// Path of the function
+        path: String,
+        /// Nesting depth of blocks
+        depth: usize,
+        /// Configured limit
+        limit: usize,
+    },
+}
+
+impl ComplexityIssue {
+    /// Gets the path of the function.
+    pub fn path(&self) -> &str {
+        match self {
+            ComplexityIssue::TooManyArgs { path, .. }
+            | ComplexityIssue::TooManyLines { path, .. }
+            | ComplexityIssue::TooDeep { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for ComplexityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComplexityIssue::TooManyArgs { path, count, limit } => write!(
+                f,
+                "`{}` takes {} arguments, more than {}",
+                path, count, limit
+            ),
+            ComplexityIssue::TooManyLines { path, count, limit } => write!(
+                f,
+                "`{}` has {} body lines, more than {}",
+                path, count, limit
+            ),
+            ComplexityIssue::TooDeep { path, depth, limit } => write!(
+                f,
+                "`{}` nests blocks {} deep, more than {}",
+                path, depth, limit
+            ),
+        }
+    }
+}
+
+struct Checker<'a> {
+    limits: &'a ComplexityLimits,
+    path: Vec<String>,
+    issues: Vec<ComplexityIssue>,
+}
+
+impl Checker<'_> {
+    fn nested(&mut self, name: &str, walk: impl FnOnce(&mut Self)) {
+        self.path.push(name.to_string());
+        walk(self);
+        self.path.pop();
+    }
+}
+
+impl Visitor for Checker<'_> {
+    fn visit_module(&mut self, module: &Module) {
+        self.nested(module.name(), |v| walk_module(v, module));
+    }
+
+    fn visit_struct(&mut self, r#struct: &Struct) {
+        self.nested(r#struct.name(), |v| walk_struct(v, r#struct));
+    }
+
+    fn visit_enum(&mut self, r#enum: &Enum) {
+        self.nested(r#enum.name(), |v| walk_enum(v, r#enum));
+    }
+
+    fn visit_trait(&mut self, r#trait: &Trait) {
+        self.nested(r#trait.name(), |v| walk_trait(v, r#trait));
+    }
+
+    fn visit_impl(&mut self, r#impl: &Impl) {
+        self.nested(r#impl.target().name(), |v| walk_impl(v, r#impl));
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let mut path = self.path.clone();
+        path.push(function.name().to_string());
+        let path = path.join("::");
+        let limits = self.limits;
+
+        let args = function.args().len() + usize::from(*function.self_arg() != SelfArg::None);
+        if args > limits.max_args {
+            self.issues.push(ComplexityIssue::TooManyArgs {
+                path: path.clone(),
+                count: args,
+                limit: limits.max_args,
+            });
+        }
+
+        let lines = body_lines(function.body());
+        if lines > limits.max_lines {
+            self.issues.push(ComplexityIssue::TooManyLines {
+                path: path.clone(),
+                count: lines,
+                limit: limits.max_lines,
+            });
+        }
+
+        let depth = body_depth(function.body());
+        if depth > limits.max_depth {
+            self.issues.push(ComplexityIssue::TooDeep {
+                path,
+                depth,
+                limit: limits.max_depth,
+            });
+        }
+    }
+}
+
+fn body_lines(body: &[Body]) -> usize {
+    body.iter()
+        .map(|b| match b {
+            Body::String(s) => s.lines().count().max(1),
+            Body::Block(block) => body_lines(block.body()),
+            Body::Comment(_) => 0,
+            Body::Attributed(_, body) => body_lines(core::slice::from_ref(body)),
+            Body::LetElse(_) => 3,
+        })
+        .sum()
+}
+
+fn body_depth(body: &[Body]) -> usize {
+    body.iter()
+        .map(|b| match b {
+            Body::String(_) | Body::Comment(_) | Body::LetElse(_) => 0,
+            Body::Block(block) => 1 + body_depth(block.body()),
+            Body::Attributed(_, body) => body_depth(core::slice::from_ref(body)),
+        })
+        .max()
+        .unwrap_or(0)
+}
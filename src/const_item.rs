@@ -0,0 +1,377 @@
+use core::fmt::{self, Write};
+
+use crate::attribute::Attribute;
+use crate::doc::Doc;
+use crate::formatter::Formatter;
+use crate::lint::Lint;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// Defines the keyword of a [`ConstItem`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConstKind {
+    /// `const NAME: Ty = value;`
+    #[default]
+    Const,
+    /// `static NAME: Ty = value;`
+    Static,
+    /// `static mut NAME: Ty = value;`
+    StaticMut,
+}
+
+/// Defines a `const` or `static` item with its value.
+///
+/// Unlike [`AssociatedConst`](crate::AssociatedConst), which declares a
+/// const in a trait, a const item is complete: it can be documented and
+/// carry lints and attributes. Consts can be pushed to scopes, modules and
+/// `impl` blocks; statics only to scopes and modules.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConstItem {
+    /// Item name
+    name: String,
+
+    /// Item type
+    ty: Type,
+
+    /// Initializer expression
+    value: String,
+
+    /// Item keyword
+    kind: ConstKind,
+
+    /// Visibility
+    vis: Vis,
+
+    /// Item documentation
+    doc: Option<Doc>,
+
+    /// Lint rules, e.g. `#[allow(clippy::unreadable_literal)]`
+    lints: Vec<Lint>,
+
+    /// Other attributes
+    attributes: Vec<Attribute>,
+
+    /// Whether to omit the blank line before the item
+    compact: bool,
+
+    /// Where the item was generated from
+    provenance: Option<Provenance>,
+}
+
+impl ConstItem {
+    /// Creates a new `const` item.
+    pub fn new(name: impl Into<String>, ty: impl Into<Type>, value: impl Into<String>) -> Self {
+        ConstItem {
+            name: name.into(),
+            ty: ty.into(),
+            value: value.into(),
+            kind: ConstKind::Const,
+            vis: Vis::Private,
+            doc: None,
+            lints: Vec::new(),
+            attributes: Vec::new(),
+            compact: false,
+            provenance: None,
+        }
+    }
+
+    /// Creates a new `static` item.
+    pub fn new_static(
+        name: impl Into<String>,
+        ty: impl Into<Type>,
+        value: impl Into<String>,
+    ) -> Self {
+        ConstItem::new(name, ty, value).with_kind(ConstKind::Static)
+    }
+
+    /// Gets the item name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the item name.
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the item name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Gets a mutable reference to the item name.
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    /// Gets the item type.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Sets the item type.
+    pub fn set_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
+        self.ty = ty.into();
+        self
+    }
+
+    /// Sets the item type.
+    pub fn with_ty(mut self, ty: impl Into<Type>) -> Self {
+        self.set_ty(ty);
+        self
+    }
+
+    /// Gets a mutable reference to the item type.
+    pub fn ty_mut(&mut self) -> &mut Type {
+        &mut self.ty
+    }
+
+    /// Gets the initializer expression.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Sets the initializer expression.
+    pub fn set_value(&mut self, value: impl Into<String>) -> &mut Self {
+        self.value = value.into();
+        self
+    }
+
+    /// Sets the initializer expression.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Gets a mutable reference to the initializer expression.
+    pub fn value_mut(&mut self) -> &mut String {
+        &mut self.value
+    }
+
+    /// Gets the item keyword.
+    pub fn kind(&self) -> ConstKind {
+        self.kind
+    }
+
+    /// Sets the item keyword.
+    pub fn set_kind(&mut self, kind: ConstKind) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the item keyword.
+    pub fn with_kind(mut self, kind: ConstKind) -> Self {
+        self.set_kind(kind);
+        self
+    }
+
+    /// Gets a mutable reference to the item keyword.
+    pub fn kind_mut(&mut self) -> &mut ConstKind {
+        &mut self.kind
+    }
+
+    /// Gets the visibility.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets a mutable reference to the visibility.
+    pub fn vis_mut(&mut self) -> &mut Vis {
+        &mut self.vis
+    }
+
+    /// Gets the item documentation.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// Sets the item documentation.
+    pub fn set_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the item documentation.
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
+    where
+        S: Into<Doc>,
+    {
+        self.set_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the item documentation.
+    pub fn doc_mut(&mut self) -> &mut Option<Doc> {
+        &mut self.doc
+    }
+
+    /// Gets the lints.
+    pub fn lints(&self) -> &[Lint] {
+        &self.lints
+    }
+
+    /// Sets the lints.
+    pub fn set_lints<L>(&mut self, lints: impl IntoIterator<Item = L>) -> &mut Self
+    where
+        L: Into<Lint>,
+    {
+        self.lints = lints.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the lints.
+    pub fn with_lints<L>(mut self, lints: impl IntoIterator<Item = L>) -> Self
+    where
+        L: Into<Lint>,
+    {
+        self.set_lints(lints);
+        self
+    }
+
+    /// Gets a mutable reference to the lints.
+    pub fn lints_mut(&mut self) -> &mut Vec<Lint> {
+        &mut self.lints
+    }
+
+    /// Pushes a lint.
+    pub fn push_lint(&mut self, lint: impl Into<Lint>) -> &mut Self {
+        self.lints.push(lint.into());
+        self
+    }
+
+    /// Pushes a lint.
+    pub fn with_lint(mut self, lint: impl Into<Lint>) -> Self {
+        self.push_lint(lint);
+        self
+    }
+
+    /// Gets the attributes.
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Sets the attributes.
+    pub fn set_attributes<A>(&mut self, attributes: impl IntoIterator<Item = A>) -> &mut Self
+    where
+        A: Into<Attribute>,
+    {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the attributes.
+    pub fn with_attributes<A>(mut self, attributes: impl IntoIterator<Item = A>) -> Self
+    where
+        A: Into<Attribute>,
+    {
+        self.set_attributes(attributes);
+        self
+    }
+
+    /// Gets a mutable reference to the attributes.
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+
+    /// Pushes an attribute.
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Pushes an attribute.
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.push_attribute(attribute);
+        self
+    }
+
+    /// Returns whether the item is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets whether the item is rendered without a blank line before it,
+    /// e.g. to group consecutive constants.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether the item is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the item is rendered without a
+    /// blank line before it.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    /// Gets where the item was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Sets where the item was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    /// Sets where the item was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the item was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
+    }
+
+    /// Formats the item using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref doc) = self.doc {
+            doc.fmt(fmt)?;
+        }
+        for lint in &self.lints {
+            lint.fmt(fmt)?;
+        }
+        for attr in &self.attributes {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        self.vis.fmt(fmt)?;
+        match self.kind {
+            ConstKind::Const => write!(fmt, "const ")?,
+            ConstKind::Static => write!(fmt, "static ")?,
+            ConstKind::StaticMut => write!(fmt, "static mut ")?,
+        }
+        write!(fmt, "{}: ", self.name)?;
+        self.ty.fmt(fmt)?;
+        writeln!(fmt, " = {};", self.value)
+    }
+}
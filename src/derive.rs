@@ -0,0 +1,133 @@
+use core::fmt;
+
+use crate::prelude::*;
+
+/// Defines a trait to derive, for use with `push_derive`.
+///
+/// Using the variants instead of strings catches typos in the names of common
+/// traits at compile time.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Derive {
+    /// `Debug`
+    Debug,
+    /// `Clone`
+    Clone,
+    /// `Copy`
+    Copy,
+    /// `PartialEq`
+    PartialEq,
+    /// `Eq`
+    Eq,
+    /// `PartialOrd`
+    PartialOrd,
+    /// `Ord`
+    Ord,
+    /// `Hash`
+    Hash,
+    /// `Default`
+    Default,
+    /// `serde::Serialize`, named by path so no import is needed
+    Serialize,
+    /// `serde::Deserialize`, named by path so no import is needed
+    Deserialize,
+    /// Any other derive macro, used verbatim
+    Custom(String),
+}
+
+impl Derive {
+    /// Creates a derive of any other macro.
+    pub fn custom(name: impl Into<String>) -> Self {
+        Derive::Custom(name.into())
+    }
+
+    /// Gets the name of the derive macro.
+    pub fn name(&self) -> &str {
+        match self {
+            Derive::Debug => "Debug",
+            Derive::Clone => "Clone",
+            Derive::Copy => "Copy",
+            Derive::PartialEq => "PartialEq",
+            Derive::Eq => "Eq",
+            Derive::PartialOrd => "PartialOrd",
+            Derive::Ord => "Ord",
+            Derive::Hash => "Hash",
+            Derive::Default => "Default",
+            Derive::Serialize => "serde::Serialize",
+            Derive::Deserialize => "serde::Deserialize",
+            Derive::Custom(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Derive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl From<Derive> for String {
+    fn from(derive: Derive) -> Self {
+        match derive {
+            Derive::Custom(name) => name,
+            derive => derive.name().to_string(),
+        }
+    }
+}
+
+/// Defines how the derives of a type are rendered.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeriveOrder {
+    /// Duplicates are dropped, the first occurrence keeps its position.
+    #[default]
+    Deduped,
+    /// Duplicates are dropped and the derives of [`Derive`] come first, in
+    /// the order of its variants, followed by any other derives as pushed.
+    Canonical,
+    /// Derives are rendered exactly as pushed, duplicates included.
+    Exact,
+}
+
+impl DeriveOrder {
+    /// Returns the derives to render, in order.
+    pub(crate) fn apply<'a>(&self, derives: &'a [String]) -> Vec<&'a str> {
+        if *self == DeriveOrder::Exact {
+            return derives.iter().map(String::as_str).collect();
+        }
+
+        let mut ret: Vec<&str> = Vec::with_capacity(derives.len());
+        for derive in derives {
+            if !ret.contains(&derive.as_str()) {
+                ret.push(derive);
+            }
+        }
+
+        if *self == DeriveOrder::Canonical {
+            ret.sort_by_key(|derive| canonical_rank(derive));
+        }
+        ret
+    }
+}
+
+fn canonical_rank(name: &str) -> usize {
+    const CANONICAL: &[&str] = &[
+        "Debug",
+        "Clone",
+        "Copy",
+        "PartialEq",
+        "Eq",
+        "PartialOrd",
+        "Ord",
+        "Hash",
+        "Default",
+        "Serialize",
+        "Deserialize",
+    ];
+
+    let name = name.strip_prefix("serde::").unwrap_or(name);
+    CANONICAL
+        .iter()
+        .position(|derive| *derive == name)
+        .unwrap_or(CANONICAL.len())
+}
@@ -1,9 +1,11 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
 use crate::formatter::Formatter;
+use crate::prelude::*;
 
 /// Wrapper type over a documentation string.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Doc(String);
 
 impl<S: Into<String>> From<S> for Doc {
@@ -19,7 +21,7 @@ impl Doc {
     }
 
     /// Gets the inner `String` type.
-    pub fn as_inner(&self) -> &String {
+    pub fn as_inner(&self) -> &str {
         &self.0
     }
 
@@ -30,8 +32,18 @@ impl Doc {
 
     /// Formats the doc using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_prefix("///", fmt)
+    }
+
+    /// Formats the doc as inner doc comments, documenting the enclosing
+    /// module or crate.
+    pub(crate) fn fmt_inner(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_prefix("//!", fmt)
+    }
+
+    fn fmt_with_prefix(&self, prefix: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
         for line in self.0.lines() {
-            write!(fmt, "///")?;
+            write!(fmt, "{}", prefix)?;
             if !line.is_empty() {
                 write!(fmt, " {}", line)?;
             }
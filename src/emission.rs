@@ -0,0 +1,63 @@
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+
+/// A generator producing an `impl` block, such as [`ImplStub`](crate::ImplStub).
+///
+/// Generators implementing this trait can be emitted either as expanded code
+/// or as a macro invocation, see [`Emission`].
+pub trait ImplGenerator {
+    /// Error returned when the generator is incomplete or inconsistent.
+    type Error;
+
+    /// Generates the expanded `impl` block.
+    fn generate_impl(&self) -> Result<Impl, Self::Error>;
+
+    /// Gets the arguments of a macro invocation generating the same `impl`
+    /// block, starting with the implementing type.
+    fn macro_args(&self) -> Vec<String>;
+}
+
+/// Defines how the output of an [`ImplGenerator`] is emitted.
+///
+/// ```rust
+/// use simple_codegen::{Emission, ImplStub, Scope, Trait};
+///
+/// let stub = ImplStub::new(Trait::new("Handler"), "Foo");
+///
+/// let mut scope = Scope::new();
+/// scope.push_item(Emission::macro_call("impl_handler").emit(&stub).unwrap());
+///
+/// assert_eq!(scope.to_string(), "impl_handler!(Foo);");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Emission {
+    /// The expanded `impl` block.
+    #[default]
+    Expanded,
+    /// An invocation of the named macro, e.g. `impl_trait_for!(Foo);`.
+    ///
+    /// The macro must be defined by the generated crate. The generator is not
+    /// run, so it is not checked for completeness.
+    Macro(String),
+}
+
+impl Emission {
+    /// Creates a strategy emitting an invocation of the named macro.
+    pub fn macro_call(name: impl Into<String>) -> Self {
+        Emission::Macro(name.into())
+    }
+
+    /// Emits the output of the generator.
+    pub fn emit<G: ImplGenerator>(&self, generator: &G) -> Result<Item, G::Error> {
+        match self {
+            Emission::Expanded => generator.generate_impl().map(Item::Impl),
+            Emission::Macro(name) => Ok(Item::Raw(format!(
+                "{}!({});",
+                name,
+                generator.macro_args().join(", ")
+            ))),
+        }
+    }
+}
@@ -1,20 +1,31 @@
-use std::fmt;
+use core::fmt::{self, Write};
 
+use crate::attribute::Attribute;
 use crate::bound::Bound;
+use crate::case::to_camel_case;
+use crate::derive::DeriveOrder;
 use crate::doc::Doc;
 use crate::formatter::Formatter;
+use crate::function::Function;
 use crate::generic_parameter::GenericParameter;
+use crate::ident::sanitize_ident;
 use crate::lint::Lint;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::repr::Repr;
 use crate::r#type::Type;
 use crate::type_def::TypeDef;
 use crate::variant::Variant;
 use crate::visibility::Vis;
 
 /// Defines an [enum](https://doc.rust-lang.org/rust-by-example/custom_types/enum.html).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Enum {
     type_def: TypeDef,
     variants: Vec<Variant>,
+    spaced_variants: bool,
+    tests: Vec<Function>,
 }
 
 impl Enum {
@@ -23,6 +34,8 @@ impl Enum {
         Enum {
             type_def: TypeDef::new(name.into()),
             variants: Vec::new(),
+            spaced_variants: false,
+            tests: Vec::new(),
         }
     }
 
@@ -107,7 +120,7 @@ impl Enum {
     }
 
     /// Sets the generics for this enum.
-    pub fn with_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
+    pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
     {
@@ -167,7 +180,7 @@ impl Enum {
     }
 
     /// Pushes a `where` bound to the enum.
-    pub fn with_bound(&mut self, bound: impl Into<Bound>) -> &mut Self {
+    pub fn with_bound(mut self, bound: impl Into<Bound>) -> Self {
         self.push_bound(bound);
         self
     }
@@ -226,6 +239,8 @@ impl Enum {
     }
 
     /// Pushes a new type that the struct should derive.
+    ///
+    /// Accepts a [`Derive`](crate::Derive) or the name of any derive macro.
     pub fn push_derive(&mut self, derive: impl Into<String>) -> &mut Self {
         self.type_def.push_derive(derive.into());
         self
@@ -237,6 +252,26 @@ impl Enum {
         self
     }
 
+    /// Gets how the derives for this enum are rendered.
+    pub fn derive_order(&self) -> DeriveOrder {
+        self.type_def.derive_order()
+    }
+
+    /// Sets how the derives for this enum are rendered.
+    ///
+    /// Duplicate derives are dropped by default, use [`DeriveOrder::Exact`]
+    /// to render them exactly as pushed.
+    pub fn set_derive_order(&mut self, derive_order: DeriveOrder) -> &mut Self {
+        self.type_def.set_derive_order(derive_order);
+        self
+    }
+
+    /// Sets how the derives for this enum are rendered.
+    pub fn with_derive_order(mut self, derive_order: DeriveOrder) -> Self {
+        self.set_derive_order(derive_order);
+        self
+    }
+
     /// Gets the lints for this enum.
     pub fn lints(&self) -> &[Lint] {
         self.type_def.lints()
@@ -277,37 +312,94 @@ impl Enum {
         self
     }
 
-    /// Gets the representation.
-    pub fn repr(&self) -> Option<&String> {
+    /// Gets the attributes of the enum.
+    pub fn attributes(&self) -> &[Attribute] {
+        self.type_def.attributes()
+    }
+
+    /// Sets the attributes of the enum.
+    pub fn set_attributes<A>(&mut self, attributes: impl IntoIterator<Item = A>) -> &mut Self
+    where
+        A: Into<Attribute>,
+    {
+        self.type_def.set_attributes(attributes);
+        self
+    }
+
+    /// Sets the attributes of the enum.
+    pub fn with_attributes<A>(mut self, attributes: impl IntoIterator<Item = A>) -> Self
+    where
+        A: Into<Attribute>,
+    {
+        self.set_attributes(attributes);
+        self
+    }
+
+    /// Gets a mutable reference to the attributes of the enum.
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        self.type_def.attributes_mut()
+    }
+
+    /// Pushes a new attribute to the enum.
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.type_def.push_attribute(attribute.into());
+        self
+    }
+
+    /// Pushes a new attribute to the enum.
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.push_attribute(attribute);
+        self
+    }
+
+    /// Gets the representation hints.
+    pub fn repr(&self) -> &[Repr] {
         self.type_def.repr()
     }
 
-    /// Sets the representation.
-    pub fn set_repr(&mut self, repr: impl Into<Option<String>>) -> &mut Self {
+    /// Sets the representation hints, e.g. `[Repr::C, Repr::Packed(Some(4))]`.
+    ///
+    /// This used to take a single `impl Into<Option<String>>`; such values
+    /// still work, as `Some("C")` is a list of one hint. Hints are written as
+    /// given, [`Scope::check`](crate::Scope::check) reports the ones that
+    /// conflict or are not supported on enums.
+    pub fn set_repr<R>(&mut self, repr: impl IntoIterator<Item = R>) -> &mut Self
+    where
+        R: Into<Repr>,
+    {
         self.type_def.set_repr(repr);
         self
     }
 
-    /// Sets the representation.
-    pub fn with_repr(mut self, repr: impl Into<Option<String>>) -> Self {
+    /// Sets the representation hints.
+    pub fn with_repr<R>(mut self, repr: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Into<Repr>,
+    {
         self.set_repr(repr);
         self
     }
 
-    /// Gets a mutable reference to the representation.
-    pub fn repr_mut(&mut self) -> Option<&mut String> {
+    /// Gets a mutable reference to the representation hints.
+    pub fn repr_mut(&mut self) -> &mut Vec<Repr> {
         self.type_def.repr_mut()
     }
 
+    /// Pushes a representation hint.
+    pub fn push_repr(&mut self, repr: impl Into<Repr>) -> &mut Self {
+        self.type_def.push_repr(repr);
+        self
+    }
+
     /// Gets the macros for this enum.
-    pub fn macros(&self) -> &[String] {
+    pub fn macros(&self) -> &[Attribute] {
         self.type_def.macros()
     }
 
     /// Sets the macros for this enum.
     pub fn set_macros<S>(&mut self, macros: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.type_def.set_macros(macros);
         self
@@ -316,25 +408,26 @@ impl Enum {
     /// Sets the macros for this enum.
     pub fn with_macros<S>(mut self, macros: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_macros(macros);
         self
     }
 
     /// Gets a mutable reference to the macros for this enum.
-    pub fn macros_mut(&mut self) -> &mut Vec<String> {
+    pub fn macros_mut(&mut self) -> &mut Vec<Attribute> {
         self.type_def.macros_mut()
     }
 
-    /// Pushes an arbitrary macro.
-    pub fn push_macro(&mut self, r#macro: impl Into<String>) -> &mut Self {
+    /// Pushes an attribute macro, e.g. `#[enum_dispatch]`, written after the
+    /// other attributes of the enum. Duplicates are skipped.
+    pub fn push_macro(&mut self, r#macro: impl Into<Attribute>) -> &mut Self {
         self.type_def.push_macro(r#macro);
         self
     }
 
-    /// Pushes an arbitrary macro.
-    pub fn with_macro(mut self, r#macro: impl Into<String>) -> Self {
+    /// Pushes an attribute macro, e.g. `#[enum_dispatch]`.
+    pub fn with_macro(mut self, r#macro: impl Into<Attribute>) -> Self {
         self.push_macro(r#macro);
         self
     }
@@ -379,12 +472,200 @@ impl Enum {
         self
     }
 
+    /// Pushes a variant to the enum, converting its name to `CamelCase`.
+    ///
+    /// The converted name is also passed through [`sanitize_ident`], so schema
+    /// values like `"in-progress"` become valid variant names.
+    pub fn push_variant_auto_case(&mut self, variant: impl Into<Variant>) -> &mut Self {
+        let mut variant = variant.into();
+        let name = sanitize_ident(to_camel_case(variant.name()));
+        variant.set_name(name);
+        self.push_variant(variant)
+    }
+
+    /// Pushes a variant to the enum, converting its name to `CamelCase`.
+    ///
+    /// The converted name is also passed through [`sanitize_ident`], so schema
+    /// values like `"in-progress"` become valid variant names.
+    pub fn with_variant_auto_case(mut self, variant: impl Into<Variant>) -> Self {
+        self.push_variant_auto_case(variant);
+        self
+    }
+
+    /// Returns whether variants are separated by a blank line.
+    pub fn has_spaced_variants(&self) -> bool {
+        self.spaced_variants
+    }
+
+    /// Sets whether variants are separated by a blank line.
+    ///
+    /// Useful for enums whose variants are documented or span several lines.
+    pub fn set_spaced_variants(&mut self, spaced_variants: bool) -> &mut Self {
+        self.spaced_variants = spaced_variants;
+        self
+    }
+
+    /// Sets whether variants are separated by a blank line.
+    pub fn with_spaced_variants(mut self, spaced_variants: bool) -> Self {
+        self.set_spaced_variants(spaced_variants);
+        self
+    }
+
+    /// Gets a mutable reference to whether variants are separated by a blank
+    /// line.
+    pub fn spaced_variants_mut(&mut self) -> &mut bool {
+        &mut self.spaced_variants
+    }
+
+    /// Gets the tests attached to the enum.
+    pub fn tests(&self) -> &[Function] {
+        &self.tests
+    }
+
+    /// Sets the tests attached to the enum.
+    pub fn set_tests<F>(&mut self, tests: impl IntoIterator<Item = F>) -> &mut Self
+    where
+        F: Into<Function>,
+    {
+        self.tests = tests.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the tests attached to the enum.
+    pub fn with_tests<F>(mut self, tests: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Into<Function>,
+    {
+        self.set_tests(tests);
+        self
+    }
+
+    /// Gets a mutable reference to the tests attached to the enum.
+    pub fn tests_mut(&mut self) -> &mut Vec<Function> {
+        &mut self.tests
+    }
+
+    /// Attaches a test to the enum.
+    ///
+    /// Tests are rendered in a `#[cfg(test)] mod tests` at the end of the
+    /// scope containing the enum, or appended to the `tests` module of that
+    /// scope if it already has one.
+    pub fn push_test(&mut self, test: impl Into<Function>) -> &mut Self {
+        self.tests.push(test.into());
+        self
+    }
+
+    /// Attaches a test to the enum.
+    ///
+    /// Tests are rendered in a `#[cfg(test)] mod tests` at the end of the
+    /// scope containing the enum.
+    pub fn with_test(mut self, test: impl Into<Function>) -> Self {
+        self.push_test(test);
+        self
+    }
+
+    /// Returns whether the enum is `#[non_exhaustive]`.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.type_def.is_non_exhaustive()
+    }
+
+    /// Sets whether the enum is `#[non_exhaustive]`.
+    pub fn set_non_exhaustive(&mut self, non_exhaustive: bool) -> &mut Self {
+        self.type_def.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    /// Sets whether the enum is `#[non_exhaustive]`.
+    pub fn with_non_exhaustive(mut self, non_exhaustive: bool) -> Self {
+        self.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    /// Gets a mutable reference to whether the enum is `#[non_exhaustive]`.
+    pub fn non_exhaustive_mut(&mut self) -> &mut bool {
+        self.type_def.non_exhaustive_mut()
+    }
+
+    /// Gets the `#[must_use]` attribute of the enum: `None` if it is not
+    /// `#[must_use]`, otherwise the optional reason.
+    pub fn must_use(&self) -> Option<Option<&str>> {
+        self.type_def.must_use()
+    }
+
+    /// Marks the enum as `#[must_use]`, with an optional reason, e.g.
+    /// `#[must_use = "reason"]`.
+    pub fn set_must_use(&mut self, reason: Option<&str>) -> &mut Self {
+        self.type_def.set_must_use(reason);
+        self
+    }
+
+    /// Marks the enum as `#[must_use]`, with an optional reason.
+    pub fn with_must_use(mut self, reason: Option<&str>) -> Self {
+        self.set_must_use(reason);
+        self
+    }
+
+    /// Gets a mutable reference to the `#[must_use]` attribute of the enum.
+    pub fn must_use_mut(&mut self) -> &mut Option<Option<String>> {
+        self.type_def.must_use_mut()
+    }
+
+    /// Gets whether the enum is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.type_def.is_compact()
+    }
+
+    /// Sets whether the enum is rendered without a blank line before it.
+    ///
+    /// Use it to keep small related enums together, e.g. a few fieldless
+    /// status enums.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.type_def.set_compact(compact);
+        self
+    }
+
+    /// Sets whether the enum is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the enum is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        self.type_def.compact_mut()
+    }
+
+    /// Gets where the enum was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.type_def.provenance()
+    }
+
+    /// Sets where the enum was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.type_def.set_provenance(provenance);
+        self
+    }
+
+    /// Sets where the enum was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the enum was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        self.type_def.provenance_mut()
+    }
+
     /// Formats the enum using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         self.type_def.fmt_head("enum", &[], fmt)?;
 
         fmt.block(|fmt| {
-            for variant in &self.variants {
+            for (i, variant) in self.variants.iter().enumerate() {
+                if i != 0 && self.spaced_variants {
+                    writeln!(fmt)?;
+                }
                 variant.fmt(fmt)?;
             }
 
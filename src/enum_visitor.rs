@@ -0,0 +1,158 @@
+use crate::associated_type::AssociatedType;
+use crate::case::to_snake_case;
+use crate::r#enum::Enum;
+use crate::fields::Fields;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::r#trait::Trait;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// Generates a visitor trait for an enum.
+///
+/// The trait has a `visit_` method per variant, taking references to the
+/// fields of the variant and returning the `Output` of the visitor. The enum
+/// gets an `accept` method dispatching to the method of its variant. Tuple
+/// fields are passed as `f0`, `f1`, ... and named fields by their names, so
+/// `accept` names its visitor parameter `__visitor` to keep clear of them.
+/// The trait is generic over the generics of the enum, e.g. `ExprVisitor<T>`
+/// for `Expr<T>`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumVisitor {
+    /// Enum to visit
+    target: Enum,
+
+    /// Name of the visitor trait
+    trait_name: String,
+}
+
+impl EnumVisitor {
+    /// Creates a visitor for the enum, named after it, e.g. `ExprVisitor`.
+    pub fn new(target: impl Into<Enum>) -> Self {
+        let target = target.into();
+        let trait_name = format!("{}Visitor", target.name());
+        EnumVisitor { target, trait_name }
+    }
+
+    /// Gets the enum to visit.
+    pub fn target(&self) -> &Enum {
+        &self.target
+    }
+
+    /// Gets the name of the visitor trait.
+    pub fn trait_name(&self) -> &str {
+        &self.trait_name
+    }
+
+    /// Sets the name of the visitor trait.
+    pub fn set_trait_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.trait_name = name.into();
+        self
+    }
+
+    /// Sets the name of the visitor trait.
+    pub fn with_trait_name(mut self, name: impl Into<String>) -> Self {
+        self.set_trait_name(name);
+        self
+    }
+
+    /// Generates the visitor trait and the impl block with `accept`.
+    pub fn generate(&self) -> Vec<Item> {
+        vec![self.visitor_trait().into(), self.accept_impl().into()]
+    }
+
+    /// Generates the visitor trait.
+    pub fn visitor_trait(&self) -> Trait {
+        let mut ret = Trait::new(self.trait_name.as_str())
+            .with_vis(self.target.vis().clone())
+            .with_generics(self.target.generics().iter().cloned())
+            .with_bounds(self.target.bounds().iter().cloned())
+            .with_associated_type(AssociatedType::new("Output"));
+        for variant in self.target.variants() {
+            let mut function = Function::new(method_name(variant.name()))
+                .with_self_arg(SelfArg::WithMutSelfRef)
+                .with_ret("Self::Output");
+            for (name, ty) in bindings(variant.fields()) {
+                function.push_arg(name, Type::reference(ty.clone()));
+            }
+            ret.push_function(function);
+        }
+        ret
+    }
+
+    /// Generates the impl block of the enum with the `accept` method.
+    pub fn accept_impl(&self) -> Impl {
+        let name = self.target.name();
+        let generics = self.target.generics();
+        let visitor = if generics.iter().any(|g| g.bare_name() == "V") {
+            "__V"
+        } else {
+            "V"
+        };
+        let mut r#trait = self.trait_name.clone();
+        if !generics.is_empty() {
+            let names: Vec<&str> = generics.iter().map(|g| g.bare_name()).collect();
+            r#trait = format!("{}<{}>", r#trait, names.join(", "));
+        }
+        let mut function = Function::new("accept")
+            .with_vis(Vis::Pub)
+            .with_generic(format!("{}: {}", visitor, r#trait))
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("__visitor", format!("&mut {}", visitor))
+            .with_ret(format!("{}::Output", visitor));
+
+        if self.target.variants().is_empty() {
+            function.push_line("match *self {}");
+        } else {
+            function.push_line("match self {");
+            for variant in self.target.variants() {
+                let binds: Vec<String> = bindings(variant.fields())
+                    .into_iter()
+                    .map(|(name, _)| name)
+                    .collect();
+                let pattern = match variant.fields() {
+                    Fields::Empty => String::new(),
+                    Fields::Tuple(_) => format!("({})", binds.join(", ")),
+                    Fields::Named(_) => format!(" {{ {} }}", binds.join(", ")),
+                };
+                function.push_line(format!(
+                    "    {}::{}{} => __visitor.{}({}),",
+                    name,
+                    variant.name(),
+                    pattern,
+                    method_name(variant.name()),
+                    binds.join(", ")
+                ));
+            }
+            function.push_line("}");
+        }
+
+        Impl::new_for_definition(name, generics)
+            .with_bounds(self.target.bounds().iter().cloned())
+            .with_function(function)
+    }
+}
+
+/// Returns the name of the visitor method of a variant.
+fn method_name(variant: &str) -> String {
+    format!("visit_{}", to_snake_case(variant))
+}
+
+/// Returns the names bound to the fields of a variant, with their types.
+fn bindings(fields: &Fields) -> Vec<(String, &Type)> {
+    match fields {
+        Fields::Empty => Vec::new(),
+        Fields::Tuple(tys) => tys
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (format!("f{}", i), f.ty()))
+            .collect(),
+        Fields::Named(fields) => fields
+            .iter()
+            .map(|f| (f.name().to_string(), f.ty()))
+            .collect(),
+    }
+}
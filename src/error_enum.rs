@@ -0,0 +1,289 @@
+use thiserror::Error;
+
+use crate::r#enum::Enum;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::r#type::Type;
+use crate::variant::Variant;
+use crate::visibility::Vis;
+
+/// Defines a variant of an [`ErrorEnum`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorVariant {
+    /// Variant name
+    name: String,
+
+    /// Format string of the `Display` message
+    message: String,
+
+    /// Wrapped error, if any
+    source: Option<Type>,
+}
+
+impl ErrorVariant {
+    /// Creates a variant displayed with the given message.
+    pub fn new(name: impl Into<String>, message: impl Into<String>) -> Self {
+        ErrorVariant {
+            name: name.into(),
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates a variant wrapping a `source` error.
+    ///
+    /// The message can refer to the wrapped error as `{source}`.
+    pub fn wrapping(
+        name: impl Into<String>,
+        message: impl Into<String>,
+        source: impl Into<Type>,
+    ) -> Self {
+        ErrorVariant {
+            source: Some(source.into()),
+            ..ErrorVariant::new(name, message)
+        }
+    }
+
+    /// Gets the variant name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the format string of the `Display` message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the wrapped error type, if any.
+    pub fn source(&self) -> Option<&Type> {
+        self.source.as_ref()
+    }
+}
+
+/// Generates an error enum with its `Display`, `Error` and `From` impls.
+///
+/// This produces the same code as deriving `thiserror::Error`: each variant
+/// is displayed with its message, and variants wrapping another error return
+/// it from `source()` and can be converted from it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorEnum {
+    /// Name of the enum
+    name: String,
+
+    /// Visibility of the enum
+    vis: Vis,
+
+    /// Variants of the enum
+    variants: Vec<ErrorVariant>,
+}
+
+impl ErrorEnum {
+    /// Creates an error enum without variants.
+    pub fn new(name: impl Into<String>) -> Self {
+        ErrorEnum {
+            name: name.into(),
+            vis: Vis::Private,
+            variants: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the enum.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the visibility of the enum.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility of the enum.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility of the enum.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets the variants of the enum.
+    pub fn variants(&self) -> &[ErrorVariant] {
+        &self.variants
+    }
+
+    /// Pushes a variant displayed with the given message.
+    pub fn push_variant(
+        &mut self,
+        name: impl Into<String>,
+        message: impl Into<String>,
+    ) -> &mut Self {
+        self.variants.push(ErrorVariant::new(name, message));
+        self
+    }
+
+    /// Pushes a variant displayed with the given message.
+    pub fn with_variant(mut self, name: impl Into<String>, message: impl Into<String>) -> Self {
+        self.push_variant(name, message);
+        self
+    }
+
+    /// Pushes a variant wrapping a `source` error.
+    ///
+    /// The message can refer to the wrapped error as `{source}`.
+    pub fn push_wrapping_variant(
+        &mut self,
+        name: impl Into<String>,
+        message: impl Into<String>,
+        source: impl Into<Type>,
+    ) -> &mut Self {
+        self.variants
+            .push(ErrorVariant::wrapping(name, message, source));
+        self
+    }
+
+    /// Pushes a variant wrapping a `source` error.
+    pub fn with_wrapping_variant(
+        mut self,
+        name: impl Into<String>,
+        message: impl Into<String>,
+        source: impl Into<Type>,
+    ) -> Self {
+        self.push_wrapping_variant(name, message, source);
+        self
+    }
+
+    /// Generates the enum, followed by its `Display` and `Error` impls and a
+    /// `From` impl for each wrapped error.
+    ///
+    /// Fails if two variants wrap the same error type, as the `From` impls
+    /// would conflict.
+    pub fn generate(&self) -> Result<Vec<Item>, ErrorEnumError> {
+        let mut items = vec![
+            Item::from(self.to_enum()),
+            Item::from(self.display_impl()),
+            Item::from(self.error_impl()),
+        ];
+        items.extend(self.from_impls()?.into_iter().map(Item::from));
+        Ok(items)
+    }
+
+    /// Generates the enum definition.
+    pub fn to_enum(&self) -> Enum {
+        let mut ret = Enum::new(self.name.as_str())
+            .with_vis(self.vis.clone())
+            .with_derive("Debug");
+        for variant in &self.variants {
+            let mut v = Variant::new(variant.name.as_str());
+            if let Some(source) = &variant.source {
+                v.push_tuple_field(source.clone());
+            }
+            ret.push_variant(v);
+        }
+        ret
+    }
+
+    /// Generates the `impl std::fmt::Display` block.
+    pub fn display_impl(&self) -> Impl {
+        let mut function = Function::new("fmt")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("f", "&mut std::fmt::Formatter<'_>")
+            .with_ret("std::fmt::Result");
+        if self.variants.is_empty() {
+            function.push_line("match *self {}");
+            return Impl::new(self.name.as_str())
+                .with_impl_trait("std::fmt::Display")
+                .with_function(function);
+        }
+
+        function.push_line("match self {");
+        for variant in &self.variants {
+            let pattern = match variant.source {
+                Some(_) if variant.message.contains("{source") => "(source)",
+                Some(_) => "(_)",
+                None => "",
+            };
+            function.push_line(format!(
+                "    Self::{}{} => write!(f, {:?}),",
+                variant.name, pattern, variant.message
+            ));
+        }
+        function.push_line("}");
+
+        Impl::new(self.name.as_str())
+            .with_impl_trait("std::fmt::Display")
+            .with_function(function)
+    }
+
+    /// Generates the `impl std::error::Error` block.
+    ///
+    /// `source()` is only overridden if a variant wraps another error.
+    pub fn error_impl(&self) -> Impl {
+        let mut ret = Impl::new(self.name.as_str()).with_impl_trait("std::error::Error");
+
+        let wrapping = self.variants.iter().filter(|v| v.source.is_some()).count();
+        if wrapping == 0 {
+            return ret;
+        }
+
+        let mut function = Function::new("source")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_ret("Option<&(dyn std::error::Error + 'static)>")
+            .with_line("match self {");
+        for variant in self.variants.iter().filter(|v| v.source.is_some()) {
+            function.push_line(format!(
+                "    Self::{}(source) => Some(source),",
+                variant.name
+            ));
+        }
+        if wrapping != self.variants.len() {
+            function.push_line("    _ => None,");
+        }
+        function.push_line("}");
+
+        ret.push_function(function);
+        ret
+    }
+
+    /// Generates an `impl From<Source>` block for each wrapped error.
+    ///
+    /// Fails if two variants wrap the same error type.
+    pub fn from_impls(&self) -> Result<Vec<Impl>, ErrorEnumError> {
+        let mut seen: Vec<&Type> = Vec::new();
+        let mut ret = Vec::new();
+        for variant in &self.variants {
+            let Some(source) = &variant.source else {
+                continue;
+            };
+            if seen.contains(&source) {
+                return Err(ErrorEnumError::DuplicateSource(source.to_source()));
+            }
+            seen.push(source);
+
+            let function = Function::new("from")
+                .with_arg("source", source.clone())
+                .with_ret("Self")
+                .with_line(format!("Self::{}(source)", variant.name));
+            ret.push(
+                Impl::new(self.name.as_str())
+                    .with_impl_trait(format!("From<{}>", source.to_source()))
+                    .with_function(function),
+            );
+        }
+        Ok(ret)
+    }
+}
+
+/// Error returned when an [`ErrorEnum`] cannot be generated.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ErrorEnumError {
+    /// Several variants wrap the same error type.
+    #[error("several variants wrap `{0}`")]
+    DuplicateSource(String),
+}
@@ -0,0 +1,217 @@
+use crate::prelude::*;
+use thiserror::Error;
+
+use crate::emission::ImplGenerator;
+use crate::r#enum::Enum;
+use crate::fields::Fields;
+use crate::function::Function;
+use crate::r#impl::Impl;
+use crate::variant::Variant;
+
+/// Generates a `From` impl converting one error enum into another.
+///
+/// Each variant of the source enum is mapped to an expression building the
+/// target error. Fields of the source variant are bound in the match arm:
+/// tuple fields as `f0`, `f1`, ... and named fields by their names. Variants
+/// without a mapping get their own arm using the default expression, if any,
+/// so that variants added to the source enum later fail to compile instead
+/// of being silently mapped; see [`unmapped`].
+///
+/// [`unmapped`]: #method.unmapped
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ErrorMapping {
+    /// Source error enum
+    source: Enum,
+
+    /// Name of the target error type
+    target: String,
+
+    /// Mapped source variants and their target expressions
+    arms: IndexMap<String, String>,
+
+    /// Expression used for unmapped variants
+    default: Option<String>,
+}
+
+impl ErrorMapping {
+    /// Creates a mapping from the `source` enum to the `target` type.
+    pub fn new(source: impl Into<Enum>, target: impl Into<String>) -> Self {
+        ErrorMapping {
+            source: source.into(),
+            target: target.into(),
+            arms: IndexMap::default(),
+            default: None,
+        }
+    }
+
+    /// Gets the source error enum.
+    pub fn source(&self) -> &Enum {
+        &self.source
+    }
+
+    /// Gets the name of the target error type.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Gets the mapped source variants and their target expressions.
+    pub fn arms(&self) -> &IndexMap<String, String> {
+        &self.arms
+    }
+
+    /// Maps a source variant to an expression building the target error.
+    pub fn push_arm(&mut self, variant: impl Into<String>, expr: impl Into<String>) -> &mut Self {
+        self.arms.insert(variant.into(), expr.into());
+        self
+    }
+
+    /// Maps a source variant to an expression building the target error.
+    pub fn with_arm(mut self, variant: impl Into<String>, expr: impl Into<String>) -> Self {
+        self.push_arm(variant, expr);
+        self
+    }
+
+    /// Gets the expression used for unmapped variants.
+    pub fn default(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+
+    /// Sets the expression used for unmapped variants.
+    pub fn set_default<S>(&mut self, expr: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.default = expr.into().map(Into::into);
+        self
+    }
+
+    /// Sets the expression used for unmapped variants.
+    pub fn with_default<S>(mut self, expr: impl Into<Option<S>>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_default(expr);
+        self
+    }
+
+    /// Gets the source variants that have no mapping and use the default
+    /// expression.
+    pub fn unmapped(&self) -> Vec<&str> {
+        self.source
+            .variants()
+            .iter()
+            .map(|v| v.name())
+            .filter(|name| !self.arms.contains_key(*name))
+            .collect()
+    }
+
+    /// Generates the `impl From<Source> for Target` block.
+    ///
+    /// Fails if a mapped variant does not exist in the source enum, or if a
+    /// source variant is unmapped and there is no default expression.
+    pub fn generate(&self) -> Result<Impl, ErrorMappingError> {
+        let variants = self.source.variants();
+
+        let unknown: Vec<String> = self
+            .arms
+            .keys()
+            .filter(|name| !variants.iter().any(|v| v.name() == name.as_str()))
+            .cloned()
+            .collect();
+        if !unknown.is_empty() {
+            return Err(ErrorMappingError::UnknownVariants(unknown));
+        }
+
+        let unmapped = self.unmapped();
+        if !unmapped.is_empty() && self.default.is_none() {
+            return Err(ErrorMappingError::Unmapped(
+                unmapped.into_iter().map(String::from).collect(),
+            ));
+        }
+
+        let source = self.source.name();
+        let mut function = Function::new("from")
+            .with_arg("value", source)
+            .with_ret("Self")
+            .with_line("match value {");
+
+        let mut binds = false;
+        for variant in variants {
+            let (pattern, expr) = match self.arms.get(variant.name()) {
+                Some(expr) => {
+                    binds |= !matches!(variant.fields(), Fields::Empty);
+                    (pattern(variant), expr)
+                }
+                None => (
+                    wildcard(variant).to_string(),
+                    self.default.as_ref().expect("checked above"),
+                ),
+            };
+            function.push_line(format!(
+                "    {}::{}{} => {},",
+                source,
+                variant.name(),
+                pattern,
+                expr
+            ));
+        }
+        function.push_line("}");
+
+        if binds {
+            function.push_attribute("allow(unused_variables)");
+        }
+
+        Ok(Impl::new(self.target.as_str())
+            .with_impl_trait(format!("From<{}>", source))
+            .with_function(function))
+    }
+}
+
+impl ImplGenerator for ErrorMapping {
+    type Error = ErrorMappingError;
+
+    fn generate_impl(&self) -> Result<Impl, Self::Error> {
+        self.generate()
+    }
+
+    /// Returns the target type, then the source enum.
+    fn macro_args(&self) -> Vec<String> {
+        vec![self.target.clone(), self.source.name().to_string()]
+    }
+}
+
+/// Error returned when an [`ErrorMapping`] is incomplete or inconsistent.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ErrorMappingError {
+    /// Mapped variants do not exist in the source enum.
+    #[error("unknown source variants: {}", .0.join(", "))]
+    UnknownVariants(Vec<String>),
+    /// Source variants are neither mapped nor covered by a default.
+    #[error("unmapped source variants: {}", .0.join(", "))]
+    Unmapped(Vec<String>),
+}
+
+/// Renders the pattern binding the fields of a variant.
+fn pattern(variant: &Variant) -> String {
+    match variant.fields() {
+        Fields::Empty => String::new(),
+        Fields::Tuple(tys) => {
+            let binds: Vec<String> = (0..tys.len()).map(|i| format!("f{}", i)).collect();
+            format!("({})", binds.join(", "))
+        }
+        Fields::Named(fields) => {
+            let binds: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+            format!(" {{ {} }}", binds.join(", "))
+        }
+    }
+}
+
+/// Renders the pattern ignoring the fields of a variant.
+fn wildcard(variant: &Variant) -> &'static str {
+    match variant.fields() {
+        Fields::Empty => "",
+        Fields::Tuple(_) => "(..)",
+        Fields::Named(_) => " { .. }",
+    }
+}
@@ -1,12 +1,16 @@
+use crate::attribute::{Attribute, push_unique};
 use crate::doc::Doc;
+use crate::prelude::*;
+use crate::symbol::Symbol;
 use crate::r#type::Type;
 use crate::visibility::Vis;
 
 /// Defines a struct field.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
     /// Field name
-    name: String,
+    name: Symbol,
 
     /// Field type
     ty: Type,
@@ -14,19 +18,30 @@ pub struct Field {
     /// Field documentation
     doc: Option<Doc>,
 
-    /// Field annotation
-    annotations: Vec<String>,
+    /// Field annotations, e.g. `#[serde(rename = "field")]`
+    annotations: Vec<Attribute>,
 
     /// Field value
     value: String,
 
     /// The visibility of the field
     vis: Vis,
+
+    /// Whether the field may be omitted
+    optional: bool,
+
+    /// Whether the value of the field must not be printed
+    sensitive: bool,
 }
 
 impl Field {
     /// Creates a field definition with the provided name and type
-    pub fn new(name: impl Into<String>, ty: impl Into<Type>) -> Self {
+    ///
+    /// The name is used verbatim, so a field named after a keyword must be
+    /// escaped, e.g. `type` as `r#type` with [`escape_ident`](crate::escape_ident).
+    /// Keys taken from a schema, such as `content-type`, can be turned into
+    /// valid names with [`sanitize_ident`](crate::sanitize_ident).
+    pub fn new(name: impl Into<Symbol>, ty: impl Into<Type>) -> Self {
         Field {
             name: name.into(),
             ty: ty.into(),
@@ -34,6 +49,8 @@ impl Field {
             annotations: Vec::new(),
             value: String::new(),
             vis: Vis::Private,
+            optional: false,
+            sensitive: false,
         }
     }
 
@@ -43,20 +60,25 @@ impl Field {
     }
 
     /// Sets the name of the field.
-    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+    pub fn set_name(&mut self, name: impl Into<Symbol>) -> &mut Self {
         self.name = name.into();
         self
     }
 
     /// Sets the name of the field.
-    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+    pub fn with_name(mut self, name: impl Into<Symbol>) -> Self {
         self.set_name(name);
         self
     }
 
     /// Gets a mutable reference to the name of the field.
     pub fn name_mut(&mut self) -> &mut String {
-        &mut self.name
+        self.name.to_mut()
+    }
+
+    /// Gets the name of the field as a symbol.
+    pub fn symbol(&self) -> &Symbol {
+        &self.name
     }
 
     /// Gets the type of the field.
@@ -107,41 +129,49 @@ impl Field {
     }
 
     /// Gets the annotations for the field.
-    pub fn annotations(&self) -> &[String] {
+    pub fn annotations(&self) -> &[Attribute] {
         &self.annotations
     }
 
-    /// Sets field's annotations.
+    /// Sets field's annotations, skipping duplicates.
     pub fn set_annotations<S>(&mut self, annotations: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
-        self.annotations = annotations.into_iter().map(Into::into).collect();
+        self.annotations.clear();
+        for annotation in annotations {
+            self.push_annotation(annotation);
+        }
         self
     }
 
-    /// Sets field's annotations.
+    /// Sets field's annotations, skipping duplicates.
     pub fn with_annotations<S>(mut self, annotations: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_annotations(annotations);
         self
     }
 
     /// Gets a mutable reference to the annotations for the field.
-    pub fn annotations_mut(&mut self) -> &mut Vec<String> {
+    pub fn annotations_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.annotations
     }
 
-    /// Pushes a single annotation.
-    pub fn push_annotation(&mut self, annotation: impl Into<String>) -> &mut Self {
-        self.annotations.push(annotation.into());
+    /// Pushes a single annotation, e.g. `#[serde(default)]` or a
+    /// [`SerdeAttr`](crate::SerdeAttr).
+    ///
+    /// Annotations are written in the order they are pushed; pushing one equal
+    /// to an existing annotation has no effect.
+    pub fn push_annotation(&mut self, annotation: impl Into<Attribute>) -> &mut Self {
+        push_unique(&mut self.annotations, annotation.into());
         self
     }
 
-    /// Pushes a single annotation.
-    pub fn with_annotation(mut self, annotation: impl Into<String>) -> Self {
+    /// Pushes a single annotation, e.g. `#[serde(default)]` or a
+    /// [`SerdeAttr`](crate::SerdeAttr).
+    pub fn with_annotation(mut self, annotation: impl Into<Attribute>) -> Self {
         self.push_annotation(annotation);
         self
     }
@@ -189,4 +219,69 @@ impl Field {
     pub fn vis_mut(&mut self) -> &mut Vis {
         &mut self.vis
     }
+
+    /// Returns whether the field is optional.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Returns whether the field is required, i.e. not optional.
+    pub fn is_required(&self) -> bool {
+        !self.optional
+    }
+
+    /// Sets whether the field is optional.
+    ///
+    /// An optional field is declared with its type wrapped in `Option<...>`,
+    /// unless the type already is an `Option`. Generators such as
+    /// [`StructConversion`](crate::StructConversion) treat optional fields as
+    /// ones that may be left out.
+    pub fn set_optional(&mut self, optional: bool) -> &mut Self {
+        self.optional = optional;
+        self
+    }
+
+    /// Sets whether the field is optional.
+    pub fn with_optional(mut self, optional: bool) -> Self {
+        self.set_optional(optional);
+        self
+    }
+
+    /// Returns whether the field is sensitive.
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Sets whether the field is sensitive, e.g. a password or a token.
+    ///
+    /// The value of a sensitive field is printed as `"***"` by the `Debug`
+    /// impl generated with [`Struct::redacted_debug_impl`](crate::Struct::redacted_debug_impl).
+    pub fn set_sensitive(&mut self, sensitive: bool) -> &mut Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Sets whether the field is sensitive.
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.set_sensitive(sensitive);
+        self
+    }
+
+    /// Gets the type the field is declared with, which is the type wrapped in
+    /// `Option<...>` for optional fields.
+    pub fn declared_ty(&self) -> Type {
+        if !self.optional || is_option(&self.ty) {
+            return self.ty.clone();
+        }
+
+        Type::option(self.ty.clone())
+    }
+}
+
+fn is_option(ty: &Type) -> bool {
+    let name = ty.name();
+    matches!(
+        &name[..name.find('<').unwrap_or(name.len())],
+        "Option" | "std::option::Option" | "core::option::Option"
+    )
 }
@@ -1,17 +1,19 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
 use crate::field::Field;
 use crate::formatter::Formatter;
-use crate::r#type::Type;
+use crate::prelude::*;
+use crate::tuple_field::TupleField;
 
 /// Defines a set of fields.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Fields {
     /// An empty set of fields.
     Empty,
 
-    /// A tuple of types.
-    Tuple(Vec<Type>),
+    /// A tuple of fields.
+    Tuple(Vec<TupleField>),
 
     /// A named set of fields.
     Named(Vec<Field>),
@@ -29,6 +31,54 @@ impl Fields {
         Fields::Empty
     }
 
+    /// Creates an empty set of named fields with room for at least
+    /// `capacity` fields.
+    ///
+    /// Unlike [`Fields::new`], the fields are named from the start: if no
+    /// field is pushed, they are rendered as `{}`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Fields::Named(Vec::with_capacity(capacity))
+    }
+
+    /// Creates an empty set of tuple fields with room for at least
+    /// `capacity` fields.
+    ///
+    /// If no field is pushed, they are rendered as `()`.
+    pub fn tuple_with_capacity(capacity: usize) -> Self {
+        Fields::Tuple(Vec::with_capacity(capacity))
+    }
+
+    /// Reserves room for at least `additional` more fields.
+    ///
+    /// Does nothing on an empty set of fields, which is neither named nor
+    /// tuple yet.
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        match *self {
+            Fields::Empty => {}
+            Fields::Tuple(ref mut fields) => fields.reserve(additional),
+            Fields::Named(ref mut fields) => fields.reserve(additional),
+        }
+
+        self
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        match *self {
+            Fields::Empty => 0,
+            Fields::Tuple(ref fields) => fields.len(),
+            Fields::Named(ref fields) => fields.len(),
+        }
+    }
+
+    /// Returns whether there are no fields.
+    ///
+    /// Named or tuple fields can be empty too, e.g. after filtering them, in
+    /// which case they are rendered as `{}` or `()`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Push a named field.
     pub fn push_named(&mut self, field: impl Into<Field>) -> &mut Self {
         match *self {
@@ -50,8 +100,8 @@ impl Fields {
         self
     }
 
-    /// Pushes a tuple type.
-    pub fn push_tuple(&mut self, ty: impl Into<Type>) -> &mut Self {
+    /// Pushes a tuple field, or a type for a private field.
+    pub fn push_tuple(&mut self, ty: impl Into<TupleField>) -> &mut Self {
         match *self {
             Fields::Empty => {
                 *self = Fields::Tuple(vec![ty.into()]);
@@ -65,8 +115,8 @@ impl Fields {
         self
     }
 
-    /// Pushes a tuple type.
-    pub fn with_tuple(mut self, ty: impl Into<Type>) -> Self {
+    /// Pushes a tuple field, or a type for a private field.
+    pub fn with_tuple(mut self, ty: impl Into<TupleField>) -> Self {
         self.push_tuple(ty);
         self
     }
@@ -74,43 +124,75 @@ impl Fields {
     /// Formats the fields using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         match *self {
+            Fields::Named(ref fields) if fields.is_empty() => {
+                write!(fmt, " {{}}")?;
+            }
             Fields::Named(ref fields) => {
-                assert!(!fields.is_empty());
-
-                fmt.block(|fmt| {
+                // The closing brace is left open-ended, so that structs and
+                // enum variants can follow it with what they need.
+                if !fmt.is_start_of_line() {
+                    write!(fmt, " ")?;
+                }
+                writeln!(fmt, "{{")?;
+                fmt.indent(|fmt| {
                     for f in fields {
                         if let Some(doc) = f.doc() {
                             for l in doc.as_inner().lines() {
                                 writeln!(fmt, "/// {}", l)?;
                             }
                         }
-                        if !f.annotations().is_empty() {
-                            for ann in f.annotations() {
-                                writeln!(fmt, "{}", ann)?;
-                            }
+                        for ann in f.annotations() {
+                            writeln!(fmt, "#[{}]", ann)?;
                         }
                         f.vis().fmt(fmt)?;
                         write!(fmt, "{}: ", f.name())?;
-                        f.ty().fmt(fmt)?;
+                        f.declared_ty().fmt(fmt)?;
                         writeln!(fmt, ",")?;
                     }
 
                     Ok(())
                 })?;
+                write!(fmt, "}}")?;
             }
-            Fields::Tuple(ref tys) => {
-                assert!(!tys.is_empty());
+            Fields::Tuple(ref fields) => {
+                // Docs need a line of their own, so documented fields are
+                // rendered one per line.
+                if fields.iter().any(|f| f.doc().is_some()) {
+                    writeln!(fmt, "(")?;
+                    fmt.indent(|fmt| {
+                        for f in fields {
+                            if let Some(doc) = f.doc() {
+                                for l in doc.as_inner().lines() {
+                                    writeln!(fmt, "/// {}", l)?;
+                                }
+                            }
+                            for ann in f.annotations() {
+                                writeln!(fmt, "#[{}]", ann)?;
+                            }
+                            f.vis().fmt(fmt)?;
+                            f.ty().fmt(fmt)?;
+                            writeln!(fmt, ",")?;
+                        }
 
-                write!(fmt, "(")?;
+                        Ok(())
+                    })?;
+                    write!(fmt, ")")?;
+                } else {
+                    write!(fmt, "(")?;
 
-                for (i, ty) in tys.iter().enumerate() {
-                    if i != 0 {
-                        write!(fmt, ", ")?;
+                    for (i, f) in fields.iter().enumerate() {
+                        if i != 0 {
+                            write!(fmt, ", ")?;
+                        }
+                        for ann in f.annotations() {
+                            write!(fmt, "#[{}] ", ann)?;
+                        }
+                        f.vis().fmt(fmt)?;
+                        f.ty().fmt(fmt)?;
                     }
-                    ty.fmt(fmt)?;
-                }
 
-                write!(fmt, ")")?;
+                    write!(fmt, ")")?;
+                }
             }
             Fields::Empty => {}
         }
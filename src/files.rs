@@ -0,0 +1,1380 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::attribute::Attribute;
+use crate::blank_lines::BlankLines;
+use crate::cfg::Cfg;
+use crate::r#enum::Enum;
+use crate::field::Field;
+use crate::fields::Fields;
+use crate::formatter::{Formatter, NewlineStyle};
+use crate::function::Function;
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::manifest::{CargoTarget, Manifest};
+use crate::mod_decl::ModDecl;
+use crate::module::Module;
+use crate::provenance::SourceMap;
+use crate::scope::Scope;
+use crate::sha256;
+use crate::r#struct::Struct;
+use crate::toolchain::{Edition, RustVersion};
+use crate::r#trait::Trait;
+use crate::r#type::{Type, TypeKind};
+use crate::type_alias::TypeAlias;
+use crate::type_def::TypeDef;
+use crate::type_registry::TypeRegistry;
+use crate::variant::Variant;
+use crate::visibility::Vis;
+use crate::visit::{
+    Visitor, walk_enum, walk_field, walk_fields, walk_function, walk_impl, walk_item, walk_module,
+    walk_scope, walk_struct, walk_trait, walk_type, walk_type_alias, walk_variant,
+};
+
+const BENCHES: &str = "benches";
+const EXAMPLES: &str = "examples";
+const HASH_PREFIX: &str = "// @generated-hash: ";
+const MANIFEST: &str = "Cargo.toml";
+
+/// Defines a generated source file.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct File {
+    /// Path of the file, relative to the library root
+    path: PathBuf,
+
+    /// Contents of the file
+    scope: Scope,
+
+    /// Line terminator used when writing the file
+    newline_style: NewlineStyle,
+
+    /// Whether the written file ends with a line terminator
+    trailing_newline: bool,
+
+    /// Whether the written file starts with a hash of its contents
+    content_hash: bool,
+}
+
+impl File {
+    /// Creates a new, empty file at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        File {
+            path: path.into(),
+            scope: Scope::new(),
+            newline_style: NewlineStyle::default(),
+            trailing_newline: true,
+            content_hash: false,
+        }
+    }
+
+    /// Gets the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Sets the path of the file.
+    pub fn set_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the path of the file.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Gets a mutable reference to the path of the file.
+    pub fn path_mut(&mut self) -> &mut PathBuf {
+        &mut self.path
+    }
+
+    /// Gets the file's scope.
+    pub fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    /// Sets the file's scope.
+    pub fn set_scope(&mut self, scope: impl Into<Scope>) -> &mut Self {
+        self.scope = scope.into();
+        self
+    }
+
+    /// Sets the file's scope.
+    pub fn with_scope(mut self, scope: impl Into<Scope>) -> Self {
+        self.set_scope(scope);
+        self
+    }
+
+    /// Gets a mutable reference to the file's scope.
+    pub fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+
+    /// Gets the line terminator used when writing the file.
+    pub fn newline_style(&self) -> NewlineStyle {
+        self.newline_style
+    }
+
+    /// Sets the line terminator used when writing the file, `\n` by default.
+    pub fn set_newline_style(&mut self, newline_style: NewlineStyle) -> &mut Self {
+        self.newline_style = newline_style;
+        self
+    }
+
+    /// Sets the line terminator used when writing the file, `\n` by default.
+    pub fn with_newline_style(mut self, newline_style: NewlineStyle) -> Self {
+        self.set_newline_style(newline_style);
+        self
+    }
+
+    /// Gets a mutable reference to the line terminator used when writing
+    /// the file.
+    pub fn newline_style_mut(&mut self) -> &mut NewlineStyle {
+        &mut self.newline_style
+    }
+
+    /// Returns whether the written file ends with a line terminator.
+    pub fn has_trailing_newline(&self) -> bool {
+        self.trailing_newline
+    }
+
+    /// Sets whether the written file ends with a line terminator, which is
+    /// the default.
+    pub fn set_trailing_newline(&mut self, trailing_newline: bool) -> &mut Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Sets whether the written file ends with a line terminator, which is
+    /// the default.
+    pub fn with_trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.set_trailing_newline(trailing_newline);
+        self
+    }
+
+    /// Gets a mutable reference to whether the written file ends with a line
+    /// terminator.
+    pub fn trailing_newline_mut(&mut self) -> &mut bool {
+        &mut self.trailing_newline
+    }
+
+    /// Returns whether the written file starts with a hash of its contents.
+    pub fn has_content_hash(&self) -> bool {
+        self.content_hash
+    }
+
+    /// Sets whether the written file starts with a hash of its contents.
+    ///
+    /// The hash is written as a `// @generated-hash: <sha256>` comment on
+    /// the first line and covers the rest of the file, which lets
+    /// [`is_up_to_date`](#method.is_up_to_date) tell whether the file needs
+    /// to be written again.
+    pub fn set_content_hash(&mut self, content_hash: bool) -> &mut Self {
+        self.content_hash = content_hash;
+        self
+    }
+
+    /// Sets whether the written file starts with a hash of its contents.
+    pub fn with_content_hash(mut self, content_hash: bool) -> Self {
+        self.set_content_hash(content_hash);
+        self
+    }
+
+    /// Gets a mutable reference to whether the written file starts with a
+    /// hash of its contents.
+    pub fn content_hash_mut(&mut self) -> &mut bool {
+        &mut self.content_hash
+    }
+
+    /// Gets the path of the module defined by the file, e.g. `crate::models`.
+    ///
+    /// The path is derived from the file path relative to the `src`
+    /// directory, with `lib.rs`, `main.rs` and `mod.rs` standing for their
+    /// parent directory.
+    pub fn module_path(&self) -> String {
+        let path = self.path.strip_prefix("src").unwrap_or(&self.path);
+        let mut module = String::from("crate");
+
+        for component in path.with_extension("").components() {
+            let name = component.as_os_str().to_string_lossy();
+            if !matches!(&*name, "lib" | "main" | "mod") {
+                module.push_str("::");
+                module.push_str(&name);
+            }
+        }
+
+        module
+    }
+
+    /// Renders the contents of the file.
+    ///
+    /// Like `Scope::to_string`, the output uses `\n` and has no trailing
+    /// newline; the newline options only apply to [`generate`](#method.generate).
+    pub fn render(&self) -> String {
+        self.scope.to_string()
+    }
+
+    /// Collects the provenance of the items of the file, with the lines they
+    /// are written at by [`generate`](#method.generate).
+    ///
+    /// The source map refers to the file by its path and can be written next
+    /// to it with [`SourceMap::to_json`]. For the files of a [`Library`], use
+    /// [`Library::source_maps`], which accounts for the inserted `use`
+    /// statements.
+    pub fn source_map(&self) -> SourceMap {
+        let mut source_map = self
+            .scope
+            .write_with(io::sink(), self.newline_style, self.trailing_newline)
+            .expect("rendering to memory cannot fail");
+        source_map.set_file(self.path.to_string_lossy());
+
+        if self.content_hash {
+            for mapping in source_map.mappings_mut() {
+                mapping.shift(1);
+            }
+        }
+
+        source_map
+    }
+
+    /// Writes the file under the given root directory.
+    ///
+    /// Missing parent directories of the file are created. Lines end with the
+    /// file's newline style, and so does the file unless the trailing newline
+    /// is disabled. If enabled, the file starts with a hash of its contents.
+    pub fn generate(&self, root: impl AsRef<Path>) -> io::Result<()> {
+        let path = root.as_ref().join(&self.path);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut w = BufWriter::new(fs::File::create(path)?);
+        if self.content_hash {
+            w.write_all(&self.contents()?)?;
+        } else {
+            self.scope
+                .write_with(&mut w, self.newline_style, self.trailing_newline)?;
+        }
+        w.flush()
+    }
+
+    /// Checks whether the file under the given root directory already has
+    /// the contents [`generate`](#method.generate) would write, so build
+    /// scripts can skip writing it and avoid triggering a recompile.
+    ///
+    /// Only files written with a content hash are recognized: the hash of
+    /// the file on disk must match both its contents and the contents that
+    /// would be generated. Missing or unreadable files are out of date.
+    pub fn is_up_to_date(&self, root: impl AsRef<Path>) -> bool {
+        let Ok(existing) = fs::read(root.as_ref().join(&self.path)) else {
+            return false;
+        };
+        let Ok(body) = self.body() else {
+            return false;
+        };
+
+        let hash_line = hash_line(&body, self.newline_style);
+        existing.strip_prefix(hash_line.as_bytes()) == Some(&body[..])
+    }
+
+    /// Renders the bytes written by `generate`.
+    pub(crate) fn contents(&self) -> io::Result<Vec<u8>> {
+        let body = self.body()?;
+        if !self.content_hash {
+            return Ok(body);
+        }
+
+        let mut contents = hash_line(&body, self.newline_style).into_bytes();
+        contents.extend(body);
+        Ok(contents)
+    }
+
+    /// Renders the contents written after the hash line.
+    fn body(&self) -> io::Result<Vec<u8>> {
+        let mut body = Vec::new();
+        self.scope
+            .write_with(&mut body, self.newline_style, self.trailing_newline)?;
+        Ok(body)
+    }
+}
+
+/// Defines a set of generated files sharing a root directory.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Library {
+    /// Root directory of the library
+    path: PathBuf,
+
+    /// Files of the library
+    files: Vec<File>,
+
+    /// Types used to resolve cross-file references
+    registry: TypeRegistry,
+
+    /// Cargo features declared by the library
+    features: Vec<String>,
+
+    /// Edition of the generated code, if set
+    edition: Option<Edition>,
+
+    /// Oldest Rust version the generated code must compile with, if set
+    rust_version: Option<RustVersion>,
+
+    /// `Cargo.toml` written at the library root, if any
+    manifest: Option<Manifest>,
+}
+
+impl Library {
+    /// Creates a new, empty library rooted at the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Library {
+            path: path.into(),
+            files: Vec::new(),
+            registry: TypeRegistry::new(),
+            features: Vec::new(),
+            edition: None,
+            rust_version: None,
+            manifest: None,
+        }
+    }
+
+    /// Gets the root directory of the library.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Sets the root directory of the library.
+    pub fn set_path(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the root directory of the library.
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Gets a mutable reference to the root directory of the library.
+    pub fn path_mut(&mut self) -> &mut PathBuf {
+        &mut self.path
+    }
+
+    /// Gets the files of the library.
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
+
+    /// Sets the files of the library.
+    pub fn set_files<F>(&mut self, files: impl IntoIterator<Item = F>) -> &mut Self
+    where
+        F: Into<File>,
+    {
+        self.files = files.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the files of the library.
+    pub fn with_files<F>(mut self, files: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Into<File>,
+    {
+        self.set_files(files);
+        self
+    }
+
+    /// Gets a mutable reference to the files of the library.
+    pub fn files_mut(&mut self) -> &mut Vec<File> {
+        &mut self.files
+    }
+
+    /// Gets the type registry of the library.
+    pub fn registry(&self) -> &TypeRegistry {
+        &self.registry
+    }
+
+    /// Sets the type registry of the library.
+    pub fn set_registry(&mut self, registry: impl Into<TypeRegistry>) -> &mut Self {
+        self.registry = registry.into();
+        self
+    }
+
+    /// Sets the type registry of the library.
+    pub fn with_registry(mut self, registry: impl Into<TypeRegistry>) -> Self {
+        self.set_registry(registry);
+        self
+    }
+
+    /// Gets a mutable reference to the type registry of the library.
+    pub fn registry_mut(&mut self) -> &mut TypeRegistry {
+        &mut self.registry
+    }
+
+    /// Gets the Cargo features declared by the library.
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Sets the Cargo features declared by the library.
+    pub fn set_features<S>(&mut self, features: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the Cargo features declared by the library.
+    pub fn with_features<S>(mut self, features: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_features(features);
+        self
+    }
+
+    /// Gets a mutable reference to the Cargo features declared by the library.
+    pub fn features_mut(&mut self) -> &mut Vec<String> {
+        &mut self.features
+    }
+
+    /// Declares a Cargo feature.
+    ///
+    /// Declaring a feature twice has no effect.
+    pub fn push_feature(&mut self, feature: impl Into<String>) -> &mut Self {
+        let feature = feature.into();
+        if !self.features.contains(&feature) {
+            self.features.push(feature);
+        }
+        self
+    }
+
+    /// Declares a Cargo feature.
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.push_feature(feature);
+        self
+    }
+
+    /// Gets the edition of the generated code, if set.
+    pub fn edition(&self) -> Option<Edition> {
+        self.edition
+    }
+
+    /// Sets the edition of the generated code.
+    ///
+    /// The edition applies to every file whose scope does not set its own,
+    /// see [`Scope::set_edition`].
+    pub fn set_edition(&mut self, edition: impl Into<Option<Edition>>) -> &mut Self {
+        self.edition = edition.into();
+        self
+    }
+
+    /// Sets the edition of the generated code.
+    pub fn with_edition(mut self, edition: impl Into<Option<Edition>>) -> Self {
+        self.set_edition(edition);
+        self
+    }
+
+    /// Gets a mutable reference to the edition of the generated code.
+    pub fn edition_mut(&mut self) -> &mut Option<Edition> {
+        &mut self.edition
+    }
+
+    /// Gets the oldest Rust version the generated code must compile with, if
+    /// set.
+    pub fn rust_version(&self) -> Option<RustVersion> {
+        self.rust_version
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    ///
+    /// The version applies to every file whose scope does not set its own,
+    /// see [`Scope::set_rust_version`].
+    pub fn set_rust_version(&mut self, rust_version: impl Into<Option<RustVersion>>) -> &mut Self {
+        self.rust_version = rust_version.into();
+        self
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    pub fn with_rust_version(mut self, rust_version: impl Into<Option<RustVersion>>) -> Self {
+        self.set_rust_version(rust_version);
+        self
+    }
+
+    /// Gets a mutable reference to the oldest Rust version the generated
+    /// code must compile with.
+    pub fn rust_version_mut(&mut self) -> &mut Option<RustVersion> {
+        &mut self.rust_version
+    }
+
+    /// Gets the manifest written at the library root, if any.
+    pub fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
+    /// Sets the manifest written as `Cargo.toml` at the library root.
+    ///
+    /// The written manifest also declares the features of the library it
+    /// does not declare itself, and uses the edition and Rust version of the
+    /// library unless it sets its own.
+    pub fn set_manifest(&mut self, manifest: impl Into<Option<Manifest>>) -> &mut Self {
+        self.manifest = manifest.into();
+        self
+    }
+
+    /// Sets the manifest written as `Cargo.toml` at the library root.
+    pub fn with_manifest(mut self, manifest: impl Into<Option<Manifest>>) -> Self {
+        self.set_manifest(manifest);
+        self
+    }
+
+    /// Gets a mutable reference to the manifest written at the library root.
+    pub fn manifest_mut(&mut self) -> &mut Option<Manifest> {
+        &mut self.manifest
+    }
+
+    /// Returns the manifest as written by [`generate`](#method.generate),
+    /// completed with the features, edition, Rust version, benchmarks and
+    /// examples of the library.
+    ///
+    /// A feature named after an optional dependency of the manifest enables
+    /// it, e.g. `serde = ["dep:serde"]`. Benchmarks are declared without the
+    /// default test harness, as generated benchmarks usually bring their own,
+    /// e.g. criterion.
+    ///
+    /// Fails if a feature is referenced by the files but not declared, see
+    /// [`check_features`](#method.check_features).
+    pub fn resolved_manifest(&self) -> Result<Option<Manifest>, FeatureError> {
+        let Some(mut manifest) = self.manifest.clone() else {
+            return Ok(None);
+        };
+        self.check_features()?;
+
+        if manifest.edition().is_none() {
+            manifest.set_edition(self.edition);
+        }
+        if manifest.rust_version().is_none() {
+            manifest.set_rust_version(self.rust_version);
+        }
+        for feature in &self.features {
+            if manifest.features().contains_key(feature) {
+                continue;
+            }
+            let optional = manifest
+                .dependencies()
+                .iter()
+                .any(|dependency| dependency.name() == feature && dependency.is_optional());
+            let enables = optional.then(|| format!("dep:{}", feature));
+            manifest.push_feature(feature.as_str(), enables);
+        }
+
+        for name in self.examples() {
+            if !manifest
+                .examples()
+                .iter()
+                .any(|v| v.name() == Some(name.as_str()))
+            {
+                manifest.push_example(name);
+            }
+        }
+        for name in self.benches() {
+            if !manifest
+                .benches()
+                .iter()
+                .any(|v| v.name() == Some(name.as_str()))
+            {
+                manifest.push_bench(CargoTarget::new(name).with_harness(false));
+            }
+        }
+
+        Ok(Some(manifest))
+    }
+
+    /// Collects the features referenced by `cfg` and `cfg_attr` attributes
+    /// in the files of the library, e.g. those created with [`Cfg::feature`].
+    ///
+    /// [`Cfg::feature`]: crate::Cfg::feature
+    pub fn referenced_features(&self) -> BTreeSet<String> {
+        let mut features = CfgFeatures::default();
+
+        for file in &self.files {
+            features.visit_scope(file.scope());
+        }
+
+        features.0
+    }
+
+    /// Checks that every referenced feature is declared by the library.
+    pub fn check_features(&self) -> Result<(), FeatureError> {
+        let undeclared: Vec<String> = self
+            .referenced_features()
+            .into_iter()
+            .filter(|feature| !self.features.contains(feature))
+            .collect();
+
+        if undeclared.is_empty() {
+            Ok(())
+        } else {
+            Err(FeatureError::Undeclared(undeclared))
+        }
+    }
+
+    /// Pushes a file to the library.
+    pub fn push_file(&mut self, file: impl Into<File>) -> &mut Self {
+        self.files.push(file.into());
+        self
+    }
+
+    /// Pushes a file to the library.
+    pub fn with_file(mut self, file: impl Into<File>) -> Self {
+        self.push_file(file);
+        self
+    }
+
+    /// Pushes a new file, returning a mutable reference to it.
+    pub fn new_file(&mut self, path: impl Into<PathBuf>) -> &mut File {
+        self.push_file(File::new(path));
+        self.files.last_mut().unwrap()
+    }
+
+    /// Pushes a benchmark to the library, placing it under `benches/`.
+    ///
+    /// Paths not already under `benches/` are moved there, e.g. `parse.rs`
+    /// becomes `benches/parse.rs`.
+    pub fn push_bench(&mut self, file: impl Into<File>) -> &mut Self {
+        self.push_target(BENCHES, file.into())
+    }
+
+    /// Pushes a benchmark to the library, placing it under `benches/`.
+    pub fn with_bench(mut self, file: impl Into<File>) -> Self {
+        self.push_bench(file);
+        self
+    }
+
+    /// Pushes an example to the library, placing it under `examples/`.
+    ///
+    /// Paths not already under `examples/` are moved there, e.g. `basic.rs`
+    /// becomes `examples/basic.rs`.
+    pub fn push_example(&mut self, file: impl Into<File>) -> &mut Self {
+        self.push_target(EXAMPLES, file.into())
+    }
+
+    /// Pushes an example to the library, placing it under `examples/`.
+    pub fn with_example(mut self, file: impl Into<File>) -> Self {
+        self.push_example(file);
+        self
+    }
+
+    /// Gets the names of the benchmarks of the library.
+    pub fn benches(&self) -> Vec<String> {
+        self.target_names(BENCHES)
+    }
+
+    /// Gets the names of the examples of the library.
+    pub fn examples(&self) -> Vec<String> {
+        self.target_names(EXAMPLES)
+    }
+
+    /// Renders the `[[bench]]` and `[[example]]` tables of the library's
+    /// `Cargo.toml`.
+    ///
+    /// Benchmarks are registered without the default test harness, as
+    /// generated benchmarks usually bring their own, e.g. criterion.
+    pub fn targets_table(&self) -> String {
+        let mut ret = String::new();
+
+        for name in self.benches() {
+            ret.push_str(&format!(
+                "[[bench]]\nname = \"{}\"\nharness = false\n\n",
+                name
+            ));
+        }
+        for name in self.examples() {
+            ret.push_str(&format!("[[example]]\nname = \"{}\"\n\n", name));
+        }
+
+        let trimmed = ret.trim_end().len();
+        ret.truncate(trimmed);
+        if !ret.is_empty() {
+            ret.push('\n');
+        }
+        ret
+    }
+
+    fn push_target(&mut self, dir: &str, mut file: File) -> &mut Self {
+        if !file.path.starts_with(dir) {
+            file.path = Path::new(dir).join(&file.path);
+        }
+        self.push_file(file)
+    }
+
+    /// Names targets after their file, or their directory for `main.rs`.
+    fn target_names(&self, dir: &str) -> Vec<String> {
+        self.files
+            .iter()
+            .filter_map(|file| {
+                let path = file.path.strip_prefix(dir).ok()?;
+                let mut components = path.components();
+                let first = components.next()?.as_os_str().to_str()?;
+                match components.next() {
+                    None => first.strip_suffix(".rs").map(String::from),
+                    Some(c) if c.as_os_str() == "main.rs" && components.next().is_none() => {
+                        Some(first.to_string())
+                    }
+                    Some(_) => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Gets a reference to the file with the given path, if any.
+    pub fn get_file(&self, path: impl AsRef<Path>) -> Option<&File> {
+        let path = path.as_ref();
+        self.files.iter().find(|file| file.path() == path)
+    }
+
+    /// Gets a mutable reference to the file with the given path, if any.
+    pub fn get_file_mut(&mut self, path: impl AsRef<Path>) -> Option<&mut File> {
+        let path = path.as_ref();
+        self.files.iter_mut().find(|file| file.path() == path)
+    }
+
+    /// Splits a scope into numbered part files of at most `max_lines` lines.
+    ///
+    /// The items of `scope` are distributed over `{base_name}/part_0.rs`,
+    /// `{base_name}/part_1.rs`, ..., each carrying the scope's imports and a
+    /// `use super::*;` so parts can refer to each other. A parent
+    /// `{base_name}/mod.rs` declares the parts and re-exports their contents.
+    /// Lines are counted as the files are written, blank lines included.
+    ///
+    /// When there are several parts, private items, along with the private
+    /// fields and inherent methods of types, are made `pub(super)`, so they
+    /// stay visible to the other parts and nowhere else.
+    ///
+    /// A single item longer than `max_lines` gets a part of its own.
+    pub fn push_scope_paginated(
+        &mut self,
+        base_name: impl Into<PathBuf>,
+        scope: Scope,
+        max_lines: usize,
+    ) -> &mut Self {
+        let base_name = base_name.into();
+
+        let mut header = Scope::new().with_imports(scope.imports().clone());
+        header.push_import("super", "*", Vis::Private);
+        header
+            .set_edition(scope.edition())
+            .set_rust_version(scope.rust_version());
+        // A blank line separates the imports from the items.
+        let header_lines = line_count(&header.to_string()) + 1;
+
+        let edition = scope.edition().or(self.edition).unwrap_or_default();
+        let rust_version = scope.rust_version().or(self.rust_version);
+        let blank_lines = BlankLines::new();
+
+        let mut parts: Vec<Scope> = Vec::new();
+        let mut part_lines = 0;
+
+        for item in scope.items() {
+            let mut rendered = String::new();
+            let mut fmt = Formatter::new(&mut rendered)
+                .with_edition(edition)
+                .with_rust_version(rust_version);
+            item.fmt(&mut fmt).unwrap();
+            let item_lines = line_count(&rendered);
+
+            let added = parts.last().and_then(|part| {
+                let prev = part.items().last()?;
+                Some(blank_lines.separating(prev, item) + item_lines)
+            });
+            match (parts.last_mut(), added) {
+                (Some(part), Some(added)) if part_lines + added <= max_lines => {
+                    part.items_mut().push(item.clone());
+                    part_lines += added;
+                }
+                _ => {
+                    let mut part = header.clone();
+                    part.items_mut().push(item.clone());
+                    parts.push(part);
+                    part_lines = header_lines + item_lines;
+                }
+            }
+        }
+
+        if parts.len() > 1 {
+            for item in parts.iter_mut().flat_map(|part| part.items_mut()) {
+                share_with_parts(item);
+            }
+        }
+
+        let mut parent = Scope::new();
+        if let Some(doc) = scope.doc() {
+            parent.set_doc(doc.clone());
+        }
+
+        for (i, part) in parts.into_iter().enumerate() {
+            let name = format!("part_{}", i);
+            parent.push_import(name.clone(), "*", Vis::Pub);
+            parent.push_mod_decl(ModDecl::new(name.clone()).with_compact(i != 0));
+            self.push_file(File::new(base_name.join(format!("{}.rs", name))).with_scope(part));
+        }
+
+        self.push_file(File::new(base_name.join("mod.rs")).with_scope(parent));
+        self
+    }
+
+    /// Gets the registry of all types, combining the explicitly registered
+    /// types with those defined by the files of the library.
+    pub fn resolved_registry(&self) -> TypeRegistry {
+        let mut registry = self.registry.clone();
+        for file in &self.files {
+            registry.register_scope(file.scope(), &file.module_path());
+        }
+        registry
+    }
+
+    /// Returns a copy of `file` with `use` statements added for the
+    /// registered types it references from other modules.
+    ///
+    /// Each module of the file, the file itself included, is resolved on its
+    /// own: references are the unqualified names in the types of its items
+    /// and in the bodies of its functions, e.g. `User` in `Option<User>`.
+    /// String and char literals and comments in bodies are skipped.
+    /// Types defined in the module and types it already imports by name are
+    /// skipped, so a nested module imports the types of its parent it uses.
+    pub fn resolve_imports(&self, file: &File) -> File {
+        self.resolve_imports_with(&self.resolved_registry(), file)
+    }
+
+    pub(crate) fn resolve_imports_with(&self, registry: &TypeRegistry, file: &File) -> File {
+        let module = file.module_path();
+        let mut file = file.clone();
+
+        let scope = file.scope_mut();
+        if scope.edition().is_none() {
+            scope.set_edition(self.edition);
+        }
+        if scope.rust_version().is_none() {
+            scope.set_rust_version(self.rust_version);
+        }
+        resolve_scope_imports(registry, scope, &module);
+
+        file
+    }
+
+    /// Renders all files of the library in memory, keyed by their path
+    /// relative to the library root.
+    ///
+    /// The contents are exactly what [`generate`](#method.generate) would
+    /// write, including inserted `use` statements, but nothing is written.
+    /// A manifest failing the feature check is left out, as `generate`
+    /// reports it as a failure rather than writing it.
+    pub fn render(&self) -> BTreeMap<PathBuf, String> {
+        let registry = self.resolved_registry();
+
+        let mut rendered: BTreeMap<_, _> = self
+            .files
+            .iter()
+            .map(|file| {
+                let contents = self
+                    .resolve_imports_with(&registry, file)
+                    .contents()
+                    .expect("rendering to memory cannot fail");
+                let contents = String::from_utf8(contents).expect("generated code is valid UTF-8");
+                (file.path().to_path_buf(), contents)
+            })
+            .collect();
+
+        if let Ok(Some(manifest)) = self.resolved_manifest() {
+            rendered.insert(PathBuf::from(MANIFEST), manifest.to_string());
+        }
+
+        rendered
+    }
+
+    /// Collects the provenance of the items of each file of the library,
+    /// keyed by their path relative to the library root.
+    ///
+    /// The lines are those of the files [`generate`](#method.generate) would
+    /// write, including inserted `use` statements.
+    pub fn source_maps(&self) -> BTreeMap<PathBuf, SourceMap> {
+        let registry = self.resolved_registry();
+
+        self.files
+            .iter()
+            .map(|file| {
+                let source_map = self.resolve_imports_with(&registry, file).source_map();
+                (file.path().to_path_buf(), source_map)
+            })
+            .collect()
+    }
+
+    /// Writes all files of the library under its root directory.
+    ///
+    /// `use` statements are inserted for references to registered types, see
+    /// [`resolve_imports`](#method.resolve_imports). Missing directories are
+    /// created. A file failing to be written does not stop the generation of
+    /// the others; the returned report lists the outcome of each file.
+    ///
+    /// With a [manifest](#method.set_manifest), `Cargo.toml` is written
+    /// after the files. It fails with [`io::ErrorKind::InvalidInput`] if a
+    /// feature is referenced by the files but not declared.
+    pub fn generate(&self) -> GenerateReport {
+        let registry = self.resolved_registry();
+
+        GenerateReport::from_results(
+            self.files
+                .iter()
+                .map(|file| self.generate_file(&registry, file))
+                .chain(self.generate_manifest()),
+        )
+    }
+
+    /// Writes all files of the library under its root directory, rendering
+    /// and writing them concurrently.
+    ///
+    /// This behaves like [`generate`](#method.generate); the report lists
+    /// the files in the same order regardless of which thread handled them.
+    #[cfg(feature = "rayon")]
+    pub fn generate_parallel(&self) -> GenerateReport {
+        use rayon::prelude::*;
+
+        let registry = self.resolved_registry();
+        let results: Vec<_> = self
+            .files
+            .par_iter()
+            .map(|file| self.generate_file(&registry, file))
+            .collect();
+
+        GenerateReport::from_results(results.into_iter().chain(self.generate_manifest()))
+    }
+
+    fn generate_file(&self, registry: &TypeRegistry, file: &File) -> (PathBuf, io::Result<()>) {
+        let result = self
+            .resolve_imports_with(registry, file)
+            .generate(&self.path);
+        (file.path().to_path_buf(), result)
+    }
+
+    fn generate_manifest(&self) -> Option<(PathBuf, io::Result<()>)> {
+        let result = match self.resolved_manifest() {
+            Ok(manifest) => {
+                let manifest = manifest?;
+                fs::create_dir_all(&self.path)
+                    .and_then(|()| fs::write(self.path.join(MANIFEST), manifest.to_string()))
+            }
+            Err(err) => Err(io::Error::new(io::ErrorKind::InvalidInput, err)),
+        };
+        Some((PathBuf::from(MANIFEST), result))
+    }
+}
+
+/// Outcome of [`Library::generate`] for each file of the library.
+#[derive(Default, Debug)]
+#[must_use]
+pub struct GenerateReport {
+    /// Paths of the files written
+    written: Vec<PathBuf>,
+
+    /// Paths of the files that failed to be written, with the error
+    failures: Vec<(PathBuf, io::Error)>,
+}
+
+impl GenerateReport {
+    fn from_results(results: impl IntoIterator<Item = (PathBuf, io::Result<()>)>) -> Self {
+        let mut report = GenerateReport::default();
+        for (path, result) in results {
+            match result {
+                Ok(()) => report.written.push(path),
+                Err(err) => report.failures.push((path, err)),
+            }
+        }
+        report
+    }
+
+    /// Gets the paths of the files written, relative to the library root.
+    pub fn written(&self) -> &[PathBuf] {
+        &self.written
+    }
+
+    /// Gets the paths of the files that failed to be written, relative to the
+    /// library root, with the error.
+    pub fn failures(&self) -> &[(PathBuf, io::Error)] {
+        &self.failures
+    }
+
+    /// Returns whether all files were written.
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Converts the report into a result, failing with the first error.
+    pub fn into_result(self) -> io::Result<()> {
+        match self.failures.into_iter().next() {
+            Some((path, err)) => Err(io::Error::new(
+                err.kind(),
+                format!("{}: {}", path.display(), err),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Error returned when the features of a [`Library`] are inconsistent.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum FeatureError {
+    /// Features are referenced by `cfg` attributes but not declared.
+    #[error("features referenced but not declared: {}", .0.join(", "))]
+    Undeclared(Vec<String>),
+}
+
+/// Collects the features referenced by the `cfg` predicates of a scope.
+#[derive(Default)]
+struct CfgFeatures(BTreeSet<String>);
+
+impl CfgFeatures {
+    fn push_cfg(&mut self, cfg: Option<&Cfg>) {
+        if let Some(cfg) = cfg {
+            self.0.extend(cfg.features().into_iter().map(String::from));
+        }
+    }
+
+    fn push_attributes<'a>(&mut self, attributes: impl IntoIterator<Item = &'a Attribute>) {
+        for attr in attributes {
+            self.push_cfg(Cfg::from_attribute(attr).as_ref());
+        }
+    }
+
+    fn push_type_def(&mut self, type_def: &TypeDef) {
+        self.push_attributes(type_def.attributes());
+        self.push_attributes(type_def.macros());
+    }
+}
+
+impl Visitor for CfgFeatures {
+    fn visit_scope(&mut self, scope: &Scope) {
+        self.push_attributes(scope.attributes());
+        walk_scope(self, scope);
+    }
+
+    fn visit_item(&mut self, item: &Item) {
+        match item {
+            Item::Const(v) => self.push_attributes(v.attributes()),
+            Item::ModDecl(v) => {
+                self.push_cfg(v.cfg());
+                self.push_attributes(v.attributes());
+            }
+            _ => {}
+        }
+        walk_item(self, item);
+    }
+
+    fn visit_module(&mut self, module: &Module) {
+        self.push_attributes(module.attributes());
+        walk_module(self, module);
+    }
+
+    fn visit_struct(&mut self, r#struct: &Struct) {
+        self.push_attributes(r#struct.attributes());
+        self.push_attributes(r#struct.macros());
+        walk_struct(self, r#struct);
+    }
+
+    fn visit_enum(&mut self, r#enum: &Enum) {
+        self.push_attributes(r#enum.attributes());
+        self.push_attributes(r#enum.macros());
+        walk_enum(self, r#enum);
+    }
+
+    fn visit_variant(&mut self, variant: &Variant) {
+        self.push_attributes(variant.annotations());
+        walk_variant(self, variant);
+    }
+
+    fn visit_trait(&mut self, r#trait: &Trait) {
+        self.push_attributes(r#trait.attributes());
+        self.push_attributes(r#trait.macros());
+        walk_trait(self, r#trait);
+    }
+
+    fn visit_impl(&mut self, r#impl: &Impl) {
+        self.push_cfg(r#impl.cfg());
+        self.push_attributes(r#impl.attributes());
+        self.push_attributes(r#impl.macros());
+        for cst in r#impl.consts() {
+            self.push_attributes(cst.attributes());
+        }
+        walk_impl(self, r#impl);
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        self.push_cfg(function.cfg());
+        self.push_attributes(function.attributes());
+        walk_function(self, function);
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) {
+        self.push_type_def(type_alias.type_def());
+        walk_type_alias(self, type_alias);
+    }
+
+    fn visit_fields(&mut self, fields: &Fields) {
+        if let Fields::Tuple(fields) = fields {
+            for field in fields {
+                self.push_attributes(field.annotations());
+            }
+        }
+        walk_fields(self, fields);
+    }
+
+    fn visit_field(&mut self, field: &Field) {
+        self.push_attributes(field.annotations());
+        walk_field(self, field);
+    }
+}
+
+fn hash_line(body: &[u8], newline_style: NewlineStyle) -> String {
+    format!(
+        "{}{}{}",
+        HASH_PREFIX,
+        sha256::hex_digest(body),
+        newline_style.as_str()
+    )
+}
+
+fn line_count(s: &str) -> usize {
+    s.lines().count()
+}
+
+/// Makes a private item, and the private fields and inherent methods of a
+/// type, `pub(super)` so the other parts of a paginated scope can use them.
+fn share_with_parts(item: &mut Item) {
+    fn share(vis: &mut Vis) {
+        if *vis == Vis::Private {
+            *vis = Vis::PubSuper;
+        }
+    }
+
+    match item {
+        Item::Module(v) => share(v.vis_mut()),
+        Item::ModDecl(v) => share(v.vis_mut()),
+        Item::Const(v) => share(v.vis_mut()),
+        Item::Struct(v) => {
+            share(v.vis_mut());
+            match v.fields_mut() {
+                Fields::Named(fields) => fields.iter_mut().for_each(|f| share(f.vis_mut())),
+                Fields::Tuple(fields) => fields.iter_mut().for_each(|f| share(f.vis_mut())),
+                Fields::Empty => {}
+            }
+        }
+        Item::Function(v) => share(v.vis_mut()),
+        Item::Trait(v) => share(v.vis_mut()),
+        Item::Enum(v) => share(v.vis_mut()),
+        Item::Impl(v) if v.impl_trait().is_none() => {
+            v.functions_mut()
+                .iter_mut()
+                .for_each(|f| share(f.vis_mut()));
+            v.consts_mut().iter_mut().for_each(|c| share(c.vis_mut()));
+        }
+        Item::TypeAlias(v) => share(v.vis_mut()),
+        _ => {}
+    }
+}
+
+/// Imports the registered types referenced by the items of `scope`, defined
+/// as module `module`, then does the same for its nested modules.
+fn resolve_scope_imports(registry: &TypeRegistry, scope: &mut Scope, module: &str) {
+    let mut references = References::default();
+    references.visit_scope(scope);
+
+    for name in references.names {
+        let Some(path) = registry.get(&name) else {
+            continue;
+        };
+
+        let defined = scope.items().iter().any(|item| {
+            matches!(
+                item,
+                Item::Module(_)
+                    | Item::Struct(_)
+                    | Item::Enum(_)
+                    | Item::Trait(_)
+                    | Item::TypeAlias(_)
+            ) && item.name() == Some(&name)
+        });
+        let imported = scope
+            .imports()
+            .values()
+            .any(|imports| imports.contains_key(&name));
+
+        if path != module && !defined && !imported {
+            scope.push_import(path, name, Vis::Private);
+        }
+    }
+
+    for item in scope.items_mut() {
+        if let Item::Module(v) = item {
+            let path = format!("{}::{}", module, v.name());
+            resolve_scope_imports(registry, v.scope_mut(), &path);
+        }
+    }
+}
+
+/// Collects the unqualified names referenced by the items of a scope, not
+/// descending into nested modules.
+#[derive(Default)]
+struct References {
+    names: Vec<String>,
+}
+
+impl References {
+    fn push_source(&mut self, source: &str) {
+        for name in unqualified_idents(source) {
+            if !self.names.iter().any(|v| v == name) {
+                self.names.push(name.to_string());
+            }
+        }
+    }
+}
+
+impl Visitor for References {
+    fn visit_module(&mut self, _module: &Module) {}
+
+    fn visit_function(&mut self, function: &Function) {
+        for body in function.body() {
+            let mut source = String::new();
+            body.fmt(&mut Formatter::new(&mut source)).unwrap();
+            self.push_source(&strip_literals(&source));
+        }
+        walk_function(self, function);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        match ty.kind() {
+            TypeKind::Path => self.push_source(&ty.to_source()),
+            TypeKind::Applied(_) => {
+                self.push_source(ty.name());
+                walk_type(self, ty);
+            }
+            _ => walk_type(self, ty),
+        }
+    }
+}
+
+/// Blanks out the string and char literals and the comments of `s`, so that
+/// the words in them are not taken for references.
+fn strip_literals(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut ret = String::with_capacity(s.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let rest = &chars[i..];
+        let end = match rest {
+            ['/', '/', ..] => rest.iter().position(|&c| c == '\n').unwrap_or(rest.len()),
+            ['/', '*', ..] => {
+                let mut depth = 0;
+                let mut j = 0;
+                while j < rest.len() {
+                    match &rest[j..] {
+                        ['/', '*', ..] => (depth, j) = (depth + 1, j + 2),
+                        ['*', '/', ..] => {
+                            (depth, j) = (depth - 1, j + 2);
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => j += 1,
+                    }
+                }
+                j.min(rest.len())
+            }
+            ['"', ..] => quoted_len(rest, 0),
+            ['b', '"', ..] => quoted_len(rest, 1),
+            ['r', '#' | '"', ..] => raw_len(rest, 1),
+            ['b', 'r', '#' | '"', ..] => raw_len(rest, 2),
+            ['\'', '\\', ..] => rest[2..]
+                .iter()
+                .position(|&c| c == '\'')
+                .map_or(1, |end| end + 3),
+            // A char literal, unlike a lifetime, closes right after one char
+            ['\'', _, '\'', ..] => 3,
+            _ => 0,
+        };
+
+        // Only literals that start a token, not the `r` ending an identifier
+        let in_ident = i > 0 && (chars[i - 1] == '_' || chars[i - 1].is_alphanumeric());
+        if end == 0 || (in_ident && matches!(chars[i], 'r' | 'b')) {
+            ret.push(chars[i]);
+            i += 1;
+        } else {
+            ret.extend(rest[..end].iter().map(|&c| if c == '\n' { c } else { ' ' }));
+            i += end;
+        }
+    }
+
+    ret
+}
+
+/// Returns the length of the quoted string starting after `prefix` chars.
+fn quoted_len(s: &[char], prefix: usize) -> usize {
+    let mut escaped = false;
+    for (j, &c) in s.iter().enumerate().skip(prefix + 1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return j + 1,
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+/// Returns the length of the raw string whose `#`s start after `prefix`
+/// chars, or 0 if it is not a raw string.
+fn raw_len(s: &[char], prefix: usize) -> usize {
+    let hashes = s[prefix..].iter().take_while(|&&c| c == '#').count();
+    let open = prefix + hashes;
+    if s.get(open) != Some(&'"') {
+        return 0;
+    }
+
+    let close: Vec<char> = core::iter::once('"')
+        .chain(core::iter::repeat_n('#', hashes))
+        .collect();
+    s[open + 1..]
+        .windows(close.len())
+        .position(|w| w == close.as_slice())
+        .map_or(s.len(), |end| open + 1 + end + close.len())
+}
+
+/// Lists the identifiers of `s` that are not qualified by a path or a `.`.
+fn unqualified_idents(s: &str) -> Vec<&str> {
+    let mut idents = Vec::new();
+    let mut start = None;
+
+    for (i, c) in s.char_indices().chain([(s.len(), ' ')]) {
+        if c == '_' || c.is_alphanumeric() {
+            start.get_or_insert(i);
+            continue;
+        }
+
+        if let Some(begin) = start.take() {
+            let ident = &s[begin..i];
+            let qualified = s[..begin].ends_with("::") || s[..begin].ends_with('.');
+            let numeric = ident.starts_with(|c: char| c.is_numeric());
+            if !qualified && !numeric && !idents.contains(&ident) {
+                idents.push(ident);
+            }
+        }
+    }
+
+    idents
+}
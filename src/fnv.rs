@@ -0,0 +1,30 @@
+use core::hash::{BuildHasherDefault, Hasher};
+
+/// Builds the hasher of the maps of the crate, e.g.
+/// [`Scope::imports`](crate::Scope::imports).
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+/// Hashes with FNV-1a.
+///
+/// Unlike the random hasher of `std`, it is available without `std`, so the
+/// maps of the crate have the same type whether `std` is enabled or not.
+#[derive(Clone, Copy, Debug)]
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
@@ -1,32 +1,357 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::async_style::AsyncTraitStyle;
+use crate::blank_lines::BlankLines;
 use crate::bound::Bound;
+use crate::prelude::*;
+use crate::provenance::{Provenance, SourceMap, SourceMapping};
+use crate::toolchain::{Edition, RustVersion};
+use crate::r#type::Type;
 
 const DEFAULT_INDENT: usize = 4;
 
+/// Defines how `where` clauses are laid out.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WhereStyle {
+    /// The first predicate follows `where`, the others are aligned with it:
+    ///
+    /// ```text
+    /// where T: Foo,
+    ///       U: Bar,
+    /// ```
+    #[default]
+    Aligned,
+    /// `where` on its own line and one indented predicate per line, as
+    /// rustfmt does:
+    ///
+    /// ```text
+    /// where
+    ///     T: Foo,
+    ///     U: Bar,
+    /// ```
+    Multiline,
+}
+
+/// Defines the line terminator written by a [`Formatter`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NewlineStyle {
+    /// `\n`, as used on Unix.
+    #[default]
+    Lf,
+    /// `\r\n`, as used on Windows.
+    CrLf,
+}
+
+impl NewlineStyle {
+    /// Gets the line terminator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NewlineStyle::Lf => "\n",
+            NewlineStyle::CrLf => "\r\n",
+        }
+    }
+}
+
 /// Configures how a scope is formatted.
-#[derive(Debug)]
 pub struct Formatter<'a> {
     /// Write destination
-    dst: &'a mut String,
+    dst: &'a mut dyn fmt::Write,
+
+    /// Whether the last character written was a newline.
+    start_of_line: bool,
 
     /// Number of spaces to start a new line with.
     spaces: usize,
 
     /// Number of spaces per indentiation
     indent: usize,
+
+    /// Layout of `where` clauses
+    where_style: WhereStyle,
+
+    /// Line terminator
+    newline_style: NewlineStyle,
+
+    /// Blank lines between items
+    blank_lines: BlankLines,
+
+    /// Maximum line width before generics are wrapped
+    max_width: Option<usize>,
+
+    /// Edition of the generated code
+    edition: Edition,
+
+    /// Oldest Rust version the generated code must compile with
+    rust_version: Option<RustVersion>,
+
+    /// Number of characters written on the current line
+    column: usize,
+
+    /// Number of lines written
+    line: usize,
+
+    /// Whether item provenance is rendered as comments
+    provenance_comments: bool,
+
+    /// Provenance of the items written
+    source_map: SourceMap,
 }
 
 impl<'a> Formatter<'a> {
     /// Return a new formatter that writes to the given string.
     pub fn new(dst: &'a mut String) -> Self {
+        let start_of_line = dst.is_empty() || dst.ends_with('\n');
+
         Formatter {
             dst,
+            start_of_line,
             spaces: 0,
             indent: DEFAULT_INDENT,
+            where_style: WhereStyle::default(),
+            newline_style: NewlineStyle::default(),
+            blank_lines: BlankLines::new(),
+            max_width: None,
+            edition: Edition::default(),
+            rust_version: None,
+            column: 0,
+            line: 0,
+            provenance_comments: false,
+            source_map: SourceMap::new(),
         }
     }
 
+    /// Return a new formatter that streams to the given writer.
+    ///
+    /// The writer is assumed to be at the start of a line.
+    pub fn from_writer(dst: &'a mut dyn fmt::Write) -> Self {
+        Formatter {
+            dst,
+            start_of_line: true,
+            spaces: 0,
+            indent: DEFAULT_INDENT,
+            where_style: WhereStyle::default(),
+            newline_style: NewlineStyle::default(),
+            blank_lines: BlankLines::new(),
+            max_width: None,
+            edition: Edition::default(),
+            rust_version: None,
+            column: 0,
+            line: 0,
+            provenance_comments: false,
+            source_map: SourceMap::new(),
+        }
+    }
+
+    /// Gets the layout of `where` clauses.
+    pub fn where_style(&self) -> WhereStyle {
+        self.where_style
+    }
+
+    /// Sets the layout of `where` clauses.
+    pub fn set_where_style(&mut self, where_style: WhereStyle) -> &mut Self {
+        self.where_style = where_style;
+        self
+    }
+
+    /// Sets the layout of `where` clauses.
+    pub fn with_where_style(mut self, where_style: WhereStyle) -> Self {
+        self.set_where_style(where_style);
+        self
+    }
+
+    /// Gets the line terminator.
+    pub fn newline_style(&self) -> NewlineStyle {
+        self.newline_style
+    }
+
+    /// Sets the line terminator.
+    pub fn set_newline_style(&mut self, newline_style: NewlineStyle) -> &mut Self {
+        self.newline_style = newline_style;
+        self
+    }
+
+    /// Sets the line terminator.
+    pub fn with_newline_style(mut self, newline_style: NewlineStyle) -> Self {
+        self.set_newline_style(newline_style);
+        self
+    }
+
+    /// Gets the blank lines written between items.
+    pub fn blank_lines(&self) -> &BlankLines {
+        &self.blank_lines
+    }
+
+    /// Sets the blank lines written between items.
+    pub fn set_blank_lines(&mut self, blank_lines: BlankLines) -> &mut Self {
+        self.blank_lines = blank_lines;
+        self
+    }
+
+    /// Sets the blank lines written between items.
+    pub fn with_blank_lines(mut self, blank_lines: BlankLines) -> Self {
+        self.set_blank_lines(blank_lines);
+        self
+    }
+
+    /// Gets the maximum line width, if any.
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Sets the maximum line width.
+    ///
+    /// Generic lists, derive lists and function arguments that would end
+    /// past this column are written one per line instead:
+    ///
+    /// ```text
+    /// HashMap<
+    ///     VeryLongKeyType,
+    ///     VeryLongValueType,
+    /// >
+    /// ```
+    ///
+    /// By default, lines are never wrapped.
+    pub fn set_max_width(&mut self, max_width: impl Into<Option<usize>>) -> &mut Self {
+        self.max_width = max_width.into();
+        self
+    }
+
+    /// Sets the maximum line width.
+    pub fn with_max_width(mut self, max_width: impl Into<Option<usize>>) -> Self {
+        self.set_max_width(max_width);
+        self
+    }
+
+    /// Gets the edition of the generated code.
+    pub fn edition(&self) -> Edition {
+        self.edition
+    }
+
+    /// Sets the edition of the generated code.
+    pub fn set_edition(&mut self, edition: Edition) -> &mut Self {
+        self.edition = edition;
+        self
+    }
+
+    /// Sets the edition of the generated code.
+    pub fn with_edition(mut self, edition: Edition) -> Self {
+        self.set_edition(edition);
+        self
+    }
+
+    /// Gets the oldest Rust version the generated code must compile with, if
+    /// any.
+    pub fn rust_version(&self) -> Option<RustVersion> {
+        self.rust_version
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    ///
+    /// Constructs newer than the version are replaced with older
+    /// equivalents: async functions of traits and their impls are emitted as
+    /// boxed futures before Rust 1.75, and `let ... else` statements as
+    /// `match` expressions before Rust 1.65. Trait objects of the 2015
+    /// edition omit `dyn` before Rust 1.27.
+    pub fn set_rust_version(&mut self, rust_version: impl Into<Option<RustVersion>>) -> &mut Self {
+        self.rust_version = rust_version.into();
+        self
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    pub fn with_rust_version(mut self, rust_version: impl Into<Option<RustVersion>>) -> Self {
+        self.set_rust_version(rust_version);
+        self
+    }
+
+    /// Returns a formatter writing to `dst` with the indentation, `where`
+    /// layout, edition and Rust version of this one, to render part of an
+    /// item before writing it.
+    pub(crate) fn nested<'b>(&self, dst: &'b mut String) -> Formatter<'b> {
+        let mut ret = Formatter::new(dst);
+        ret.indent = self.indent;
+        ret.where_style = self.where_style;
+        ret.edition = self.edition;
+        ret.rust_version = self.rust_version;
+        ret
+    }
+
+    /// Renders a type as this formatter would write it.
+    pub(crate) fn render_type(&self, ty: &Type) -> String {
+        let mut ret = String::new();
+        ty.fmt(&mut self.nested(&mut ret)).unwrap();
+        ret
+    }
+
+    /// Returns whether the generated code may use a construct stabilized in
+    /// the given version.
+    pub(crate) fn supports(&self, version: RustVersion) -> bool {
+        self.rust_version.is_none_or(|v| v >= version)
+    }
+
+    /// Returns the async style to emit instead of `style`, as native async
+    /// functions in traits need Rust 1.75.
+    pub(crate) fn async_style(&self, style: AsyncTraitStyle) -> AsyncTraitStyle {
+        match style {
+            AsyncTraitStyle::Native | AsyncTraitStyle::ImplFuture
+                if !self.supports(RustVersion::ASYNC_FN_IN_TRAIT) =>
+            {
+                AsyncTraitStyle::BoxFuture
+            }
+            style => style,
+        }
+    }
+
+    /// Returns whether item provenance is rendered as comments.
+    pub fn provenance_comments(&self) -> bool {
+        self.provenance_comments
+    }
+
+    /// Sets whether item provenance is rendered as a
+    /// `// generated from <source>:<line>` comment above the item.
+    pub fn set_provenance_comments(&mut self, provenance_comments: bool) -> &mut Self {
+        self.provenance_comments = provenance_comments;
+        self
+    }
+
+    /// Sets whether item provenance is rendered as comments.
+    pub fn with_provenance_comments(mut self, provenance_comments: bool) -> Self {
+        self.set_provenance_comments(provenance_comments);
+        self
+    }
+
+    /// Gets the provenance of the items written so far, with lines counted
+    /// from the creation of the formatter.
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Consumes the formatter, returning the provenance of the items written.
+    pub fn into_source_map(self) -> SourceMap {
+        self.source_map
+    }
+
+    /// Records the provenance of an item starting on the current line, and
+    /// writes it as a comment if enabled.
+    pub(crate) fn fmt_provenance(
+        &mut self,
+        kind: &str,
+        name: Option<&str>,
+        provenance: Option<&Provenance>,
+    ) -> fmt::Result {
+        let Some(provenance) = provenance else {
+            return Ok(());
+        };
+
+        let mapping = SourceMapping::new(self.line + 1, kind, name, provenance.clone());
+        self.source_map.push_mapping(mapping);
+        if self.provenance_comments {
+            writeln!(self, "// generated from {}", provenance)?;
+        }
+        Ok(())
+    }
+
     /// Wrap the given function inside a block.
     pub fn block<F>(&mut self, f: F) -> fmt::Result
     where
@@ -53,26 +378,70 @@ impl<'a> Formatter<'a> {
         ret
     }
 
+    /// Returns whether the formatter is inside an indented block.
+    pub(crate) fn is_indented(&self) -> bool {
+        self.spaces > 0
+    }
+
     /// Check if current destination is the start of a new line.
     pub fn is_start_of_line(&self) -> bool {
-        self.dst.is_empty() || self.dst.as_bytes().last() == Some(&b'\n')
+        self.start_of_line
+    }
+
+    /// Returns whether `len` more characters fit on the current line.
+    fn fits(&self, len: usize) -> bool {
+        let column = if self.start_of_line {
+            self.spaces
+        } else {
+            self.column
+        };
+        self.max_width
+            .is_none_or(|max_width| column + len <= max_width)
+    }
+
+    fn push_spaces(&mut self) -> fmt::Result {
+        self.column += self.spaces;
+        write!(self.dst, "{:1$}", "", self.spaces)
     }
 
-    fn push_spaces(&mut self) {
-        for _ in 0..self.spaces {
-            self.dst.push(' ');
+    fn push_str(&mut self, s: &str) -> fmt::Result {
+        if let Some(&last) = s.as_bytes().last() {
+            self.start_of_line = last == b'\n';
+        }
+        match s.rfind('\n') {
+            Some(i) => self.column = s[i + 1..].chars().count(),
+            None => self.column += s.chars().count(),
         }
+        self.line += s.bytes().filter(|&b| b == b'\n').count();
+        self.dst.write_str(s)
+    }
+}
+
+impl fmt::Debug for Formatter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Formatter")
+            .field("start_of_line", &self.start_of_line)
+            .field("spaces", &self.spaces)
+            .field("indent", &self.indent)
+            .field("where_style", &self.where_style)
+            .field("newline_style", &self.newline_style)
+            .field("blank_lines", &self.blank_lines)
+            .field("max_width", &self.max_width)
+            .field("line", &self.line)
+            .field("provenance_comments", &self.provenance_comments)
+            .finish_non_exhaustive()
     }
 }
 
 impl fmt::Write for Formatter<'_> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
+        let newline = self.newline_style.as_str();
         let mut first = true;
         let mut should_indent = self.is_start_of_line();
 
         for line in s.lines() {
             if !first {
-                self.dst.push('\n');
+                self.push_str(newline)?;
             }
 
             first = false;
@@ -80,17 +449,17 @@ impl fmt::Write for Formatter<'_> {
             let do_indent = should_indent && !line.is_empty() && line.as_bytes()[0] != b'\n';
 
             if do_indent {
-                self.push_spaces();
+                self.push_spaces()?;
             }
 
             // If this loops again, then we just wrote a new line
             should_indent = true;
 
-            self.dst.push_str(line);
+            self.push_str(line)?;
         }
 
         if s.as_bytes().last() == Some(&b'\n') {
-            self.dst.push('\n');
+            self.push_str(newline)?;
         }
 
         Ok(())
@@ -99,37 +468,113 @@ impl fmt::Write for Formatter<'_> {
 
 /// Format generics.
 pub fn fmt_generics(generics: &[String], fmt: &mut Formatter<'_>) -> fmt::Result {
-    if !generics.is_empty() {
-        write!(fmt, "<")?;
+    fmt_generic_list(generics, fmt, |ty, fmt| write!(fmt, "{}", ty))
+}
+
+/// Formats a list of generics between angle brackets, one per line if the
+/// list doesn't fit within the formatter's maximum width.
+pub(crate) fn fmt_generic_list<T, F>(generics: &[T], fmt: &mut Formatter<'_>, f: F) -> fmt::Result
+where
+    F: Fn(&T, &mut Formatter<'_>) -> fmt::Result,
+{
+    if generics.is_empty() {
+        return Ok(());
+    }
 
-        for (i, ty) in generics.iter().enumerate() {
+    fmt_list("<", generics, ">", fmt, f)
+}
+
+/// Formats a comma-separated list between `open` and `close`, one item per
+/// line with a trailing comma if the list doesn't fit within the formatter's
+/// maximum width.
+pub(crate) fn fmt_list<T, F>(
+    open: &str,
+    items: &[T],
+    close: &str,
+    fmt: &mut Formatter<'_>,
+    f: F,
+) -> fmt::Result
+where
+    F: Fn(&T, &mut Formatter<'_>) -> fmt::Result,
+{
+    let mut flat = String::new();
+    {
+        let mut flat_fmt = Formatter::new(&mut flat);
+        flat_fmt.edition = fmt.edition;
+        flat_fmt.rust_version = fmt.rust_version;
+        write!(flat_fmt, "{}", open)?;
+        for (i, item) in items.iter().enumerate() {
             if i != 0 {
-                write!(fmt, ", ")?
+                write!(flat_fmt, ", ")?
             }
-            write!(fmt, "{}", ty)?;
+            f(item, &mut flat_fmt)?;
         }
+        write!(flat_fmt, "{}", close)?;
+    }
 
-        write!(fmt, ">")?;
+    if items.is_empty() || flat.contains('\n') || fmt.fits(flat.chars().count()) {
+        return write!(fmt, "{}", flat);
     }
 
-    Ok(())
+    writeln!(fmt, "{}", open)?;
+    fmt.indent(|fmt| {
+        for item in items {
+            f(item, fmt)?;
+            writeln!(fmt, ",")?;
+        }
+        Ok(())
+    })?;
+    write!(fmt, "{}", close)
+}
+
+/// Format a `#[must_use]` attribute with an optional reason.
+pub(crate) fn fmt_must_use(reason: Option<&str>, fmt: &mut Formatter<'_>) -> fmt::Result {
+    match reason {
+        Some(reason) => writeln!(fmt, "#[must_use = {:?}]", reason),
+        None => writeln!(fmt, "#[must_use]"),
+    }
 }
 
 /// Format generic bounds.
 pub fn fmt_bounds(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
-    if !bounds.is_empty() {
+    fmt_bounds_terminated(bounds, ",", fmt)
+}
+
+/// Format the bounds in a `where` clause, ending the last one with
+/// `terminator`, e.g. `;` for the declaration of a trait method.
+pub(crate) fn fmt_bounds_terminated(
+    bounds: &[Bound],
+    terminator: &str,
+    fmt: &mut Formatter<'_>,
+) -> fmt::Result {
+    if bounds.is_empty() {
+        return Ok(());
+    }
+
+    let last = bounds.len() - 1;
+    if fmt.where_style == WhereStyle::Multiline {
         writeln!(fmt)?;
+        writeln!(fmt, "where")?;
+        return fmt.indent(|fmt| {
+            for (i, bound) in bounds.iter().enumerate() {
+                write!(fmt, "{}: ", bound.name())?;
+                fmt_bound_rhs(bound.traits(), fmt)?;
+                writeln!(fmt, "{}", if i == last { terminator } else { "," })?;
+            }
+            Ok(())
+        });
+    }
 
-        // Write first bound
-        write!(fmt, "where {}: ", bounds[0].name())?;
-        fmt_bound_rhs(bounds[0].traits(), fmt)?;
-        writeln!(fmt, ",")?;
+    writeln!(fmt)?;
 
-        for bound in &bounds[1..] {
+    for (i, bound) in bounds.iter().enumerate() {
+        if i == 0 {
+            write!(fmt, "where {}: ", bound.name())?;
+        } else {
             write!(fmt, "      {}: ", bound.name())?;
-            fmt_bound_rhs(bound.traits(), fmt)?;
-            writeln!(fmt, ",")?;
         }
+        fmt_bound_rhs(bound.traits(), fmt)?;
+        writeln!(fmt, "{}", if i == last { terminator } else { "," })?;
     }
 
     Ok(())
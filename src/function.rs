@@ -1,17 +1,25 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::attribute::Attribute;
 use crate::block::Block;
-use crate::body::Body;
+use crate::body::{Body, LetElse, dedent};
 use crate::bound::Bound;
+use crate::cfg::Cfg;
+use crate::comment::Comment;
 use crate::doc::Doc;
 use crate::field::Field;
-use crate::formatter::{Formatter, fmt_bounds, fmt_generics};
+use crate::formatter::{
+    Formatter, fmt_bounds, fmt_bounds_terminated, fmt_generics, fmt_list, fmt_must_use,
+};
 use crate::lint::Lint;
-use crate::r#type::Type;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::r#type::{Type, TypeKind};
 use crate::visibility::Vis;
 
 /// Defines a [function](https://doc.rust-lang.org/rust-by-example/fn.html).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Function {
     /// Name of the function
     name: String,
@@ -28,6 +36,12 @@ pub struct Function {
     /// Whether or not this function is `async` or not
     r#async: bool,
 
+    /// Whether the function is `const`
+    r#const: bool,
+
+    /// Whether the function is `unsafe`
+    r#unsafe: bool,
+
     /// Function generics
     generics: Vec<String>,
 
@@ -46,11 +60,23 @@ pub struct Function {
     /// Body contents
     body: Vec<Body>,
 
+    /// Condition under which the function is compiled
+    cfg: Option<Cfg>,
+
     /// Function attributes, e.g., `#[no_mangle]`.
-    attributes: Vec<String>,
+    attributes: Vec<Attribute>,
+
+    /// Function `#[must_use]` attribute, with its optional reason
+    must_use: Option<Option<String>>,
 
     /// Function `extern` ABI
     extern_abi: Option<String>,
+
+    /// Whether to omit the blank line before the item
+    compact: bool,
+
+    /// Where the item was generated from
+    provenance: Option<Provenance>,
 }
 
 impl Function {
@@ -62,14 +88,20 @@ impl Function {
             lints: Vec::new(),
             vis: Vis::Private,
             r#async: false,
+            r#const: false,
+            r#unsafe: false,
             generics: Vec::new(),
             self_arg: SelfArg::None,
             args: Vec::new(),
             ret: None,
             bounds: Vec::new(),
             body: Vec::new(),
+            cfg: None,
             attributes: Vec::new(),
+            must_use: None,
             extern_abi: None,
+            compact: false,
+            provenance: None,
         }
     }
 
@@ -207,6 +239,52 @@ impl Function {
         &mut self.r#async
     }
 
+    /// Gets whether the function is `const`.
+    pub fn is_const(&self) -> bool {
+        self.r#const
+    }
+
+    /// Sets whether the function is `const`, i.e. callable in constant
+    /// contexts.
+    pub fn set_const(&mut self, r#const: bool) -> &mut Self {
+        self.r#const = r#const;
+        self
+    }
+
+    /// Sets whether the function is `const`.
+    pub fn with_const(mut self, r#const: bool) -> Self {
+        self.set_const(r#const);
+        self
+    }
+
+    /// Gets a mutable reference to whether the function is `const`.
+    pub fn const_mut(&mut self) -> &mut bool {
+        &mut self.r#const
+    }
+
+    /// Gets whether the function is `unsafe`.
+    pub fn is_unsafe(&self) -> bool {
+        self.r#unsafe
+    }
+
+    /// Sets whether the function is `unsafe`, i.e. has preconditions its
+    /// callers must uphold.
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Sets whether the function is `unsafe`.
+    pub fn with_unsafe(mut self, r#unsafe: bool) -> Self {
+        self.set_unsafe(r#unsafe);
+        self
+    }
+
+    /// Gets a mutable reference to whether the function is `unsafe`.
+    pub fn unsafe_mut(&mut self) -> &mut bool {
+        &mut self.r#unsafe
+    }
+
     /// Gets the generics for the function.
     pub fn generics(&self) -> &[String] {
         &self.generics
@@ -247,6 +325,98 @@ impl Function {
         self
     }
 
+    /// Gets the names of the type and const generics, which are the ones a
+    /// turbofish lists. Bounds and defaults are stripped, e.g. `T: Clone`
+    /// gives `T` and `const N: usize` gives `N`.
+    pub fn turbofish_generics(&self) -> Vec<&str> {
+        self.generics
+            .iter()
+            .map(|generic| {
+                let name = generic.trim();
+                let name = name.strip_prefix("const ").unwrap_or(name).trim_start();
+                name.split([':', '=']).next().unwrap_or(name).trim()
+            })
+            .filter(|name| !name.is_empty() && !name.starts_with('\''))
+            .collect()
+    }
+
+    /// Returns the path to call the function with, with a turbofish naming its
+    /// generics, e.g. `foo::<A, B>`, or just the name if it has none.
+    pub fn turbofish(&self) -> String {
+        self.turbofish_with(self.turbofish_generics())
+    }
+
+    /// Returns the path to call the function with, with a turbofish of the
+    /// given types, e.g. `foo::<u8, String>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of types does not match the type and const
+    /// generics of the function.
+    pub fn turbofish_with<S>(&self, types: impl IntoIterator<Item = S>) -> String
+    where
+        S: AsRef<str>,
+    {
+        let types: Vec<S> = types.into_iter().collect();
+        let expected = self.turbofish_generics().len();
+        assert_eq!(
+            types.len(),
+            expected,
+            "function `{}` takes {} generic arguments",
+            self.name,
+            expected
+        );
+
+        if types.is_empty() {
+            return self.name.clone();
+        }
+
+        let types: Vec<&str> = types.iter().map(AsRef::as_ref).collect();
+        format!("{}::<{}>", self.name, types.join(", "))
+    }
+
+    /// Returns an expression calling the function with the given arguments,
+    /// e.g. `foo::<A, B>(x, y)`.
+    ///
+    /// Functions taking `self` are called as a method on `self`, e.g.
+    /// `self.foo::<A>(x)`.
+    pub fn call<S>(&self, args: impl IntoIterator<Item = S>) -> String
+    where
+        S: AsRef<str>,
+    {
+        self.call_with(self.turbofish_generics(), args)
+    }
+
+    /// Returns an expression calling the function with the given generic
+    /// types and arguments, e.g. `foo::<u8, String>(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of types does not match the type and const
+    /// generics of the function.
+    pub fn call_with<T, S>(
+        &self,
+        types: impl IntoIterator<Item = T>,
+        args: impl IntoIterator<Item = S>,
+    ) -> String
+    where
+        T: AsRef<str>,
+        S: AsRef<str>,
+    {
+        let args: Vec<S> = args.into_iter().collect();
+        let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+        let receiver = match self.self_arg {
+            SelfArg::None => "",
+            _ => "self.",
+        };
+        format!(
+            "{}{}({})",
+            receiver,
+            self.turbofish_with(types),
+            args.join(", ")
+        )
+    }
+
     /// Gets the `self` argument type.
     pub fn self_arg(&self) -> &SelfArg {
         &self.self_arg
@@ -269,6 +439,59 @@ impl Function {
         &mut self.self_arg
     }
 
+    /// Ties the `self` reference and the references in the return type to a
+    /// lifetime, e.g. `fn get<'a>(&'a self) -> &'a str`.
+    ///
+    /// The lifetime is declared on the function if needed. References in the
+    /// return type that already have a lifetime are left as is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function does not take `self` by reference.
+    pub fn set_self_lifetime(&mut self, lifetime: impl Into<String>) -> &mut Self {
+        let mut lifetime = lifetime.into();
+        if !lifetime.starts_with('\'') {
+            lifetime.insert(0, '\'');
+        }
+
+        self.self_arg = match &self.self_arg {
+            SelfArg::WithSelfRef | SelfArg::WithSelfRefLifetime(_) => {
+                SelfArg::WithSelfRefLifetime(lifetime.clone())
+            }
+            SelfArg::WithMutSelfRef | SelfArg::WithMutSelfRefLifetime(_) => {
+                SelfArg::WithMutSelfRefLifetime(lifetime.clone())
+            }
+            _ => panic!("self lifetimes need a `self` reference: {}", self.name),
+        };
+
+        if !self.generics.contains(&lifetime) {
+            self.generics.insert(0, lifetime.clone());
+        }
+
+        if let Some(ret) = &mut self.ret {
+            if let TypeKind::Reference { lifetime: None, .. } = ret.kind() {
+                ret.set_lifetime(lifetime.clone());
+            }
+            if let Some(name) = with_lifetime(ret.name(), &lifetime) {
+                ret.set_name(name);
+            }
+            for generic in ret.generics_mut() {
+                if let Some(name) = with_lifetime(generic.name(), &lifetime) {
+                    generic.set_name(name);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Ties the `self` reference and the references in the return type to a
+    /// lifetime, e.g. `fn get<'a>(&'a self) -> &'a str`.
+    pub fn with_self_lifetime(mut self, lifetime: impl Into<String>) -> Self {
+        self.set_self_lifetime(lifetime);
+        self
+    }
+
     /// Gets the function arguments.
     pub fn args(&self) -> &[Field] {
         &self.args
@@ -299,9 +522,9 @@ impl Function {
 
     /// Pushes a function argument.
     pub fn push_arg(&mut self, name: impl Into<String>, ty: impl Into<Type>) -> &mut Self {
-        // While a `Field` is used here, both `documentation`, `visibility`
-        // and `annotation` does not make sense for function arguments.
-        // Simply use empty strings.
+        // While a `Field` is used here, `documentation` and `visibility` do
+        // not make sense for function arguments. Annotations are written
+        // before the argument, e.g. `#[cfg(unix)] fd: RawFd`.
         let f = Field::new(name.into(), ty.into());
         self.args.push(f);
         self
@@ -335,6 +558,28 @@ impl Function {
         self.ret.as_mut()
     }
 
+    /// Sets the function return type to `Result<ok, err>`.
+    pub fn set_ret_result(&mut self, ok: impl Into<Type>, err: impl Into<Type>) -> &mut Self {
+        self.set_ret(Type::result(ok, err))
+    }
+
+    /// Sets the function return type to `Result<ok, err>`.
+    pub fn with_ret_result(mut self, ok: impl Into<Type>, err: impl Into<Type>) -> Self {
+        self.set_ret_result(ok, err);
+        self
+    }
+
+    /// Sets the function return type to `Option<inner>`.
+    pub fn set_ret_option(&mut self, inner: impl Into<Type>) -> &mut Self {
+        self.set_ret(Type::option(inner))
+    }
+
+    /// Sets the function return type to `Option<inner>`.
+    pub fn with_ret_option(mut self, inner: impl Into<Type>) -> Self {
+        self.set_ret_option(inner);
+        self
+    }
+
     /// Gets the bounds of the function.
     pub fn bounds(&self) -> &[Bound] {
         &self.bounds
@@ -390,7 +635,7 @@ impl Function {
     }
 
     /// Sets the body of the function.
-    pub fn with_body<B>(&mut self, body: impl IntoIterator<Item = B>) -> &mut Self
+    pub fn with_body<B>(mut self, body: impl IntoIterator<Item = B>) -> Self
     where
         B: Into<Body>,
     {
@@ -415,6 +660,34 @@ impl Function {
         self
     }
 
+    /// Pushes a multi-line snippet to the function implementation.
+    ///
+    /// The blank lines around the snippet and the indentation common to all of
+    /// its lines are removed, so that the snippet is indented like the
+    /// surrounding code, whatever its indentation in the source.
+    pub fn push_raw_indented(&mut self, snippet: impl AsRef<str>) -> &mut Self {
+        self.body.push(Body::String(dedent(snippet.as_ref())));
+        self
+    }
+
+    /// Pushes a multi-line snippet to the function implementation.
+    pub fn with_raw_indented(mut self, snippet: impl AsRef<str>) -> Self {
+        self.push_raw_indented(snippet);
+        self
+    }
+
+    /// Pushes a comment to the function implementation.
+    pub fn push_comment(&mut self, comment: impl Into<Comment>) -> &mut Self {
+        self.body.push(Body::Comment(comment.into()));
+        self
+    }
+
+    /// Pushes a comment to the function implementation.
+    pub fn with_comment(mut self, comment: impl Into<Comment>) -> Self {
+        self.push_comment(comment);
+        self
+    }
+
     /// Pushes a block to the function implementation
     pub fn push_block(&mut self, block: impl Into<Block>) -> &mut Self {
         self.body.push(Body::Block(block.into()));
@@ -427,15 +700,117 @@ impl Function {
         self
     }
 
+    /// Pushes a line with an attribute, e.g. `allow(unused_variables)`,
+    /// written on the line before it.
+    pub fn push_attributed_line(
+        &mut self,
+        attribute: impl Into<Attribute>,
+        line: impl Into<String>,
+    ) -> &mut Self {
+        self.body.push(Body::Attributed(
+            attribute.into(),
+            Box::new(Body::String(line.into())),
+        ));
+        self
+    }
+
+    /// Pushes a line with an attribute, written on the line before it.
+    pub fn with_attributed_line(
+        mut self,
+        attribute: impl Into<Attribute>,
+        line: impl Into<String>,
+    ) -> Self {
+        self.push_attributed_line(attribute, line);
+        self
+    }
+
+    /// Pushes a block with an attribute, e.g. `cfg(unix)`, written on the
+    /// line before it.
+    pub fn push_attributed_block(
+        &mut self,
+        attribute: impl Into<Attribute>,
+        block: impl Into<Block>,
+    ) -> &mut Self {
+        self.body.push(Body::Attributed(
+            attribute.into(),
+            Box::new(Body::Block(block.into())),
+        ));
+        self
+    }
+
+    /// Pushes a block with an attribute, written on the line before it.
+    pub fn with_attributed_block(
+        mut self,
+        attribute: impl Into<Attribute>,
+        block: impl Into<Block>,
+    ) -> Self {
+        self.push_attributed_block(attribute, block);
+        self
+    }
+
+    /// Pushes a `let ... else` statement, given the diverging expression of
+    /// its `else` block without the `;`, e.g. `return None`.
+    ///
+    /// For toolchains older than Rust 1.65 the statement is written as a
+    /// `match` binding the variables of the pattern instead, see
+    /// [`Formatter::set_rust_version`].
+    pub fn push_let_else(
+        &mut self,
+        pattern: impl Into<String>,
+        expr: impl Into<String>,
+        diverge: impl Into<String>,
+    ) -> &mut Self {
+        self.body
+            .push(Body::LetElse(LetElse::new(pattern, expr, diverge)));
+        self
+    }
+
+    /// Pushes a `let ... else` statement.
+    pub fn with_let_else(
+        mut self,
+        pattern: impl Into<String>,
+        expr: impl Into<String>,
+        diverge: impl Into<String>,
+    ) -> Self {
+        self.push_let_else(pattern, expr, diverge);
+        self
+    }
+
+    /// Gets the condition under which the function is compiled.
+    pub fn cfg(&self) -> Option<&Cfg> {
+        self.cfg.as_ref()
+    }
+
+    /// Sets the condition under which the function is compiled.
+    ///
+    /// The condition is rendered as `#[cfg(...)]` before the other
+    /// attributes, in free functions as well as in traits and impl blocks.
+    pub fn set_cfg(&mut self, cfg: Cfg) -> &mut Self {
+        self.cfg = Some(cfg);
+        self
+    }
+
+    /// Sets the condition under which the function is compiled.
+    pub fn with_cfg(mut self, cfg: Cfg) -> Self {
+        self.set_cfg(cfg);
+        self
+    }
+
+    /// Gets a mutable reference to the condition under which the function is
+    /// compiled.
+    pub fn cfg_mut(&mut self) -> &mut Option<Cfg> {
+        &mut self.cfg
+    }
+
     /// Gets the attributes for the function.
-    pub fn attributes(&self) -> &[String] {
+    pub fn attributes(&self) -> &[Attribute] {
         &self.attributes
     }
 
     /// Sets the attributes for the function.
     pub fn set_attributes<S>(&mut self, attributes: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.attributes = attributes.into_iter().map(Into::into).collect();
         self
@@ -444,32 +819,56 @@ impl Function {
     /// Sets the attributes for the function.
     pub fn with_attributes<S>(mut self, attributes: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_attributes(attributes);
         self
     }
 
     /// Gets a mutable reference to the attributes for the function.
-    pub fn attributes_mut(&mut self) -> &mut Vec<String> {
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.attributes
     }
 
     /// Pushes an attribute to the function.
-    pub fn push_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
         self.attributes.push(attribute.into());
         self
     }
 
     /// Pushes an attribute to the function.
-    pub fn with_attribute(mut self, attribute: impl Into<String>) -> Self {
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
         self.push_attribute(attribute);
         self
     }
 
+    /// Gets the `#[must_use]` attribute of the function: `None` if it is not
+    /// `#[must_use]`, otherwise the optional reason.
+    pub fn must_use(&self) -> Option<Option<&str>> {
+        self.must_use.as_ref().map(Option::as_deref)
+    }
+
+    /// Marks the function as `#[must_use]`, with an optional reason, e.g.
+    /// `#[must_use = "reason"]`.
+    pub fn set_must_use(&mut self, reason: Option<&str>) -> &mut Self {
+        self.must_use = Some(reason.map(String::from));
+        self
+    }
+
+    /// Marks the function as `#[must_use]`, with an optional reason.
+    pub fn with_must_use(mut self, reason: Option<&str>) -> Self {
+        self.set_must_use(reason);
+        self
+    }
+
+    /// Gets a mutable reference to the `#[must_use]` attribute of the function.
+    pub fn must_use_mut(&mut self) -> &mut Option<Option<String>> {
+        &mut self.must_use
+    }
+
     /// Gets the `extern` ABI for the function.
-    pub fn extern_abi(&self) -> Option<&String> {
-        self.extern_abi.as_ref()
+    pub fn extern_abi(&self) -> Option<&str> {
+        self.extern_abi.as_deref()
     }
 
     /// Sets an `extern` ABI for the function.
@@ -489,16 +888,71 @@ impl Function {
         self.extern_abi.as_mut()
     }
 
+    /// Gets whether the function is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets whether the function is rendered without a blank line before it.
+    ///
+    /// Use it to keep short related functions together, e.g. a run of
+    /// one-line helpers.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether the function is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the function is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    /// Gets where the function was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Sets where the function was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    /// Sets where the function was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the function was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
+    }
+
     /// Formats the function using the given formatter.
     pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
         if let Some(ref doc) = self.doc {
             doc.fmt(fmt)?;
         }
 
+        if let Some(ref cfg) = self.cfg {
+            writeln!(fmt, "#[{}]", cfg.to_attribute())?;
+        }
+
         for lint in self.lints.iter() {
             lint.fmt(fmt)?;
         }
 
+        if let Some(ref must_use) = self.must_use {
+            fmt_must_use(must_use.as_deref(), fmt)?;
+        }
+
         for attr in self.attributes.iter() {
             writeln!(fmt, "#[{}]", attr)?;
         }
@@ -512,59 +966,59 @@ impl Function {
             self.vis.fmt(fmt)?;
         }
 
-        if let Some(ref extern_abi) = self.extern_abi {
-            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
+        if self.r#const {
+            write!(fmt, "const ")?;
         }
 
         if self.r#async {
             write!(fmt, "async ")?;
         }
 
-        write!(fmt, "fn {}", self.name)?;
-        fmt_generics(&self.generics, fmt)?;
-
-        write!(fmt, "(")?;
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
+        }
 
-        match self.self_arg {
-            SelfArg::None => {}
-            SelfArg::WithSelf => {
-                write!(fmt, "self")?;
-            }
-            SelfArg::WithSelfRef => {
-                write!(fmt, "&self")?;
-            }
-            SelfArg::WithMutSelf => {
-                write!(fmt, "mut self")?;
-            }
-            SelfArg::WithMutSelfRef => {
-                write!(fmt, "&mut self")?;
-            }
+        if let Some(ref extern_abi) = self.extern_abi {
+            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
         }
 
-        for (i, arg) in self.args.iter().enumerate() {
-            if i != 0 || self.self_arg != SelfArg::None {
-                write!(fmt, ", ")?;
-            }
+        write!(fmt, "fn {}", self.name)?;
+        fmt_generics(&self.generics, fmt)?;
 
-            write!(fmt, "{}: ", arg.name())?;
-            arg.ty().fmt(fmt)?;
+        // `None` stands for the self argument
+        let mut args: Vec<Option<&Field>> = Vec::with_capacity(self.args.len() + 1);
+        if self.self_arg != SelfArg::None {
+            args.push(None);
         }
-
-        write!(fmt, ")")?;
+        args.extend(self.args.iter().map(Some));
+        fmt_list("(", &args, ")", fmt, |arg, fmt| match arg {
+            None => self.self_arg.fmt(fmt),
+            Some(arg) => {
+                for ann in arg.annotations() {
+                    write!(fmt, "#[{}] ", ann)?;
+                }
+                write!(fmt, "{}: ", arg.name())?;
+                arg.ty().fmt(fmt)
+            }
+        })?;
 
         if let Some(ref ret) = self.ret {
             write!(fmt, " -> ")?;
             ret.fmt(fmt)?;
         }
 
-        fmt_bounds(&self.bounds, fmt)?;
-
         if self.body.is_empty() {
             if !is_trait {
                 panic!("impl blocks must define fn bodies");
             }
-            writeln!(fmt, ";")
+            if self.bounds.is_empty() {
+                writeln!(fmt, ";")
+            } else {
+                fmt_bounds_terminated(&self.bounds, ";", fmt)
+            }
         } else {
+            fmt_bounds(&self.bounds, fmt)?;
+
             fmt.block(|fmt| {
                 for b in self.body.iter() {
                     b.fmt(fmt)?;
@@ -575,8 +1029,27 @@ impl Function {
     }
 }
 
+/// Adds the lifetime to the references of a type lacking one, returning
+/// `None` if there are none.
+fn with_lifetime(ty: &str, lifetime: &str) -> Option<String> {
+    let mut ret = String::with_capacity(ty.len());
+    let mut changed = false;
+
+    for (i, c) in ty.char_indices() {
+        ret.push(c);
+        if c == '&' && !ty[i + 1..].trim_start().starts_with(['\'', '&']) {
+            ret.push_str(lifetime);
+            ret.push(' ');
+            changed = true;
+        }
+    }
+
+    changed.then_some(ret)
+}
+
 /// An enum for whether a function takes in self.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelfArg {
     /// Corresponds to f()
     None,
@@ -588,4 +1061,49 @@ pub enum SelfArg {
     WithMutSelf,
     /// Corresponds to f(&mut self)
     WithMutSelfRef,
+    /// Corresponds to f(&'a self), with the given lifetime
+    WithSelfRefLifetime(String),
+    /// Corresponds to f(&'a mut self), with the given lifetime
+    WithMutSelfRefLifetime(String),
+    /// Corresponds to f(self: T) with an arbitrary receiver type, e.g.
+    /// `self: Pin<&mut Self>` or `self: Arc<Self>`
+    WithTypedSelf(Type),
+}
+
+impl SelfArg {
+    /// Creates a receiver of the given type, e.g. `Pin<&mut Self>`.
+    pub fn typed(ty: impl Into<Type>) -> Self {
+        SelfArg::WithTypedSelf(ty.into())
+    }
+
+    /// Formats the receiver as written in the argument list.
+    pub(crate) fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SelfArg::None => {}
+            SelfArg::WithSelf => {
+                write!(fmt, "self")?;
+            }
+            SelfArg::WithSelfRef => {
+                write!(fmt, "&self")?;
+            }
+            SelfArg::WithMutSelf => {
+                write!(fmt, "mut self")?;
+            }
+            SelfArg::WithMutSelfRef => {
+                write!(fmt, "&mut self")?;
+            }
+            SelfArg::WithSelfRefLifetime(lifetime) => {
+                write!(fmt, "&{} self", lifetime)?;
+            }
+            SelfArg::WithMutSelfRefLifetime(lifetime) => {
+                write!(fmt, "&{} mut self", lifetime)?;
+            }
+            SelfArg::WithTypedSelf(ty) => {
+                write!(fmt, "self: ")?;
+                ty.fmt(fmt)?;
+            }
+        }
+
+        Ok(())
+    }
 }
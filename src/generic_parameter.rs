@@ -1,9 +1,11 @@
-use std::fmt::Write;
+use core::fmt::Write;
 
 use crate::formatter::Formatter;
+use crate::prelude::*;
 
 /// Defines a generic parameter.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GenericParameter {
     name: String,
     traits: Vec<String>,
@@ -80,8 +82,32 @@ impl GenericParameter {
         self
     }
 
+    /// Returns the name of the generic parameter without its bounds, e.g.
+    /// `T` for `T: Clone` and `N` for `const N: usize`.
+    pub(crate) fn bare_name(&self) -> &str {
+        generic_name(&self.name)
+    }
+
+    /// Returns the generic parameter as declared, with its traits, e.g.
+    /// `T: Clone + Send`.
+    pub(crate) fn declaration(&self) -> String {
+        let mut ret = self.name.clone();
+        if !self.traits.is_empty() {
+            ret.push_str(": ");
+            ret.push_str(&self.traits.join(" + "));
+        }
+        ret
+    }
+
+    /// Returns whether the generic parameter is a type, rather than a
+    /// lifetime or a const.
+    pub(crate) fn is_type(&self) -> bool {
+        let name = self.name.trim();
+        !name.starts_with('\'') && !name.starts_with("const ")
+    }
+
     /// Formats the generic parameter using the given formatter.
-    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
         write!(fmt, "{}", self.name)?;
         if !self.traits.is_empty() {
             write!(fmt, ": ")?;
@@ -95,3 +121,15 @@ impl GenericParameter {
         Ok(())
     }
 }
+
+/// Returns the name of a generic declaration, e.g. `T` for `T: Clone` and `N`
+/// for `const N: usize`.
+pub(crate) fn generic_name(declaration: &str) -> &str {
+    let declaration = declaration.trim();
+    let declaration = declaration.strip_prefix("const ").unwrap_or(declaration);
+    declaration
+        .split([':', '='])
+        .next()
+        .unwrap_or(declaration)
+        .trim()
+}
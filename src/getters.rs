@@ -0,0 +1,68 @@
+use crate::doc::Doc;
+use crate::fields::Fields;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::prelude::*;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::r#type::Type;
+
+impl Struct {
+    /// Generates a trait with a getter per field, and its impl for the
+    /// struct.
+    ///
+    /// Each getter is named after its field, returns a reference to the
+    /// declared type of the field and carries its documentation, e.g.
+    /// `fn name(&self) -> &String;`. Getters of tuple fields are named after
+    /// their index, e.g. `fn _0(&self) -> &String;`. The getters take
+    /// `&self`, so the trait can be used as a trait object.
+    ///
+    /// The trait has the visibility and the generics of the struct, e.g.
+    /// `impl<T> Get<T> for Wrapper<T>`.
+    pub fn getter_trait(&self, name: impl Into<String>) -> (Trait, Impl) {
+        // The getter name, the accessed field, its type and its documentation
+        let getters: Vec<(String, String, Type, Option<&Doc>)> = match self.fields() {
+            Fields::Empty => Vec::new(),
+            Fields::Named(fields) => fields
+                .iter()
+                .map(|field| {
+                    let name = field.name().to_string();
+                    (name.clone(), name, field.declared_ty(), field.doc())
+                })
+                .collect(),
+            Fields::Tuple(fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    (
+                        format!("_{}", i),
+                        i.to_string(),
+                        field.ty().clone(),
+                        field.doc(),
+                    )
+                })
+                .collect(),
+        };
+
+        let name = name.into();
+        let mut r#trait = Trait::new(name.as_str()).with_vis(self.vis().clone());
+        let mut impl_trait = Type::new(name);
+        for generic in self.generics() {
+            r#trait.push_generic(generic.declaration());
+            impl_trait.push_generic(generic.bare_name());
+        }
+
+        let mut r#impl =
+            Impl::new_for_definition(self.name(), self.generics()).with_impl_trait(impl_trait);
+        for (getter, field, ty, doc) in getters {
+            let getter = Function::new(getter)
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret(Type::reference(ty));
+
+            r#impl.push_function(getter.clone().with_line(format!("&self.{}", field)));
+            r#trait.push_function(getter.with_doc::<Doc>(doc.cloned()));
+        }
+
+        (r#trait, r#impl)
+    }
+}
@@ -0,0 +1,83 @@
+use crate::keywords::{KEYWORDS_NOT_RAW, KEYWORDS_RESERVED, KEYWORDS_STRICT};
+use crate::prelude::*;
+
+/// Returns whether the given name is a strict or reserved Rust keyword.
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS_STRICT.contains(&name) || KEYWORDS_RESERVED.contains(&name)
+}
+
+/// Returns whether the given name can be used verbatim as a Rust identifier.
+///
+/// Raw identifiers (e.g. `r#type`) are accepted, keywords are not.
+pub fn is_valid_ident(name: &str) -> bool {
+    match name.strip_prefix("r#") {
+        Some(raw) => is_ident_shape(raw) && !KEYWORDS_NOT_RAW.contains(&raw),
+        None => is_ident_shape(name) && !is_keyword(name),
+    }
+}
+
+/// Escapes the given name if it is a keyword.
+///
+/// Keywords are escaped as raw identifiers (`type` becomes `r#type`), except
+/// for the keywords that cannot be raw identifiers (`crate`, `self`, `Self`
+/// and `super`), which get a trailing underscore instead.
+pub fn escape_ident(name: impl Into<String>) -> String {
+    let name = name.into();
+
+    if KEYWORDS_NOT_RAW.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else if is_keyword(&name) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+/// Turns an arbitrary string into a valid Rust identifier.
+///
+/// Characters that are not allowed in identifiers are replaced with `_`, a
+/// leading digit is prefixed with `_`, and keywords are escaped with
+/// [`escape_ident`]. An empty string (or a lone `_`) becomes `_unnamed`.
+pub fn sanitize_ident(name: impl AsRef<str>) -> String {
+    let name = name.as_ref();
+
+    if let Some(raw) = name.strip_prefix("r#") {
+        if is_valid_ident(name) {
+            return name.to_string();
+        }
+        return sanitize_ident(raw);
+    }
+
+    let mut ident: String = name
+        .chars()
+        .map(|c| if is_ident_continue(c) { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() || ident == "_" {
+        return String::from("_unnamed");
+    }
+
+    if ident.starts_with(|c: char| c.is_numeric()) {
+        ident.insert(0, '_');
+    }
+
+    escape_ident(ident)
+}
+
+fn is_ident_shape(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if is_ident_start(c) => {}
+        _ => return false,
+    }
+
+    name != "_" && chars.all(is_ident_continue)
+}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
@@ -1,18 +1,34 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
+
+use thiserror::Error;
 
 use crate::associated_const::AssociatedConst;
 use crate::associated_type::AssociatedType;
+use crate::async_style::AsyncTraitStyle;
+use crate::attribute::{Attribute, push_unique};
 use crate::bound::Bound;
+use crate::cfg::Cfg;
+use crate::const_item::{ConstItem, ConstKind};
+use crate::doc::Doc;
 use crate::formatter::{Formatter, fmt_bounds, fmt_generics};
 use crate::function::Function;
+use crate::generic_parameter::GenericParameter;
+use crate::lint::Lint;
+use crate::orphan::UnqualifiedTargetError;
+use crate::prelude::*;
+use crate::provenance::Provenance;
 use crate::r#type::Type;
 
 /// Defines an impl block.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Impl {
     /// The struct being implemented
     target: Type,
 
+    /// The crate the target is defined in, if remote
+    origin: Option<String>,
+
     /// Impl level generics
     generics: Vec<String>,
 
@@ -25,12 +41,40 @@ pub struct Impl {
     /// Associated types
     associated_types: Vec<AssociatedType>,
 
+    /// Const items, e.g. documented constants of an inherent impl
+    consts: Vec<ConstItem>,
+
     /// Bounds
     bounds: Vec<Bound>,
 
-    macros: Vec<String>,
+    /// Documentation
+    doc: Option<Doc>,
+
+    /// Condition under which the impl block is compiled
+    cfg: Option<Cfg>,
+
+    /// Lint rules, e.g. `#[allow(clippy::needless_lifetimes)]`
+    lints: Vec<Lint>,
+
+    /// Attributes, e.g. `#[automatically_derived]`
+    attributes: Vec<Attribute>,
+
+    /// Attribute macros, e.g. `#[async_trait]`
+    macros: Vec<Attribute>,
 
     functions: Vec<Function>,
+
+    /// Whether to omit the blank line before the item
+    compact: bool,
+
+    /// Where the item was generated from
+    provenance: Option<Provenance>,
+
+    /// How async functions of a trait impl are emitted
+    async_style: AsyncTraitStyle,
+
+    /// Whether the impl is an `unsafe impl`
+    r#unsafe: bool,
 }
 
 impl Impl {
@@ -38,16 +82,55 @@ impl Impl {
     pub fn new(target: impl Into<Type>) -> Self {
         Impl {
             target: target.into(),
+            origin: None,
             generics: Vec::new(),
             impl_trait: None,
             associated_consts: Vec::new(),
             associated_types: Vec::new(),
+            consts: Vec::new(),
             bounds: Vec::new(),
             functions: Vec::new(),
+            doc: None,
+            cfg: None,
+            lints: Vec::new(),
+            attributes: Vec::new(),
             macros: Vec::new(),
+            compact: false,
+            provenance: None,
+            async_style: AsyncTraitStyle::default(),
+            r#unsafe: false,
         }
     }
 
+    /// Creates an impl block for a type defined with the given generics,
+    /// declaring them on the block and naming them in the target, e.g.
+    /// `impl<T: Clone> Wrapper<T>` for `struct Wrapper<T: Clone>`.
+    pub(crate) fn new_for_definition(name: &str, generics: &[GenericParameter]) -> Self {
+        let target = generics.iter().fold(Type::new(name), |target, generic| {
+            target.with_generic(generic.bare_name())
+        });
+        Impl::new(target).with_generics(generics.iter().map(GenericParameter::declaration))
+    }
+
+    /// Creates a new impl definition for a type defined in another crate.
+    ///
+    /// The first segment of `path` is recorded as the crate the target comes
+    /// from, so `chrono::DateTime<Utc>` has an origin of `chrono`. This allows
+    /// [`Impl::check_orphan_rule`] to flag trait impls the compiler would
+    /// reject.
+    ///
+    /// Fails if `path` is not qualified with a crate name.
+    pub fn new_remote(path: impl Into<Type>) -> Result<Self, UnqualifiedTargetError> {
+        let target = path.into();
+        let Some(origin) = crate::orphan::path_crate(target.name()) else {
+            return Err(UnqualifiedTargetError {
+                target: target.name().to_string(),
+            });
+        };
+        let origin = origin.to_string();
+        Ok(Impl::new(target).with_origin(origin))
+    }
+
     /// Gets the target type of the impl block.
     pub fn target(&self) -> &Type {
         &self.target
@@ -70,6 +153,28 @@ impl Impl {
         &mut self.target
     }
 
+    /// Gets the crate the target type is defined in, if remote.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Sets the crate the target type is defined in.
+    pub fn set_origin(&mut self, origin: impl Into<String>) -> &mut Self {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    /// Sets the crate the target type is defined in.
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.set_origin(origin);
+        self
+    }
+
+    /// Gets a mutable reference to the crate the target type is defined in.
+    pub fn origin_mut(&mut self) -> &mut Option<String> {
+        &mut self.origin
+    }
+
     /// Gets the generics for the impl block.
     pub fn generics(&self) -> &[String] {
         &self.generics
@@ -234,6 +339,81 @@ impl Impl {
         self
     }
 
+    /// Gets the const items.
+    pub fn consts(&self) -> &[ConstItem] {
+        &self.consts
+    }
+
+    /// Sets the const items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the items is a `static`, which impl blocks cannot
+    /// contain.
+    pub fn set_consts<C>(&mut self, consts: impl IntoIterator<Item = C>) -> &mut Self
+    where
+        C: Into<ConstItem>,
+    {
+        self.consts.clear();
+        for item in consts {
+            self.push_const(item);
+        }
+        self
+    }
+
+    /// Sets the const items.
+    pub fn with_consts<C>(mut self, consts: impl IntoIterator<Item = C>) -> Self
+    where
+        C: Into<ConstItem>,
+    {
+        self.set_consts(consts);
+        self
+    }
+
+    /// Gets a mutable reference to the const items.
+    ///
+    /// Statics added through this reference are not written.
+    pub fn consts_mut(&mut self) -> &mut Vec<ConstItem> {
+        &mut self.consts
+    }
+
+    /// Pushes a const item.
+    ///
+    /// Unlike an associated const, a const item can carry documentation,
+    /// lints and attributes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is a `static`, which impl blocks cannot contain.
+    /// See [`try_push_const`](#method.try_push_const) for a fallible version.
+    pub fn push_const(&mut self, item: impl Into<ConstItem>) -> &mut Self {
+        if let Err(e) = self.try_push_const(item) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// Pushes a const item, or returns an error if the item is a `static`.
+    pub fn try_push_const(
+        &mut self,
+        item: impl Into<ConstItem>,
+    ) -> Result<&mut Self, ImplStaticError> {
+        let item = item.into();
+        if item.kind() != ConstKind::Const {
+            return Err(ImplStaticError {
+                name: item.name().to_string(),
+            });
+        }
+        self.consts.push(item);
+        Ok(self)
+    }
+
+    /// Pushes a const item.
+    pub fn with_const(mut self, item: impl Into<ConstItem>) -> Self {
+        self.push_const(item);
+        self
+    }
+
     /// Gets the bounds for the impl block.
     pub fn bounds(&self) -> &[Bound] {
         &self.bounds
@@ -274,42 +454,185 @@ impl Impl {
         self
     }
 
+    /// Gets the documentation for the impl block.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// Sets the documentation for the impl block.
+    pub fn set_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the documentation for the impl block.
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
+    where
+        S: Into<Doc>,
+    {
+        self.set_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the documentation for the impl block.
+    pub fn doc_mut(&mut self) -> Option<&mut Doc> {
+        self.doc.as_mut()
+    }
+
+    /// Gets the lints for the impl block.
+    pub fn lints(&self) -> &[Lint] {
+        &self.lints
+    }
+
+    /// Sets the lints for the impl block.
+    pub fn set_lints<L>(&mut self, lints: impl IntoIterator<Item = L>) -> &mut Self
+    where
+        L: Into<Lint>,
+    {
+        self.lints = lints.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the lints for the impl block.
+    pub fn with_lints<L>(mut self, lints: impl IntoIterator<Item = L>) -> Self
+    where
+        L: Into<Lint>,
+    {
+        self.set_lints(lints);
+        self
+    }
+
+    /// Gets a mutable reference to the lints for the impl block.
+    pub fn lints_mut(&mut self) -> &mut Vec<Lint> {
+        &mut self.lints
+    }
+
+    /// Pushes a lint for the impl block.
+    pub fn push_lint(&mut self, lint: impl Into<Lint>) -> &mut Self {
+        self.lints.push(lint.into());
+        self
+    }
+
+    /// Pushes a lint for the impl block.
+    pub fn with_lint(mut self, lint: impl Into<Lint>) -> Self {
+        self.push_lint(lint);
+        self
+    }
+
+    /// Gets the condition under which the impl block is compiled.
+    pub fn cfg(&self) -> Option<&Cfg> {
+        self.cfg.as_ref()
+    }
+
+    /// Sets the condition under which the impl block is compiled.
+    ///
+    /// The condition is rendered as `#[cfg(...)]` before the other
+    /// attributes.
+    pub fn set_cfg(&mut self, cfg: Cfg) -> &mut Self {
+        self.cfg = Some(cfg);
+        self
+    }
+
+    /// Sets the condition under which the impl block is compiled.
+    pub fn with_cfg(mut self, cfg: Cfg) -> Self {
+        self.set_cfg(cfg);
+        self
+    }
+
+    /// Gets a mutable reference to the condition under which the impl block
+    /// is compiled.
+    pub fn cfg_mut(&mut self) -> &mut Option<Cfg> {
+        &mut self.cfg
+    }
+
+    /// Gets the attributes for the impl block.
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Sets the attributes for the impl block.
+    pub fn set_attributes<S>(&mut self, attributes: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: Into<Attribute>,
+    {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the attributes for the impl block.
+    pub fn with_attributes<S>(mut self, attributes: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<Attribute>,
+    {
+        self.set_attributes(attributes);
+        self
+    }
+
+    /// Gets a mutable reference to the attributes for the impl block.
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+
+    /// Pushes an attribute to the impl block.
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Pushes an attribute to the impl block.
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.push_attribute(attribute);
+        self
+    }
+
     /// Gets the macros for the impl block.
-    pub fn macros(&self) -> &[String] {
+    pub fn macros(&self) -> &[Attribute] {
         &self.macros
     }
 
     /// Sets the macros for the impl block.
     pub fn set_macros<S>(&mut self, macros: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
-        self.macros = macros.into_iter().map(Into::into).collect();
+        self.macros.clear();
+        for r#macro in macros {
+            self.push_macro(r#macro);
+        }
         self
     }
 
     /// Sets the macros for the impl block.
     pub fn with_macros<S>(mut self, macros: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_macros(macros);
         self
     }
 
     /// Gets a mutable reference to the macros for the impl block.
-    pub fn macros_mut(&mut self) -> &mut Vec<String> {
+    pub fn macros_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.macros
     }
 
-    /// Pushes a macro to the impl block.
-    pub fn push_macro(&mut self, r#macro: impl Into<String>) -> &mut Self {
-        self.macros.push(r#macro.into());
+    /// Pushes an attribute macro to the impl block, e.g. `#[async_trait]`.
+    ///
+    /// Macros are written after the other attributes, in the order they are
+    /// pushed; duplicates are skipped.
+    pub fn push_macro(&mut self, r#macro: impl Into<Attribute>) -> &mut Self {
+        let r#macro = r#macro.into();
+        if !self.attributes.contains(&r#macro) {
+            push_unique(&mut self.macros, r#macro);
+        }
         self
     }
 
-    /// Pushes a macro to the impl block.
-    pub fn with_macro(mut self, r#macro: impl Into<String>) -> Self {
+    /// Pushes an attribute macro to the impl block, e.g. `#[async_trait]`.
+    pub fn with_macro(mut self, r#macro: impl Into<Attribute>) -> Self {
         self.push_macro(r#macro);
         self
     }
@@ -354,10 +677,127 @@ impl Impl {
         self
     }
 
+    /// Gets whether the impl block is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets whether the impl block is rendered without a blank line before it.
+    ///
+    /// Use it to keep short related impl blocks together, e.g. marker trait
+    /// impls such as `unsafe impl Send for Handle {}`.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether the impl block is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the impl block is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    /// Gets where the impl block was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Sets where the impl block was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    /// Sets where the impl block was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the impl block was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
+    }
+
+    /// Gets how the async functions of the impl block are emitted.
+    pub fn async_style(&self) -> AsyncTraitStyle {
+        self.async_style
+    }
+
+    /// Sets how the async functions of the impl block are emitted.
+    ///
+    /// This only applies to trait impls and should match the style of the
+    /// trait.
+    pub fn set_async_style(&mut self, async_style: AsyncTraitStyle) -> &mut Self {
+        self.async_style = async_style;
+        self
+    }
+
+    /// Sets how the async functions of the impl block are emitted.
+    pub fn with_async_style(mut self, async_style: AsyncTraitStyle) -> Self {
+        self.set_async_style(async_style);
+        self
+    }
+
+    /// Gets a mutable reference to how the async functions of the impl block are
+    /// emitted.
+    pub fn async_style_mut(&mut self) -> &mut AsyncTraitStyle {
+        &mut self.async_style
+    }
+
+    /// Gets whether the impl block is an `unsafe impl`.
+    pub fn is_unsafe(&self) -> bool {
+        self.r#unsafe
+    }
+
+    /// Sets whether the impl block is an `unsafe impl`.
+    ///
+    /// This is needed to implement an unsafe trait, e.g. `Send` or `Sync`.
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Sets whether the impl block is an `unsafe impl`.
+    pub fn with_unsafe(mut self, r#unsafe: bool) -> Self {
+        self.set_unsafe(r#unsafe);
+        self
+    }
+
+    /// Gets a mutable reference to whether the impl block is an `unsafe impl`.
+    pub fn unsafe_mut(&mut self) -> &mut bool {
+        &mut self.r#unsafe
+    }
+
     /// Formats the impl block using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref doc) = self.doc {
+            doc.fmt(fmt)?;
+        }
+        if let Some(ref cfg) = self.cfg {
+            writeln!(fmt, "#[{}]", cfg.to_attribute())?;
+        }
+        for lint in self.lints.iter() {
+            lint.fmt(fmt)?;
+        }
+        for attr in self.attributes.iter() {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
         for m in self.macros.iter() {
-            writeln!(fmt, "{}", m)?;
+            writeln!(fmt, "#[{}]", m)?;
+        }
+        if self.impl_trait.is_some()
+            && let Some(attr) = self.async_style.attribute()
+        {
+            writeln!(fmt, "{}", attr)?;
+        }
+        if self.r#unsafe {
+            write!(fmt, "unsafe ")?;
         }
         write!(fmt, "impl")?;
         fmt_generics(&self.generics[..], fmt)?;
@@ -382,48 +822,67 @@ impl Impl {
                         "Associated consts must have a concrete value in impl blocks"
                     );
                     cst.concrete_vis().fmt(fmt)?;
-                    writeln!(
-                        fmt,
-                        "const {}: {} = {};",
-                        cst.name(),
-                        cst.ty(),
-                        cst.concrete_value().unwrap(),
-                    )?;
+                    write!(fmt, "const {}: ", cst.name())?;
+                    cst.ty().fmt(fmt)?;
+                    writeln!(fmt, " = {};", cst.concrete_value().unwrap())?;
+                }
+            }
+
+            // format const items
+            let consts = self.consts.iter().filter(|c| c.kind() == ConstKind::Const);
+            for (i, cst) in consts.enumerate() {
+                if i != 0 && !cst.is_compact() {
+                    writeln!(fmt)?;
                 }
+                fmt.fmt_provenance("const", Some(cst.name()), cst.provenance())?;
+                cst.fmt(fmt)?;
             }
 
             // format associated types
             if !self.associated_types.is_empty() {
                 for ty in &self.associated_types {
-                    let Some((concrete_name, concrete_generics)) = ty.concrete_ty() else {
+                    let Some(concrete_ty) = ty.concrete_ty() else {
                         panic!(
                             "Associated types must have a concrete type in impl blocks: {}",
                             ty.name()
                         );
                     };
-                    writeln!(
-                        fmt,
-                        "type {} = {}{};",
-                        ty.name(),
-                        concrete_name,
-                        if concrete_generics.is_empty() {
-                            String::new()
-                        } else {
-                            format!("<{}>", concrete_generics.join(", "))
-                        }
-                    )?;
+                    write!(fmt, "type {} = ", ty.name())?;
+                    concrete_ty.fmt(fmt)?;
+                    writeln!(fmt, ";")?;
                 }
             }
 
             for (i, func) in self.functions.iter().enumerate() {
-                if i != 0 || !self.associated_types.is_empty() {
+                if i != 0 || !self.associated_types.is_empty() || !self.consts.is_empty() {
                     writeln!(fmt)?;
                 }
+                fmt.fmt_provenance("fn", Some(func.name()), func.provenance())?;
 
-                func.fmt(false, fmt)?;
+                if self.impl_trait.is_some() {
+                    let style = fmt.async_style(self.async_style);
+                    func.desugar_async_with(style, fmt).fmt(false, fmt)?;
+                } else {
+                    func.fmt(false, fmt)?;
+                }
             }
 
             Ok(())
         })
     }
 }
+
+/// Error returned when a `static` is pushed to an impl block.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("impl blocks cannot contain the static `{name}`")]
+pub struct ImplStaticError {
+    /// Name of the static
+    name: String,
+}
+
+impl ImplStaticError {
+    /// Gets the name of the static.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
@@ -0,0 +1,221 @@
+use crate::prelude::*;
+use thiserror::Error;
+
+use crate::associated_type::AssociatedType;
+use crate::body::Body;
+use crate::emission::ImplGenerator;
+use crate::r#impl::Impl;
+use crate::r#trait::Trait;
+use crate::r#type::Type;
+
+/// Generates an `impl` block stubbing out the items of a trait.
+///
+/// Required functions get the stub body, `todo!()` by default. Associated
+/// types without an assigned type are left as `_` holes, which fail to
+/// compile until they are filled in. Associated consts need a value unless
+/// the trait provides a default.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImplStub {
+    /// Trait to implement
+    r#trait: Trait,
+
+    /// Type implementing the trait
+    target: Type,
+
+    /// Lines of the stubbed function bodies
+    body: Vec<String>,
+
+    /// Whether provided functions are stubbed too
+    provided: bool,
+
+    /// Assigned associated types
+    types: IndexMap<String, String>,
+
+    /// Assigned associated const values
+    consts: IndexMap<String, String>,
+}
+
+impl ImplStub {
+    /// Creates a stub generator implementing `trait` for `target`.
+    pub fn new(r#trait: impl Into<Trait>, target: impl Into<Type>) -> Self {
+        ImplStub {
+            r#trait: r#trait.into(),
+            target: target.into(),
+            body: vec!["todo!()".to_string()],
+            provided: false,
+            types: IndexMap::default(),
+            consts: IndexMap::default(),
+        }
+    }
+
+    /// Gets the trait to implement.
+    pub fn r#trait(&self) -> &Trait {
+        &self.r#trait
+    }
+
+    /// Gets the type implementing the trait.
+    pub fn target(&self) -> &Type {
+        &self.target
+    }
+
+    /// Gets the lines of the stubbed function bodies.
+    pub fn body(&self) -> &[String] {
+        &self.body
+    }
+
+    /// Sets the lines of the stubbed function bodies.
+    pub fn set_body<S>(&mut self, body: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.body = body.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the lines of the stubbed function bodies.
+    pub fn with_body<S>(mut self, body: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_body(body);
+        self
+    }
+
+    /// Gets whether functions with a default body in the trait are stubbed
+    /// too.
+    pub fn provided(&self) -> bool {
+        self.provided
+    }
+
+    /// Sets whether functions with a default body in the trait are stubbed
+    /// too.
+    pub fn set_provided(&mut self, provided: bool) -> &mut Self {
+        self.provided = provided;
+        self
+    }
+
+    /// Sets whether functions with a default body in the trait are stubbed
+    /// too.
+    pub fn with_provided(mut self, provided: bool) -> Self {
+        self.set_provided(provided);
+        self
+    }
+
+    /// Gets the assigned associated types.
+    pub fn types(&self) -> &IndexMap<String, String> {
+        &self.types
+    }
+
+    /// Assigns a type to an associated type.
+    pub fn push_type(&mut self, name: impl Into<String>, ty: impl Into<String>) -> &mut Self {
+        self.types.insert(name.into(), ty.into());
+        self
+    }
+
+    /// Assigns a type to an associated type.
+    pub fn with_type(mut self, name: impl Into<String>, ty: impl Into<String>) -> Self {
+        self.push_type(name, ty);
+        self
+    }
+
+    /// Gets the assigned associated const values.
+    pub fn consts(&self) -> &IndexMap<String, String> {
+        &self.consts
+    }
+
+    /// Assigns a value to an associated const.
+    pub fn push_const(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.consts.insert(name.into(), value.into());
+        self
+    }
+
+    /// Assigns a value to an associated const.
+    pub fn with_const(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.push_const(name, value);
+        self
+    }
+
+    /// Generates the `impl Trait for Target` block.
+    ///
+    /// Fails if an assigned type or const does not exist in the trait, or if
+    /// a const without a default in the trait has no value.
+    pub fn generate(&self) -> Result<Impl, ImplStubError> {
+        let r#trait = &self.r#trait;
+
+        let unknown: Vec<String> = self
+            .types
+            .keys()
+            .filter(|name| !r#trait.associated_type().iter().any(|t| t.name() == *name))
+            .chain(self.consts.keys().filter(|name| {
+                !r#trait
+                    .associated_consts()
+                    .iter()
+                    .any(|c| c.name() == *name)
+            }))
+            .cloned()
+            .collect();
+        if !unknown.is_empty() {
+            return Err(ImplStubError::UnknownItems(unknown));
+        }
+
+        let missing: Vec<String> = r#trait
+            .associated_consts()
+            .iter()
+            .filter(|c| c.concrete_value().is_none() && !self.consts.contains_key(c.name()))
+            .map(|c| c.name().to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(ImplStubError::MissingConsts(missing));
+        }
+
+        let mut r#impl = Impl::new(self.target.clone()).with_impl_trait(r#trait.name());
+
+        for cst in r#trait.associated_consts() {
+            if let Some(value) = self.consts.get(cst.name()) {
+                r#impl.push_associated_const(cst.clone().with_concrete_value(value.as_str()));
+            }
+        }
+
+        for ty in r#trait.associated_type() {
+            let concrete = self.types.get(ty.name()).map_or("_", String::as_str);
+            r#impl.push_associated_type(AssociatedType::new_with_concrete_ty(ty.name(), concrete));
+        }
+
+        for function in r#trait.functions() {
+            if !function.body().is_empty() && !self.provided {
+                continue;
+            }
+
+            let mut function = function.clone();
+            *function.body_mut() = self.body.iter().cloned().map(Body::String).collect();
+            r#impl.push_function(function);
+        }
+
+        Ok(r#impl)
+    }
+}
+
+impl ImplGenerator for ImplStub {
+    type Error = ImplStubError;
+
+    fn generate_impl(&self) -> Result<Impl, Self::Error> {
+        self.generate()
+    }
+
+    /// Returns the implementing type.
+    fn macro_args(&self) -> Vec<String> {
+        vec![self.target.to_source()]
+    }
+}
+
+/// Error returned when an [`ImplStub`] is incomplete or inconsistent.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ImplStubError {
+    /// Assigned associated types or consts do not exist in the trait.
+    #[error("unknown associated items: {}", .0.join(", "))]
+    UnknownItems(Vec<String>),
+    /// Associated consts have neither a value nor a default in the trait.
+    #[error("associated consts without a value: {}", .0.join(", "))]
+    MissingConsts(Vec<String>),
+}
@@ -1,7 +1,10 @@
+use crate::import_group::ImportGroup;
+use crate::prelude::*;
 use crate::visibility::Vis;
 
 /// Defines an import (`use` statement).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Import {
     #[allow(dead_code)]
     line: String,
@@ -13,8 +16,17 @@ pub struct Import {
 impl Import {
     /// Creates a new import.
     pub fn new(path: impl Into<String>, ty: impl Into<String>) -> Self {
+        let path = path.into();
+        let ty = ty.into();
         Import {
-            line: format!("{}::{}", path.into(), ty.into()),
+            // `use path::{self}` imports the module itself
+            line: if path.is_empty() {
+                ty
+            } else if ty == "self" {
+                path
+            } else {
+                format!("{}::{}", path, ty)
+            },
             vis: Vis::Private,
         }
     }
@@ -24,6 +36,11 @@ impl Import {
         &self.line
     }
 
+    /// Classifies the import by origin.
+    pub fn group(&self) -> ImportGroup {
+        ImportGroup::of(&self.line)
+    }
+
     /// Gets the import visibility.
     pub fn vis(&self) -> &Vis {
         &self.vis
@@ -0,0 +1,32 @@
+/// Origin of an import, as used by rustfmt's
+/// `group_imports = "StdExternalCrate"`.
+///
+/// Groups are ordered as they are written: standard library first, then
+/// external crates, then the current crate.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImportGroup {
+    /// `std`, `core` and `alloc`.
+    Std,
+    /// Any other crate.
+    External,
+    /// `crate`, `self` and `super`.
+    Crate,
+}
+
+impl ImportGroup {
+    /// Classifies an import path, e.g. `std::fmt` or `crate::model`.
+    pub fn of(path: &str) -> Self {
+        let root = path
+            .trim_start_matches("::")
+            .split("::")
+            .next()
+            .unwrap_or("");
+
+        match root {
+            "std" | "core" | "alloc" => ImportGroup::Std,
+            "crate" | "self" | "super" => ImportGroup::Crate,
+            _ => ImportGroup::External,
+        }
+    }
+}
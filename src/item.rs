@@ -1,17 +1,32 @@
+use core::fmt::{self, Write};
+
+use crate::comment::Comment;
+use crate::const_item::{ConstItem, ConstKind};
 use crate::r#enum::Enum;
+use crate::fields::Fields;
+use crate::formatter::Formatter;
 use crate::function::Function;
 use crate::r#impl::Impl;
+use crate::item_kind::ItemKind;
 use crate::line_break::LineBreak;
+use crate::mod_decl::ModDecl;
 use crate::module::Module;
+use crate::prelude::*;
+use crate::provenance::Provenance;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
 use crate::r#type_alias::TypeAlias;
 
 /// An `Item` is a single item in a `Scope`.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Item {
     /// A module.
     Module(Module),
+    /// A module declaration, e.g. `mod foo;`.
+    ModDecl(ModDecl),
+    /// A `const` or `static` item.
+    Const(ConstItem),
     /// A struct.
     Struct(Struct),
     /// A function.
@@ -24,18 +39,318 @@ pub enum Item {
     Impl(Impl),
     /// A raw string.
     Raw(String),
+    /// A comment.
+    Comment(Comment),
     /// A type alias.
     TypeAlias(TypeAlias),
     /// A line break.
     LineBreak(LineBreak),
 }
 
+impl Item {
+    /// Gets the name of the item.
+    ///
+    /// `impl` blocks are named after their target type. Raw strings, comments
+    /// and line breaks have no name.
+    pub fn name(&self) -> Option<&str> {
+        match *self {
+            Item::Module(ref v) => Some(v.name()),
+            Item::ModDecl(ref v) => Some(v.name()),
+            Item::Const(ref v) => Some(v.name()),
+            Item::Struct(ref v) => Some(v.name()),
+            Item::Function(ref v) => Some(v.name()),
+            Item::Trait(ref v) => Some(v.name()),
+            Item::Enum(ref v) => Some(v.name()),
+            Item::Impl(ref v) => Some(v.target().name()),
+            Item::Raw(_) | Item::Comment(_) => None,
+            Item::TypeAlias(ref v) => Some(v.name()),
+            Item::LineBreak(_) => None,
+        }
+    }
+
+    /// Gets the kind of the item.
+    pub fn kind(&self) -> ItemKind {
+        match *self {
+            Item::Module(_) => ItemKind::Module,
+            Item::ModDecl(_) => ItemKind::ModDecl,
+            Item::Const(_) => ItemKind::Const,
+            Item::Struct(_) => ItemKind::Struct,
+            Item::Function(_) => ItemKind::Function,
+            Item::Trait(_) => ItemKind::Trait,
+            Item::Enum(_) => ItemKind::Enum,
+            Item::Impl(_) => ItemKind::Impl,
+            Item::Raw(_) => ItemKind::Raw,
+            Item::Comment(_) => ItemKind::Comment,
+            Item::TypeAlias(_) => ItemKind::TypeAlias,
+            Item::LineBreak(_) => ItemKind::LineBreak,
+        }
+    }
+
+    /// Gets a reference to the module if the item is a module.
+    pub fn as_module(&self) -> Option<&Module> {
+        match *self {
+            Item::Module(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the module if the item is a module.
+    pub fn as_module_mut(&mut self) -> Option<&mut Module> {
+        match *self {
+            Item::Module(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the module declaration if the item is a module
+    /// declaration.
+    pub fn as_mod_decl(&self) -> Option<&ModDecl> {
+        match *self {
+            Item::ModDecl(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the module declaration if the item is a
+    /// module declaration.
+    pub fn as_mod_decl_mut(&mut self) -> Option<&mut ModDecl> {
+        match *self {
+            Item::ModDecl(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the `const` or `static` item if the item is one.
+    pub fn as_const(&self) -> Option<&ConstItem> {
+        match *self {
+            Item::Const(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the `const` or `static` item if the item
+    /// is one.
+    pub fn as_const_mut(&mut self) -> Option<&mut ConstItem> {
+        match *self {
+            Item::Const(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the struct if the item is a struct.
+    pub fn as_struct(&self) -> Option<&Struct> {
+        match *self {
+            Item::Struct(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the struct if the item is a struct.
+    pub fn as_struct_mut(&mut self) -> Option<&mut Struct> {
+        match *self {
+            Item::Struct(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the function if the item is a function.
+    pub fn as_function(&self) -> Option<&Function> {
+        match *self {
+            Item::Function(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the function if the item is a function.
+    pub fn as_function_mut(&mut self) -> Option<&mut Function> {
+        match *self {
+            Item::Function(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the trait if the item is a trait.
+    pub fn as_trait(&self) -> Option<&Trait> {
+        match *self {
+            Item::Trait(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the trait if the item is a trait.
+    pub fn as_trait_mut(&mut self) -> Option<&mut Trait> {
+        match *self {
+            Item::Trait(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the enum if the item is an enum.
+    pub fn as_enum(&self) -> Option<&Enum> {
+        match *self {
+            Item::Enum(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the enum if the item is an enum.
+    pub fn as_enum_mut(&mut self) -> Option<&mut Enum> {
+        match *self {
+            Item::Enum(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a reference to the `impl` block if the item is an `impl` block.
+    pub fn as_impl(&self) -> Option<&Impl> {
+        match *self {
+            Item::Impl(ref v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets a mutable reference to the `impl` block if the item is an `impl` block.
+    pub fn as_impl_mut(&mut self) -> Option<&mut Impl> {
+        match *self {
+            Item::Impl(ref mut v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Gets where the item was generated from.
+    ///
+    /// Raw strings, comments and line breaks have no provenance.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        match *self {
+            Item::Module(ref v) => v.provenance(),
+            Item::ModDecl(ref v) => v.provenance(),
+            Item::Const(ref v) => v.provenance(),
+            Item::Struct(ref v) => v.provenance(),
+            Item::Function(ref v) => v.provenance(),
+            Item::Trait(ref v) => v.provenance(),
+            Item::Enum(ref v) => v.provenance(),
+            Item::Impl(ref v) => v.provenance(),
+            Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => None,
+            Item::TypeAlias(ref v) => v.provenance(),
+        }
+    }
+
+    /// Returns whether the item is rendered without a blank line before it.
+    ///
+    /// Raw strings and line breaks are never compact; a raw string can hold
+    /// a group of lines itself.
+    pub fn is_compact(&self) -> bool {
+        match *self {
+            Item::Module(ref v) => v.is_compact(),
+            Item::ModDecl(ref v) => v.is_compact(),
+            Item::Const(ref v) => v.is_compact(),
+            Item::Struct(ref v) => v.is_compact(),
+            Item::Function(ref v) => v.is_compact(),
+            Item::Trait(ref v) => v.is_compact(),
+            Item::Enum(ref v) => v.is_compact(),
+            Item::Impl(ref v) => v.is_compact(),
+            Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => false,
+            Item::TypeAlias(ref v) => v.is_compact(),
+        }
+    }
+
+    /// Returns whether the names of two items would collide, i.e. whether
+    /// they share the type or value namespace, e.g. a struct and an enum, or
+    /// a unit struct and a function.
+    pub(crate) fn shares_namespace(&self, other: &Item) -> bool {
+        let (types, values) = self.namespaces();
+        let (other_types, other_values) = other.namespaces();
+        (types && other_types) || (values && other_values)
+    }
+
+    /// Returns whether the item's name is in the type and the value
+    /// namespace. Unit and tuple structs are in both.
+    fn namespaces(&self) -> (bool, bool) {
+        match *self {
+            Item::Module(_)
+            | Item::ModDecl(_)
+            | Item::Trait(_)
+            | Item::Enum(_)
+            | Item::TypeAlias(_) => (true, false),
+            Item::Struct(ref v) => (true, !matches!(v.fields(), Fields::Named(_))),
+            Item::Function(_) | Item::Const(_) => (false, true),
+            Item::Impl(_) | Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => (false, false),
+        }
+    }
+
+    /// Gets the keyword of the item's kind, e.g. `struct`.
+    pub(crate) fn kind_name(&self) -> &'static str {
+        match *self {
+            Item::Module(_) | Item::ModDecl(_) => "mod",
+            Item::Const(ref v) => match v.kind() {
+                ConstKind::Const => "const",
+                ConstKind::Static | ConstKind::StaticMut => "static",
+            },
+            Item::Struct(_) => "struct",
+            Item::Function(_) => "fn",
+            Item::Trait(_) => "trait",
+            Item::Enum(_) => "enum",
+            Item::Impl(_) => "impl",
+            Item::Raw(_) => "raw",
+            Item::Comment(_) => "comment",
+            Item::TypeAlias(_) => "type",
+            Item::LineBreak(_) => "line break",
+        }
+    }
+
+    /// Gets the rank of the item's kind when sorting by kind.
+    pub(crate) fn kind_rank(&self) -> usize {
+        match *self {
+            Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => 0,
+            Item::Module(_) | Item::ModDecl(_) => 1,
+            Item::Const(_) | Item::TypeAlias(_) => 2,
+            Item::Struct(_) => 3,
+            Item::Enum(_) => 4,
+            Item::Trait(_) => 5,
+            Item::Impl(_) => 6,
+            Item::Function(_) => 7,
+        }
+    }
+
+    /// Formats the item using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.fmt_provenance(self.kind_name(), self.name(), self.provenance())?;
+
+        match *self {
+            Item::Module(ref v) => v.fmt(fmt),
+            Item::ModDecl(ref v) => v.fmt(fmt),
+            Item::Const(ref v) => v.fmt(fmt),
+            Item::Struct(ref v) => v.fmt(fmt),
+            Item::Function(ref v) => v.fmt(false, fmt),
+            Item::Trait(ref v) => v.fmt(fmt),
+            Item::Enum(ref v) => v.fmt(fmt),
+            Item::Impl(ref v) => v.fmt(fmt),
+            Item::Raw(ref v) => writeln!(fmt, "{}", v),
+            Item::Comment(ref v) => v.fmt(fmt),
+            Item::TypeAlias(ref v) => v.fmt(fmt),
+            Item::LineBreak(ref v) => v.fmt(fmt),
+        }
+    }
+}
+
 impl From<Module> for Item {
     fn from(value: Module) -> Self {
         Item::Module(value)
     }
 }
 
+impl From<ModDecl> for Item {
+    fn from(value: ModDecl) -> Self {
+        Item::ModDecl(value)
+    }
+}
+
+impl From<ConstItem> for Item {
+    fn from(value: ConstItem) -> Self {
+        Item::Const(value)
+    }
+}
+
 impl From<Struct> for Item {
     fn from(value: Struct) -> Self {
         Item::Struct(value)
@@ -72,6 +387,12 @@ impl From<String> for Item {
     }
 }
 
+impl From<Comment> for Item {
+    fn from(value: Comment) -> Self {
+        Item::Comment(value)
+    }
+}
+
 impl From<TypeAlias> for Item {
     fn from(value: TypeAlias) -> Self {
         Item::TypeAlias(value)
@@ -0,0 +1,29 @@
+/// The kind of an [`Item`](crate::Item), without its contents.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemKind {
+    /// A module.
+    Module,
+    /// A module declaration, e.g. `mod foo;`.
+    ModDecl,
+    /// A `const` or `static` item.
+    Const,
+    /// A struct.
+    Struct,
+    /// A function.
+    Function,
+    /// A trait.
+    Trait,
+    /// An enum.
+    Enum,
+    /// An implementation.
+    Impl,
+    /// A raw string.
+    Raw,
+    /// A comment.
+    Comment,
+    /// A type alias.
+    TypeAlias,
+    /// A line break.
+    LineBreak,
+}
@@ -0,0 +1,33 @@
+use core::cmp::Ordering;
+
+use crate::item::Item;
+
+/// Strategy used to sort the items of a scope.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemOrder {
+    /// Alphabetical by item name.
+    Name,
+    /// By item kind (modules, type aliases, structs, enums, traits, `impl`
+    /// blocks, then functions), then alphabetical by name.
+    KindThenName,
+}
+
+impl ItemOrder {
+    /// Compares two items according to this order.
+    ///
+    /// Unnamed items (raw strings, comments and line breaks) compare before
+    /// named ones. [`Scope::sort_items`] never compares them though, as it
+    /// keeps them with the item that follows them.
+    ///
+    /// [`Scope::sort_items`]: crate::Scope::sort_items
+    pub fn compare(&self, a: &Item, b: &Item) -> Ordering {
+        match self {
+            ItemOrder::Name => a.name().cmp(&b.name()),
+            ItemOrder::KindThenName => a
+                .kind_rank()
+                .cmp(&b.kind_rank())
+                .then_with(|| a.name().cmp(&b.name())),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 const KW_AS: &str = "as";
+const KW_ASYNC: &str = "async";
+const KW_AWAIT: &str = "await";
 const KW_BREAK: &str = "break";
 const KW_CONST: &str = "const";
 const KW_CONTINUE: &str = "continue";
 const KW_CRATE: &str = "crate";
+const KW_DYN: &str = "dyn";
 const KW_ELSE: &str = "else";
 const KW_ENUM: &str = "enum";
 const KW_EXTERN: &str = "extern";
@@ -34,12 +37,31 @@ const KW_USE: &str = "use";
 const KW_WHERE: &str = "where";
 const KW_WHILE: &str = "while";
 
-const KEYWORDS_STRICT: [&str] = [
+const KW_ABSTRACT: &str = "abstract";
+const KW_BECOME: &str = "become";
+const KW_BOX: &str = "box";
+const KW_DO: &str = "do";
+const KW_FINAL: &str = "final";
+const KW_GEN: &str = "gen";
+const KW_MACRO: &str = "macro";
+const KW_OVERRIDE: &str = "override";
+const KW_PRIV: &str = "priv";
+const KW_TRY: &str = "try";
+const KW_TYPEOF: &str = "typeof";
+const KW_UNSIZED: &str = "unsized";
+const KW_VIRTUAL: &str = "virtual";
+const KW_YIELD: &str = "yield";
+
+/// Keywords that can only be used in their keyword context.
+pub(crate) const KEYWORDS_STRICT: &[&str] = &[
     KW_AS,
+    KW_ASYNC,
+    KW_AWAIT,
     KW_BREAK,
     KW_CONST,
     KW_CONTINUE,
     KW_CRATE,
+    KW_DYN,
     KW_ELSE,
     KW_ENUM,
     KW_EXTERN,
@@ -71,3 +93,24 @@ const KEYWORDS_STRICT: [&str] = [
     KW_WHERE,
     KW_WHILE,
 ];
+
+/// Keywords reserved for future use.
+pub(crate) const KEYWORDS_RESERVED: &[&str] = &[
+    KW_ABSTRACT,
+    KW_BECOME,
+    KW_BOX,
+    KW_DO,
+    KW_FINAL,
+    KW_GEN,
+    KW_MACRO,
+    KW_OVERRIDE,
+    KW_PRIV,
+    KW_TRY,
+    KW_TYPEOF,
+    KW_UNSIZED,
+    KW_VIRTUAL,
+    KW_YIELD,
+];
+
+/// Keywords that cannot be used as raw identifiers.
+pub(crate) const KEYWORDS_NOT_RAW: &[&str] = &[KW_CRATE, KW_SELFVALUE, KW_SELFTYPE, KW_SUPER];
@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_debug_implementations, missing_docs)]
 
 //! Provides a builder API for generating Rust code.
@@ -22,27 +23,95 @@
 //!
 //! println!("{}", scope.to_string());
 //! ```
+//!
+//! Without the default `std` feature, the crate is `no_std` and only needs
+//! `alloc`. The code model and the formatter are available, while writing
+//! files, build scripts and golden tests need `std`. The maps of the crate
+//! use [`FnvBuildHasher`] with or without `std`.
+
+extern crate alloc;
 
 mod associated_const;
 mod associated_type;
+mod async_style;
+mod attribute;
+mod bitflags;
+mod blank_lines;
+mod blanket;
 mod block;
 mod body;
 mod bound;
+mod case;
+mod cfg;
+mod check;
+mod comment;
+mod compat;
+mod complexity;
+mod const_item;
+mod derive;
 mod doc;
+mod emission;
+mod enum_visitor;
+mod error_enum;
+mod error_mapping;
 mod field;
 mod fields;
+#[cfg(feature = "std")]
+mod files;
+mod fnv;
 mod formatter;
 mod function;
 mod generic_parameter;
+mod getters;
+mod ident;
+mod impl_stub;
 mod import;
+mod import_group;
 mod item;
+mod item_kind;
+mod item_order;
+mod keywords;
 mod line_break;
 mod lint;
+mod manifest;
+mod merge;
+mod mod_decl;
 mod module;
+mod newtype;
+mod orphan;
+#[cfg(feature = "syn")]
+mod parse;
+mod prelude;
+mod prelude_module;
+mod provenance;
+mod redact;
+mod rename;
+mod replace;
+mod repr;
 mod scope;
+mod serde_attr;
+#[cfg(feature = "std")]
+mod sha256;
+mod struct_conversion;
+mod symbol;
+mod target_os;
+mod template;
+#[cfg(feature = "proc-macro2")]
+mod tokens;
+mod toolchain;
+mod tuple_field;
 mod type_def;
+mod typestate;
 mod variant;
 mod visibility;
+mod visit;
+#[cfg(feature = "std")]
+mod write;
+
+#[cfg(feature = "std")]
+pub mod build_script;
+#[cfg(feature = "std")]
+pub mod testing;
 
 mod r#enum;
 mod r#impl;
@@ -50,26 +119,71 @@ mod r#struct;
 mod r#trait;
 mod r#type;
 mod type_alias;
+mod type_registry;
 
 pub use associated_const::*;
 pub use associated_type::*;
+pub use async_style::*;
+pub use attribute::*;
+pub use bitflags::*;
+pub use blank_lines::*;
+pub use blanket::*;
 pub use block::*;
 pub use bound::*;
+pub use case::*;
+pub use cfg::*;
+pub use check::*;
+pub use comment::*;
+pub use compat::*;
+pub use complexity::*;
+pub use const_item::*;
+pub use derive::*;
+pub use emission::*;
 pub use r#enum::*;
+pub use enum_visitor::*;
+pub use error_enum::*;
+pub use error_mapping::*;
 pub use field::*;
 pub use fields::*;
+#[cfg(feature = "std")]
+pub use files::*;
+pub use fnv::*;
 pub use formatter::*;
 pub use function::*;
 pub use generic_parameter::*;
+pub use ident::*;
 pub use r#impl::*;
+pub use impl_stub::*;
 pub use import::*;
+pub use import_group::*;
 pub use item::*;
+pub use item_kind::*;
+pub use item_order::*;
 pub use lint::*;
+pub use manifest::*;
+pub use merge::*;
+pub use mod_decl::*;
 pub use module::*;
+pub use newtype::*;
+pub use orphan::*;
+pub use prelude_module::*;
+pub use provenance::*;
+pub use rename::*;
+pub use repr::*;
 pub use scope::*;
+pub use serde_attr::*;
 pub use r#struct::*;
+pub use struct_conversion::*;
+pub use symbol::*;
+pub use target_os::*;
+pub use template::*;
+pub use toolchain::*;
 pub use r#trait::*;
+pub use tuple_field::*;
 pub use r#type::*;
 pub use type_alias::*;
+pub use type_registry::*;
+pub use typestate::*;
 pub use variant::*;
 pub use visibility::*;
+pub use visit::*;
@@ -1,8 +1,9 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
 use crate::formatter::Formatter;
 
-#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineBreak {}
 
 impl LineBreak {
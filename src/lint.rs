@@ -1,9 +1,11 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
 use crate::formatter::Formatter;
+use crate::prelude::*;
 
 /// Types of lint levels.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Lint {
     /// Corresponds to #[allow(...)]
     Allow(String),
@@ -0,0 +1,709 @@
+use core::fmt::{self, Write};
+
+use crate::prelude::*;
+
+use crate::toolchain::{Edition, RustVersion};
+
+/// Defines the `Cargo.toml` manifest of a generated crate.
+///
+/// The manifest renders as TOML through its `Display` implementation, with
+/// the tables in the order `[package]`, `[lib]`, `[[bin]]`, `[[example]]`,
+/// `[[bench]]`, `[features]`, `[dependencies]` and `[dev-dependencies]`.
+/// Empty tables are omitted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    /// Name of the package
+    name: String,
+
+    /// Version of the package
+    version: String,
+
+    /// Edition of the package, if set
+    edition: Option<Edition>,
+
+    /// Minimum supported Rust version of the package, if set
+    rust_version: Option<RustVersion>,
+
+    /// Description of the package
+    description: Option<String>,
+
+    /// License of the package, as an SPDX expression
+    license: Option<String>,
+
+    /// Library target, if customized
+    lib: Option<CargoTarget>,
+
+    /// Binary targets
+    bins: Vec<CargoTarget>,
+
+    /// Example targets
+    examples: Vec<CargoTarget>,
+
+    /// Benchmark targets
+    benches: Vec<CargoTarget>,
+
+    /// Features and the features or dependencies they enable
+    features: IndexMap<String, Vec<String>>,
+
+    /// Dependencies
+    dependencies: Vec<Dependency>,
+
+    /// Dependencies of tests, examples and benchmarks
+    dev_dependencies: Vec<Dependency>,
+}
+
+impl Manifest {
+    /// Creates a manifest for the package with the given name, at version
+    /// `0.1.0`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Manifest {
+            name: name.into(),
+            version: String::from("0.1.0"),
+            edition: None,
+            rust_version: None,
+            description: None,
+            license: None,
+            lib: None,
+            bins: Vec::new(),
+            examples: Vec::new(),
+            benches: Vec::new(),
+            features: IndexMap::default(),
+            dependencies: Vec::new(),
+            dev_dependencies: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the package.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the name of the package.
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the name of the package.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Gets the version of the package.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Sets the version of the package.
+    pub fn set_version(&mut self, version: impl Into<String>) -> &mut Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Sets the version of the package.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.set_version(version);
+        self
+    }
+
+    /// Gets the edition of the package, if set.
+    pub fn edition(&self) -> Option<Edition> {
+        self.edition
+    }
+
+    /// Sets the edition of the package.
+    ///
+    /// Without an edition, a manifest generated by a
+    /// [`Library`](crate::Library) uses the edition of the library, if any.
+    pub fn set_edition(&mut self, edition: impl Into<Option<Edition>>) -> &mut Self {
+        self.edition = edition.into();
+        self
+    }
+
+    /// Sets the edition of the package.
+    pub fn with_edition(mut self, edition: impl Into<Option<Edition>>) -> Self {
+        self.set_edition(edition);
+        self
+    }
+
+    /// Gets the minimum supported Rust version of the package, if set.
+    pub fn rust_version(&self) -> Option<RustVersion> {
+        self.rust_version
+    }
+
+    /// Sets the minimum supported Rust version of the package.
+    ///
+    /// Without a version, a manifest generated by a
+    /// [`Library`](crate::Library) uses the version of the library, if any.
+    pub fn set_rust_version(&mut self, rust_version: impl Into<Option<RustVersion>>) -> &mut Self {
+        self.rust_version = rust_version.into();
+        self
+    }
+
+    /// Sets the minimum supported Rust version of the package.
+    pub fn with_rust_version(mut self, rust_version: impl Into<Option<RustVersion>>) -> Self {
+        self.set_rust_version(rust_version);
+        self
+    }
+
+    /// Gets the description of the package.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Sets the description of the package.
+    pub fn set_description(&mut self, description: impl Into<String>) -> &mut Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the description of the package.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.set_description(description);
+        self
+    }
+
+    /// Gets the license of the package.
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    /// Sets the license of the package, as an SPDX expression, e.g.
+    /// `MIT OR Apache-2.0`.
+    pub fn set_license(&mut self, license: impl Into<String>) -> &mut Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    /// Sets the license of the package.
+    pub fn with_license(mut self, license: impl Into<String>) -> Self {
+        self.set_license(license);
+        self
+    }
+
+    /// Gets the library target, if customized.
+    pub fn lib(&self) -> Option<&CargoTarget> {
+        self.lib.as_ref()
+    }
+
+    /// Sets the library target, rendered as the `[lib]` table.
+    pub fn set_lib(&mut self, lib: impl Into<CargoTarget>) -> &mut Self {
+        self.lib = Some(lib.into());
+        self
+    }
+
+    /// Sets the library target.
+    pub fn with_lib(mut self, lib: impl Into<CargoTarget>) -> Self {
+        self.set_lib(lib);
+        self
+    }
+
+    /// Gets the binary targets.
+    pub fn bins(&self) -> &[CargoTarget] {
+        &self.bins
+    }
+
+    /// Pushes a binary target, rendered as a `[[bin]]` table.
+    pub fn push_bin(&mut self, bin: impl Into<CargoTarget>) -> &mut Self {
+        self.bins.push(bin.into());
+        self
+    }
+
+    /// Pushes a binary target.
+    pub fn with_bin(mut self, bin: impl Into<CargoTarget>) -> Self {
+        self.push_bin(bin);
+        self
+    }
+
+    /// Gets the example targets.
+    pub fn examples(&self) -> &[CargoTarget] {
+        &self.examples
+    }
+
+    /// Pushes an example target, rendered as an `[[example]]` table.
+    ///
+    /// A manifest generated by a [`Library`](crate::Library) declares the
+    /// examples of the library that are not pushed here.
+    pub fn push_example(&mut self, example: impl Into<CargoTarget>) -> &mut Self {
+        self.examples.push(example.into());
+        self
+    }
+
+    /// Pushes an example target.
+    pub fn with_example(mut self, example: impl Into<CargoTarget>) -> Self {
+        self.push_example(example);
+        self
+    }
+
+    /// Gets the benchmark targets.
+    pub fn benches(&self) -> &[CargoTarget] {
+        &self.benches
+    }
+
+    /// Pushes a benchmark target, rendered as a `[[bench]]` table.
+    ///
+    /// Benchmarks bringing their own harness, e.g. criterion, need
+    /// [`CargoTarget::set_harness`] set to `false`. A manifest generated by a
+    /// [`Library`](crate::Library) declares the benchmarks of the library that
+    /// are not pushed here, without the default harness.
+    pub fn push_bench(&mut self, bench: impl Into<CargoTarget>) -> &mut Self {
+        self.benches.push(bench.into());
+        self
+    }
+
+    /// Pushes a benchmark target.
+    pub fn with_bench(mut self, bench: impl Into<CargoTarget>) -> Self {
+        self.push_bench(bench);
+        self
+    }
+
+    /// Gets the features and the features or dependencies they enable.
+    pub fn features(&self) -> &IndexMap<String, Vec<String>> {
+        &self.features
+    }
+
+    /// Declares a feature enabling the given features or dependencies, e.g.
+    /// `std` or `dep:serde`.
+    ///
+    /// Declaring a feature twice replaces what it enables.
+    pub fn push_feature<S>(
+        &mut self,
+        name: impl Into<String>,
+        enables: impl IntoIterator<Item = S>,
+    ) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.features
+            .insert(name.into(), enables.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Declares a feature enabling the given features or dependencies.
+    pub fn with_feature<S>(
+        mut self,
+        name: impl Into<String>,
+        enables: impl IntoIterator<Item = S>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.push_feature(name, enables);
+        self
+    }
+
+    /// Gets the dependencies.
+    pub fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
+    /// Pushes a dependency.
+    pub fn push_dependency(&mut self, dependency: impl Into<Dependency>) -> &mut Self {
+        self.dependencies.push(dependency.into());
+        self
+    }
+
+    /// Pushes a dependency.
+    pub fn with_dependency(mut self, dependency: impl Into<Dependency>) -> Self {
+        self.push_dependency(dependency);
+        self
+    }
+
+    /// Gets the dependencies of tests, examples and benchmarks.
+    pub fn dev_dependencies(&self) -> &[Dependency] {
+        &self.dev_dependencies
+    }
+
+    /// Pushes a dependency of tests, examples and benchmarks.
+    pub fn push_dev_dependency(&mut self, dependency: impl Into<Dependency>) -> &mut Self {
+        self.dev_dependencies.push(dependency.into());
+        self
+    }
+
+    /// Pushes a dependency of tests, examples and benchmarks.
+    pub fn with_dev_dependency(mut self, dependency: impl Into<Dependency>) -> Self {
+        self.push_dev_dependency(dependency);
+        self
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[package]")?;
+        writeln!(f, "name = {}", TomlStr(&self.name))?;
+        writeln!(f, "version = {}", TomlStr(&self.version))?;
+        if let Some(edition) = self.edition {
+            writeln!(f, "edition = \"{}\"", edition)?;
+        }
+        if let Some(rust_version) = self.rust_version {
+            writeln!(f, "rust-version = \"{}\"", rust_version)?;
+        }
+        if let Some(ref description) = self.description {
+            writeln!(f, "description = {}", TomlStr(description))?;
+        }
+        if let Some(ref license) = self.license {
+            writeln!(f, "license = {}", TomlStr(license))?;
+        }
+
+        if let Some(ref lib) = self.lib {
+            writeln!(f, "\n[lib]")?;
+            lib.fmt(f)?;
+        }
+
+        for (table, targets) in [
+            ("bin", &self.bins),
+            ("example", &self.examples),
+            ("bench", &self.benches),
+        ] {
+            for target in targets {
+                writeln!(f, "\n[[{}]]", table)?;
+                target.fmt(f)?;
+            }
+        }
+
+        if !self.features.is_empty() {
+            writeln!(f, "\n[features]")?;
+            for (name, enables) in &self.features {
+                writeln!(f, "{} = {}", TomlKey(name), TomlArray(enables))?;
+            }
+        }
+
+        for (table, dependencies) in [
+            ("dependencies", &self.dependencies),
+            ("dev-dependencies", &self.dev_dependencies),
+        ] {
+            if !dependencies.is_empty() {
+                writeln!(f, "\n[{}]", table)?;
+                for dependency in dependencies {
+                    writeln!(f, "{}", dependency)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Defines a library, binary, example or benchmark target of a [`Manifest`].
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CargoTarget {
+    /// Name of the target, if it differs from the default one
+    name: Option<String>,
+
+    /// Path of the root source file, relative to the manifest
+    path: Option<String>,
+
+    /// Whether the target uses the default test harness, if set
+    harness: Option<bool>,
+}
+
+impl CargoTarget {
+    /// Creates a target with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        CargoTarget {
+            name: Some(name.into()),
+            path: None,
+            harness: None,
+        }
+    }
+
+    /// Gets the name of the target, if set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the name of the target.
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the name of the target.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Gets the path of the root source file, if set.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Sets the path of the root source file, relative to the manifest, e.g.
+    /// `src/bin/cli.rs`.
+    pub fn set_path(&mut self, path: impl Into<String>) -> &mut Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the path of the root source file.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Gets whether the target uses the default test harness, if set.
+    pub fn harness(&self) -> Option<bool> {
+        self.harness
+    }
+
+    /// Sets whether the target uses the default test harness, e.g. `false`
+    /// for criterion benchmarks.
+    pub fn set_harness(&mut self, harness: impl Into<Option<bool>>) -> &mut Self {
+        self.harness = harness.into();
+        self
+    }
+
+    /// Sets whether the target uses the default test harness.
+    pub fn with_harness(mut self, harness: impl Into<Option<bool>>) -> Self {
+        self.set_harness(harness);
+        self
+    }
+
+    /// Writes the keys of the target's table.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref name) = self.name {
+            writeln!(f, "name = {}", TomlStr(name))?;
+        }
+        if let Some(ref path) = self.path {
+            writeln!(f, "path = {}", TomlStr(path))?;
+        }
+        if let Some(harness) = self.harness {
+            writeln!(f, "harness = {}", harness)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for CargoTarget {
+    fn from(name: &str) -> Self {
+        CargoTarget::new(name)
+    }
+}
+
+impl From<String> for CargoTarget {
+    fn from(name: String) -> Self {
+        CargoTarget::new(name)
+    }
+}
+
+/// Defines a dependency of a [`Manifest`].
+///
+/// A dependency only specifying a version renders as `name = "version"`,
+/// otherwise as an inline table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dependency {
+    /// Name of the dependency
+    name: String,
+
+    /// Version requirement
+    version: Option<String>,
+
+    /// Path of a local dependency, relative to the manifest
+    path: Option<String>,
+
+    /// Features enabled on the dependency
+    features: Vec<String>,
+
+    /// Whether the dependency is only built when a feature enables it
+    optional: bool,
+
+    /// Whether the default features of the dependency are enabled
+    default_features: bool,
+}
+
+impl Dependency {
+    /// Creates a dependency on the given version of a crate, e.g. `1.0`.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Dependency {
+            name: name.into(),
+            version: Some(version.into()),
+            path: None,
+            features: Vec::new(),
+            optional: false,
+            default_features: true,
+        }
+    }
+
+    /// Creates a dependency on a local crate, given its path relative to the
+    /// manifest.
+    pub fn new_path(name: impl Into<String>, path: impl Into<String>) -> Self {
+        Dependency {
+            name: name.into(),
+            version: None,
+            path: Some(path.into()),
+            features: Vec::new(),
+            optional: false,
+            default_features: true,
+        }
+    }
+
+    /// Gets the name of the dependency.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the version requirement, if any.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Sets the version requirement.
+    pub fn set_version(&mut self, version: impl Into<String>) -> &mut Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the version requirement.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.set_version(version);
+        self
+    }
+
+    /// Gets the path of a local dependency, if any.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Gets the features enabled on the dependency.
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Enables a feature of the dependency.
+    pub fn push_feature(&mut self, feature: impl Into<String>) -> &mut Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    /// Enables a feature of the dependency.
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.push_feature(feature);
+        self
+    }
+
+    /// Returns whether the dependency is optional.
+    pub fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// Sets whether the dependency is only built when a feature enables it.
+    pub fn set_optional(&mut self, optional: bool) -> &mut Self {
+        self.optional = optional;
+        self
+    }
+
+    /// Sets whether the dependency is only built when a feature enables it.
+    pub fn with_optional(mut self, optional: bool) -> Self {
+        self.set_optional(optional);
+        self
+    }
+
+    /// Returns whether the default features of the dependency are enabled.
+    pub fn is_default_features(&self) -> bool {
+        self.default_features
+    }
+
+    /// Sets whether the default features of the dependency are enabled.
+    pub fn set_default_features(&mut self, default_features: bool) -> &mut Self {
+        self.default_features = default_features;
+        self
+    }
+
+    /// Sets whether the default features of the dependency are enabled.
+    pub fn with_default_features(mut self, default_features: bool) -> Self {
+        self.set_default_features(default_features);
+        self
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = ", TomlKey(&self.name))?;
+
+        let simple = self.path.is_none()
+            && self.features.is_empty()
+            && !self.optional
+            && self.default_features;
+        if let (true, Some(version)) = (simple, &self.version) {
+            return write!(f, "{}", TomlStr(version));
+        }
+
+        let mut keys = Vec::new();
+        if let Some(ref version) = self.version {
+            keys.push(format!("version = {}", TomlStr(version)));
+        }
+        if let Some(ref path) = self.path {
+            keys.push(format!("path = {}", TomlStr(path)));
+        }
+        if !self.default_features {
+            keys.push(String::from("default-features = false"));
+        }
+        if !self.features.is_empty() {
+            keys.push(format!("features = {}", TomlArray(&self.features)));
+        }
+        if self.optional {
+            keys.push(String::from("optional = true"));
+        }
+
+        write!(f, "{{ {} }}", keys.join(", "))
+    }
+}
+
+/// Writes a TOML basic string.
+struct TomlStr<'a>(&'a str);
+
+impl fmt::Display for TomlStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\t' => f.write_str("\\t")?,
+                c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+                c => f.write_char(c)?,
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+/// Writes a TOML key, quoting it unless it is a bare key.
+struct TomlKey<'a>(&'a str);
+
+impl fmt::Display for TomlKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bare = !self.0.is_empty()
+            && self
+                .0
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if bare {
+            f.write_str(self.0)
+        } else {
+            TomlStr(self.0).fmt(f)
+        }
+    }
+}
+
+/// Writes a TOML array of strings on a single line.
+struct TomlArray<'a>(&'a [String]);
+
+impl fmt::Display for TomlArray<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+        for (i, s) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            TomlStr(s).fmt(f)?;
+        }
+        f.write_char(']')
+    }
+}
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Strategy used when merging scopes that define items with the same name.
+///
+/// Modules with the same name are always merged recursively; the strategy
+/// applies to the other named items (structs, enums, traits, functions and
+/// type aliases). `impl` blocks, raw strings and line breaks never conflict.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergeStrategy {
+    /// Fail with a [`MergeError`].
+    #[default]
+    Error,
+    /// Keep the item of the scope being merged into.
+    KeepExisting,
+    /// Replace the item with the one of the scope being merged.
+    Replace,
+}
+
+/// Error returned when merging scopes fails.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum MergeError {
+    /// Both scopes define an item with the same name.
+    #[error("{kind} `{name}` is defined in both scopes")]
+    Conflict {
+        /// Kind of the conflicting items, e.g. `struct`
+        kind: &'static str,
+        /// Path of the conflicting items, e.g. `module::Foo`
+        name: String,
+    },
+}
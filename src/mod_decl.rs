@@ -0,0 +1,284 @@
+use core::fmt::{self, Write};
+
+use crate::attribute::Attribute;
+use crate::cfg::Cfg;
+use crate::doc::Doc;
+use crate::formatter::Formatter;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::visibility::Vis;
+
+/// Defines a module declaration whose contents live in another file, e.g.
+/// `mod foo;`.
+///
+/// Unlike [`Module`](crate::Module), which renders its contents inline, a
+/// declaration only refers to a file, typically written as a separate
+/// [`File`](crate::File).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModDecl {
+    /// Module name
+    name: String,
+
+    /// Visibility
+    vis: Vis,
+
+    /// Module documentation
+    doc: Option<Doc>,
+
+    /// File of the module, rendered as `#[path = "..."]`
+    path: Option<String>,
+
+    /// Condition under which the module is compiled
+    cfg: Option<Cfg>,
+
+    /// Other attributes
+    attributes: Vec<Attribute>,
+
+    /// Whether to omit the blank line before the item
+    compact: bool,
+
+    /// Where the item was generated from
+    provenance: Option<Provenance>,
+}
+
+impl ModDecl {
+    /// Creates a new module declaration.
+    pub fn new(name: impl Into<String>) -> Self {
+        ModDecl {
+            name: name.into(),
+            vis: Vis::Private,
+            doc: None,
+            path: None,
+            cfg: None,
+            attributes: Vec::new(),
+            compact: false,
+            provenance: None,
+        }
+    }
+
+    /// Gets the module name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the module name.
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the module name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Gets a mutable reference to the module name.
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    /// Gets the visibility.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets a mutable reference to the visibility.
+    pub fn vis_mut(&mut self) -> &mut Vis {
+        &mut self.vis
+    }
+
+    /// Gets the module documentation.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// Sets the module documentation.
+    pub fn set_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the module documentation.
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
+    where
+        S: Into<Doc>,
+    {
+        self.set_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the module documentation.
+    pub fn doc_mut(&mut self) -> &mut Option<Doc> {
+        &mut self.doc
+    }
+
+    /// Gets the file of the module, if it is not at the default location.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Sets the file of the module, rendered as `#[path = "..."]`.
+    pub fn set_path(&mut self, path: impl Into<String>) -> &mut Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Sets the file of the module, rendered as `#[path = "..."]`.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.set_path(path);
+        self
+    }
+
+    /// Gets a mutable reference to the file of the module.
+    pub fn path_mut(&mut self) -> &mut Option<String> {
+        &mut self.path
+    }
+
+    /// Gets the condition under which the module is compiled.
+    pub fn cfg(&self) -> Option<&Cfg> {
+        self.cfg.as_ref()
+    }
+
+    /// Sets the condition under which the module is compiled.
+    pub fn set_cfg(&mut self, cfg: Cfg) -> &mut Self {
+        self.cfg = Some(cfg);
+        self
+    }
+
+    /// Sets the condition under which the module is compiled.
+    pub fn with_cfg(mut self, cfg: Cfg) -> Self {
+        self.set_cfg(cfg);
+        self
+    }
+
+    /// Gets a mutable reference to the condition under which the module is
+    /// compiled.
+    pub fn cfg_mut(&mut self) -> &mut Option<Cfg> {
+        &mut self.cfg
+    }
+
+    /// Gets the attributes.
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Sets the attributes.
+    pub fn set_attributes<A>(&mut self, attributes: impl IntoIterator<Item = A>) -> &mut Self
+    where
+        A: Into<Attribute>,
+    {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the attributes.
+    pub fn with_attributes<A>(mut self, attributes: impl IntoIterator<Item = A>) -> Self
+    where
+        A: Into<Attribute>,
+    {
+        self.set_attributes(attributes);
+        self
+    }
+
+    /// Gets a mutable reference to the attributes.
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+
+    /// Pushes an attribute.
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Pushes an attribute.
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.push_attribute(attribute);
+        self
+    }
+
+    /// Returns whether the declaration is rendered without a blank line
+    /// before it.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets whether the declaration is rendered without a blank line before
+    /// it, e.g. to group consecutive declarations.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether the declaration is rendered without a blank line before
+    /// it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the declaration is rendered
+    /// without a blank line before it.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    /// Gets where the module declaration was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Sets where the module declaration was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    /// Sets where the module declaration was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the module declaration was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
+    }
+
+    /// Formats the module declaration using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref doc) = self.doc {
+            doc.fmt(fmt)?;
+        }
+
+        if let Some(ref cfg) = self.cfg {
+            writeln!(fmt, "#[{}]", cfg.to_attribute())?;
+        }
+        if let Some(ref path) = self.path {
+            writeln!(fmt, "#[path = {:?}]", path)?;
+        }
+        for attr in &self.attributes {
+            writeln!(fmt, "#[{}]", attr)?;
+        }
+
+        self.vis.fmt(fmt)?;
+        writeln!(fmt, "mod {};", self.name)
+    }
+}
@@ -1,7 +1,11 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
-use indexmap::IndexMap;
+use crate::prelude::*;
+use thiserror::Error;
 
+use crate::attribute::Attribute;
+use crate::comment::Comment;
+use crate::const_item::ConstItem;
 use crate::doc::Doc;
 use crate::r#enum::Enum;
 use crate::formatter::Formatter;
@@ -9,13 +13,16 @@ use crate::function::Function;
 use crate::r#impl::Impl;
 use crate::import::Import;
 use crate::lint::Lint;
-use crate::scope::Scope;
+use crate::provenance::Provenance;
+use crate::scope::{MissingDefaultError, Scope};
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
+use crate::r#type::Type;
 use crate::visibility::Vis;
 
 /// Defines a module.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Module {
     /// Module name
     name: String,
@@ -30,10 +37,16 @@ pub struct Module {
     scope: Scope,
 
     /// Module attributes, e.g., `#[allow(unused_imports)]`.
-    attributes: Vec<String>,
+    attributes: Vec<Attribute>,
 
     /// Lint rules, e.g. `#[allow(unused_imports)]`
     lints: Vec<Lint>,
+
+    /// Whether to omit the blank line before the item
+    compact: bool,
+
+    /// Where the item was generated from
+    provenance: Option<Provenance>,
 }
 
 impl Module {
@@ -46,6 +59,8 @@ impl Module {
             scope: Scope::new(),
             attributes: Vec::new(),
             lints: Vec::new(),
+            compact: false,
+            provenance: None,
         }
     }
 
@@ -86,7 +101,7 @@ impl Module {
     }
 
     /// Sets the module documentation.
-    pub fn with_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
     where
         S: Into<Doc>,
     {
@@ -199,15 +214,33 @@ impl Module {
         self
     }
 
+    /// Import several types into the module's scope, all with the same
+    /// visibility.
+    ///
+    /// Takes pairs of a path and the types to import from it.
+    pub fn push_imports<P, I, T>(
+        &mut self,
+        imports: impl IntoIterator<Item = (P, I)>,
+        vis: impl Into<Vis>,
+    ) -> &mut Self
+    where
+        P: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.scope.push_imports(imports, vis);
+        self
+    }
+
     /// Gets the attributes for the module.
-    pub fn attributes(&self) -> &[String] {
+    pub fn attributes(&self) -> &[Attribute] {
         &self.attributes
     }
 
     /// Sets the attributes for the module.
     pub fn set_attributes<S>(&mut self, attributes: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.attributes = attributes.into_iter().map(Into::into).collect();
         self
@@ -216,25 +249,25 @@ impl Module {
     /// Sets the attributes for the module.
     pub fn with_attributes<S>(mut self, attributes: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_attributes(attributes);
         self
     }
 
     /// Gets a mutable reference to the attributes for the module.
-    pub fn attributes_mut(&mut self) -> &mut Vec<String> {
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.attributes
     }
 
     /// Adds an attribute to the module.
-    pub fn push_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
         self.attributes.push(attribute.into());
         self
     }
 
     /// Adds an attribute to the module.
-    pub fn with_attribute(mut self, attribute: impl Into<String>) -> Self {
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
         self.push_attribute(attribute);
         self
     }
@@ -311,6 +344,46 @@ impl Module {
         self.scope.get_or_new_module(name.into())
     }
 
+    /// Gets a mutable reference to the module at the given path of nested
+    /// modules, creating the missing modules along the way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is empty.
+    pub fn get_or_new_module_path<S>(&mut self, path: impl IntoIterator<Item = S>) -> &mut Module
+    where
+        S: AsRef<str>,
+    {
+        self.scope.get_or_new_module_path(path)
+    }
+
+    /// Gets a mutable reference to the innermost module of a dotted package
+    /// name, creating the missing modules along the way.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the package name is empty.
+    pub fn get_or_new_package(&mut self, package: impl AsRef<str>) -> &mut Module {
+        self.scope.get_or_new_package(package)
+    }
+
+    /// Pushes a new module definition, returning a mutable reference to it,
+    /// or an error if a module with the same name is already defined in this
+    /// module.
+    pub fn try_new_module(
+        &mut self,
+        name: impl Into<String>,
+    ) -> Result<&mut Module, DuplicateModuleError> {
+        self.scope.try_new_module(name)
+    }
+
+    /// Pushes a module definition, returning a mutable reference to it, or
+    /// an error if a module with the same name is already defined in this
+    /// module.
+    pub fn try_push_module(&mut self, item: Module) -> Result<&mut Module, DuplicateModuleError> {
+        self.scope.try_push_module(item)
+    }
+
     /// Pushes a module definition.
     ///
     /// # Panics
@@ -339,17 +412,47 @@ impl Module {
         self
     }
 
+    /// Gets a reference to a struct if it exists in this module.
+    pub fn get_struct<'a>(&self, name: impl Into<&'a str>) -> Option<&Struct> {
+        self.scope.get_struct(name)
+    }
+
+    /// Gets a mutable reference to a struct if it exists in this module.
+    pub fn get_struct_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Struct> {
+        self.scope.get_struct_mut(name)
+    }
+
     /// Pushes a new function definition, returning a mutable reference to it.
     pub fn new_function(&mut self, name: impl Into<String>) -> &mut Function {
         self.scope.new_function(name.into())
     }
 
+    /// Pushes a new function returning `Result<ok, E>`, where `E` is the
+    /// default error type of the module scope, or an error if there is none.
+    pub fn new_fallible_function(
+        &mut self,
+        name: impl Into<String>,
+        ok: impl Into<Type>,
+    ) -> Result<&mut Function, MissingDefaultError> {
+        self.scope.new_fallible_function(name, ok)
+    }
+
     /// Pushes a function definition
     pub fn push_function(&mut self, item: Function) -> &mut Self {
         self.scope.push_function(item);
         self
     }
 
+    /// Gets a reference to a function if it exists in this module.
+    pub fn get_function<'a>(&self, name: impl Into<&'a str>) -> Option<&Function> {
+        self.scope.get_function(name)
+    }
+
+    /// Gets a mutable reference to a function if it exists in this module.
+    pub fn get_function_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Function> {
+        self.scope.get_function_mut(name)
+    }
+
     /// Pushes a new enum definition, returning a mutable reference to it.
     pub fn new_enum(&mut self, name: impl Into<String>) -> &mut Enum {
         self.scope.new_enum(name.into())
@@ -361,6 +464,16 @@ impl Module {
         self
     }
 
+    /// Gets a reference to an enum if it exists in this module.
+    pub fn get_enum<'a>(&self, name: impl Into<&'a str>) -> Option<&Enum> {
+        self.scope.get_enum(name)
+    }
+
+    /// Gets a mutable reference to an enum if it exists in this module.
+    pub fn get_enum_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Enum> {
+        self.scope.get_enum_mut(name)
+    }
+
     /// Pushes a new `impl` block, returning a mutable reference to it.
     pub fn new_impl(&mut self, target: impl Into<String>) -> &mut Impl {
         self.scope.new_impl(target.into())
@@ -372,6 +485,18 @@ impl Module {
         self
     }
 
+    /// Gets a reference to the first `impl` block for the given target type,
+    /// if it exists in this module.
+    pub fn get_impl_for<'a>(&self, target: impl Into<&'a str>) -> Option<&Impl> {
+        self.scope.get_impl_for(target)
+    }
+
+    /// Gets a mutable reference to the first `impl` block for the given
+    /// target type, if it exists in this module.
+    pub fn get_impl_for_mut<'a>(&mut self, target: impl Into<&'a str>) -> Option<&mut Impl> {
+        self.scope.get_impl_for_mut(target)
+    }
+
     /// Pushes a new trait
     pub fn new_trait(&mut self, name: impl Into<String>) -> &mut Trait {
         self.scope.new_trait(name.into())
@@ -383,6 +508,94 @@ impl Module {
         self
     }
 
+    /// Gets a reference to a trait if it exists in this module.
+    pub fn get_trait<'a>(&self, name: impl Into<&'a str>) -> Option<&Trait> {
+        self.scope.get_trait(name)
+    }
+
+    /// Gets a mutable reference to a trait if it exists in this module.
+    pub fn get_trait_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Trait> {
+        self.scope.get_trait_mut(name)
+    }
+
+    /// Pushes a new `const` item.
+    pub fn new_const(
+        &mut self,
+        name: impl Into<String>,
+        ty: impl Into<Type>,
+        value: impl Into<String>,
+    ) -> &mut ConstItem {
+        self.scope.new_const(name, ty, value)
+    }
+
+    /// Pushes a new `static` item.
+    pub fn new_static(
+        &mut self,
+        name: impl Into<String>,
+        ty: impl Into<Type>,
+        value: impl Into<String>,
+    ) -> &mut ConstItem {
+        self.scope.new_static(name, ty, value)
+    }
+
+    /// Pushes a `const` or `static` item.
+    pub fn push_const(&mut self, item: ConstItem) -> &mut Self {
+        self.scope.push_const(item);
+        self
+    }
+
+    /// Pushes a comment, attached to the next item.
+    pub fn push_comment(&mut self, comment: impl Into<Comment>) -> &mut Self {
+        self.scope.push_comment(comment);
+        self
+    }
+
+    /// Gets whether the module is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Sets whether the module is rendered without a blank line before it.
+    ///
+    /// Use it to keep small related modules together.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Sets whether the module is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the module is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    /// Gets where the module was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Sets where the module was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    /// Sets where the module was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the module was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
+    }
+
     /// Formats the module using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if let Some(ref doc) = self.doc {
@@ -408,3 +621,12 @@ impl Module {
         Ok(())
     }
 }
+
+/// Error returned when pushing a module whose name is already defined in the
+/// scope.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("module `{name}` is already defined")]
+pub struct DuplicateModuleError {
+    /// Name of the module
+    pub name: String,
+}
@@ -0,0 +1,254 @@
+use crate::associated_type::AssociatedType;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::r#struct::Struct;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// Defines an impl generated for a [`Newtype`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NewtypeImpl {
+    /// `From<Inner>` for the newtype
+    From,
+    /// `From<Newtype>` for the inner type
+    Into,
+    /// `Deref` to the inner type
+    Deref,
+    /// `Display`, forwarded to the inner type
+    Display,
+    /// `Add`, adding the inner values
+    Add,
+    /// `Sub`, subtracting the inner values
+    Sub,
+    /// Serde derives, serialized as the inner type
+    SerdeTransparent,
+}
+
+/// Generates a newtype wrapping a single value, with a chosen set of impls.
+///
+/// ```
+/// use simple_codegen::*;
+///
+/// let meters = Newtype::new("Meters", "f64")
+///     .with_vis(Vis::Pub)
+///     .with_impl(NewtypeImpl::From)
+///     .with_impl(NewtypeImpl::Add);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Newtype {
+    /// Name of the newtype
+    name: String,
+
+    /// Wrapped type
+    inner: Type,
+
+    /// Visibility of the newtype
+    vis: Vis,
+
+    /// Traits to derive
+    derives: Vec<String>,
+
+    /// Impls to generate
+    impls: Vec<NewtypeImpl>,
+}
+
+impl Newtype {
+    /// Creates a newtype wrapping the `inner` type, deriving `Clone`,
+    /// `PartialEq` and `Debug`.
+    ///
+    /// `Copy` is not derived, as most wrapped types, e.g. `String`, are not
+    /// `Copy`; push it with [`push_derive`](#method.push_derive) when they are.
+    pub fn new(name: impl Into<String>, inner: impl Into<Type>) -> Self {
+        Newtype {
+            name: name.into(),
+            inner: inner.into(),
+            vis: Vis::Private,
+            derives: ["Clone", "PartialEq", "Debug"]
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            impls: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the newtype.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the wrapped type.
+    pub fn inner(&self) -> &Type {
+        &self.inner
+    }
+
+    /// Gets the visibility of the newtype.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility of the newtype.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility of the newtype.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets the traits to derive.
+    pub fn derives(&self) -> &[String] {
+        &self.derives
+    }
+
+    /// Sets the traits to derive.
+    pub fn set_derives<S>(&mut self, derives: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.derives = derives.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the traits to derive.
+    pub fn with_derives<S>(mut self, derives: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_derives(derives);
+        self
+    }
+
+    /// Pushes a trait to derive, e.g. `Copy`.
+    pub fn push_derive(&mut self, derive: impl Into<String>) -> &mut Self {
+        self.derives.push(derive.into());
+        self
+    }
+
+    /// Pushes a trait to derive.
+    pub fn with_derive(mut self, derive: impl Into<String>) -> Self {
+        self.push_derive(derive);
+        self
+    }
+
+    /// Gets the impls to generate.
+    pub fn impls(&self) -> &[NewtypeImpl] {
+        &self.impls
+    }
+
+    /// Pushes an impl to generate.
+    pub fn push_impl(&mut self, r#impl: NewtypeImpl) -> &mut Self {
+        if !self.impls.contains(&r#impl) {
+            self.impls.push(r#impl);
+        }
+        self
+    }
+
+    /// Pushes an impl to generate.
+    pub fn with_impl(mut self, r#impl: NewtypeImpl) -> Self {
+        self.push_impl(r#impl);
+        self
+    }
+
+    /// Generates the newtype, followed by its impls in the order they were
+    /// pushed.
+    pub fn generate(&self) -> Vec<Item> {
+        let mut r#struct = Struct::new(self.name.as_str())
+            .with_vis(self.vis.clone())
+            .with_derives(self.derives.iter().map(String::as_str))
+            .with_tuple_field(self.inner.clone());
+        if self.impls.contains(&NewtypeImpl::SerdeTransparent) {
+            r#struct.push_derive("serde::Serialize");
+            r#struct.push_derive("serde::Deserialize");
+            r#struct.push_attribute("serde(transparent)");
+        }
+
+        let mut items = vec![Item::from(r#struct)];
+        for r#impl in &self.impls {
+            let r#impl = match r#impl {
+                NewtypeImpl::From => self.impl_from_inner(),
+                NewtypeImpl::Into => self.impl_into_inner(),
+                NewtypeImpl::Deref => self.impl_deref(),
+                NewtypeImpl::Display => self.impl_display(),
+                NewtypeImpl::Add => self.impl_operator("Add", "add", "+"),
+                NewtypeImpl::Sub => self.impl_operator("Sub", "sub", "-"),
+                NewtypeImpl::SerdeTransparent => continue,
+            };
+            items.push(r#impl.into());
+        }
+        items
+    }
+
+    /// Generates `impl From<Inner> for Newtype`.
+    fn impl_from_inner(&self) -> Impl {
+        Impl::new(self.name.as_str())
+            .with_impl_trait(format!("From<{}>", self.inner.to_source()))
+            .with_function(
+                Function::new("from")
+                    .with_arg("value", self.inner.clone())
+                    .with_ret("Self")
+                    .with_line("Self(value)"),
+            )
+    }
+
+    /// Generates `impl From<Newtype> for Inner`.
+    fn impl_into_inner(&self) -> Impl {
+        Impl::new(self.inner.clone())
+            .with_impl_trait(format!("From<{}>", self.name))
+            .with_function(
+                Function::new("from")
+                    .with_arg("value", self.name.as_str())
+                    .with_ret("Self")
+                    .with_line("value.0"),
+            )
+    }
+
+    /// Generates `impl Deref for Newtype`.
+    fn impl_deref(&self) -> Impl {
+        Impl::new(self.name.as_str())
+            .with_impl_trait("std::ops::Deref")
+            .with_associated_type(AssociatedType::new_with_concrete_ty(
+                "Target",
+                self.inner.clone(),
+            ))
+            .with_function(
+                Function::new("deref")
+                    .with_self_arg(SelfArg::WithSelfRef)
+                    .with_ret("&Self::Target")
+                    .with_line("&self.0"),
+            )
+    }
+
+    /// Generates `impl Display for Newtype`.
+    fn impl_display(&self) -> Impl {
+        Impl::new(self.name.as_str())
+            .with_impl_trait("std::fmt::Display")
+            .with_function(
+                Function::new("fmt")
+                    .with_self_arg(SelfArg::WithSelfRef)
+                    .with_arg("f", "&mut std::fmt::Formatter<'_>")
+                    .with_ret("std::fmt::Result")
+                    .with_line("std::fmt::Display::fmt(&self.0, f)"),
+            )
+    }
+
+    /// Generates the impl of a binary operator trait.
+    fn impl_operator(&self, r#trait: &str, method: &str, op: &str) -> Impl {
+        Impl::new(self.name.as_str())
+            .with_impl_trait(format!("std::ops::{}", r#trait))
+            .with_associated_type(AssociatedType::new_with_concrete_ty("Output", "Self"))
+            .with_function(
+                Function::new(method)
+                    .with_self_arg(SelfArg::WithSelf)
+                    .with_arg("rhs", "Self")
+                    .with_ret("Self")
+                    .with_line(format!("Self(self.0 {} rhs.0)", op)),
+            )
+    }
+}
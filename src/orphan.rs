@@ -0,0 +1,178 @@
+use thiserror::Error;
+
+use crate::r#impl::Impl;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::visit::{Visitor, walk_impl};
+
+/// Traits from the standard prelude, which are foreign even when unqualified.
+const PRELUDE_TRAITS: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Clone",
+    "Copy",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Send",
+    "Sized",
+    "Sync",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Unpin",
+];
+
+/// Types from the standard prelude, which are foreign even when unqualified.
+const PRELUDE_TYPES: &[&str] = &[
+    "Box", "Option", "Result", "String", "Vec", "bool", "char", "f32", "f64", "i8", "i16", "i32",
+    "i64", "i128", "isize", "str", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Error returned when an impl block would break the orphan rule.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error(
+    "impl of foreign trait `{trait}` from `{trait_origin}` for `{target}` from `{origin}` \
+     breaks the orphan rule"
+)]
+pub struct OrphanRuleError {
+    /// The trait being implemented
+    pub r#trait: String,
+
+    /// The crate the trait is defined in
+    pub trait_origin: String,
+
+    /// The target type of the impl block
+    pub target: String,
+
+    /// The crate the target type is defined in
+    pub origin: String,
+}
+
+/// Error returned by [`Impl::new_remote`] when the target is not qualified
+/// with a crate name.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("remote impl target `{target}` has no crate path")]
+pub struct UnqualifiedTargetError {
+    pub(crate) target: String,
+}
+
+impl UnqualifiedTargetError {
+    /// Gets the name of the unqualified target type.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+}
+
+impl Impl {
+    /// Checks that a trait impl for a remote type does not break the orphan
+    /// rule.
+    ///
+    /// Only targets with an [`origin`](Impl::origin) are checked. The trait
+    /// is foreign if its path starts with a crate name or it is part of the
+    /// standard prelude, e.g. `Clone` or `From`. As in the compiler, a trait
+    /// with a local type among its generics, e.g. `From<MyType>` or
+    /// `From<&MyType>`, is allowed. Lifetimes and references to foreign types,
+    /// e.g. `From<&'a str>`, are not local.
+    pub fn check_orphan_rule(&self) -> Result<(), OrphanRuleError> {
+        let (Some(origin), Some(r#trait)) = (self.origin(), self.impl_trait()) else {
+            return Ok(());
+        };
+        let Some(trait_origin) = type_crate(r#trait.name(), PRELUDE_TRAITS) else {
+            return Ok(());
+        };
+
+        let has_local_generic = r#trait.generics().iter().any(|generic| {
+            let Some(name) = referent(generic.name()) else {
+                return false;
+            };
+            !self.generics().iter().any(|g| g == name) && type_crate(name, PRELUDE_TYPES).is_none()
+        });
+        if has_local_generic {
+            return Ok(());
+        }
+
+        Err(OrphanRuleError {
+            r#trait: r#trait.to_source(),
+            trait_origin: trait_origin.to_string(),
+            target: self.target().to_source(),
+            origin: origin.to_string(),
+        })
+    }
+}
+
+impl Scope {
+    /// Checks every impl block of the scope, including nested modules, with
+    /// [`Impl::check_orphan_rule`] and returns the violations found.
+    pub fn check_orphan_rule(&self) -> Vec<OrphanRuleError> {
+        let mut checker = OrphanChecker { errors: Vec::new() };
+        checker.visit_scope(self);
+        checker.errors
+    }
+}
+
+struct OrphanChecker {
+    errors: Vec<OrphanRuleError>,
+}
+
+impl Visitor for OrphanChecker {
+    fn visit_impl(&mut self, r#impl: &Impl) {
+        if let Err(e) = r#impl.check_orphan_rule() {
+            self.errors.push(e);
+        }
+        walk_impl(self, r#impl);
+    }
+}
+
+/// Returns the crate a path-qualified name starts with, e.g. `serde` for
+/// `serde::Serialize`. Paths relative to the current crate return `None`.
+pub(crate) fn path_crate(path: &str) -> Option<&str> {
+    let path = path.trim_start_matches("::");
+    let path = &path[..path.find('<').unwrap_or(path.len())];
+    let (first, _) = path.split_once("::")?;
+    match first {
+        "crate" | "self" | "super" | "Self" => None,
+        _ => Some(first),
+    }
+}
+
+/// Returns the type behind any references, e.g. `str` for `&'a str`, or
+/// `None` for a lifetime argument. References are fundamental, so `&MyType`
+/// is as local as `MyType`.
+fn referent(name: &str) -> Option<&str> {
+    let mut name = name.trim();
+    if name.starts_with('\'') {
+        return None;
+    }
+    while let Some(rest) = name.strip_prefix('&') {
+        name = rest.trim_start();
+        if name.starts_with('\'') {
+            name = name.split_once(' ').map_or("", |(_, ty)| ty.trim_start());
+        }
+        name = name.strip_prefix("mut ").unwrap_or(name).trim_start();
+    }
+    Some(name)
+}
+
+fn type_crate<'a>(name: &'a str, prelude: &[&str]) -> Option<&'a str> {
+    let name = name.trim_start_matches("mut ").trim();
+    path_crate(name).or_else(|| {
+        let base = &name[..name.find('<').unwrap_or(name.len())];
+        prelude.contains(&base).then_some("std")
+    })
+}
@@ -0,0 +1,784 @@
+use proc_macro2::{LineColumn, Literal, TokenStream, TokenTree};
+use quote::ToTokens;
+
+use crate::associated_const::AssociatedConst;
+use crate::associated_type::AssociatedType;
+use crate::attribute::{Attribute, AttributeArgs};
+use crate::bound::Bound;
+use crate::cfg::Cfg;
+use crate::const_item::{ConstItem, ConstKind};
+use crate::r#enum::Enum;
+use crate::field::Field;
+use crate::fields::Fields;
+use crate::function::{Function, SelfArg};
+use crate::generic_parameter::GenericParameter;
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::mod_decl::ModDecl;
+use crate::module::Module;
+use crate::repr::Repr;
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::tokens::print_tokens;
+use crate::r#trait::Trait;
+use crate::tuple_field::TupleField;
+use crate::type_alias::TypeAlias;
+use crate::variant::Variant;
+use crate::visibility::Vis;
+
+impl Scope {
+    /// Parses Rust source code into a scope.
+    ///
+    /// Imports, modules, structs, enums, traits, `impl` blocks, functions,
+    /// consts, statics and type aliases are lifted into the builder model,
+    /// along with their fields, variants, generics, qualifiers, derives,
+    /// attributes and docs. Inner attributes and docs of the file and of
+    /// inline modules are kept on their scope. Function bodies are kept as
+    /// source lines. Any other item is kept verbatim as a raw item.
+    ///
+    /// Formatting and comments outside of function bodies are not preserved.
+    /// Constructs the model cannot represent, such as enum discriminants or
+    /// negative impls, are reported as errors rather than dropped.
+    pub fn from_source(source: &str) -> syn::Result<Scope> {
+        let file = syn::parse_file(source)?;
+        let parser = Parser {
+            lines: source.lines().collect(),
+        };
+
+        let mut scope = Scope::new();
+        parser.inner_attrs(&mut scope, &file.attrs);
+        parser.items(&mut scope, &file.items)?;
+
+        Ok(scope)
+    }
+}
+
+struct Parser<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl Parser<'_> {
+    fn items(&self, scope: &mut Scope, items: &[syn::Item]) -> syn::Result<()> {
+        for item in items {
+            match item {
+                syn::Item::Use(v) => {
+                    let vis = vis(&v.vis);
+                    for (path, name) in use_tree(v)? {
+                        scope.push_import(path, name, vis.clone());
+                    }
+                }
+                syn::Item::Mod(v) if v.content.is_none() => {
+                    let mut decl = ModDecl::new(v.ident.to_string());
+                    decl.set_vis(vis(&v.vis));
+                    let (doc, attrs) = self.attrs(&v.attrs);
+                    decl.set_doc::<String>(doc);
+                    let mut attrs: Vec<Attribute> =
+                        attrs.into_iter().map(Attribute::from).collect();
+
+                    // `#[cfg]` and `#[path]` render before the other
+                    // attributes, so they are only lifted into their fields
+                    // when that keeps the source order.
+                    let path = attrs.iter().position(|attr| attr.path() == "path");
+                    if let Some(i) = path
+                        && attrs[..i].iter().all(|attr| attr.path() == "cfg")
+                        && let AttributeArgs::Value(value) = attrs[i].args()
+                        && let Ok(value) = syn::parse_str::<syn::LitStr>(value)
+                    {
+                        let mut cfgs: Vec<Cfg> = attrs
+                            .drain(..i)
+                            .filter_map(|attr| Cfg::from_attribute(&attr))
+                            .collect();
+                        let cfg = match cfgs.len() {
+                            0 | 1 => cfgs.pop(),
+                            _ => Some(Cfg::all(cfgs)),
+                        };
+                        if let Some(cfg) = cfg {
+                            decl.set_cfg(cfg);
+                        }
+                        attrs.remove(0);
+                        decl.set_path(value.value());
+                    }
+                    decl.set_attributes(attrs);
+                    scope.push_mod_decl(decl);
+                }
+                syn::Item::Mod(v) => {
+                    let mut module = Module::new(v.ident.to_string());
+                    module.set_vis(vis(&v.vis));
+                    let (inner, outer): (Vec<_>, Vec<_>) = v
+                        .attrs
+                        .iter()
+                        .cloned()
+                        .partition(|attr| matches!(attr.style, syn::AttrStyle::Inner(_)));
+                    let (doc, attrs) = self.attrs(&outer);
+                    if let Some(doc) = doc {
+                        module.set_doc(doc);
+                    }
+                    module.set_attributes(attrs);
+                    self.inner_attrs(module.scope_mut(), &inner);
+                    if let Some((_, items)) = &v.content {
+                        self.items(module.scope_mut(), items)?;
+                    }
+                    // Modules may share a name when they are gated by
+                    // exclusive `#[cfg]`s, so the name is not checked.
+                    scope.push_item(Item::Module(module));
+                }
+                syn::Item::Struct(v) => {
+                    scope.push_struct(self.r#struct(v)?);
+                }
+                syn::Item::Enum(v) => {
+                    scope.push_enum(self.r#enum(v)?);
+                }
+                syn::Item::Trait(v) => {
+                    scope.push_trait(self.r#trait(v)?);
+                }
+                syn::Item::Impl(v) => {
+                    scope.push_impl(self.r#impl(v)?);
+                }
+                syn::Item::Fn(v) => {
+                    let mut function = self.signature(&v.sig, &v.attrs)?;
+                    function.set_vis(vis(&v.vis));
+                    self.body(&mut function, &v.block)?;
+                    scope.push_function(function);
+                }
+                syn::Item::Const(v) if v.generics.params.is_empty() => {
+                    scope.push_const(self.r#const(
+                        ConstItem::new(v.ident.to_string(), print(&v.ty), print(&v.expr)),
+                        &v.vis,
+                        &v.attrs,
+                    ));
+                }
+                syn::Item::Static(v) => {
+                    let kind = match v.mutability {
+                        syn::StaticMutability::Mut(_) => ConstKind::StaticMut,
+                        _ => ConstKind::Static,
+                    };
+                    let item = ConstItem::new(v.ident.to_string(), print(&v.ty), print(&v.expr))
+                        .with_kind(kind);
+                    scope.push_const(self.r#const(item, &v.vis, &v.attrs));
+                }
+                // Type aliases have no attributes in the model, so ones with
+                // attributes are kept verbatim
+                syn::Item::Type(v)
+                    if v.generics.params.is_empty() && self.attrs(&v.attrs).1.is_empty() =>
+                {
+                    let mut alias = TypeAlias::new(v.ident.to_string(), print(&v.ty));
+                    alias.set_vis(vis(&v.vis));
+                    let (doc, _) = self.attrs(&v.attrs);
+                    if let Some(doc) = doc {
+                        alias.set_doc(doc);
+                    }
+                    scope.push_type_alias(alias);
+                }
+                _ => {
+                    scope.raw(self.source_of(item.to_token_stream()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the inner docs and attributes of a file or inline module.
+    fn inner_attrs(&self, scope: &mut Scope, attrs: &[syn::Attribute]) {
+        let (doc, attrs) = self.attrs(attrs);
+        if let Some(doc) = doc {
+            scope.set_inner_doc(doc);
+        }
+        scope.set_attributes(attrs);
+    }
+
+    fn r#const(
+        &self,
+        mut item: ConstItem,
+        vis: &syn::Visibility,
+        attrs: &[syn::Attribute],
+    ) -> ConstItem {
+        item.set_vis(self::vis(vis));
+        let (doc, attrs) = self.attrs(attrs);
+        item.set_doc::<String>(doc);
+        item.set_attributes(attrs);
+        item
+    }
+
+    fn r#struct(&self, item: &syn::ItemStruct) -> syn::Result<Struct> {
+        let mut ret = Struct::new(item.ident.to_string());
+        ret.set_vis(vis(&item.vis));
+
+        let (doc, attrs) = self.attrs(&item.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        for attr in attrs {
+            if let Some(derives) = derives(&attr) {
+                ret.derives_mut().extend(derives);
+            } else if let Some(repr) = repr(&attr) {
+                ret.repr_mut().extend(repr);
+            } else {
+                ret.push_attribute(attr);
+            }
+        }
+
+        let (generics, bounds) = generics(&item.generics)?;
+        ret.set_generics(generics);
+        ret.set_bounds(bounds);
+
+        ret.set_fields(self.fields(&item.fields));
+
+        Ok(ret)
+    }
+
+    fn r#enum(&self, item: &syn::ItemEnum) -> syn::Result<Enum> {
+        let mut ret = Enum::new(item.ident.to_string());
+        ret.set_vis(vis(&item.vis));
+
+        let (doc, attrs) = self.attrs(&item.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        for attr in attrs {
+            if let Some(derives) = derives(&attr) {
+                ret.derives_mut().extend(derives);
+            } else if let Some(repr) = repr(&attr) {
+                ret.repr_mut().extend(repr);
+            } else {
+                ret.push_attribute(attr);
+            }
+        }
+
+        let (generics, bounds) = generics(&item.generics)?;
+        ret.set_generics(generics);
+        ret.set_bounds(bounds);
+
+        for v in &item.variants {
+            if let Some((_, discriminant)) = &v.discriminant {
+                return Err(unsupported(discriminant, "enum discriminants"));
+            }
+            let mut variant = Variant::new(v.ident.to_string());
+
+            let (doc, attrs) = self.attrs(&v.attrs);
+            variant.set_doc::<String>(doc);
+            for attr in attrs {
+                variant.push_annotation(attr);
+            }
+
+            variant.set_fields(self.fields(&v.fields));
+
+            ret.push_variant(variant);
+        }
+
+        Ok(ret)
+    }
+
+    fn r#trait(&self, item: &syn::ItemTrait) -> syn::Result<Trait> {
+        let mut ret = Trait::new(item.ident.to_string());
+        ret.set_vis(vis(&item.vis));
+        ret.set_unsafe(item.unsafety.is_some());
+        ret.set_auto(item.auto_token.is_some());
+
+        let (doc, attrs) = self.attrs(&item.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        ret.set_attributes(attrs);
+
+        let (generics, bounds) = generics(&item.generics)?;
+        ret.set_generics(generics);
+        ret.set_bounds(bounds);
+
+        for parent in &item.supertraits {
+            ret.push_parent(print(parent));
+        }
+
+        for trait_item in &item.items {
+            match trait_item {
+                syn::TraitItem::Fn(v) => {
+                    let mut function = self.signature(&v.sig, &v.attrs)?;
+                    if let Some(block) = &v.default {
+                        self.body(&mut function, block)?;
+                    }
+                    ret.push_function(function);
+                }
+                syn::TraitItem::Type(v)
+                    if v.attrs.is_empty()
+                        && v.generics.params.is_empty()
+                        && v.default.is_none() =>
+                {
+                    let bounds = v.bounds.iter().map(print);
+                    ret.push_associated_type(AssociatedType::new_with_bounds(
+                        v.ident.to_string(),
+                        bounds,
+                    ));
+                }
+                syn::TraitItem::Const(v)
+                    if v.attrs.is_empty()
+                        && v.generics.params.is_empty()
+                        && v.default.is_none() =>
+                {
+                    ret.push_associated_const(AssociatedConst::new(
+                        v.ident.to_string(),
+                        print(&v.ty),
+                    ));
+                }
+                _ => {
+                    return Err(unsupported(
+                        trait_item,
+                        "trait items other than functions, types and consts",
+                    ));
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+
+    fn r#impl(&self, item: &syn::ItemImpl) -> syn::Result<Impl> {
+        if let Some(defaultness) = &item.defaultness {
+            return Err(unsupported(defaultness, "`default impl` blocks"));
+        }
+
+        let mut ret = Impl::new(print(&item.self_ty));
+        ret.set_unsafe(item.unsafety.is_some());
+
+        for param in &item.generics.params {
+            ret.push_generic(print(param));
+        }
+        if let Some(where_clause) = &item.generics.where_clause {
+            ret.set_bounds(where_bounds(where_clause)?);
+        }
+        if let Some((negative, path, _)) = &item.trait_ {
+            if let Some(negative) = negative {
+                return Err(unsupported(negative, "negative impls"));
+            }
+            ret.set_impl_trait(print(path));
+        }
+        let (doc, attrs) = self.attrs(&item.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        ret.set_attributes(attrs);
+
+        for impl_item in &item.items {
+            match impl_item {
+                syn::ImplItem::Fn(v) => {
+                    if let Some(defaultness) = &v.defaultness {
+                        return Err(unsupported(defaultness, "`default` functions"));
+                    }
+                    let mut function = self.signature(&v.sig, &v.attrs)?;
+                    function.set_vis(vis(&v.vis));
+                    self.body(&mut function, &v.block)?;
+                    ret.push_function(function);
+                }
+                syn::ImplItem::Const(v)
+                    if v.defaultness.is_none() && v.generics.params.is_empty() =>
+                {
+                    ret.push_const(self.r#const(
+                        ConstItem::new(v.ident.to_string(), print(&v.ty), print(&v.expr)),
+                        &v.vis,
+                        &v.attrs,
+                    ));
+                }
+                syn::ImplItem::Type(v)
+                    if v.attrs.is_empty()
+                        && v.defaultness.is_none()
+                        && v.generics.params.is_empty() =>
+                {
+                    ret.push_associated_type(AssociatedType::new_with_concrete_ty(
+                        v.ident.to_string(),
+                        print(&v.ty),
+                    ));
+                }
+                _ => {
+                    return Err(unsupported(
+                        impl_item,
+                        "impl items other than functions, types and consts",
+                    ));
+                }
+            }
+        }
+
+        Ok(ret)
+    }
+
+    /// Converts the fields of a struct or variant, keeping empty braces and
+    /// parentheses apart from unit structs and variants.
+    fn fields(&self, fields: &syn::Fields) -> Fields {
+        match fields {
+            syn::Fields::Named(named) => {
+                let mut ret = Fields::with_capacity(named.named.len());
+                for field in &named.named {
+                    let name = field.ident.as_ref().map(ToString::to_string);
+                    ret.push_named(self.field(name.unwrap_or_default(), field));
+                }
+                ret
+            }
+            syn::Fields::Unnamed(unnamed) => {
+                let mut ret = Fields::tuple_with_capacity(unnamed.unnamed.len());
+                for field in &unnamed.unnamed {
+                    ret.push_tuple(self.tuple_field(field));
+                }
+                ret
+            }
+            syn::Fields::Unit => Fields::Empty,
+        }
+    }
+
+    fn field(&self, name: String, field: &syn::Field) -> Field {
+        let mut ret = Field::new(name, print(&field.ty));
+        ret.set_vis(vis(&field.vis));
+
+        let (doc, attrs) = self.attrs(&field.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        for attr in attrs {
+            ret.push_annotation(attr);
+        }
+
+        ret
+    }
+
+    fn tuple_field(&self, field: &syn::Field) -> TupleField {
+        let mut ret = TupleField::new(print(&field.ty));
+        ret.set_vis(vis(&field.vis));
+
+        let (doc, attrs) = self.attrs(&field.attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        for attr in attrs {
+            ret.push_annotation(attr);
+        }
+
+        ret
+    }
+
+    fn signature(&self, sig: &syn::Signature, attrs: &[syn::Attribute]) -> syn::Result<Function> {
+        if let Some(variadic) = &sig.variadic {
+            return Err(unsupported(variadic, "variadic functions"));
+        }
+
+        let mut ret = Function::new(sig.ident.to_string());
+
+        let (doc, attrs) = self.attrs(attrs);
+        if let Some(doc) = doc {
+            ret.set_doc(doc);
+        }
+        ret.set_attributes(attrs);
+        ret.set_const(sig.constness.is_some());
+        ret.set_async(sig.asyncness.is_some());
+        ret.set_unsafe(sig.unsafety.is_some());
+        if let Some(abi) = &sig.abi {
+            ret.set_extern_abi(abi.name.as_ref().map_or("C".to_string(), |n| n.value()));
+        }
+
+        for param in &sig.generics.params {
+            ret.push_generic(print(param));
+        }
+        if let Some(where_clause) = &sig.generics.where_clause {
+            ret.set_bounds(where_bounds(where_clause)?);
+        }
+
+        for input in &sig.inputs {
+            match input {
+                syn::FnArg::Receiver(receiver) if !receiver.attrs.is_empty() => {
+                    return Err(unsupported(receiver, "attributes on `self` arguments"));
+                }
+                syn::FnArg::Receiver(receiver) if receiver.colon_token.is_some() => {
+                    ret.set_self_arg(SelfArg::typed(print(&receiver.ty)));
+                }
+                syn::FnArg::Receiver(receiver) => {
+                    ret.set_self_arg(match (&receiver.reference, &receiver.mutability) {
+                        (Some((_, Some(lt))), Some(_)) => {
+                            SelfArg::WithMutSelfRefLifetime(lt.to_string())
+                        }
+                        (Some((_, Some(lt))), None) => {
+                            SelfArg::WithSelfRefLifetime(lt.to_string())
+                        }
+                        (Some(_), Some(_)) => SelfArg::WithMutSelfRef,
+                        (Some(_), None) => SelfArg::WithSelfRef,
+                        (None, Some(_)) => SelfArg::WithMutSelf,
+                        (None, None) => SelfArg::WithSelf,
+                    });
+                }
+                syn::FnArg::Typed(arg) => {
+                    let mut field = Field::new(print(&arg.pat), print(&arg.ty));
+                    for attr in &arg.attrs {
+                        field.push_annotation(print(&attr.meta));
+                    }
+                    ret.args_mut().push(field);
+                }
+            }
+        }
+
+        if let syn::ReturnType::Type(_, ty) = &sig.output {
+            ret.set_ret(print(ty));
+        }
+
+        Ok(ret)
+    }
+
+    /// Copies the lines of a function body from the source.
+    ///
+    /// Body lines are re-indented when written, so literals spanning several
+    /// lines, whose contents would change, are reported as errors.
+    fn body(&self, function: &mut Function, block: &syn::Block) -> syn::Result<()> {
+        if let Some(literal) =
+            multi_line_literal(block.stmts.iter().flat_map(|stmt| stmt.to_token_stream()))
+        {
+            return Err(syn::Error::new(
+                literal.span(),
+                "literals spanning several lines in function bodies are not supported",
+            ));
+        }
+
+        let span = block.brace_token.span;
+        let body = self.slice(span.open().end(), span.close().start());
+
+        let lines: Vec<&str> = body.lines().collect();
+        let start = lines.iter().position(|l| !l.trim().is_empty());
+        let end = lines.iter().rposition(|l| !l.trim().is_empty());
+        let (Some(start), Some(end)) = (start, end) else {
+            // An empty body still needs a block.
+            function.push_line("");
+            return Ok(());
+        };
+        let lines = &lines[start..=end];
+
+        let indent = lines
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        for line in lines {
+            let line = line.get(indent..).unwrap_or("").trim_end();
+            function.push_line(line);
+        }
+
+        Ok(())
+    }
+
+    /// Splits attributes into the documentation and the other attributes,
+    /// rendered without `#[...]`.
+    fn attrs(&self, attrs: &[syn::Attribute]) -> (Option<String>, Vec<String>) {
+        let mut doc: Option<String> = None;
+        let mut rest = Vec::new();
+
+        for attr in attrs {
+            if let syn::Meta::NameValue(nv) = &attr.meta
+                && nv.path.is_ident("doc")
+                && let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) = &nv.value
+            {
+                let value = s.value();
+                let line = value.strip_prefix(' ').unwrap_or(&value);
+                match &mut doc {
+                    Some(doc) => {
+                        doc.push('\n');
+                        doc.push_str(line);
+                    }
+                    None => doc = Some(line.to_string()),
+                }
+                continue;
+            }
+
+            rest.push(print(&attr.meta));
+        }
+
+        (doc, rest)
+    }
+
+    /// Copies the source of the given tokens.
+    fn source_of(&self, tokens: TokenStream) -> String {
+        let mut spans = tokens.into_iter().map(|t| t.span());
+        let Some(first) = spans.next() else {
+            return String::new();
+        };
+        let last = spans.last().unwrap_or(first);
+        self.slice(first.start(), last.end())
+    }
+
+    fn slice(&self, start: LineColumn, end: LineColumn) -> String {
+        let mut ret = String::new();
+
+        for line in start.line..=end.line {
+            let text = self.lines.get(line - 1).copied().unwrap_or("");
+            let from = if line == start.line { start.column } else { 0 };
+            let to = if line == end.line {
+                end.column
+            } else {
+                text.chars().count()
+            };
+
+            if line != start.line {
+                ret.push('\n');
+            }
+            ret.extend(text.chars().skip(from).take(to.saturating_sub(from)));
+        }
+
+        ret
+    }
+}
+
+/// Finds a literal spanning several lines, e.g. a multi-line string.
+fn multi_line_literal(tokens: impl IntoIterator<Item = TokenTree>) -> Option<Literal> {
+    tokens.into_iter().find_map(|token| match token {
+        TokenTree::Literal(literal) => {
+            let span = literal.span();
+            (span.start().line != span.end().line).then_some(literal)
+        }
+        TokenTree::Group(group) => multi_line_literal(group.stream()),
+        _ => None,
+    })
+}
+
+/// Builds the error reported for a construct the model cannot represent.
+fn unsupported(tokens: &impl ToTokens, what: &str) -> syn::Error {
+    syn::Error::new_spanned(tokens, format!("{} are not supported", what))
+}
+
+fn vis(vis: &syn::Visibility) -> Vis {
+    match vis {
+        syn::Visibility::Public(_) => Vis::Pub,
+        syn::Visibility::Inherited => Vis::Private,
+        syn::Visibility::Restricted(r) => match print(&r.path).as_str() {
+            "crate" if r.in_token.is_none() => Vis::PubCrate,
+            "self" if r.in_token.is_none() => Vis::PubSelf,
+            "super" if r.in_token.is_none() => Vis::PubSuper,
+            path if r.in_token.is_some() => Vis::PubIn(path.to_string()),
+            _ => Vis::Custom(print(vis)),
+        },
+    }
+}
+
+/// Flattens a use tree into paths and imported names.
+///
+/// A path starting with `::` keeps it, and `self as name` imports the module
+/// itself under a new name.
+fn use_tree(item: &syn::ItemUse) -> syn::Result<Vec<(String, String)>> {
+    fn walk(
+        tree: &syn::UseTree,
+        prefix: &str,
+        out: &mut Vec<(String, String)>,
+    ) -> syn::Result<()> {
+        let join = |name: &str| match prefix {
+            "" => name.to_string(),
+            "::" => format!("::{}", name),
+            prefix => format!("{}::{}", prefix, name),
+        };
+
+        // A single segment after `::` has no path to import it from.
+        if prefix == "::" && !matches!(tree, syn::UseTree::Path(_) | syn::UseTree::Group(_)) {
+            return Err(unsupported(
+                tree,
+                "single-segment imports starting with `::`",
+            ));
+        }
+
+        match tree {
+            syn::UseTree::Path(p) => walk(&p.tree, &join(&p.ident.to_string()), out)?,
+            syn::UseTree::Name(n) => out.push((prefix.to_string(), n.ident.to_string())),
+            syn::UseTree::Rename(r) if r.ident == "self" => {
+                let (path, module) = match prefix.rsplit_once("::") {
+                    Some((path, module)) => (path, module),
+                    None => ("", prefix),
+                };
+                if path.is_empty() && prefix.starts_with("::") {
+                    return Err(unsupported(
+                        tree,
+                        "single-segment imports starting with `::`",
+                    ));
+                }
+                out.push((path.to_string(), format!("{} as {}", module, r.rename)));
+            }
+            syn::UseTree::Rename(r) => {
+                out.push((prefix.to_string(), format!("{} as {}", r.ident, r.rename)))
+            }
+            syn::UseTree::Glob(_) => out.push((prefix.to_string(), "*".to_string())),
+            syn::UseTree::Group(g) => {
+                for tree in &g.items {
+                    walk(tree, prefix, out)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    let prefix = if item.leading_colon.is_some() {
+        "::"
+    } else {
+        ""
+    };
+    let mut out = Vec::new();
+    walk(&item.tree, prefix, &mut out)?;
+    Ok(out)
+}
+
+fn derives(attr: &str) -> Option<Vec<String>> {
+    let list = attr.strip_prefix("derive(")?.strip_suffix(')')?;
+    Some(list.split(',').map(|d| d.trim().to_string()).collect())
+}
+
+fn repr(attr: &str) -> Option<Vec<Repr>> {
+    let repr = attr.strip_prefix("repr(")?.strip_suffix(')')?;
+    repr.split(',').map(|hint| hint.parse().ok()).collect()
+}
+
+fn generics(generics: &syn::Generics) -> syn::Result<(Vec<GenericParameter>, Vec<Bound>)> {
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            // The declaration is kept whole, as a default goes after the
+            // traits, e.g. `T: Clone = u32`.
+            syn::GenericParam::Type(t) if t.default.is_some() => {
+                GenericParameter::new(print(param))
+            }
+            syn::GenericParam::Type(t) => {
+                let mut ret = GenericParameter::new(t.ident.to_string());
+                for bound in &t.bounds {
+                    ret.push_trait(print(bound));
+                }
+                ret
+            }
+            _ => GenericParameter::new(print(param)),
+        })
+        .collect();
+
+    let bounds = match &generics.where_clause {
+        Some(where_clause) => where_bounds(where_clause)?,
+        None => Vec::new(),
+    };
+
+    Ok((params, bounds))
+}
+
+fn where_bounds(where_clause: &syn::WhereClause) -> syn::Result<Vec<Bound>> {
+    where_clause
+        .predicates
+        .iter()
+        .map(|predicate| match predicate {
+            syn::WherePredicate::Type(t) => {
+                let name = match &t.lifetimes {
+                    Some(lifetimes) => format!("{} {}", print(lifetimes), print(&t.bounded_ty)),
+                    None => print(&t.bounded_ty),
+                };
+                Ok(Bound::new(name, t.bounds.iter().map(print)))
+            }
+            syn::WherePredicate::Lifetime(l) => Ok(Bound::new(
+                l.lifetime.to_string(),
+                l.bounds.iter().map(print),
+            )),
+            _ => Err(unsupported(predicate, "where predicates")),
+        })
+        .collect()
+}
+
+/// Prints tokens with conventional Rust spacing, e.g. `Vec<&'a str>`.
+fn print(tokens: &impl ToTokens) -> String {
+    print_tokens(tokens.to_token_stream())
+}
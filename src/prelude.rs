@@ -0,0 +1,10 @@
+//! Imports the items of the standard prelude from `alloc`, so that the crate
+//! builds without `std`.
+
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
+
+/// An `IndexMap` with the hasher of the crate, the same with or without `std`.
+pub(crate) type IndexMap<K, V> = indexmap::IndexMap<K, V, crate::fnv::FnvBuildHasher>;
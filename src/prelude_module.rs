@@ -0,0 +1,225 @@
+use crate::attribute::Attribute;
+use crate::item::Item;
+use crate::module::Module;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::visibility::Vis;
+
+/// Generates a `prelude` module re-exporting the public types of a scope.
+///
+/// Public structs, enums, traits and type aliases are collected from the
+/// scope and, recursively, from its public modules. Items and modules gated
+/// by a `#[cfg(...)]` attribute are skipped, as a `use` of them would fail to
+/// compile whenever they are left out. Each type is matched by
+/// its path relative to the scope, e.g. `models::User`, against patterns in
+/// which `*` stands for any sequence of characters, `::` included.
+///
+/// ```
+/// use simple_codegen::*;
+///
+/// let prelude = PreludeModule::new()
+///     .with_include("models::*")
+///     .with_exclude("*Builder");
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PreludeModule {
+    /// Name of the generated module
+    name: String,
+
+    /// Patterns a type must match one of to be re-exported
+    include: Vec<String>,
+
+    /// Patterns excluding a type from the re-exports
+    exclude: Vec<String>,
+}
+
+impl PreludeModule {
+    /// Creates a generator of a public module named `prelude`, re-exporting
+    /// every public type.
+    pub fn new() -> Self {
+        PreludeModule {
+            name: String::from("prelude"),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the generated module.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sets the name of the generated module.
+    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the name of the generated module.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// Gets the patterns a type must match one of to be re-exported.
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    /// Pushes a pattern a type must match one of to be re-exported.
+    ///
+    /// Without include patterns, every public type is re-exported.
+    pub fn push_include(&mut self, pattern: impl Into<String>) -> &mut Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Pushes a pattern a type must match one of to be re-exported.
+    pub fn with_include(mut self, pattern: impl Into<String>) -> Self {
+        self.push_include(pattern);
+        self
+    }
+
+    /// Gets the patterns excluding a type from the re-exports.
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    /// Pushes a pattern excluding a type from the re-exports, even if it
+    /// matches an include pattern.
+    pub fn push_exclude(&mut self, pattern: impl Into<String>) -> &mut Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Pushes a pattern excluding a type from the re-exports.
+    pub fn with_exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.push_exclude(pattern);
+        self
+    }
+
+    /// Generates the module, to be pushed into `scope`.
+    ///
+    /// Types are re-exported through `super`, in the order they are defined.
+    /// When several types share a name, only the first one is re-exported.
+    /// An existing module with the name of the generated one is skipped.
+    pub fn generate(&self, scope: &Scope) -> Module {
+        let mut types = Vec::new();
+        self.collect(scope, "", &mut types);
+
+        let mut module = Module::new(self.name.as_str()).with_vis(Vis::Pub);
+        let mut names = Vec::new();
+        for (path, name) in types {
+            if !names.contains(&name) {
+                module.push_import(path, name, Vis::Pub);
+                names.push(name);
+            }
+        }
+        module
+    }
+
+    /// Collects the re-exported types of `scope`, with the path of the
+    /// module defining them.
+    fn collect<'a>(&self, scope: &'a Scope, module: &str, types: &mut Vec<(String, &'a str)>) {
+        for item in scope.items() {
+            let (vis, gated) = match item {
+                Item::Module(v) if module.is_empty() && v.name() == self.name => continue,
+                Item::Module(v) => {
+                    if *v.vis() == Vis::Pub && !is_gated([v.attributes()]) {
+                        self.collect(v.scope(), &join(module, v.name()), types);
+                    }
+                    continue;
+                }
+                Item::Struct(v) => (v.vis(), is_gated([v.attributes(), v.macros()])),
+                Item::Enum(v) => (v.vis(), is_gated([v.attributes(), v.macros()])),
+                Item::Trait(v) => (v.vis(), is_gated([v.attributes(), v.macros()])),
+                Item::TypeAlias(v) => {
+                    let type_def = v.type_def();
+                    (
+                        v.vis(),
+                        is_gated([type_def.attributes(), type_def.macros()]),
+                    )
+                }
+                _ => continue,
+            };
+
+            let name = item.name().unwrap();
+            if *vis == Vis::Pub && !gated && self.is_match(&join(module, name)) {
+                types.push((join("super", module), name));
+            }
+        }
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|pattern| matches_pattern(pattern, path));
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| matches_pattern(pattern, path))
+    }
+}
+
+impl Default for PreludeModule {
+    fn default() -> Self {
+        PreludeModule::new()
+    }
+}
+
+impl Scope {
+    /// Pushes a prelude module re-exporting the public types of the scope.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a module with the name of the prelude is already defined in
+    /// this scope, see [`Scope::push_module`].
+    pub fn push_prelude(&mut self, prelude: &PreludeModule) -> &mut Self {
+        let module = prelude.generate(self);
+        self.push_module(module)
+    }
+}
+
+/// Returns whether any of the attributes is a `#[cfg(...)]`.
+fn is_gated<'a>(attributes: impl IntoIterator<Item = &'a [Attribute]>) -> bool {
+    attributes
+        .into_iter()
+        .flatten()
+        .any(|attr| attr.path() == "cfg")
+}
+
+/// Joins a module path and a name, either of which may be empty.
+fn join(module: &str, name: &str) -> String {
+    match (module.is_empty(), name.is_empty()) {
+        (true, _) => name.to_string(),
+        (_, true) => module.to_string(),
+        _ => format!("{}::{}", module, name),
+    }
+}
+
+/// Returns whether `path` matches `pattern`, where `*` stands for any
+/// sequence of characters.
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
@@ -0,0 +1,284 @@
+use core::fmt::{self, Write};
+
+use crate::prelude::*;
+
+/// Describes where a generated item comes from, e.g. a schema file and line.
+///
+/// Provenance is metadata: it is only rendered as a comment when the
+/// formatter has [provenance comments](crate::Formatter::set_provenance_comments)
+/// enabled, and it is collected into a [`SourceMap`] while formatting.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Provenance {
+    /// Source the item was generated from, e.g. a file path
+    source: String,
+
+    /// Line in the source, starting at 1
+    line: Option<usize>,
+}
+
+impl Provenance {
+    /// Creates a provenance pointing at a whole source.
+    pub fn new(source: impl Into<String>) -> Self {
+        Provenance {
+            source: source.into(),
+            line: None,
+        }
+    }
+
+    /// Creates a provenance pointing at a line of a source, starting at 1.
+    pub fn at(source: impl Into<String>, line: usize) -> Self {
+        Provenance::new(source).with_line(line)
+    }
+
+    /// Gets the source the item was generated from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Sets the source the item was generated from.
+    pub fn set_source(&mut self, source: impl Into<String>) -> &mut Self {
+        self.source = source.into();
+        self
+    }
+
+    /// Sets the source the item was generated from.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.set_source(source);
+        self
+    }
+
+    /// Gets a mutable reference to the source the item was generated from.
+    pub fn source_mut(&mut self) -> &mut String {
+        &mut self.source
+    }
+
+    /// Gets the line in the source, if known.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Sets the line in the source, starting at 1.
+    pub fn set_line(&mut self, line: usize) -> &mut Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the line in the source, starting at 1.
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.set_line(line);
+        self
+    }
+
+    /// Gets a mutable reference to the line in the source.
+    pub fn line_mut(&mut self) -> &mut Option<usize> {
+        &mut self.line
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for Provenance {
+    fn from(source: &str) -> Self {
+        Provenance::new(source)
+    }
+}
+
+impl From<String> for Provenance {
+    fn from(source: String) -> Self {
+        Provenance::new(source)
+    }
+}
+
+/// Maps a generated item to its [`Provenance`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceMapping {
+    /// Line of the generated item, starting at 1
+    line: usize,
+
+    /// Keyword of the item's kind, e.g. `struct`
+    kind: String,
+
+    /// Name of the item, if it has one
+    name: Option<String>,
+
+    /// Where the item comes from
+    provenance: Provenance,
+}
+
+impl SourceMapping {
+    pub(crate) fn new(
+        line: usize,
+        kind: &str,
+        name: Option<&str>,
+        provenance: Provenance,
+    ) -> Self {
+        SourceMapping {
+            line,
+            kind: kind.to_string(),
+            name: name.map(String::from),
+            provenance,
+        }
+    }
+
+    /// Moves the mapping down by the given number of lines.
+    #[cfg(feature = "std")]
+    pub(crate) fn shift(&mut self, lines: usize) {
+        self.line += lines;
+    }
+
+    /// Gets the line of the generated item, starting at 1.
+    ///
+    /// The line is the first one of the item, including its documentation
+    /// and attributes.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Gets the keyword of the item's kind, e.g. `struct`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Gets the name of the item, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets where the item comes from.
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+}
+
+/// Report of where the items of a generated file come from.
+///
+/// The report is collected by the [`Formatter`](crate::Formatter) and can be
+/// written as JSON next to the generated file.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceMap {
+    /// Generated file, if known
+    file: Option<String>,
+
+    /// Mappings, in order of appearance
+    mappings: Vec<SourceMapping>,
+}
+
+impl SourceMap {
+    /// Creates an empty source map.
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    /// Gets the generated file.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Sets the generated file.
+    pub fn set_file(&mut self, file: impl Into<String>) -> &mut Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Sets the generated file.
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.set_file(file);
+        self
+    }
+
+    /// Gets the mappings, in order of appearance.
+    pub fn mappings(&self) -> &[SourceMapping] {
+        &self.mappings
+    }
+
+    /// Gets a mutable reference to the mappings.
+    pub fn mappings_mut(&mut self) -> &mut Vec<SourceMapping> {
+        &mut self.mappings
+    }
+
+    /// Pushes a mapping.
+    pub fn push_mapping(&mut self, mapping: SourceMapping) -> &mut Self {
+        self.mappings.push(mapping);
+        self
+    }
+
+    /// Renders the source map as JSON.
+    ///
+    /// ```rust
+    /// use simple_codegen::{Provenance, Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("User").set_provenance(Provenance::at("schema.json", 3));
+    ///
+    /// assert_eq!(
+    ///     scope.source_map().to_json(),
+    ///     r#"{"mappings":[{"line":1,"kind":"struct","name":"User","source":"schema.json","source_line":3}]}"#,
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut ret = String::from("{");
+
+        if let Some(ref file) = self.file {
+            write!(ret, "\"file\":{},", json_string(file)).unwrap();
+        }
+
+        ret.push_str("\"mappings\":[");
+        for (i, mapping) in self.mappings.iter().enumerate() {
+            if i != 0 {
+                ret.push(',');
+            }
+
+            write!(
+                ret,
+                "{{\"line\":{},\"kind\":{}",
+                mapping.line,
+                json_string(&mapping.kind)
+            )
+            .unwrap();
+            if let Some(ref name) = mapping.name {
+                write!(ret, ",\"name\":{}", json_string(name)).unwrap();
+            }
+            write!(
+                ret,
+                ",\"source\":{}",
+                json_string(mapping.provenance.source())
+            )
+            .unwrap();
+            if let Some(line) = mapping.provenance.line() {
+                write!(ret, ",\"source_line\":{}", line).unwrap();
+            }
+            ret.push('}');
+        }
+        ret.push_str("]}");
+
+        ret
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut ret = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => ret.push_str("\\\""),
+            '\\' => ret.push_str("\\\\"),
+            '\n' => ret.push_str("\\n"),
+            '\r' => ret.push_str("\\r"),
+            '\t' => ret.push_str("\\t"),
+            c if c.is_control() => write!(ret, "\\u{:04x}", c as u32).unwrap(),
+            c => ret.push(c),
+        }
+    }
+    ret.push('"');
+    ret
+}
@@ -0,0 +1,68 @@
+use crate::bound::Bound;
+use crate::fields::Fields;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::prelude::*;
+use crate::r#struct::Struct;
+
+/// Placeholder printed instead of the value of a sensitive field.
+const REDACTED: &str = "\"***\"";
+
+impl Struct {
+    /// Generates an `impl std::fmt::Debug` block printing the value of
+    /// sensitive [fields](crate::Field::set_sensitive) and
+    /// [tuple fields](crate::TupleField::set_sensitive) as `"***"`.
+    ///
+    /// The output otherwise matches the derived impl, which requires the type
+    /// generics of the struct to implement `Debug` as well. The struct should
+    /// not also derive `Debug`.
+    pub fn redacted_debug_impl(&self) -> Impl {
+        let mut function = Function::new("fmt")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("f", "&mut std::fmt::Formatter<'_>")
+            .with_ret("std::fmt::Result");
+
+        match self.fields() {
+            Fields::Empty => {
+                function.push_line(format!("f.write_str({:?})", self.name()));
+            }
+            Fields::Tuple(fields) => {
+                function.push_line(format!("f.debug_tuple({:?})", self.name()));
+                for (i, field) in fields.iter().enumerate() {
+                    let value = if field.is_sensitive() {
+                        REDACTED.to_string()
+                    } else {
+                        format!("self.{}", i)
+                    };
+                    function.push_line(format!("    .field(&{})", value));
+                }
+                function.push_line("    .finish()");
+            }
+            Fields::Named(fields) => {
+                function.push_line(format!("f.debug_struct({:?})", self.name()));
+                for field in fields {
+                    let name = field.name();
+                    let value = if field.is_sensitive() {
+                        REDACTED.to_string()
+                    } else {
+                        format!("self.{}", name)
+                    };
+                    function.push_line(format!(
+                        "    .field({:?}, &{})",
+                        name.trim_start_matches("r#"),
+                        value
+                    ));
+                }
+                function.push_line("    .finish()");
+            }
+        }
+
+        let mut r#impl = Impl::new_for_definition(self.name(), self.generics())
+            .with_impl_trait("std::fmt::Debug")
+            .with_function(function);
+        for generic in self.generics().iter().filter(|g| g.is_type()) {
+            r#impl.push_bound(Bound::new(generic.bare_name(), ["std::fmt::Debug"]));
+        }
+        r#impl
+    }
+}
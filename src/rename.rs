@@ -0,0 +1,378 @@
+use crate::body::Body;
+use crate::r#enum::Enum;
+use crate::field::Field;
+use crate::function::Function;
+use crate::generic_parameter::GenericParameter;
+use crate::r#impl::Impl;
+use crate::module::Module;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::r#type::{Type, TypeKind};
+use crate::type_alias::TypeAlias;
+use crate::variant::Variant;
+use crate::visit::{
+    Visitor, VisitorMut, walk_enum, walk_enum_mut, walk_field_mut, walk_function_mut,
+    walk_impl_mut, walk_module, walk_module_mut, walk_struct, walk_struct_mut, walk_trait,
+    walk_trait_mut, walk_type_alias_mut, walk_type_mut, walk_variant_mut,
+};
+
+/// Renames types throughout a scope.
+///
+/// Every type defined in the scope (structs, enums, traits and type aliases,
+/// in nested modules too) gets the configured prefix and suffix, and explicit
+/// renames from the name map apply to any type, defined in the scope or not.
+/// Definitions are renamed along with every reference to them in fields,
+/// arguments, return types, `impl` targets, generics, bounds, associated
+/// types and consts, and function bodies.
+///
+/// Only unqualified names and paths into the scope are renamed, e.g. `Foo`,
+/// `self::Foo` or `models::Foo` for a module `models` of the scope, so that
+/// `other::Foo` keeps referring to a type of another crate or module. Bodies
+/// are plain text, so every matching identifier in them is renamed, including
+/// ones inside string literals.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeRenamer {
+    /// Prefix added to the types defined in the scope
+    prefix: String,
+
+    /// Suffix added to the types defined in the scope
+    suffix: String,
+
+    /// Explicit renames, applied instead of the prefix and suffix
+    names: IndexMap<String, String>,
+}
+
+impl TypeRenamer {
+    /// Creates a new renamer that leaves all names untouched.
+    pub fn new() -> Self {
+        TypeRenamer::default()
+    }
+
+    /// Gets the prefix added to defined types.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Sets the prefix added to defined types.
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) -> &mut Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets the prefix added to defined types.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.set_prefix(prefix);
+        self
+    }
+
+    /// Gets a mutable reference to the prefix added to defined types.
+    pub fn prefix_mut(&mut self) -> &mut String {
+        &mut self.prefix
+    }
+
+    /// Gets the suffix added to defined types.
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Sets the suffix added to defined types.
+    pub fn set_suffix(&mut self, suffix: impl Into<String>) -> &mut Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Sets the suffix added to defined types.
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.set_suffix(suffix);
+        self
+    }
+
+    /// Gets a mutable reference to the suffix added to defined types.
+    pub fn suffix_mut(&mut self) -> &mut String {
+        &mut self.suffix
+    }
+
+    /// Gets the explicit renames.
+    pub fn names(&self) -> &IndexMap<String, String> {
+        &self.names
+    }
+
+    /// Gets a mutable reference to the explicit renames.
+    pub fn names_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.names
+    }
+
+    /// Adds an explicit rename.
+    pub fn push_name(&mut self, from: impl Into<String>, to: impl Into<String>) -> &mut Self {
+        self.names.insert(from.into(), to.into());
+        self
+    }
+
+    /// Adds an explicit rename.
+    pub fn with_name(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.push_name(from, to);
+        self
+    }
+
+    /// Applies the renames to the given scope.
+    pub fn apply(&self, scope: &mut Scope) {
+        let mut defined = DefinedTypes::default();
+        defined.visit_scope(scope);
+
+        let mut names = IndexMap::default();
+        if !self.prefix.is_empty() || !self.suffix.is_empty() {
+            for name in defined.types {
+                let renamed = format!("{}{}{}", self.prefix, name, self.suffix);
+                names.insert(name, renamed);
+            }
+        }
+        names.extend(self.names.clone());
+
+        Renamer::new(names)
+            .with_local_modules(defined.modules)
+            .visit_scope(scope);
+    }
+}
+
+/// Collects the names of the types and modules defined in a scope.
+#[derive(Default)]
+struct DefinedTypes {
+    types: Vec<String>,
+    modules: Vec<String>,
+}
+
+impl Visitor for DefinedTypes {
+    fn visit_module(&mut self, module: &Module) {
+        self.modules.push(module.name().to_string());
+        walk_module(self, module);
+    }
+
+    fn visit_struct(&mut self, r#struct: &Struct) {
+        self.types.push(r#struct.name().to_string());
+        walk_struct(self, r#struct);
+    }
+
+    fn visit_enum(&mut self, r#enum: &Enum) {
+        self.types.push(r#enum.name().to_string());
+        walk_enum(self, r#enum);
+    }
+
+    fn visit_trait(&mut self, r#trait: &Trait) {
+        self.types.push(r#trait.name().to_string());
+        walk_trait(self, r#trait);
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) {
+        self.types.push(type_alias.name().to_string());
+    }
+}
+
+/// Applies a resolved name map.
+pub(crate) struct Renamer {
+    /// Identifiers and their replacements
+    names: IndexMap<String, String>,
+
+    /// Whether function, module, variant and field names are renamed too
+    values: bool,
+
+    /// Modules a qualified name may be in to be renamed, or `None` to rename
+    /// qualified names regardless of their path
+    local_modules: Option<Vec<String>>,
+}
+
+impl Renamer {
+    pub(crate) fn new(names: IndexMap<String, String>) -> Self {
+        Renamer {
+            names,
+            values: false,
+            local_modules: None,
+        }
+    }
+
+    /// Only renames names that are unqualified or qualified by a path made
+    /// of `self`, `super`, `crate` and the given modules.
+    pub(crate) fn with_local_modules(mut self, modules: Vec<String>) -> Self {
+        self.local_modules = Some(modules);
+        self
+    }
+
+    /// Also renames the names of functions, modules, variants and fields.
+    pub(crate) fn with_values(mut self) -> Self {
+        self.values = true;
+        self
+    }
+
+    fn rename(&self, s: &mut String) {
+        if let Some(renamed) = self.renamed(s) {
+            *s = renamed;
+        }
+    }
+
+    /// Returns the renamed string, or `None` if no identifier in it changes.
+    fn renamed(&self, s: &str) -> Option<String> {
+        let mut changed = false;
+        let mut ret = String::with_capacity(s.len());
+        let mut start = None;
+
+        for (i, c) in s.char_indices().chain([(s.len(), '\0')]) {
+            if c == '_' || c.is_alphanumeric() {
+                start.get_or_insert(i);
+                continue;
+            }
+
+            if let Some(begin) = start.take() {
+                let ident = &s[begin..i];
+                match self.names.get(ident) {
+                    Some(renamed) if self.is_local(&s[..begin]) => {
+                        changed = true;
+                        ret.push_str(renamed);
+                    }
+                    _ => ret.push_str(ident),
+                }
+            }
+            if i < s.len() {
+                ret.push(c);
+            }
+        }
+
+        changed.then_some(ret)
+    }
+
+    /// Returns whether an identifier preceded by `before` is unqualified or
+    /// qualified by a path into the renamed scope.
+    fn is_local(&self, before: &str) -> bool {
+        let Some(ref modules) = self.local_modules else {
+            return true;
+        };
+
+        let mut before = before;
+        while let Some(rest) = before.strip_suffix("::") {
+            let segment_start = rest
+                .rfind(|c: char| !(c == '_' || c.is_alphanumeric()))
+                .map_or(0, |i| i + 1);
+            let segment = &rest[segment_start..];
+            let local = matches!(segment, "self" | "super" | "crate")
+                || modules.iter().any(|module| module == segment);
+            if !local {
+                return false;
+            }
+            before = &rest[..segment_start];
+        }
+        true
+    }
+
+    fn rename_generics(&self, generics: &mut [GenericParameter]) {
+        for generic in generics {
+            self.rename(generic.name_mut());
+            for bound in generic.traits_mut() {
+                self.rename(bound);
+            }
+        }
+    }
+
+    /// Renames the generic parameters an item declares. Their bounds are
+    /// renamed as the walk visits them.
+    fn rename_generic_names(&self, generics: &mut [GenericParameter]) {
+        for generic in generics {
+            self.rename(generic.name_mut());
+        }
+    }
+
+    fn rename_body(&self, body: &mut [Body]) {
+        for body in body {
+            match body {
+                Body::String(line) => self.rename(line),
+                Body::Block(block) => self.rename_body(block.body_mut()),
+                Body::Comment(_) => {}
+                Body::Attributed(_, body) => self.rename_body(core::slice::from_mut(&mut **body)),
+                Body::LetElse(let_else) => {
+                    for part in let_else.parts_mut() {
+                        self.rename(part);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl VisitorMut for Renamer {
+    fn visit_struct(&mut self, r#struct: &mut Struct) {
+        self.rename(r#struct.name_mut());
+        self.rename_generic_names(r#struct.generics_mut());
+        walk_struct_mut(self, r#struct);
+    }
+
+    fn visit_enum(&mut self, r#enum: &mut Enum) {
+        self.rename(r#enum.name_mut());
+        self.rename_generic_names(r#enum.generics_mut());
+        walk_enum_mut(self, r#enum);
+    }
+
+    fn visit_trait(&mut self, r#trait: &mut Trait) {
+        self.rename(r#trait.name_mut());
+        self.rename_generic_names(r#trait.generics_mut());
+        walk_trait_mut(self, r#trait);
+    }
+
+    fn visit_type_alias(&mut self, type_alias: &mut TypeAlias) {
+        self.rename(type_alias.name_mut());
+        self.rename_generic_names(type_alias.generics_mut());
+        walk_type_alias_mut(self, type_alias);
+    }
+
+    fn visit_impl(&mut self, r#impl: &mut Impl) {
+        for generic in r#impl.generics_mut() {
+            self.rename(generic);
+        }
+        walk_impl_mut(self, r#impl);
+    }
+
+    fn visit_module(&mut self, module: &mut Module) {
+        if self.values {
+            self.rename(module.name_mut());
+        }
+        walk_module_mut(self, module);
+    }
+
+    fn visit_variant(&mut self, variant: &mut Variant) {
+        if self.values {
+            self.rename(variant.name_mut());
+        }
+        walk_variant_mut(self, variant);
+    }
+
+    fn visit_field(&mut self, field: &mut Field) {
+        if self.values
+            && let Some(renamed) = self.renamed(field.name())
+        {
+            field.set_name(renamed);
+        }
+        walk_field_mut(self, field);
+    }
+
+    fn visit_function(&mut self, function: &mut Function) {
+        if self.values {
+            self.rename(function.name_mut());
+        }
+        self.rename_body(function.body_mut());
+        for generic in function.generics_mut() {
+            self.rename(generic);
+        }
+        walk_function_mut(self, function);
+    }
+
+    fn visit_type(&mut self, ty: &mut Type) {
+        // Keep shared names shared unless they change
+        if let Some(renamed) = self.renamed(ty.name()) {
+            ty.set_name(renamed);
+        }
+        // A path is renamed as a whole, type arguments included
+        match ty.kind() {
+            TypeKind::Path => self.rename_generics(ty.generics_mut()),
+            _ => walk_type_mut(self, ty),
+        }
+    }
+}
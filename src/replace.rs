@@ -0,0 +1,76 @@
+use crate::body::Body;
+use crate::function::Function;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::visit::{VisitorMut, walk_function_mut, walk_item_mut};
+
+impl Scope {
+    /// Replaces every occurrence of `pattern` in function bodies and raw items,
+    /// returning the number of replacements made.
+    ///
+    /// Names, types, fields, docs, attributes and comments are left
+    /// untouched, so only code that was written as text is affected. This covers the functions
+    /// of nested modules, traits, `impl` blocks and generated tests too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is empty.
+    pub fn replace_in_bodies(&mut self, pattern: &str, replacement: &str) -> usize {
+        assert!(!pattern.is_empty(), "replacement pattern must not be empty");
+
+        let mut replacer = BodyReplacer {
+            pattern,
+            replacement,
+            count: 0,
+        };
+        replacer.visit_scope(self);
+        replacer.count
+    }
+}
+
+struct BodyReplacer<'a> {
+    pattern: &'a str,
+    replacement: &'a str,
+    count: usize,
+}
+
+impl BodyReplacer<'_> {
+    fn replace(&mut self, text: &mut String) {
+        let count = text.matches(self.pattern).count();
+        if count > 0 {
+            *text = text.replace(self.pattern, self.replacement);
+            self.count += count;
+        }
+    }
+
+    fn replace_body(&mut self, body: &mut [Body]) {
+        for b in body {
+            match b {
+                Body::String(line) => self.replace(line),
+                Body::Block(block) => self.replace_body(block.body_mut()),
+                Body::Comment(_) => {}
+                Body::Attributed(_, body) => self.replace_body(core::slice::from_mut(body)),
+                Body::LetElse(v) => {
+                    for part in v.parts_mut() {
+                        self.replace(part);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl VisitorMut for BodyReplacer<'_> {
+    fn visit_item(&mut self, item: &mut Item) {
+        match item {
+            Item::Raw(raw) => self.replace(raw),
+            _ => walk_item_mut(self, item),
+        }
+    }
+
+    fn visit_function(&mut self, function: &mut Function) {
+        self.replace_body(function.body_mut());
+        walk_function_mut(self, function);
+    }
+}
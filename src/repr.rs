@@ -0,0 +1,230 @@
+use core::fmt;
+use core::str::FromStr;
+
+use thiserror::Error;
+
+use crate::prelude::*;
+
+/// Defines an integer type usable as the representation of an enum.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntType {
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u128`
+    U128,
+    /// `usize`
+    Usize,
+    /// `i8`
+    I8,
+    /// `i16`
+    I16,
+    /// `i32`
+    I32,
+    /// `i64`
+    I64,
+    /// `i128`
+    I128,
+    /// `isize`
+    Isize,
+}
+
+impl IntType {
+    const ALL: [IntType; 12] = [
+        IntType::U8,
+        IntType::U16,
+        IntType::U32,
+        IntType::U64,
+        IntType::U128,
+        IntType::Usize,
+        IntType::I8,
+        IntType::I16,
+        IntType::I32,
+        IntType::I64,
+        IntType::I128,
+        IntType::Isize,
+    ];
+
+    /// Gets the name of the integer type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::Usize => "usize",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Isize => "isize",
+        }
+    }
+}
+
+/// Defines a representation hint of a struct or enum, rendered in
+/// `#[repr(...)]`.
+///
+/// Several hints can be combined, e.g. `[Repr::C, Repr::Packed(Some(4))]`
+/// renders as `#[repr(C, packed(4))]`. Hints also convert from their names,
+/// so `"u8"` or `"packed(2)"` can be used in their place; unknown names are
+/// kept as [`Repr::Custom`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Repr {
+    /// `C`
+    C,
+    /// `Rust`
+    Rust,
+    /// `transparent`
+    Transparent,
+    /// An integer type, only valid for enums
+    Int(IntType),
+    /// `packed`, or `packed(n)` with an alignment, only valid for structs
+    Packed(Option<usize>),
+    /// `align(n)`, only valid for structs
+    Align(usize),
+    /// Any other hint, written verbatim
+    Custom(String),
+}
+
+/// Error returned when a representation hint is unknown or invalid.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ReprError {
+    /// The hint is not a known representation.
+    #[error("unknown repr: {0}")]
+    Unknown(String),
+
+    /// The alignment of `packed` or `align` is not a power of two.
+    #[error("repr alignment must be a power of two: {0}")]
+    InvalidAlignment(usize),
+
+    /// Two hints cannot be combined.
+    #[error("conflicting repr hints: {0} and {1}")]
+    Conflict(Repr, Repr),
+
+    /// The hint cannot be used on this kind of item.
+    #[error("repr({0}) is not supported on {1} items")]
+    Unsupported(Repr, String),
+}
+
+impl Repr {
+    /// Validates a combination of hints used on an item of the given kind,
+    /// e.g. `struct` or `enum`.
+    ///
+    /// Custom hints are not checked.
+    pub fn validate(hints: &[Repr], kind: &str) -> Result<(), ReprError> {
+        for (i, hint) in hints.iter().enumerate() {
+            if let Repr::Packed(Some(n)) | Repr::Align(n) = *hint
+                && !n.is_power_of_two()
+            {
+                return Err(ReprError::InvalidAlignment(n));
+            }
+
+            let supported = match hint {
+                Repr::Int(_) => kind == "enum",
+                Repr::Packed(_) | Repr::Align(_) => kind != "enum",
+                _ => true,
+            };
+            if !supported {
+                return Err(ReprError::Unsupported(hint.clone(), kind.to_string()));
+            }
+
+            if let Some(other) = hints[..i].iter().find(|other| conflicts(other, hint)) {
+                return Err(ReprError::Conflict(other.clone(), hint.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn conflicts(a: &Repr, b: &Repr) -> bool {
+    use Repr::*;
+
+    matches!(
+        (a, b),
+        (
+            Transparent,
+            Transparent | C | Rust | Int(_) | Packed(_) | Align(_)
+        ) | (C | Rust | Int(_) | Packed(_) | Align(_), Transparent)
+            | (C, C)
+            | (C, Rust)
+            | (Rust, C)
+            | (Rust, Rust)
+            | (Int(_), Int(_))
+            | (Packed(_) | Align(_), Packed(_) | Align(_))
+    )
+}
+
+impl fmt::Display for Repr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Repr::C => write!(f, "C"),
+            Repr::Rust => write!(f, "Rust"),
+            Repr::Transparent => write!(f, "transparent"),
+            Repr::Int(ty) => write!(f, "{}", ty.name()),
+            Repr::Packed(None) => write!(f, "packed"),
+            Repr::Packed(Some(n)) => write!(f, "packed({})", n),
+            Repr::Align(n) => write!(f, "align({})", n),
+            Repr::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for Repr {
+    type Err = ReprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let arg = |name: &str| {
+            let n = s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')?;
+            n.trim().parse::<usize>().ok()
+        };
+
+        match s {
+            "C" => Ok(Repr::C),
+            "Rust" => Ok(Repr::Rust),
+            "transparent" => Ok(Repr::Transparent),
+            "packed" => Ok(Repr::Packed(None)),
+            _ => {
+                if let Some(ty) = IntType::ALL.iter().find(|ty| ty.name() == s) {
+                    Ok(Repr::Int(*ty))
+                } else if let Some(n) = arg("packed") {
+                    Ok(Repr::Packed(Some(n)))
+                } else if let Some(n) = arg("align") {
+                    Ok(Repr::Align(n))
+                } else {
+                    Err(ReprError::Unknown(s.to_string()))
+                }
+            }
+        }
+    }
+}
+
+impl From<IntType> for Repr {
+    fn from(ty: IntType) -> Self {
+        Repr::Int(ty)
+    }
+}
+
+impl From<&str> for Repr {
+    fn from(s: &str) -> Self {
+        s.parse()
+            .unwrap_or_else(|_| Repr::Custom(s.trim().to_string()))
+    }
+}
+
+impl From<String> for Repr {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
@@ -1,17 +1,34 @@
-use std::fmt::{self, Debug, Display, Write};
-
-use indexmap::IndexMap;
-
+use alloc::collections::BTreeMap;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Display, Write};
+use core::hash::{Hash, Hasher};
+
+use thiserror::Error;
+
+use crate::attribute::Attribute;
+use crate::bound::Bound;
+use crate::case::to_snake_case;
+use crate::comment::Comment;
+use crate::const_item::ConstItem;
 use crate::doc::Doc;
 use crate::r#enum::Enum;
+use crate::field::Field;
 use crate::formatter::Formatter;
 use crate::function::Function;
+use crate::ident::sanitize_ident;
 use crate::r#impl::Impl;
 use crate::import::Import;
+use crate::import_group::ImportGroup;
 use crate::item::Item;
+use crate::item_order::ItemOrder;
 use crate::line_break::LineBreak;
-use crate::module::Module;
+use crate::merge::{MergeError, MergeStrategy};
+use crate::mod_decl::ModDecl;
+use crate::module::{DuplicateModuleError, Module};
+use crate::prelude::*;
+use crate::provenance::SourceMap;
 use crate::r#struct::Struct;
+use crate::toolchain::{Edition, RustVersion};
 use crate::r#trait::Trait;
 use crate::r#type::Type;
 use crate::type_alias::TypeAlias;
@@ -21,15 +38,31 @@ use crate::visibility::Vis;
 ///
 /// A scope contains modules, types, etc...
 #[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scope {
     /// Scope documentation
     doc: Option<Doc>,
 
+    /// Documentation of the enclosing module or crate, written as `//!`
+    inner_doc: Option<Doc>,
+
+    /// Attributes of the enclosing module or crate, written as `#![...]`
+    attributes: Vec<Attribute>,
+
     /// Imports
     imports: IndexMap<String, IndexMap<String, Import>>,
 
     /// Contents of the documentation,
     items: Vec<Item>,
+
+    /// Error type of the functions created by `new_fallible_function`
+    default_error: Option<Type>,
+
+    /// Edition of the generated code, if set
+    edition: Option<Edition>,
+
+    /// Oldest Rust version the generated code must compile with, if set
+    rust_version: Option<RustVersion>,
 }
 
 impl Default for Scope {
@@ -38,6 +71,33 @@ impl Default for Scope {
     }
 }
 
+impl Hash for Scope {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.doc.hash(state);
+        self.inner_doc.hash(state);
+        self.attributes.hash(state);
+
+        // `IndexMap` equality ignores the order of entries, so they are
+        // hashed sorted.
+        let mut imports: Vec<_> = self
+            .imports
+            .iter()
+            .map(|(path, imports)| {
+                let mut imports: Vec<_> = imports.iter().collect();
+                imports.sort_by_key(|(ty, _)| *ty);
+                (path, imports)
+            })
+            .collect();
+        imports.sort_by_key(|(path, _)| *path);
+        imports.hash(state);
+
+        self.items.hash(state);
+        self.default_error.hash(state);
+        self.edition.hash(state);
+        self.rust_version.hash(state);
+    }
+}
+
 impl Display for Scope {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut ret = String::new();
@@ -56,11 +116,39 @@ impl Scope {
     pub fn new() -> Self {
         Scope {
             doc: None,
-            imports: IndexMap::new(),
+            inner_doc: None,
+            attributes: Vec::new(),
+            imports: IndexMap::default(),
             items: Vec::new(),
+            default_error: None,
+            edition: None,
+            rust_version: None,
         }
     }
 
+    /// Creates a new scope with room for at least `capacity` items.
+    ///
+    /// Useful when generating a large number of items, to avoid growing the
+    /// item list repeatedly.
+    pub fn with_item_capacity(capacity: usize) -> Self {
+        Scope {
+            doc: None,
+            inner_doc: None,
+            attributes: Vec::new(),
+            imports: IndexMap::default(),
+            items: Vec::with_capacity(capacity),
+            default_error: None,
+            edition: None,
+            rust_version: None,
+        }
+    }
+
+    /// Reserves room for at least `additional` more items.
+    pub fn reserve_items(&mut self, additional: usize) -> &mut Self {
+        self.items.reserve(additional);
+        self
+    }
+
     /// Gets the scope documentation.
     pub fn doc(&self) -> Option<&Doc> {
         self.doc.as_ref()
@@ -76,7 +164,7 @@ impl Scope {
     }
 
     /// Sets the scope documentation.
-    pub fn with_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
     where
         S: Into<Doc>,
     {
@@ -89,6 +177,160 @@ impl Scope {
         self.doc.as_mut()
     }
 
+    /// Gets the documentation of the enclosing module or crate.
+    pub fn inner_doc(&self) -> Option<&Doc> {
+        self.inner_doc.as_ref()
+    }
+
+    /// Sets the documentation of the enclosing module or crate, written as
+    /// `//!` comments at the top of the scope.
+    pub fn set_inner_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.inner_doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the documentation of the enclosing module or crate.
+    pub fn with_inner_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
+    where
+        S: Into<Doc>,
+    {
+        self.set_inner_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the documentation of the enclosing module
+    /// or crate.
+    pub fn inner_doc_mut(&mut self) -> Option<&mut Doc> {
+        self.inner_doc.as_mut()
+    }
+
+    /// Gets the attributes of the enclosing module or crate.
+    pub fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+
+    /// Sets the attributes of the enclosing module or crate, written as
+    /// `#![...]` at the top of the scope, e.g. `no_std`.
+    pub fn set_attributes<A>(&mut self, attributes: impl IntoIterator<Item = A>) -> &mut Self
+    where
+        A: Into<Attribute>,
+    {
+        self.attributes = attributes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the attributes of the enclosing module or crate.
+    pub fn with_attributes<A>(mut self, attributes: impl IntoIterator<Item = A>) -> Self
+    where
+        A: Into<Attribute>,
+    {
+        self.set_attributes(attributes);
+        self
+    }
+
+    /// Gets a mutable reference to the attributes of the enclosing module or
+    /// crate.
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+
+    /// Pushes an attribute of the enclosing module or crate.
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+
+    /// Pushes an attribute of the enclosing module or crate.
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.push_attribute(attribute);
+        self
+    }
+
+    /// Gets the default error type of the scope.
+    pub fn default_error(&self) -> Option<&Type> {
+        self.default_error.as_ref()
+    }
+
+    /// Sets the default error type of the scope.
+    ///
+    /// Functions created with [`Scope::new_fallible_function`] return a
+    /// `Result` with this error type. Nested modules without an error type of
+    /// their own inherit it, whether they were pushed before or after it is
+    /// set.
+    pub fn set_default_error(&mut self, ty: impl Into<Type>) -> &mut Self {
+        let ty = ty.into();
+        let previous = self.default_error.replace(ty.clone());
+        self.inherit_default_error(&ty, previous.as_ref());
+        self
+    }
+
+    /// Sets the default error type of the scope.
+    pub fn with_default_error(mut self, ty: impl Into<Type>) -> Self {
+        self.set_default_error(ty);
+        self
+    }
+
+    /// Gets a mutable reference to the default error type of the scope.
+    pub fn default_error_mut(&mut self) -> &mut Option<Type> {
+        &mut self.default_error
+    }
+
+    /// Gets the edition of the generated code, if set.
+    pub fn edition(&self) -> Option<Edition> {
+        self.edition
+    }
+
+    /// Sets the edition of the generated code.
+    ///
+    /// The edition applies to the items of the scope and of its modules,
+    /// overriding the one of the formatter, see [`Formatter::set_edition`].
+    pub fn set_edition(&mut self, edition: impl Into<Option<Edition>>) -> &mut Self {
+        self.edition = edition.into();
+        self
+    }
+
+    /// Sets the edition of the generated code.
+    pub fn with_edition(mut self, edition: impl Into<Option<Edition>>) -> Self {
+        self.set_edition(edition);
+        self
+    }
+
+    /// Gets a mutable reference to the edition of the generated code.
+    pub fn edition_mut(&mut self) -> &mut Option<Edition> {
+        &mut self.edition
+    }
+
+    /// Gets the oldest Rust version the generated code must compile with, if
+    /// set.
+    pub fn rust_version(&self) -> Option<RustVersion> {
+        self.rust_version
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    ///
+    /// The version applies to the items of the scope and of its modules,
+    /// overriding the one of the formatter, see
+    /// [`Formatter::set_rust_version`] for the constructs it affects.
+    pub fn set_rust_version(&mut self, rust_version: impl Into<Option<RustVersion>>) -> &mut Self {
+        self.rust_version = rust_version.into();
+        self
+    }
+
+    /// Sets the oldest Rust version the generated code must compile with.
+    pub fn with_rust_version(mut self, rust_version: impl Into<Option<RustVersion>>) -> Self {
+        self.set_rust_version(rust_version);
+        self
+    }
+
+    /// Gets a mutable reference to the oldest Rust version the generated
+    /// code must compile with.
+    pub fn rust_version_mut(&mut self) -> &mut Option<RustVersion> {
+        &mut self.rust_version
+    }
+
     /// Gets the imported types.
     pub fn imports(&self) -> &IndexMap<String, IndexMap<String, Import>> {
         &self.imports
@@ -155,6 +397,30 @@ impl Scope {
         self
     }
 
+    /// Imports several types into the scope, all with the same visibility.
+    ///
+    /// Takes pairs of a path and the types to import from it, e.g.
+    /// `[("std::collections", ["BTreeMap", "HashSet"])]`.
+    pub fn push_imports<P, I, T>(
+        &mut self,
+        imports: impl IntoIterator<Item = (P, I)>,
+        vis: impl Into<Vis>,
+    ) -> &mut Self
+    where
+        P: Into<String>,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let vis = vis.into();
+        for (path, tys) in imports {
+            let path = path.into();
+            for ty in tys {
+                self.push_import(path.clone(), ty, vis.clone());
+            }
+        }
+        self
+    }
+
     /// Gets the items inside the scope.
     pub fn items(&self) -> &[Item] {
         &self.items
@@ -185,6 +451,289 @@ impl Scope {
         &mut self.items
     }
 
+    /// Merges another scope into this one, failing on name collisions.
+    ///
+    /// This is equivalent to [`merge_with`] using [`MergeStrategy::Error`].
+    ///
+    /// [`merge_with`]: #method.merge_with
+    pub fn merge(&mut self, other: Scope) -> Result<(), MergeError> {
+        self.merge_with(other, MergeStrategy::Error)
+    }
+
+    /// Merges another scope into this one.
+    ///
+    /// Imports and attributes are unioned, and the items of `other` are
+    /// appended, with name collisions resolved according to `strategy`. Items
+    /// collide when they have the same name in the same namespace, so a
+    /// struct and an enum named alike collide, while a struct with named
+    /// fields and a function do not. Modules with the same name are merged
+    /// recursively. The documentation, inner documentation, default error,
+    /// edition and Rust version of `other` are used only where this scope has
+    /// none.
+    ///
+    /// On error, this scope is left unchanged.
+    pub fn merge_with(&mut self, other: Scope, strategy: MergeStrategy) -> Result<(), MergeError> {
+        let mut merged = self.clone();
+        merged.merge_in_place(other, strategy, "")?;
+        *self = merged;
+        Ok(())
+    }
+
+    fn merge_in_place(
+        &mut self,
+        other: Scope,
+        strategy: MergeStrategy,
+        prefix: &str,
+    ) -> Result<(), MergeError> {
+        if self.doc.is_none() {
+            self.doc = other.doc;
+        }
+        if self.inner_doc.is_none() {
+            self.inner_doc = other.inner_doc;
+        }
+        if self.default_error.is_none() {
+            self.default_error = other.default_error;
+        }
+        self.edition = self.edition.or(other.edition);
+        self.rust_version = self.rust_version.or(other.rust_version);
+        for attribute in other.attributes {
+            if !self.attributes.contains(&attribute) {
+                self.attributes.push(attribute);
+            }
+        }
+
+        for (path, imports) in other.imports {
+            let entry = self.imports.entry(path).or_default();
+            for (ty, import) in imports {
+                entry.entry(ty).or_insert(import);
+            }
+        }
+
+        for item in other.items {
+            let existing = match item {
+                Item::Impl(_) | Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => None,
+                _ => self
+                    .items
+                    .iter()
+                    .position(|v| v.name() == item.name() && v.shares_namespace(&item)),
+            };
+
+            let Some(index) = existing else {
+                self.items.push(item);
+                continue;
+            };
+
+            match (&mut self.items[index], item) {
+                (Item::Module(existing), Item::Module(mut module)) => {
+                    let prefix = format!("{}{}::", prefix, module.name());
+                    if let (None, Some(doc)) = (existing.doc(), module.doc()) {
+                        existing.set_doc(doc.clone());
+                    }
+                    let scope = core::mem::take(module.scope_mut());
+                    existing
+                        .scope_mut()
+                        .merge_in_place(scope, strategy, &prefix)?;
+                }
+                (existing, item) => match strategy {
+                    MergeStrategy::Error => {
+                        return Err(MergeError::Conflict {
+                            kind: item.kind_name(),
+                            name: format!("{}{}", prefix, item.name().unwrap_or_default()),
+                        });
+                    }
+                    MergeStrategy::KeepExisting => {}
+                    MergeStrategy::Replace => *existing = item,
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retains only the items for which `keep` returns `true`.
+    pub fn retain_items<F>(&mut self, keep: F) -> &mut Self
+    where
+        F: FnMut(&Item) -> bool,
+    {
+        self.items.retain(keep);
+        self
+    }
+
+    /// Removes the first item with the given name, returning it.
+    ///
+    /// See [`Item::name`] for how items are named.
+    pub fn remove_item<'a>(&mut self, name: impl Into<&'a str>) -> Option<Item> {
+        let index = self.item_index(name)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Gets the index of the first item with the given name.
+    ///
+    /// See [`Item::name`] for how items are named.
+    pub fn item_index<'a>(&self, name: impl Into<&'a str>) -> Option<usize> {
+        let name = name.into();
+        self.items.iter().position(|item| item.name() == Some(name))
+    }
+
+    /// Pushes an item to the end of the scope.
+    pub fn push_item(&mut self, item: impl Into<Item>) -> &mut Self {
+        self.items.push(item.into());
+        self
+    }
+
+    /// Inserts an item at the given index, shifting later items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of items.
+    pub fn insert_item(&mut self, index: usize, item: impl Into<Item>) -> &mut Self {
+        self.items.insert(index, item.into());
+        self
+    }
+
+    /// Inserts an item right before the first item with the given name.
+    ///
+    /// Returns `None`, leaving the scope unchanged, if no item has that name.
+    pub fn insert_item_before<'a>(
+        &mut self,
+        name: impl Into<&'a str>,
+        item: impl Into<Item>,
+    ) -> Option<&mut Self> {
+        let index = self.item_index(name)?;
+        Some(self.insert_item(index, item))
+    }
+
+    /// Inserts an item right after the first item with the given name.
+    ///
+    /// Returns `None`, leaving the scope unchanged, if no item has that name.
+    pub fn insert_item_after<'a>(
+        &mut self,
+        name: impl Into<&'a str>,
+        item: impl Into<Item>,
+    ) -> Option<&mut Self> {
+        let index = self.item_index(name)?;
+        Some(self.insert_item(index + 1, item))
+    }
+
+    /// Sorts the items of the scope using the given order.
+    ///
+    /// Raw strings, comments and line breaks move along with the named item
+    /// that follows them, e.g. a comment stays above the struct it describes;
+    /// unnamed items at the end stay there. The sort is stable, so items that
+    /// compare equal keep their relative order. Nested modules are sorted as
+    /// well.
+    pub fn sort_items(&mut self, order: ItemOrder) -> &mut Self {
+        self.sort_items_by(|a, b| order.compare(a, b))
+    }
+
+    /// Sorts the items of the scope using the given comparator.
+    ///
+    /// Like [`sort_items`], unnamed items move along with the named item
+    /// that follows them, and only named items are compared. The sort is
+    /// stable. Nested modules are sorted as well.
+    ///
+    /// [`sort_items`]: #method.sort_items
+    pub fn sort_items_by<F>(&mut self, mut compare: F) -> &mut Self
+    where
+        F: FnMut(&Item, &Item) -> Ordering,
+    {
+        self.sort_items_by_ref(&mut compare);
+        self
+    }
+
+    fn sort_items_by_ref<F>(&mut self, compare: &mut F)
+    where
+        F: FnMut(&Item, &Item) -> Ordering,
+    {
+        for item in &mut self.items {
+            if let Item::Module(module) = item {
+                module.scope_mut().sort_items_by_ref(compare);
+            }
+        }
+
+        let mut groups: Vec<Vec<Item>> = Vec::new();
+        let mut pending = Vec::new();
+        for item in self.items.drain(..) {
+            let named = item.name().is_some();
+            pending.push(item);
+            if named {
+                groups.push(core::mem::take(&mut pending));
+            }
+        }
+
+        // Each group ends with its named item
+        groups.sort_by(|a, b| compare(&a[a.len() - 1], &b[b.len() - 1]));
+        self.items = groups.into_iter().flatten().chain(pending).collect();
+    }
+
+    /// Moves `impl` blocks right after the struct, enum or type alias they
+    /// target, inherent `impl` blocks first, then trait implementations.
+    ///
+    /// Other items keep their relative order, as do `impl` blocks of the same
+    /// kind for the same type. `impl` blocks for types not defined in the
+    /// scope stay where they are. Nested modules are organized as well.
+    pub fn organize(&mut self) -> &mut Self {
+        for item in &mut self.items {
+            if let Item::Module(module) = item {
+                module.scope_mut().organize();
+            }
+        }
+
+        let mut impls: BTreeMap<String, Vec<Item>> = self
+            .items
+            .iter()
+            .filter(|item| matches!(item, Item::Struct(_) | Item::Enum(_) | Item::TypeAlias(_)))
+            .filter_map(|item| item.name())
+            .map(|name| (name.to_string(), Vec::new()))
+            .collect();
+
+        let mut items = Vec::with_capacity(self.items.len());
+        for item in self.items.drain(..) {
+            match item {
+                Item::Impl(ref v) if impls.contains_key(v.target().name()) => {
+                    impls.get_mut(v.target().name()).unwrap().push(item);
+                }
+                item => items.push(item),
+            }
+        }
+
+        for item in items {
+            let attached = match item {
+                Item::Struct(_) | Item::Enum(_) | Item::TypeAlias(_) => {
+                    item.name().and_then(|name| impls.remove(name))
+                }
+                _ => None,
+            };
+
+            self.items.push(item);
+            if let Some(mut attached) = attached {
+                attached
+                    .sort_by_key(|item| item.as_impl().is_some_and(|v| v.impl_trait().is_some()));
+                self.items.extend(attached);
+            }
+        }
+
+        self
+    }
+
+    /// Sorts the imports of the scope by path, then by imported name.
+    ///
+    /// Imports of nested modules are sorted as well.
+    pub fn sort_imports(&mut self) -> &mut Self {
+        self.imports.sort_keys();
+        for names in self.imports.values_mut() {
+            names.sort_keys();
+        }
+
+        for item in &mut self.items {
+            if let Item::Module(module) = item {
+                module.scope_mut().sort_imports();
+            }
+        }
+
+        self
+    }
+
     /// Pushes a new module definition, returning a mutable reference to it.
     ///
     /// # Panics
@@ -194,18 +743,27 @@ impl Scope {
     /// in this scope will cause this function to panic.
     ///
     /// In many cases, the [`get_or_new_module`] function is preferrable, as it
-    /// will return the existing definition instead.
+    /// will return the existing definition instead, and
+    /// [`try_new_module`](Scope::try_new_module) returns an error instead.
     ///
     /// [`get_or_new_module`]: #method.get_or_new_module
     pub fn new_module(&mut self, name: impl Into<String>) -> &mut Module {
-        self.push_module(Module::new(name.into()));
-
-        match *self.items.last_mut().unwrap() {
-            Item::Module(ref mut v) => v,
-            _ => unreachable!(),
+        match self.try_new_module(name) {
+            Ok(module) => module,
+            Err(e) => panic!("{}", e),
         }
     }
 
+    /// Pushes a new module definition, returning a mutable reference to it,
+    /// or an error if a module with the same name is already defined in this
+    /// scope.
+    pub fn try_new_module(
+        &mut self,
+        name: impl Into<String>,
+    ) -> Result<&mut Module, DuplicateModuleError> {
+        self.try_push_module(Module::new(name.into()))
+    }
+
     /// Gets a mutable reference to a module if it is exists in this scope.
     pub fn get_module_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Module> {
         let name = name.into();
@@ -241,6 +799,70 @@ impl Scope {
         }
     }
 
+    /// Gets a mutable reference to the module at the given path of nested
+    /// modules, creating the missing modules along the way.
+    ///
+    /// ```rust
+    /// use simple_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.get_or_new_module_path(["a", "b", "c"]).new_struct("Foo");
+    ///
+    /// assert!(scope.get_module("a").unwrap().get_module("b").is_some());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path is empty.
+    pub fn get_or_new_module_path<S>(&mut self, path: impl IntoIterator<Item = S>) -> &mut Module
+    where
+        S: AsRef<str>,
+    {
+        let mut path = path.into_iter();
+        let first = path.next().expect("module path must not be empty");
+
+        let mut module = self.get_or_new_module(first.as_ref());
+        for name in path {
+            module = module.get_or_new_module(name.as_ref());
+        }
+        module
+    }
+
+    /// Gets a mutable reference to the innermost module of a dotted package
+    /// name, creating the missing modules along the way.
+    ///
+    /// Each segment of the package is converted to snake case and sanitized
+    /// into a valid identifier, so `com.acme.Billing.type` gives the modules
+    /// `com::acme::billing::r#type`. As with prost, the modules created are
+    /// public.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the package name is empty.
+    pub fn get_or_new_package(&mut self, package: impl AsRef<str>) -> &mut Module {
+        let mut path = package
+            .as_ref()
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| sanitize_ident(to_snake_case(segment)));
+        let first = path.next().expect("package name must not be empty");
+
+        let mut module = self.get_or_new_pub_module(&first);
+        for name in path {
+            module = module.scope_mut().get_or_new_pub_module(&name);
+        }
+        module
+    }
+
+    /// Gets a mutable reference to a module, creating it as a public module
+    /// if it does not exist.
+    fn get_or_new_pub_module(&mut self, name: &str) -> &mut Module {
+        if self.get_module(name).is_none() {
+            self.new_module(name).set_vis(Vis::Pub);
+        }
+        self.get_module_mut(name).unwrap()
+    }
+
     /// Pushes a module definition.
     ///
     /// # Panics
@@ -250,16 +872,103 @@ impl Scope {
     /// in this scope will cause this function to panic.
     ///
     /// In many cases, the [`get_or_new_module`] function is preferrable, as it will
-    /// return the existing definition instead.
+    /// return the existing definition instead, and
+    /// [`try_push_module`](Scope::try_push_module) returns an error instead.
     ///
     /// [`get_or_new_module`]: #method.get_or_new_module
     pub fn push_module(&mut self, module: impl Into<Module>) -> &mut Self {
-        let module = module.into();
-        assert!(self.get_module(module.name()).is_none());
+        if let Err(e) = self.try_push_module(module) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// Pushes a module definition, returning a mutable reference to it, or
+    /// an error if a module with the same name is already defined in this
+    /// scope.
+    pub fn try_push_module(
+        &mut self,
+        module: impl Into<Module>,
+    ) -> Result<&mut Module, DuplicateModuleError> {
+        let mut module = module.into();
+        if self.get_module(module.name()).is_some() {
+            return Err(DuplicateModuleError {
+                name: module.name().to_string(),
+            });
+        }
+        if let Some(ty) = &self.default_error {
+            module.scope_mut().inherit_default_error(ty, None);
+        }
         self.items.push(Item::Module(module));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Module(ref mut v) => Ok(v),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Pushes a new module declaration, returning a mutable reference to it.
+    pub fn new_mod_decl(&mut self, name: impl Into<String>) -> &mut ModDecl {
+        self.push_mod_decl(ModDecl::new(name));
+
+        match *self.items.last_mut().unwrap() {
+            Item::ModDecl(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Pushes a module declaration, e.g. `mod foo;`.
+    pub fn push_mod_decl(&mut self, item: ModDecl) -> &mut Self {
+        self.items.push(Item::ModDecl(item));
         self
     }
 
+    /// Marks a field as using custom serialization through `module`.
+    ///
+    /// This pushes `#[serde(with = "module")]` onto the field and defines a
+    /// module of the same name in this scope with `serialize` and
+    /// `deserialize` stubs typed after the field, returning a mutable
+    /// reference to the module so the stubs can be filled in.
+    ///
+    /// # Panics
+    ///
+    /// Like [`new_module`], this panics if a module named `module` is already
+    /// defined in this scope.
+    ///
+    /// [`new_module`]: #method.new_module
+    pub fn new_serde_with(&mut self, field: &mut Field, module: impl Into<String>) -> &mut Module {
+        let module = module.into();
+        field.push_annotation(Attribute::derive_helper("serde", "with", &module));
+
+        let mut ty = String::new();
+        field
+            .declared_ty()
+            .fmt(&mut Formatter::new(&mut ty))
+            .unwrap();
+
+        let serialize = Function::new("serialize")
+            .with_vis(Vis::Pub)
+            .with_generic("S")
+            .with_arg("value", format!("&{}", ty))
+            .with_arg("serializer", "S")
+            .with_ret("Result<S::Ok, S::Error>")
+            .with_bound(Bound::new("S", ["serde::Serializer"]))
+            .with_line("todo!()");
+
+        let deserialize = Function::new("deserialize")
+            .with_vis(Vis::Pub)
+            .with_generic("'de")
+            .with_generic("D")
+            .with_arg("deserializer", "D")
+            .with_ret(format!("Result<{}, D::Error>", ty))
+            .with_bound(Bound::new("D", ["serde::Deserializer<'de>"]))
+            .with_line("todo!()");
+
+        let module = self.new_module(module);
+        module.push_function(serialize).push_function(deserialize);
+        module
+    }
+
     /// Pushes a new struct definition, returning a mutable reference to it.
     pub fn new_struct(&mut self, name: impl Into<String>) -> &mut Struct {
         self.push_struct(Struct::new(name.into()));
@@ -276,6 +985,41 @@ impl Scope {
         self
     }
 
+    /// Gets a reference to a struct if it exists in this scope.
+    pub fn get_struct<'a>(&self, name: impl Into<&'a str>) -> Option<&Struct> {
+        let name = name.into();
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Gets a mutable reference to a struct if it exists in this scope.
+    pub fn get_struct_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Struct> {
+        let name = name.into();
+        self.items
+            .iter_mut()
+            .filter_map(|item| match item {
+                Item::Struct(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Replaces the struct with the given name, returning the previous
+    /// definition, or `None` if no such struct exists.
+    pub fn replace_struct<'a>(
+        &mut self,
+        name: impl Into<&'a str>,
+        item: Struct,
+    ) -> Option<Struct> {
+        self.get_struct_mut(name)
+            .map(|v| core::mem::replace(v, item))
+    }
+
     /// Pushes a new function definition, returning a mutable reference to it.
     pub fn new_function(&mut self, name: impl Into<String>) -> &mut Function {
         self.push_function(Function::new(name.into()));
@@ -286,12 +1030,76 @@ impl Scope {
         }
     }
 
+    /// Pushes a new function returning `Result<ok, E>`, where `E` is the
+    /// default error type of the scope, returning a mutable reference to it,
+    /// or an error if the scope has no default error type.
+    pub fn new_fallible_function(
+        &mut self,
+        name: impl Into<String>,
+        ok: impl Into<Type>,
+    ) -> Result<&mut Function, MissingDefaultError> {
+        let err = self.default_error.clone().ok_or(MissingDefaultError)?;
+        let function = self.new_function(name);
+        function.set_ret_result(ok, err);
+        Ok(function)
+    }
+
+    /// Sets the default error type of this scope and its nested modules to
+    /// `ty`, where it is unset or still `previous`, i.e. inherited.
+    fn inherit_default_error(&mut self, ty: &Type, previous: Option<&Type>) {
+        if self.default_error.is_none() || self.default_error.as_ref() == previous {
+            self.default_error = Some(ty.clone());
+        } else if self.default_error.as_ref() != Some(ty) {
+            return;
+        }
+        for item in &mut self.items {
+            if let Item::Module(module) = item {
+                module.scope_mut().inherit_default_error(ty, previous);
+            }
+        }
+    }
+
     /// Pushes a function definition
     pub fn push_function(&mut self, item: Function) -> &mut Self {
         self.items.push(Item::Function(item));
         self
     }
 
+    /// Gets a reference to a function if it exists in this scope.
+    pub fn get_function<'a>(&self, name: impl Into<&'a str>) -> Option<&Function> {
+        let name = name.into();
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Function(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Gets a mutable reference to a function if it exists in this scope.
+    pub fn get_function_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Function> {
+        let name = name.into();
+        self.items
+            .iter_mut()
+            .filter_map(|item| match item {
+                Item::Function(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Replaces the function with the given name, returning the previous
+    /// definition, or `None` if no such function exists.
+    pub fn replace_function<'a>(
+        &mut self,
+        name: impl Into<&'a str>,
+        item: Function,
+    ) -> Option<Function> {
+        self.get_function_mut(name)
+            .map(|v| core::mem::replace(v, item))
+    }
+
     /// Pushes a new trait definition, returning a mutable reference to it.
     pub fn new_trait(&mut self, name: impl Into<String>) -> &mut Trait {
         self.push_trait(Trait::new(name.into()));
@@ -308,6 +1116,37 @@ impl Scope {
         self
     }
 
+    /// Gets a reference to a trait if it exists in this scope.
+    pub fn get_trait<'a>(&self, name: impl Into<&'a str>) -> Option<&Trait> {
+        let name = name.into();
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Trait(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Gets a mutable reference to a trait if it exists in this scope.
+    pub fn get_trait_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Trait> {
+        let name = name.into();
+        self.items
+            .iter_mut()
+            .filter_map(|item| match item {
+                Item::Trait(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Replaces the trait with the given name, returning the previous
+    /// definition, or `None` if no such trait exists.
+    pub fn replace_trait<'a>(&mut self, name: impl Into<&'a str>, item: Trait) -> Option<Trait> {
+        self.get_trait_mut(name)
+            .map(|v| core::mem::replace(v, item))
+    }
+
     /// Pushes a new struct definition, returning a mutable reference to it.
     pub fn new_enum(&mut self, name: impl Into<String>) -> &mut Enum {
         self.push_enum(Enum::new(name.into()));
@@ -324,6 +1163,36 @@ impl Scope {
         self
     }
 
+    /// Gets a reference to an enum if it exists in this scope.
+    pub fn get_enum<'a>(&self, name: impl Into<&'a str>) -> Option<&Enum> {
+        let name = name.into();
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Enum(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Gets a mutable reference to an enum if it exists in this scope.
+    pub fn get_enum_mut<'a>(&mut self, name: impl Into<&'a str>) -> Option<&mut Enum> {
+        let name = name.into();
+        self.items
+            .iter_mut()
+            .filter_map(|item| match item {
+                Item::Enum(v) if v.name() == name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Replaces the enum with the given name, returning the previous
+    /// definition, or `None` if no such enum exists.
+    pub fn replace_enum<'a>(&mut self, name: impl Into<&'a str>, item: Enum) -> Option<Enum> {
+        self.get_enum_mut(name).map(|v| core::mem::replace(v, item))
+    }
+
     /// Pushes a new `impl` block, returning a mutable reference to it.
     pub fn new_impl(&mut self, target: impl Into<Type>) -> &mut Impl {
         self.push_impl(Impl::new(target.into()));
@@ -340,6 +1209,32 @@ impl Scope {
         self
     }
 
+    /// Gets a reference to the first `impl` block for the given target type,
+    /// if it exists in this scope.
+    pub fn get_impl_for<'a>(&self, target: impl Into<&'a str>) -> Option<&Impl> {
+        let target = target.into();
+        self.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Impl(v) if v.target().name() == target => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Gets a mutable reference to the first `impl` block for the given
+    /// target type, if it exists in this scope.
+    pub fn get_impl_for_mut<'a>(&mut self, target: impl Into<&'a str>) -> Option<&mut Impl> {
+        let target = target.into();
+        self.items
+            .iter_mut()
+            .filter_map(|item| match item {
+                Item::Impl(v) if v.target().name() == target => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
     /// Pushes a raw string to the scope.
     ///
     /// This string will be included verbatim in the formatted string.
@@ -348,13 +1243,22 @@ impl Scope {
         self
     }
 
+    /// Pushes a comment to the scope.
+    ///
+    /// The comment is attached to the next item, without a blank line in
+    /// between.
+    pub fn push_comment(&mut self, comment: impl Into<Comment>) -> &mut Self {
+        self.items.push(Item::Comment(comment.into()));
+        self
+    }
+
     /// Pushes a new `TypeAlias`, returning a mutable reference to it.
     pub fn new_type_alias(
         &mut self,
         name: impl Into<String>,
-        target: impl Into<String>,
+        target: impl Into<Type>,
     ) -> &mut TypeAlias {
-        self.push_type_alias(TypeAlias::new(name.into(), target.into()));
+        self.push_type_alias(TypeAlias::new(name, target));
 
         match *self.items.last_mut().unwrap() {
             Item::TypeAlias(ref mut v) => v,
@@ -368,47 +1272,176 @@ impl Scope {
         self
     }
 
+    /// Pushes a new `const` item, returning a mutable reference to it.
+    pub fn new_const(
+        &mut self,
+        name: impl Into<String>,
+        ty: impl Into<Type>,
+        value: impl Into<String>,
+    ) -> &mut ConstItem {
+        self.push_const(ConstItem::new(name, ty, value));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Const(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Pushes a new `static` item, returning a mutable reference to it.
+    pub fn new_static(
+        &mut self,
+        name: impl Into<String>,
+        ty: impl Into<Type>,
+        value: impl Into<String>,
+    ) -> &mut ConstItem {
+        self.push_const(ConstItem::new_static(name, ty, value));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Const(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Pushes a `const` or `static` item.
+    pub fn push_const(&mut self, item: ConstItem) -> &mut Self {
+        self.items.push(Item::Const(item));
+        self
+    }
+
     /// Pushes a `LineBreak`.
     pub fn push_line_break(&mut self) -> &mut Self {
         self.items.push(Item::LineBreak(LineBreak::new()));
         self
     }
 
+    /// Collects the provenance of the items of the scope, including those in
+    /// nested modules, with the lines they are rendered at by `to_string`.
+    pub fn source_map(&self) -> SourceMap {
+        let mut ret = String::new();
+        let mut fmt = Formatter::new(&mut ret);
+        self.fmt(&mut fmt).unwrap();
+        fmt.into_source_map()
+    }
+
     /// Formats the scope using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let (edition, rust_version) = (fmt.edition(), fmt.rust_version());
+        if let Some(edition) = self.edition {
+            fmt.set_edition(edition);
+        }
+        if let Some(rust_version) = self.rust_version {
+            fmt.set_rust_version(rust_version);
+        }
+
+        let res = self.fmt_contents(fmt);
+        fmt.set_edition(edition).set_rust_version(rust_version);
+        res
+    }
+
+    fn fmt_contents(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if let Some(ref doc) = self.doc {
             doc.fmt(fmt)?;
         }
 
+        if let Some(ref doc) = self.inner_doc {
+            doc.fmt_inner(fmt)?;
+        }
+        for attr in &self.attributes {
+            writeln!(fmt, "#![{}]", attr)?;
+        }
+        let has_header = self.inner_doc.is_some() || !self.attributes.is_empty();
+        if has_header && (!self.imports.is_empty() || !self.items.is_empty()) {
+            writeln!(fmt)?;
+        }
+
         self.fmt_imports(fmt)?;
 
-        if !self.imports.is_empty() {
+        // Module blocks made only of re-exports, such as preludes, end right
+        // after them; otherwise a blank line follows the imports.
+        let reexports_only = fmt.is_indented()
+            && self.items.is_empty()
+            && self
+                .imports
+                .values()
+                .flat_map(|imports| imports.values())
+                .all(|import| *import.vis() == Vis::Pub);
+        if !self.imports.is_empty() && !reexports_only {
             writeln!(fmt)?;
         }
 
         for (i, item) in self.items.iter().enumerate() {
             if i != 0 {
-                writeln!(fmt)?;
+                let lines = fmt.blank_lines().separating(&self.items[i - 1], item);
+                for _ in 0..lines {
+                    writeln!(fmt)?;
+                }
             }
 
-            match *item {
-                Item::Module(ref v) => v.fmt(fmt)?,
-                Item::Struct(ref v) => v.fmt(fmt)?,
-                Item::Function(ref v) => v.fmt(false, fmt)?,
-                Item::Trait(ref v) => v.fmt(fmt)?,
-                Item::Enum(ref v) => v.fmt(fmt)?,
-                Item::Impl(ref v) => v.fmt(fmt)?,
-                Item::Raw(ref v) => {
-                    writeln!(fmt, "{}", v)?;
+            match item {
+                Item::Module(module) if module.name() == "tests" => {
+                    self.fmt_tests_module(module, fmt)?;
                 }
-                Item::TypeAlias(ref v) => v.fmt(fmt)?,
-                Item::LineBreak(ref v) => v.fmt(fmt)?,
+                _ => item.fmt(fmt)?,
             }
         }
 
+        self.fmt_tests(fmt)?;
+
         Ok(())
     }
 
+    /// Gets the tests attached to the structs and enums of the scope.
+    fn attached_tests(&self) -> Vec<&Function> {
+        self.items
+            .iter()
+            .flat_map(|item| match item {
+                Item::Struct(v) => v.tests(),
+                Item::Enum(v) => v.tests(),
+                _ => &[],
+            })
+            .collect()
+    }
+
+    /// Formats an existing `tests` module, appending the attached tests to it.
+    fn fmt_tests_module(&self, module: &Module, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let mut module = module.clone();
+        let tests = self.attached_tests();
+        if !tests.is_empty() {
+            module.push_import("super", "*", Vis::Private);
+            for test in tests {
+                module.push_function(test_function(test));
+            }
+        }
+        Item::Module(module).fmt(fmt)
+    }
+
+    fn fmt_tests(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let tests = self.attached_tests();
+
+        // attached tests are merged into an existing `tests` module
+        if tests.is_empty() || self.get_module("tests").is_some() {
+            return Ok(());
+        }
+
+        if !self.items.is_empty() {
+            writeln!(fmt)?;
+        }
+
+        writeln!(fmt, "#[cfg(test)]")?;
+        write!(fmt, "mod tests")?;
+        fmt.block(|fmt| {
+            writeln!(fmt, "use super::*;")?;
+
+            for test in tests {
+                writeln!(fmt)?;
+                fmt.fmt_provenance("fn", Some(test.name()), test.provenance())?;
+                test_function(test).fmt(false, fmt)?;
+            }
+
+            Ok(())
+        })
+    }
+
     fn fmt_imports(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         // First, collect all visibilities
         let mut visibilities = Vec::new();
@@ -421,43 +1454,107 @@ impl Scope {
             }
         }
 
+        // Grouped imports are sorted, like rustfmt does; otherwise they keep
+        // the order they were pushed in.
+        let grouped = fmt.blank_lines().has_grouped_imports();
+        let groups: &[Option<ImportGroup>] = if grouped {
+            &[
+                Some(ImportGroup::Std),
+                Some(ImportGroup::External),
+                Some(ImportGroup::Crate),
+            ]
+        } else {
+            &[None]
+        };
+
+        let mut paths: Vec<&String> = self.imports.keys().collect();
+        if grouped {
+            paths.sort();
+        }
+
         let mut tys = Vec::new();
+        let mut wrote_group = false;
 
-        // Loop over all visibilities and format the associated imports
-        for vis in &visibilities {
-            for (path, imports) in &self.imports {
-                tys.clear();
+        for group in groups {
+            let mut wrote = false;
 
-                for (ty, import) in imports {
-                    if vis == import.vis() {
-                        tys.push(ty);
+            // Loop over all visibilities and format the associated imports
+            for vis in &visibilities {
+                for &path in &paths {
+                    if group.is_some_and(|group| ImportGroup::of(path) != group) {
+                        continue;
+                    }
+
+                    let imports = &self.imports[path];
+                    tys.clear();
+
+                    for (ty, import) in imports {
+                        if vis == import.vis() {
+                            tys.push(ty);
+                        }
                     }
-                }
 
-                if !tys.is_empty() {
-                    vis.fmt(fmt)?;
+                    if grouped {
+                        tys.sort_by_key(|ty| (*ty != "self", *ty));
+                    }
 
-                    write!(fmt, "use {}::", path)?;
+                    if !tys.is_empty() {
+                        if !wrote && wrote_group {
+                            writeln!(fmt)?;
+                        }
+                        wrote = true;
 
-                    #[allow(clippy::comparison_chain)]
-                    if tys.len() > 1 {
-                        write!(fmt, "{{")?;
+                        vis.fmt(fmt)?;
 
-                        for (i, ty) in tys.iter().enumerate() {
-                            if i != 0 {
-                                write!(fmt, ", ")?;
-                            }
-                            write!(fmt, "{}", ty)?;
+                        // A lone `self` imports the module itself, and a
+                        // single-segment import, e.g. `use foo;`, has no path.
+                        if tys.len() == 1 && *tys[0] == "self" {
+                            writeln!(fmt, "use {};", path)?;
+                            continue;
                         }
+                        if path.is_empty() {
+                            write!(fmt, "use ")?;
+                        } else {
+                            write!(fmt, "use {}::", path)?;
+                        }
+
+                        #[allow(clippy::comparison_chain)]
+                        if tys.len() > 1 {
+                            write!(fmt, "{{")?;
+
+                            for (i, ty) in tys.iter().enumerate() {
+                                if i != 0 {
+                                    write!(fmt, ", ")?;
+                                }
+                                write!(fmt, "{}", ty)?;
+                            }
 
-                        writeln!(fmt, "}};")?;
-                    } else if tys.len() == 1 {
-                        writeln!(fmt, "{};", tys[0])?;
+                            writeln!(fmt, "}};")?;
+                        } else if tys.len() == 1 {
+                            writeln!(fmt, "{};", tys[0])?;
+                        }
                     }
                 }
             }
+
+            wrote_group |= wrote;
         }
 
         Ok(())
     }
 }
+
+/// Returns an attached test marked with `#[test]`.
+fn test_function(test: &Function) -> Function {
+    if test.attributes().iter().any(|attr| attr == "test") {
+        test.clone()
+    } else {
+        test.clone().with_attribute("test")
+    }
+}
+
+/// Error returned when creating a fallible function in a scope without a
+/// default error type.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("the scope has no default error type")]
+pub struct MissingDefaultError;
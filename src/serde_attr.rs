@@ -0,0 +1,169 @@
+use core::fmt;
+
+use crate::attribute::Attribute;
+use crate::prelude::*;
+
+/// Defines a `rename_all` rule of serde.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RenameRule {
+    /// `lowercase`
+    Lower,
+    /// `UPPERCASE`
+    Upper,
+    /// `PascalCase`
+    Pascal,
+    /// `camelCase`
+    Camel,
+    /// `snake_case`
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    /// Gets the name of the rule, as written in the attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RenameRule::Lower => "lowercase",
+            RenameRule::Upper => "UPPERCASE",
+            RenameRule::Pascal => "PascalCase",
+            RenameRule::Camel => "camelCase",
+            RenameRule::Snake => "snake_case",
+            RenameRule::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            RenameRule::Kebab => "kebab-case",
+            RenameRule::ScreamingKebab => "SCREAMING-KEBAB-CASE",
+        }
+    }
+}
+
+impl fmt::Display for RenameRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Defines an option of a [`SerdeAttr`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SerdeOption {
+    /// `rename = "name"`
+    Rename(String),
+    /// `rename_all = "rule"`
+    RenameAll(RenameRule),
+    /// `default`
+    Default,
+    /// `default = "path"`
+    DefaultWith(String),
+    /// `skip_serializing_if = "path"`
+    SkipSerializingIf(String),
+    /// `tag = "name"`
+    Tag(String),
+    /// `content = "name"`
+    Content(String),
+    /// `deny_unknown_fields`
+    DenyUnknownFields,
+}
+
+impl fmt::Display for SerdeOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerdeOption::Rename(name) => write!(f, "rename = {:?}", name),
+            SerdeOption::RenameAll(rule) => write!(f, "rename_all = \"{}\"", rule),
+            SerdeOption::Default => write!(f, "default"),
+            SerdeOption::DefaultWith(path) => write!(f, "default = {:?}", path),
+            SerdeOption::SkipSerializingIf(path) => write!(f, "skip_serializing_if = {:?}", path),
+            SerdeOption::Tag(name) => write!(f, "tag = {:?}", name),
+            SerdeOption::Content(name) => write!(f, "content = {:?}", name),
+            SerdeOption::DenyUnknownFields => write!(f, "deny_unknown_fields"),
+        }
+    }
+}
+
+/// Defines a `#[serde(...)]` attribute from typed options.
+///
+/// The attribute converts into an [`Attribute`], so it can be pushed onto
+/// structs and enums with `push_attribute`, and onto fields and variants with
+/// `push_annotation`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerdeAttr {
+    options: Vec<SerdeOption>,
+}
+
+impl SerdeAttr {
+    /// Creates an attribute without options.
+    pub fn new() -> Self {
+        SerdeAttr::default()
+    }
+
+    /// Gets the options.
+    pub fn options(&self) -> &[SerdeOption] {
+        &self.options
+    }
+
+    /// Pushes an option.
+    pub fn push_option(&mut self, option: SerdeOption) -> &mut Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Pushes an option.
+    pub fn with_option(mut self, option: SerdeOption) -> Self {
+        self.push_option(option);
+        self
+    }
+
+    /// Renames the item or field.
+    pub fn with_rename(self, name: impl Into<String>) -> Self {
+        self.with_option(SerdeOption::Rename(name.into()))
+    }
+
+    /// Renames the fields or variants of the item with the given rule.
+    pub fn with_rename_all(self, rule: RenameRule) -> Self {
+        self.with_option(SerdeOption::RenameAll(rule))
+    }
+
+    /// Uses `Default::default()` for missing values.
+    pub fn with_default(self) -> Self {
+        self.with_option(SerdeOption::Default)
+    }
+
+    /// Calls the function at `path` for missing values.
+    pub fn with_default_fn(self, path: impl Into<String>) -> Self {
+        self.with_option(SerdeOption::DefaultWith(path.into()))
+    }
+
+    /// Skips serializing the field if the function at `path` returns true,
+    /// e.g. `Option::is_none`.
+    pub fn with_skip_serializing_if(self, path: impl Into<String>) -> Self {
+        self.with_option(SerdeOption::SkipSerializingIf(path.into()))
+    }
+
+    /// Tags the variants of an enum with a field of the given name.
+    pub fn with_tag(self, name: impl Into<String>) -> Self {
+        self.with_option(SerdeOption::Tag(name.into()))
+    }
+
+    /// Puts the content of the variants of an enum in a field of the given
+    /// name, next to the tag.
+    pub fn with_content(self, name: impl Into<String>) -> Self {
+        self.with_option(SerdeOption::Content(name.into()))
+    }
+
+    /// Fails to deserialize on unknown fields.
+    pub fn with_deny_unknown_fields(self) -> Self {
+        self.with_option(SerdeOption::DenyUnknownFields)
+    }
+}
+
+impl From<SerdeAttr> for Attribute {
+    fn from(attr: SerdeAttr) -> Self {
+        let options: Vec<String> = attr.options.iter().map(ToString::to_string).collect();
+        Attribute::list("serde", options.join(", "))
+    }
+}
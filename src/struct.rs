@@ -1,31 +1,50 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::attribute::{Attribute, AttributeArgs};
 use crate::bound::Bound;
+use crate::case::to_snake_case;
+use crate::comment::Comment;
+use crate::derive::DeriveOrder;
 use crate::doc::Doc;
 use crate::field::Field;
 use crate::fields::Fields;
 use crate::formatter::Formatter;
-use crate::generic_parameter::GenericParameter;
+use crate::function::Function;
+use crate::generic_parameter::{GenericParameter, generic_name};
+use crate::ident::sanitize_ident;
 use crate::lint::Lint;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::repr::Repr;
+use crate::tuple_field::TupleField;
 use crate::r#type::Type;
 use crate::type_def::TypeDef;
 use crate::visibility::Vis;
 
 /// Defines a struct.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Struct {
     type_def: TypeDef,
 
     /// Struct fields
     fields: Fields,
+
+    /// Tests attached to the struct
+    tests: Vec<Function>,
 }
 
 impl Struct {
     /// Creates a new struct definition with the given name.
+    ///
+    /// The name is not checked. Names built from schema titles, such as
+    /// `user profile`, should go through [`to_camel_case`](crate::to_camel_case)
+    /// and [`sanitize_ident`](crate::sanitize_ident) first.
     pub fn new(name: impl Into<String>) -> Self {
         Struct {
             type_def: TypeDef::new(name.into()),
             fields: Fields::Empty,
+            tests: Vec::new(),
         }
     }
 
@@ -88,7 +107,7 @@ impl Struct {
     }
 
     /// Sets the generic parameters of the struct.
-    pub fn with_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
+    pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
     {
@@ -168,7 +187,7 @@ impl Struct {
     }
 
     /// Sets the struct documentation.
-    pub fn with_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
     where
         S: Into<Doc>,
     {
@@ -181,6 +200,36 @@ impl Struct {
         self.type_def.doc_mut()
     }
 
+    /// Gets the comment written above the struct and its documentation.
+    pub fn comment(&self) -> Option<&Comment> {
+        self.type_def.comment()
+    }
+
+    /// Sets the comment written above the struct and its documentation, e.g.
+    /// a note about how the struct was generated.
+    pub fn set_comment<C>(&mut self, comment: impl Into<Option<C>>) -> &mut Self
+    where
+        C: Into<Comment>,
+    {
+        self.type_def.set_comment(comment);
+        self
+    }
+
+    /// Sets the comment written above the struct and its documentation, e.g.
+    /// a note about how the struct was generated.
+    pub fn with_comment<C>(mut self, comment: impl Into<Option<C>>) -> Self
+    where
+        C: Into<Comment>,
+    {
+        self.set_comment(comment);
+        self
+    }
+
+    /// Gets a mutable reference to the comment written above the struct.
+    pub fn comment_mut(&mut self) -> Option<&mut Comment> {
+        self.type_def.comment_mut()
+    }
+
     /// Gets the derives of the struct.
     pub fn derives(&self) -> &[String] {
         self.type_def.derives()
@@ -210,6 +259,8 @@ impl Struct {
     }
 
     /// Pushes a new type that the struct should derive.
+    ///
+    /// Accepts a [`Derive`](crate::Derive) or the name of any derive macro.
     pub fn push_derive(&mut self, derive: impl Into<String>) -> &mut Self {
         self.type_def.push_derive(derive.into());
         self
@@ -221,15 +272,35 @@ impl Struct {
         self
     }
 
+    /// Gets how the derives of the struct are rendered.
+    pub fn derive_order(&self) -> DeriveOrder {
+        self.type_def.derive_order()
+    }
+
+    /// Sets how the derives of the struct are rendered.
+    ///
+    /// Duplicate derives are dropped by default, use [`DeriveOrder::Exact`]
+    /// to render them exactly as pushed.
+    pub fn set_derive_order(&mut self, derive_order: DeriveOrder) -> &mut Self {
+        self.type_def.set_derive_order(derive_order);
+        self
+    }
+
+    /// Sets how the derives of the struct are rendered.
+    pub fn with_derive_order(mut self, derive_order: DeriveOrder) -> Self {
+        self.set_derive_order(derive_order);
+        self
+    }
+
     /// Gets the attributes of the struct.
-    pub fn attributes(&self) -> &[String] {
+    pub fn attributes(&self) -> &[Attribute] {
         self.type_def.attributes()
     }
 
     /// Sets the attributes of the struct.
     pub fn set_attributes<A>(&mut self, attributes: impl IntoIterator<Item = A>) -> &mut Self
     where
-        A: Into<String>,
+        A: Into<Attribute>,
     {
         self.type_def.set_attributes(attributes);
         self
@@ -238,25 +309,25 @@ impl Struct {
     /// Sets the attributes of the struct.
     pub fn with_attributes<A>(mut self, attributes: impl IntoIterator<Item = A>) -> Self
     where
-        A: Into<String>,
+        A: Into<Attribute>,
     {
         self.set_attributes(attributes);
         self
     }
 
     /// Gets a mutable reference to the attributes of the struct.
-    pub fn attributes_mut(&mut self) -> &mut Vec<String> {
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
         self.type_def.attributes_mut()
     }
 
     /// Pushes a new attribute to the struct.
-    pub fn push_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
         self.type_def.push_attribute(attribute.into());
         self
     }
 
     /// Pushes a new attribute to the struct.
-    pub fn with_attribute(mut self, attribute: impl Into<String>) -> Self {
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
         self.push_attribute(attribute);
         self
     }
@@ -301,37 +372,54 @@ impl Struct {
         self
     }
 
-    /// Gets the representation.
-    pub fn repr(&mut self) -> Option<&String> {
+    /// Gets the representation hints.
+    pub fn repr(&self) -> &[Repr] {
         self.type_def.repr()
     }
 
-    /// Sets the representation.
-    pub fn set_repr(&mut self, repr: impl Into<Option<String>>) -> &mut Self {
+    /// Sets the representation hints, e.g. `[Repr::C, Repr::Packed(Some(4))]`.
+    ///
+    /// This used to take a single `impl Into<Option<String>>`; such values
+    /// still work, as `Some("C")` is a list of one hint. Hints are written as
+    /// given, [`Scope::check`](crate::Scope::check) reports the ones that
+    /// conflict or are not supported on structs.
+    pub fn set_repr<R>(&mut self, repr: impl IntoIterator<Item = R>) -> &mut Self
+    where
+        R: Into<Repr>,
+    {
         self.type_def.set_repr(repr);
         self
     }
 
-    /// Sets the representation.
-    pub fn with_repr(mut self, repr: impl Into<Option<String>>) -> Self {
+    /// Sets the representation hints.
+    pub fn with_repr<R>(mut self, repr: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Into<Repr>,
+    {
         self.set_repr(repr);
         self
     }
 
-    /// Gets a mutable reference to the representation.
-    pub fn repr_mut(&mut self) -> Option<&mut String> {
+    /// Gets a mutable reference to the representation hints.
+    pub fn repr_mut(&mut self) -> &mut Vec<Repr> {
         self.type_def.repr_mut()
     }
 
+    /// Pushes a representation hint.
+    pub fn push_repr(&mut self, repr: impl Into<Repr>) -> &mut Self {
+        self.type_def.push_repr(repr);
+        self
+    }
+
     /// Gets the macros.
-    pub fn macros(&self) -> &[String] {
+    pub fn macros(&self) -> &[Attribute] {
         self.type_def.macros()
     }
 
     /// Sets the macros.
     pub fn set_macros<S>(&mut self, macros: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.type_def.set_macros(macros);
         self
@@ -340,25 +428,26 @@ impl Struct {
     /// Sets the macros.
     pub fn with_macros<S>(mut self, macros: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_macros(macros);
         self
     }
 
     /// Gets a mutable reference to the macros.
-    pub fn macros_mut(&mut self) -> &mut Vec<String> {
+    pub fn macros_mut(&mut self) -> &mut Vec<Attribute> {
         self.type_def.macros_mut()
     }
 
-    /// Pushes an arbitrary macro.
-    pub fn push_macro(&mut self, r#macro: impl Into<String>) -> &mut Self {
+    /// Pushes an attribute macro, e.g. `#[serde_as]`, written after the
+    /// other attributes of the struct. Duplicates are skipped.
+    pub fn push_macro(&mut self, r#macro: impl Into<Attribute>) -> &mut Self {
         self.type_def.push_macro(r#macro.into());
         self
     }
 
-    /// Pushes an arbitrary macro.
-    pub fn with_macro(mut self, r#macro: impl Into<String>) -> Self {
+    /// Pushes an attribute macro, e.g. `#[serde_as]`.
+    pub fn with_macro(mut self, r#macro: impl Into<Attribute>) -> Self {
         self.push_macro(r#macro);
         self
     }
@@ -403,11 +492,30 @@ impl Struct {
         self
     }
 
+    /// Pushes a named field to the struct, converting its name to `snake_case`.
+    ///
+    /// The converted name is also passed through [`sanitize_ident`], so schema
+    /// names like `"Content-Type"` or `"type"` become valid field names.
+    pub fn push_named_field_auto_case(&mut self, mut named_field: Field) -> &mut Self {
+        let name = sanitize_ident(to_snake_case(named_field.name()));
+        named_field.set_name(name);
+        self.push_named_field(named_field)
+    }
+
+    /// Pushes a named field to the struct, converting its name to `snake_case`.
+    ///
+    /// The converted name is also passed through [`sanitize_ident`], so schema
+    /// names like `"Content-Type"` or `"type"` become valid field names.
+    pub fn with_named_field_auto_case(mut self, named_field: Field) -> Self {
+        self.push_named_field_auto_case(named_field);
+        self
+    }
+
     /// Pushes a tuple field to the struct.
     ///
     /// A struct can either set tuple fields with this function or named fields
     /// with `field`, but not both.
-    pub fn push_tuple_field(&mut self, tuple_field: impl Into<Type>) -> &mut Self {
+    pub fn push_tuple_field(&mut self, tuple_field: impl Into<TupleField>) -> &mut Self {
         self.fields.push_tuple(tuple_field.into());
         self
     }
@@ -416,26 +524,283 @@ impl Struct {
     ///
     /// A struct can either set tuple fields with this function or named fields
     /// with `field`, but not both.
-    pub fn with_tuple_field(mut self, tuple_field: impl Into<Type>) -> Self {
+    pub fn with_tuple_field(mut self, tuple_field: impl Into<TupleField>) -> Self {
         self.push_tuple_field(tuple_field);
         self
     }
 
+    /// Gets the type and lifetime generics that no field uses.
+    ///
+    /// The compiler rejects structs with such generics. Const generics are
+    /// allowed to be unused, so they are never returned. Generics are
+    /// returned without their bounds, e.g. `T` for `T: Clone`.
+    pub fn unused_generics(&self) -> Vec<&str> {
+        let types: Vec<&Type> = match &self.fields {
+            Fields::Empty => Vec::new(),
+            Fields::Tuple(fields) => fields.iter().map(TupleField::ty).collect(),
+            Fields::Named(fields) => fields.iter().map(Field::ty).collect(),
+        };
+        let used: Vec<String> = types.iter().map(|ty| ty.to_source()).collect();
+
+        self.generics()
+            .iter()
+            .map(|generic| generic.name().trim())
+            .filter(|name| !name.starts_with("const "))
+            .map(generic_name)
+            .filter(|name| !used.iter().any(|source| mentions(source, name)))
+            .collect()
+    }
+
+    /// Pushes a `PhantomData` field using the generics that no field uses,
+    /// if any, so that the struct compiles.
+    ///
+    /// Named and unit structs get a field with the given name, tuple structs
+    /// a tuple field. Lifetimes are used through a reference, e.g.
+    /// `PhantomData<(&'a (), T)>`.
+    pub fn insert_phantom_data(&mut self, name: impl Into<String>) -> &mut Self {
+        let unused = self.unused_generics();
+        if unused.is_empty() {
+            return self;
+        }
+
+        let params: Vec<String> = unused
+            .iter()
+            .map(|name| {
+                if name.starts_with('\'') {
+                    format!("&{} ()", name)
+                } else {
+                    name.to_string()
+                }
+            })
+            .collect();
+        let ty = match params.as_slice() {
+            [param] => format!("std::marker::PhantomData<{}>", param),
+            params => format!("std::marker::PhantomData<({})>", params.join(", ")),
+        };
+
+        match self.fields {
+            Fields::Tuple(_) => self.push_tuple_field(ty),
+            Fields::Empty | Fields::Named(_) => self.push_named_field(Field::new(name.into(), ty)),
+        }
+    }
+
+    /// Gets the tests attached to the struct.
+    pub fn tests(&self) -> &[Function] {
+        &self.tests
+    }
+
+    /// Sets the tests attached to the struct.
+    pub fn set_tests<F>(&mut self, tests: impl IntoIterator<Item = F>) -> &mut Self
+    where
+        F: Into<Function>,
+    {
+        self.tests = tests.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the tests attached to the struct.
+    pub fn with_tests<F>(mut self, tests: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Into<Function>,
+    {
+        self.set_tests(tests);
+        self
+    }
+
+    /// Gets a mutable reference to the tests attached to the struct.
+    pub fn tests_mut(&mut self) -> &mut Vec<Function> {
+        &mut self.tests
+    }
+
+    /// Attaches a test to the struct.
+    ///
+    /// Tests are rendered in a `#[cfg(test)] mod tests` at the end of the
+    /// scope containing the struct, or appended to the `tests` module of that
+    /// scope if it already has one.
+    pub fn push_test(&mut self, test: impl Into<Function>) -> &mut Self {
+        self.tests.push(test.into());
+        self
+    }
+
+    /// Attaches a test to the struct.
+    ///
+    /// Tests are rendered in a `#[cfg(test)] mod tests` at the end of the
+    /// scope containing the struct.
+    pub fn with_test(mut self, test: impl Into<Function>) -> Self {
+        self.push_test(test);
+        self
+    }
+
+    /// Reorders the named fields by descending size to minimize padding.
+    ///
+    /// `size_of` returns the size estimate of a field type (e.g. backed by a
+    /// type-size table, see [`Type::primitive_size`]). Fields of unknown size are
+    /// moved after the sized ones, keeping their relative order. Structs with
+    /// `#[repr(C)]`, `#[repr(packed)]` or `#[repr(transparent)]` are left
+    /// untouched since their layout is part of their contract, whether the
+    /// repr is set with [`set_repr`](Self::set_repr) or pushed as an attribute.
+    ///
+    /// When the order changes, a note about the reorder is appended to the
+    /// [comment](Self::set_comment) of the struct. Returns whether the fields
+    /// were reordered.
+    pub fn optimize_layout<F>(&mut self, size_of: F) -> bool
+    where
+        F: Fn(&Type) -> Option<usize>,
+    {
+        let fixed = |repr: &Repr| matches!(repr, Repr::C | Repr::Transparent | Repr::Packed(_));
+        let mut attribute_reprs = self
+            .type_def
+            .attributes()
+            .iter()
+            .filter(|attr| attr.path() == "repr")
+            .filter_map(|attr| match attr.args() {
+                AttributeArgs::List(hints) => Some(hints.split(',')),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|hint| hint.parse::<Repr>().ok());
+        if self.type_def.repr().iter().any(fixed) || attribute_reprs.any(|r| fixed(&r)) {
+            return false;
+        }
+
+        let Fields::Named(ref mut fields) = self.fields else {
+            return false;
+        };
+
+        let original: Vec<String> = fields.iter().map(|f| f.name().to_string()).collect();
+        fields.sort_by_key(|f| core::cmp::Reverse(size_of(f.ty()).unwrap_or(0)));
+
+        let reordered = fields
+            .iter()
+            .map(Field::name)
+            .ne(original.iter().map(String::as_str));
+        if reordered {
+            let note = "Fields reordered by descending size to minimize padding.";
+            match self.comment_mut() {
+                Some(comment) => {
+                    let text = comment.text_mut();
+                    text.push('\n');
+                    text.push_str(note);
+                }
+                None => {
+                    self.set_comment(note);
+                }
+            }
+        }
+
+        reordered
+    }
+
+    /// Returns whether the struct is `#[non_exhaustive]`.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.type_def.is_non_exhaustive()
+    }
+
+    /// Sets whether the struct is `#[non_exhaustive]`.
+    pub fn set_non_exhaustive(&mut self, non_exhaustive: bool) -> &mut Self {
+        self.type_def.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    /// Sets whether the struct is `#[non_exhaustive]`.
+    pub fn with_non_exhaustive(mut self, non_exhaustive: bool) -> Self {
+        self.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    /// Gets a mutable reference to whether the struct is `#[non_exhaustive]`.
+    pub fn non_exhaustive_mut(&mut self) -> &mut bool {
+        self.type_def.non_exhaustive_mut()
+    }
+
+    /// Gets the `#[must_use]` attribute of the struct: `None` if it is not
+    /// `#[must_use]`, otherwise the optional reason.
+    pub fn must_use(&self) -> Option<Option<&str>> {
+        self.type_def.must_use()
+    }
+
+    /// Marks the struct as `#[must_use]`, with an optional reason, e.g.
+    /// `#[must_use = "reason"]`.
+    pub fn set_must_use(&mut self, reason: Option<&str>) -> &mut Self {
+        self.type_def.set_must_use(reason);
+        self
+    }
+
+    /// Marks the struct as `#[must_use]`, with an optional reason.
+    pub fn with_must_use(mut self, reason: Option<&str>) -> Self {
+        self.set_must_use(reason);
+        self
+    }
+
+    /// Gets a mutable reference to the `#[must_use]` attribute of the struct.
+    pub fn must_use_mut(&mut self) -> &mut Option<Option<String>> {
+        self.type_def.must_use_mut()
+    }
+
+    /// Gets whether the struct is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.type_def.is_compact()
+    }
+
+    /// Sets whether the struct is rendered without a blank line before it.
+    ///
+    /// Use it to keep related unit or marker structs together.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.type_def.set_compact(compact);
+        self
+    }
+
+    /// Sets whether the struct is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the struct is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        self.type_def.compact_mut()
+    }
+
+    /// Gets where the struct was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.type_def.provenance()
+    }
+
+    /// Sets where the struct was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.type_def.set_provenance(provenance);
+        self
+    }
+
+    /// Sets where the struct was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the struct was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        self.type_def.provenance_mut()
+    }
+
     /// Formats the struct using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         self.type_def.fmt_head("struct", &[], fmt)?;
         self.fields.fmt(fmt)?;
 
         match self.fields {
-            Fields::Empty => {
-                writeln!(fmt, ";")?;
-            }
-            Fields::Tuple(..) => {
-                writeln!(fmt, ";")?;
-            }
-            _ => {}
+            Fields::Empty | Fields::Tuple(..) => writeln!(fmt, ";"),
+            Fields::Named(..) => writeln!(fmt),
         }
-
-        Ok(())
     }
 }
+
+/// Returns whether the source of a type mentions the generic `name`.
+fn mentions(source: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    source.match_indices(name).any(|(i, _)| {
+        let before = source[..i].chars().next_back();
+        let after = source[i + name.len()..].chars().next();
+        !before.is_some_and(|c| is_ident(c) || c == '\'') && !after.is_some_and(is_ident)
+    })
+}
@@ -0,0 +1,250 @@
+use crate::prelude::*;
+use thiserror::Error;
+
+use crate::associated_type::AssociatedType;
+use crate::emission::ImplGenerator;
+use crate::field::Field;
+use crate::fields::Fields;
+use crate::function::Function;
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+
+/// Generates a `From` or `TryFrom` impl converting one struct into another.
+///
+/// Each field of the target struct is read from the source field of the same
+/// name unless it is renamed or given an expression. Fields of the same type
+/// are moved as is and other fields are converted with `into()`, or with
+/// `try_into()?` when an error type is set.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructConversion {
+    /// Source struct
+    source: Struct,
+
+    /// Target struct
+    target: Struct,
+
+    /// Target fields read from a differently named source field
+    renames: IndexMap<String, String>,
+
+    /// Target fields built from an expression over `value`
+    exprs: IndexMap<String, String>,
+
+    /// Error type of a `TryFrom` conversion
+    error: Option<String>,
+}
+
+impl StructConversion {
+    /// Creates a conversion from the `source` struct to the `target` struct.
+    pub fn new(source: impl Into<Struct>, target: impl Into<Struct>) -> Self {
+        StructConversion {
+            source: source.into(),
+            target: target.into(),
+            renames: IndexMap::default(),
+            exprs: IndexMap::default(),
+            error: None,
+        }
+    }
+
+    /// Gets the source struct.
+    pub fn source(&self) -> &Struct {
+        &self.source
+    }
+
+    /// Gets the target struct.
+    pub fn target(&self) -> &Struct {
+        &self.target
+    }
+
+    /// Gets the target fields read from a differently named source field.
+    pub fn renames(&self) -> &IndexMap<String, String> {
+        &self.renames
+    }
+
+    /// Reads a target field from a differently named source field.
+    pub fn push_rename(
+        &mut self,
+        target: impl Into<String>,
+        source: impl Into<String>,
+    ) -> &mut Self {
+        self.renames.insert(target.into(), source.into());
+        self
+    }
+
+    /// Reads a target field from a differently named source field.
+    pub fn with_rename(mut self, target: impl Into<String>, source: impl Into<String>) -> Self {
+        self.push_rename(target, source);
+        self
+    }
+
+    /// Gets the target fields built from an expression.
+    pub fn exprs(&self) -> &IndexMap<String, String> {
+        &self.exprs
+    }
+
+    /// Builds a target field from an expression, which may refer to the
+    /// source struct as `value`.
+    pub fn push_expr(&mut self, target: impl Into<String>, expr: impl Into<String>) -> &mut Self {
+        self.exprs.insert(target.into(), expr.into());
+        self
+    }
+
+    /// Builds a target field from an expression, which may refer to the
+    /// source struct as `value`.
+    pub fn with_expr(mut self, target: impl Into<String>, expr: impl Into<String>) -> Self {
+        self.push_expr(target, expr);
+        self
+    }
+
+    /// Gets the error type of a `TryFrom` conversion.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Sets the error type, generating `TryFrom` instead of `From`.
+    pub fn set_error<S>(&mut self, error: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.error = error.into().map(Into::into);
+        self
+    }
+
+    /// Sets the error type, generating `TryFrom` instead of `From`.
+    pub fn with_error<S>(mut self, error: impl Into<Option<S>>) -> Self
+    where
+        S: Into<String>,
+    {
+        self.set_error(error);
+        self
+    }
+
+    /// Generates the `impl From<Source> for Target` block, or `TryFrom` if
+    /// an error type is set.
+    ///
+    /// Fails if a renamed or computed field does not exist, or if a target
+    /// field has no source field of the same name and no mapping. Optional
+    /// target fields without a source are set to `None` instead. Fails too if
+    /// either struct has tuple fields.
+    pub fn generate(&self) -> Result<Impl, StructConversionError> {
+        let source = named_fields(&self.source)?;
+        let target = named_fields(&self.target)?;
+        let has = |fields: &[Field], name: &str| fields.iter().any(|f| f.name() == name);
+
+        let unknown: Vec<String> = self
+            .renames
+            .iter()
+            .filter(|(t, s)| !has(target, t) || !has(source, s))
+            .map(|(t, _)| t)
+            .chain(self.exprs.keys().filter(|t| !has(target, t)))
+            .cloned()
+            .collect();
+        if !unknown.is_empty() {
+            return Err(StructConversionError::UnknownFields(unknown));
+        }
+
+        let mut unmapped = Vec::new();
+        let mut inits = Vec::new();
+        for field in target {
+            let name = field.name();
+            let init = if let Some(expr) = self.exprs.get(name) {
+                expr.clone()
+            } else {
+                let from = self.renames.get(name).map_or(name, String::as_str);
+                match source.iter().find(|f| f.name() == from) {
+                    Some(f) if f.declared_ty() == field.declared_ty() => {
+                        format!("value.{}", from)
+                    }
+                    Some(_) if self.error.is_some() => format!("value.{}.try_into()?", from),
+                    Some(_) => format!("value.{}.into()", from),
+                    None if field.is_optional() => "None".to_string(),
+                    None => {
+                        unmapped.push(name.to_string());
+                        continue;
+                    }
+                }
+            };
+
+            inits.push(format!("    {}: {},", name, init));
+        }
+        if !unmapped.is_empty() {
+            return Err(StructConversionError::Unmapped(unmapped));
+        }
+
+        let (ok, close) = match self.error {
+            Some(_) => ("Ok(", ")"),
+            None => ("", ""),
+        };
+        let mut function = Function::new(if self.error.is_some() {
+            "try_from"
+        } else {
+            "from"
+        })
+        .with_arg("value", self.source.name());
+        if matches!(self.target.fields(), Fields::Empty) {
+            function.push_line(format!("{}Self{}", ok, close));
+        } else {
+            function.push_line(format!("{}Self {{", ok));
+            for init in inits {
+                function.push_line(init);
+            }
+            function.push_line(format!("}}{}", close));
+        }
+
+        let mut r#impl = Impl::new(self.target.name());
+        match &self.error {
+            Some(error) => {
+                r#impl
+                    .set_impl_trait(format!("TryFrom<{}>", self.source.name()))
+                    .push_associated_type(AssociatedType::new_with_concrete_ty("Error", error));
+                function.set_ret("Result<Self, Self::Error>");
+            }
+            None => {
+                r#impl.set_impl_trait(format!("From<{}>", self.source.name()));
+                function.set_ret("Self");
+            }
+        }
+
+        Ok(r#impl.with_function(function))
+    }
+}
+
+impl ImplGenerator for StructConversion {
+    type Error = StructConversionError;
+
+    fn generate_impl(&self) -> Result<Impl, Self::Error> {
+        self.generate()
+    }
+
+    /// Returns the target struct, then the source struct.
+    fn macro_args(&self) -> Vec<String> {
+        vec![
+            self.target.name().to_string(),
+            self.source.name().to_string(),
+        ]
+    }
+}
+
+/// Error returned when a [`StructConversion`] is incomplete or inconsistent.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum StructConversionError {
+    /// Mapped target fields, or the source fields they rename, do not exist.
+    #[error("unknown mapped fields: {}", .0.join(", "))]
+    UnknownFields(Vec<String>),
+    /// Target fields have no source field of the same name and no mapping.
+    #[error("unmapped target fields: {}", .0.join(", "))]
+    Unmapped(Vec<String>),
+    /// A struct has tuple fields, which cannot be converted by name.
+    #[error("struct conversions need named fields: {0}")]
+    TupleFields(String),
+}
+
+fn named_fields(r#struct: &Struct) -> Result<&[Field], StructConversionError> {
+    match r#struct.fields() {
+        Fields::Empty => Ok(&[]),
+        Fields::Named(fields) => Ok(fields),
+        Fields::Tuple(_) => Err(StructConversionError::TupleFields(
+            r#struct.name().to_string(),
+        )),
+    }
+}
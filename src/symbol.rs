@@ -0,0 +1,218 @@
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use crate::prelude::*;
+
+/// A name used by the code model, such as a type or field name.
+///
+/// A symbol either owns its string or shares it with other symbols. Shared
+/// symbols are created by an [`Interner`] and are cheap to clone, so models
+/// repeating the same names many times only store each name once. Symbols
+/// compare, hash and order by their string, whichever way they are stored.
+#[derive(Clone)]
+pub struct Symbol(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    Owned(String),
+    Shared(Arc<str>),
+}
+
+impl Symbol {
+    /// Returns the string of the symbol.
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Owned(s) => s,
+            Repr::Shared(s) => s,
+        }
+    }
+
+    /// Returns whether the string is shared with other symbols.
+    pub fn is_shared(&self) -> bool {
+        matches!(self.0, Repr::Shared(_))
+    }
+
+    /// Gets a mutable reference to the string, copying it out of the shared
+    /// storage first if needed.
+    pub fn to_mut(&mut self) -> &mut String {
+        if let Repr::Shared(s) = &self.0 {
+            self.0 = Repr::Owned(s.to_string());
+        }
+
+        match &mut self.0 {
+            Repr::Owned(s) => s,
+            Repr::Shared(_) => unreachable!(),
+        }
+    }
+}
+
+impl Deref for Symbol {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for Symbol {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for Symbol {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Symbol {}
+
+impl PartialEq<str> for Symbol {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Symbol {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl From<String> for Symbol {
+    fn from(s: String) -> Self {
+        Symbol(Repr::Owned(s))
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(s: &str) -> Self {
+        Symbol(Repr::Owned(s.to_string()))
+    }
+}
+
+impl From<&String> for Symbol {
+    fn from(s: &String) -> Self {
+        Symbol(Repr::Owned(s.clone()))
+    }
+}
+
+impl From<Cow<'_, str>> for Symbol {
+    fn from(s: Cow<'_, str>) -> Self {
+        Symbol(Repr::Owned(s.into_owned()))
+    }
+}
+
+impl From<Arc<str>> for Symbol {
+    fn from(s: Arc<str>) -> Self {
+        Symbol(Repr::Shared(s))
+    }
+}
+
+impl From<&Symbol> for Symbol {
+    fn from(s: &Symbol) -> Self {
+        s.clone()
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(s: Symbol) -> Self {
+        match s.0 {
+            Repr::Owned(s) => s,
+            Repr::Shared(s) => s.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Symbol {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Symbol {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Symbol::from)
+    }
+}
+
+/// Deduplicates names, handing out shared [`Symbol`]s.
+///
+/// Interning every type and field name of a large model stores each distinct
+/// name once, and makes cloning the model's types and fields cheaper.
+#[derive(Clone, Default, Debug)]
+pub struct Interner {
+    symbols: BTreeSet<Symbol>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Returns the shared symbol for the given name.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(name) {
+            return symbol.clone();
+        }
+
+        let symbol = Symbol::from(Arc::<str>::from(name));
+        self.symbols.insert(symbol.clone());
+        symbol
+    }
+
+    /// Returns the number of distinct names interned.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns whether no name has been interned.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
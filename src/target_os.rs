@@ -0,0 +1,213 @@
+use thiserror::Error;
+
+use crate::body::Body;
+use crate::cfg::Cfg;
+use crate::function::Function;
+use crate::r#impl::Impl;
+use crate::prelude::*;
+
+/// Generates per-OS copies of a function or `impl` block.
+///
+/// Each copy is gated on `#[cfg(target_os = "...")]` and uses the bodies
+/// overridden for its OS, falling back to the bodies of the original
+/// definition. With a fallback enabled, one more copy with the original
+/// bodies is gated on none of the target OSes matching.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetOsVariants {
+    /// Target OSes and their body overrides, keyed by function name
+    targets: IndexMap<String, IndexMap<String, Vec<String>>>,
+
+    /// Whether to emit a copy for other OSes
+    fallback: bool,
+}
+
+impl TargetOsVariants {
+    /// Creates an empty set of variants.
+    pub fn new() -> Self {
+        TargetOsVariants::default()
+    }
+
+    /// Gets the target OSes and their body overrides.
+    pub fn targets(&self) -> &IndexMap<String, IndexMap<String, Vec<String>>> {
+        &self.targets
+    }
+
+    /// Adds a target OS.
+    pub fn push_target(&mut self, os: impl Into<String>) -> &mut Self {
+        self.targets.entry(os.into()).or_default();
+        self
+    }
+
+    /// Adds a target OS.
+    pub fn with_target(mut self, os: impl Into<String>) -> Self {
+        self.push_target(os);
+        self
+    }
+
+    /// Overrides the body of a function for a target OS, adding the target
+    /// if needed.
+    pub fn push_override<S>(
+        &mut self,
+        os: impl Into<String>,
+        function: impl Into<String>,
+        lines: impl IntoIterator<Item = S>,
+    ) -> &mut Self
+    where
+        S: Into<String>,
+    {
+        self.targets
+            .entry(os.into())
+            .or_default()
+            .insert(function.into(), lines.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Overrides the body of a function for a target OS, adding the target
+    /// if needed.
+    pub fn with_override<S>(
+        mut self,
+        os: impl Into<String>,
+        function: impl Into<String>,
+        lines: impl IntoIterator<Item = S>,
+    ) -> Self
+    where
+        S: Into<String>,
+    {
+        self.push_override(os, function, lines);
+        self
+    }
+
+    /// Gets whether a copy is emitted for other OSes.
+    pub fn fallback(&self) -> bool {
+        self.fallback
+    }
+
+    /// Sets whether a copy is emitted for other OSes.
+    pub fn set_fallback(&mut self, fallback: bool) -> &mut Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Sets whether a copy is emitted for other OSes.
+    pub fn with_fallback(mut self, fallback: bool) -> Self {
+        self.set_fallback(fallback);
+        self
+    }
+
+    /// Generates the per-OS copies of a function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an override names a different function;
+    /// [`try_generate_function`](Self::try_generate_function) returns an
+    /// error instead.
+    pub fn generate_function(&self, function: &Function) -> Vec<Function> {
+        match self.try_generate_function(function) {
+            Ok(functions) => functions,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates the per-OS copies of a function, or an error if an override
+    /// names a different function.
+    pub fn try_generate_function(
+        &self,
+        function: &Function,
+    ) -> Result<Vec<Function>, UnknownOverrideError> {
+        self.variants(&[function.name()], |cfg, overrides| {
+            let mut function = function.clone();
+            apply(&mut function, overrides);
+            restrict(function.cfg_mut(), cfg);
+            function
+        })
+    }
+
+    /// Generates the per-OS copies of an `impl` block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an override names a function missing from the block;
+    /// [`try_generate_impl`](Self::try_generate_impl) returns an error
+    /// instead.
+    pub fn generate_impl(&self, r#impl: &Impl) -> Vec<Impl> {
+        match self.try_generate_impl(r#impl) {
+            Ok(impls) => impls,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Generates the per-OS copies of an `impl` block, or an error if an
+    /// override names a function missing from the block.
+    pub fn try_generate_impl(&self, r#impl: &Impl) -> Result<Vec<Impl>, UnknownOverrideError> {
+        let names: Vec<&str> = r#impl.functions().iter().map(Function::name).collect();
+
+        self.variants(&names, |cfg, overrides| {
+            let mut r#impl = r#impl.clone();
+            for function in r#impl.functions_mut() {
+                apply(function, overrides);
+            }
+            restrict(r#impl.cfg_mut(), cfg);
+            r#impl
+        })
+    }
+
+    fn variants<T>(
+        &self,
+        names: &[&str],
+        mut build: impl FnMut(Cfg, Option<&IndexMap<String, Vec<String>>>) -> T,
+    ) -> Result<Vec<T>, UnknownOverrideError> {
+        let mut ret = Vec::new();
+
+        for (os, overrides) in &self.targets {
+            if let Some(name) = overrides
+                .keys()
+                .find(|name| !names.contains(&name.as_str()))
+            {
+                return Err(UnknownOverrideError {
+                    function: name.clone(),
+                    os: os.clone(),
+                });
+            }
+
+            ret.push(build(target_os(os), Some(overrides)));
+        }
+
+        if self.fallback {
+            let cfg = Cfg::not(Cfg::any(self.targets.keys().map(|os| target_os(os))));
+            ret.push(build(cfg, None));
+        }
+
+        Ok(ret)
+    }
+}
+
+/// Error returned when a body override names a function that is not being
+/// generated.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("override of unknown function `{function}` for target OS `{os}`")]
+pub struct UnknownOverrideError {
+    /// Name of the overridden function
+    pub function: String,
+
+    /// Target OS of the override
+    pub os: String,
+}
+
+/// Restricts an item to a target OS, keeping the condition it already has.
+fn restrict(current: &mut Option<Cfg>, cfg: Cfg) {
+    *current = Some(match current.take() {
+        Some(existing) => Cfg::all([cfg, existing]),
+        None => cfg,
+    });
+}
+
+fn target_os(os: &str) -> Cfg {
+    Cfg::raw(format!("target_os = \"{}\"", os))
+}
+
+fn apply(function: &mut Function, overrides: Option<&IndexMap<String, Vec<String>>>) {
+    if let Some(lines) = overrides.and_then(|o| o.get(function.name())) {
+        *function.body_mut() = lines.iter().cloned().map(Body::String).collect();
+    }
+}
@@ -0,0 +1,208 @@
+use alloc::sync::Arc;
+
+use crate::prelude::*;
+use thiserror::Error;
+
+use crate::r#enum::Enum;
+use crate::function::Function;
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::module::Module;
+use crate::rename::Renamer;
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::type_alias::TypeAlias;
+use crate::visit::VisitorMut;
+
+/// Defines an item stamped out many times with different names and types.
+///
+/// Parameters are identifiers replaced wherever they appear in the names of
+/// the item and its types, functions, modules, variants and fields, and in
+/// the type references, generics, bounds and function bodies. Comments in
+/// function bodies are left as is.
+/// The template is shared, so cloning it is cheap, and it is only copied when
+/// instantiated.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Template<T> {
+    item: Arc<T>,
+}
+
+impl<T: TemplateItem> Template<T> {
+    /// Creates a template from the given item.
+    pub fn new(item: T) -> Self {
+        Template {
+            item: Arc::new(item),
+        }
+    }
+
+    /// Gets the templated item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns a copy of the item with the parameters substituted.
+    pub fn instantiate<K, V>(&self, params: impl IntoIterator<Item = (K, V)>) -> T
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let names: IndexMap<String, String> = params
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+
+        let mut item = T::clone(&self.item);
+        if !names.is_empty() {
+            item.accept(&mut Renamer::new(names).with_values());
+        }
+        item
+    }
+}
+
+impl Scope {
+    /// Substitutes the placeholders of a string template and pushes the result
+    /// as a raw item, to mix legacy templates with the builder.
+    ///
+    /// Placeholders are written `{{name}}`, with optional spaces around the
+    /// name, and are replaced by the value of the substitution of the same
+    /// name, verbatim. A backslash escapes a literal `{{`, as in
+    /// `format!("\{{}}")`. Substitutions that the template does not use are
+    /// ignored.
+    ///
+    /// ```
+    /// use simple_codegen::*;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope
+    ///     .push_template("const {{name}}: u32 = {{value}};", [("name", "MAX"), ("value", "10")])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(scope.to_string(), "const MAX: u32 = 10;");
+    /// ```
+    pub fn push_template<K, V>(
+        &mut self,
+        text: impl AsRef<str>,
+        substitutions: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<&mut Self, TemplateError>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let substitutions: IndexMap<String, String> = substitutions
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        let raw = substitute(text.as_ref(), &substitutions)?;
+        Ok(self.raw(raw))
+    }
+}
+
+/// Replaces the placeholders of a template with their substitutions.
+fn substitute(
+    text: &str,
+    substitutions: &IndexMap<String, String>,
+) -> Result<String, TemplateError> {
+    let mut ret = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            ret.push_str(&rest[..start - 1]);
+            ret.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        ret.push_str(&rest[..start]);
+
+        let offset = text.len() - rest.len() + start;
+        let end = rest[start..]
+            .find("}}")
+            .ok_or(TemplateError::UnclosedPlaceholder(offset))?;
+        let name = rest[start + 2..start + end].trim();
+        let value = substitutions
+            .get(name)
+            .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_string()))?;
+        ret.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    ret.push_str(rest);
+    Ok(ret)
+}
+
+/// Error returned when a string template cannot be substituted.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum TemplateError {
+    /// A placeholder has no substitution.
+    #[error("no substitution for placeholder `{0}`")]
+    UnknownPlaceholder(String),
+    /// A placeholder is not closed, at the given byte offset.
+    #[error("unclosed placeholder at offset {0}")]
+    UnclosedPlaceholder(usize),
+}
+
+impl<T: TemplateItem> From<T> for Template<T> {
+    fn from(item: T) -> Self {
+        Template::new(item)
+    }
+}
+
+/// An item that can be stamped out from a [`Template`].
+pub trait TemplateItem: Clone {
+    /// Visits the item with the given visitor.
+    fn accept(&mut self, visitor: &mut dyn VisitorMut);
+}
+
+impl TemplateItem for Scope {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_scope(self);
+    }
+}
+
+impl TemplateItem for Item {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_item(self);
+    }
+}
+
+impl TemplateItem for Module {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_module(self);
+    }
+}
+
+impl TemplateItem for Struct {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_struct(self);
+    }
+}
+
+impl TemplateItem for Enum {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_enum(self);
+    }
+}
+
+impl TemplateItem for Trait {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_trait(self);
+    }
+}
+
+impl TemplateItem for Impl {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_impl(self);
+    }
+}
+
+impl TemplateItem for Function {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_function(self);
+    }
+}
+
+impl TemplateItem for TypeAlias {
+    fn accept(&mut self, visitor: &mut dyn VisitorMut) {
+        visitor.visit_type_alias(self);
+    }
+}
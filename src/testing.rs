@@ -0,0 +1,287 @@
+//! Helpers for testing generated code.
+//!
+//! The [`assert_code_eq!`](crate::assert_code_eq) macro compares generated
+//! code against an expected string, ignoring trailing whitespace and leading
+//! or trailing blank lines, and prints a line-by-line diff on mismatch.
+//!
+//! With the `golden` feature, the [`assert_golden!`](crate::assert_golden)
+//! macro compares generated code against a checked-in golden file instead.
+//! Setting the `SIMPLE_CODEGEN_BLESS` environment variable writes the
+//! generated code to the golden files rather than comparing it.
+
+#[cfg(feature = "golden")]
+use std::path::Path;
+#[cfg(feature = "golden")]
+use std::{env, fs, io};
+
+/// Environment variable enabling the bless mode of golden file tests.
+#[cfg(feature = "golden")]
+pub const BLESS_VAR: &str = "SIMPLE_CODEGEN_BLESS";
+
+/// Asserts that generated code matches the expected code.
+///
+/// Both sides are normalized with [`normalize`](crate::testing::normalize)
+/// before comparison. On mismatch, the panic message contains a line-by-line
+/// diff, with `-` marking expected lines and `+` marking generated lines.
+///
+/// ```rust
+/// use simple_codegen::{Scope, assert_code_eq};
+///
+/// let mut scope = Scope::new();
+/// scope.new_struct("Foo");
+///
+/// assert_code_eq!(scope.to_string(), "
+///     struct Foo;
+/// ".replace("    ", ""));
+/// ```
+#[macro_export]
+macro_rules! assert_code_eq {
+    ($generated:expr, $expected:expr $(,)?) => {
+        if let Err(diff) = $crate::testing::compare(&$generated, &$expected) {
+            panic!("generated code does not match expected code:\n{}", diff);
+        }
+    };
+}
+
+/// Asserts that generated code matches a golden file.
+///
+/// The generated code is anything implementing `Display`, e.g. a `Scope`.
+/// See [`check_golden`](crate::testing::check_golden) for how the file is
+/// compared and updated.
+///
+/// ```rust,no_run
+/// use simple_codegen::{Scope, assert_golden};
+///
+/// let mut scope = Scope::new();
+/// scope.new_struct("Foo");
+///
+/// assert_golden!(scope, "tests/golden/foo.rs");
+/// ```
+#[cfg(feature = "golden")]
+#[macro_export]
+macro_rules! assert_golden {
+    ($generated:expr, $path:expr $(,)?) => {
+        if let Err(err) =
+            $crate::testing::check_golden(::std::string::ToString::to_string(&$generated), &$path)
+        {
+            panic!("{}", err);
+        }
+    };
+}
+
+/// Compares generated code against the golden file at `path`.
+///
+/// Both sides are normalized with [`normalize`] before comparison, and a
+/// line-by-line diff is returned on mismatch. If the `SIMPLE_CODEGEN_BLESS`
+/// environment variable is set to anything but `0`, the golden file is
+/// written with the generated code instead, see [`bless_golden`].
+#[cfg(feature = "golden")]
+pub fn check_golden(generated: impl AsRef<str>, path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+
+    if env::var_os(BLESS_VAR).is_some_and(|v| !v.is_empty() && v != "0") {
+        return bless_golden(generated, path)
+            .map_err(|e| format!("failed to write golden file {}: {}", path.display(), e));
+    }
+
+    let expected = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "failed to read golden file {}: {}\nrerun with {}=1 to create it",
+            path.display(),
+            e,
+            BLESS_VAR
+        )
+    })?;
+
+    compare(generated, expected).map_err(|diff| {
+        format!(
+            "generated code does not match golden file {}:\n{}\nrerun with {}=1 to update it",
+            path.display(),
+            diff,
+            BLESS_VAR
+        )
+    })
+}
+
+/// Writes generated code to the golden file at `path`, normalized and
+/// ending with a newline.
+///
+/// Missing parent directories are created.
+#[cfg(feature = "golden")]
+pub fn bless_golden(generated: impl AsRef<str>, path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut contents = normalize(generated);
+    contents.push('\n');
+    fs::write(path, contents)
+}
+
+/// Normalizes whitespace in code for comparison.
+///
+/// Trailing whitespace is removed from each line, as are leading and
+/// trailing blank lines. Indentation is preserved.
+pub fn normalize(code: impl AsRef<str>) -> String {
+    let lines: Vec<&str> = code.as_ref().lines().map(str::trim_end).collect();
+
+    let start = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let end = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(start, |i| i + 1);
+
+    lines[start..end].join("\n")
+}
+
+/// Compares generated code against expected code after normalization.
+///
+/// Returns a line-by-line diff if they differ.
+pub fn compare(generated: impl AsRef<str>, expected: impl AsRef<str>) -> Result<(), String> {
+    let generated = normalize(generated);
+    let expected = normalize(expected);
+
+    if generated == expected {
+        Ok(())
+    } else {
+        Err(diff(&expected, &generated))
+    }
+}
+
+/// Most edits searched for between the differing lines of [`diff`], which
+/// bounds its memory use.
+const MAX_EDITS: usize = 1000;
+
+/// Renders a line-by-line diff from `old` to `new`.
+///
+/// Removed lines are prefixed with `-`, added lines with `+` and unchanged
+/// lines with a space.
+///
+/// The common leading and trailing lines are skipped before searching for
+/// the shortest diff, so a small change in a large file is cheap. When the
+/// remaining lines need more than a thousand edits, they are shown as
+/// removed, then added.
+pub fn diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (old_middle, new_middle) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    let mut ret = String::new();
+    let mut push = |tag: char, line: &str| {
+        ret.push(tag);
+        ret.push_str(line);
+        ret.push('\n');
+    };
+
+    for line in &old[..prefix] {
+        push(' ', line);
+    }
+    match edit_script(old_middle, new_middle) {
+        Some(script) => {
+            for (tag, line) in script {
+                push(tag, line);
+            }
+        }
+        None => {
+            for line in old_middle {
+                push('-', line);
+            }
+            for line in new_middle {
+                push('+', line);
+            }
+        }
+    }
+    for line in &old[old.len() - suffix..] {
+        push(' ', line);
+    }
+
+    ret
+}
+
+/// Finds the shortest edit script from `old` to `new` with Myers' algorithm,
+/// or `None` if it needs more than [`MAX_EDITS`] edits.
+///
+/// Deletions come before insertions, so a changed line reads as its old
+/// version followed by its new one.
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Option<Vec<(char, &'a str)>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = (n + m).min(MAX_EDITS as isize);
+    // `v[k + offset]` is the furthest `x` reached on diagonal `k = x - y`.
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * max as usize + 3];
+    let mut trace = Vec::new();
+
+    let mut found = false;
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let down =
+                k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]);
+            let mut x = if down {
+                v[(k + 1 + offset) as usize]
+            } else {
+                v[(k - 1 + offset) as usize] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[(k + offset) as usize] = x;
+            if x >= n && y >= m {
+                found = true;
+                break 'search;
+            }
+        }
+    }
+    if !found {
+        return None;
+    }
+
+    // Walk the trace back from the end to recover the edits.
+    let mut script = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let down =
+            k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]);
+        let prev_k = if down { k + 1 } else { k - 1 };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            script.push((' ', old[x as usize]));
+        }
+        if d > 0 {
+            if x == prev_x {
+                script.push(('+', new[(y - 1) as usize]));
+            } else {
+                script.push(('-', old[(x - 1) as usize]));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    script.reverse();
+
+    Some(script)
+}
@@ -0,0 +1,261 @@
+use std::str::FromStr;
+
+use proc_macro2::{Delimiter, LexError, Spacing, TokenStream, TokenTree};
+
+use crate::formatter::Formatter;
+use crate::item::Item;
+use crate::scope::Scope;
+
+impl Scope {
+    /// Returns the generated code as a `TokenStream`.
+    ///
+    /// Comments and docs written as `///` are kept as doc attributes, all other
+    /// formatting is lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generated code is not a valid token stream,
+    /// for example because of unbalanced delimiters in a raw item or function
+    /// body.
+    pub fn to_token_stream(&self) -> Result<TokenStream, LexError> {
+        TokenStream::from_str(&self.to_string())
+    }
+
+    /// Pushes the pretty-printed tokens to the scope as a raw item.
+    ///
+    /// This allows embedding code produced with `quote!` or `syn` alongside
+    /// items built with this crate.
+    pub fn push_tokens(&mut self, tokens: TokenStream) -> &mut Self {
+        self.raw(pretty_tokens(tokens))
+    }
+}
+
+impl Item {
+    /// Returns the generated code for this item as a `TokenStream`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generated code is not a valid token stream.
+    pub fn to_token_stream(&self) -> Result<TokenStream, LexError> {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        TokenStream::from_str(&ret)
+    }
+}
+
+/// Prints tokens on a single line with conventional Rust spacing, e.g.
+/// `Vec<&'a str>`.
+#[cfg(feature = "syn")]
+pub(crate) fn print_tokens(tokens: TokenStream) -> String {
+    let mut printer = Printer::new(false);
+    printer.stream(tokens, false);
+    printer.finish()
+}
+
+/// Prints tokens as indented source lines, breaking after statements, braces
+/// and attributes.
+pub(crate) fn pretty_tokens(tokens: TokenStream) -> String {
+    let mut printer = Printer::new(true);
+    printer.stream(tokens, true);
+    printer.finish()
+}
+
+struct Printer {
+    out: String,
+    multiline: bool,
+    indent: usize,
+    newline: bool,
+}
+
+impl Printer {
+    fn new(multiline: bool) -> Self {
+        Printer {
+            out: String::new(),
+            multiline,
+            indent: 0,
+            newline: false,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        let trimmed = self.out.trim_end().len();
+        self.out.truncate(trimmed);
+        self.out
+    }
+
+    /// Writes the pending line break, if any, and the indentation of a new
+    /// line.
+    fn start(&mut self) {
+        if self.newline {
+            self.newline = false;
+            self.break_line();
+        }
+
+        if self.out.is_empty() || self.out.ends_with('\n') {
+            for _ in 0..self.indent {
+                self.out.push_str("    ");
+            }
+        }
+    }
+
+    fn break_line(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn word(&mut self, word: &str) {
+        self.start();
+        if needs_space(&self.out) || self.out.ends_with('}') {
+            self.out.push(' ');
+        }
+        self.out.push_str(word);
+    }
+
+    fn stream(&mut self, tokens: TokenStream, statements: bool) {
+        let mut joint = false;
+        let mut attribute = false;
+        // Whether the last token ends an operand, so that a `<` after it is
+        // a comparison rather than the start of generics
+        let mut operand = false;
+        // Whether the last token is `fn`, whose name takes generics
+        let mut fn_name = false;
+        // Whether the last punct is part of a comparison or shift operator
+        let mut comparison = false;
+        let mut angles = 0usize;
+        let mut tokens = tokens.into_iter().peekable();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    self.start();
+                    if needs_space(&self.out) || self.out.ends_with([')', ']']) {
+                        self.out.push(' ');
+                    }
+
+                    if !self.multiline {
+                        self.out.push_str("{ ");
+                        self.stream(group.stream(), false);
+                        self.out.push_str(" }");
+                    } else if group.stream().is_empty() {
+                        self.out.push_str("{}");
+                    } else {
+                        self.out.push('{');
+                        self.indent += 1;
+                        self.newline = true;
+                        self.stream(group.stream(), true);
+                        self.indent -= 1;
+                        self.newline = false;
+                        self.break_line();
+                        self.start();
+                        self.out.push('}');
+
+                        // Keep `} else`, `},` and `});` together.
+                        self.newline = statements
+                            && match tokens.peek() {
+                                Some(TokenTree::Punct(p)) => !matches!(p.as_char(), ',' | ';'),
+                                Some(TokenTree::Ident(i)) => i != "else",
+                                _ => true,
+                            };
+                    }
+                    joint = false;
+                    operand = false;
+                    fn_name = false;
+                }
+                TokenTree::Group(group) => {
+                    let (open, close) = match group.delimiter() {
+                        Delimiter::Parenthesis => ("(", ")"),
+                        Delimiter::Bracket => ("[", "]"),
+                        _ => ("", ""),
+                    };
+                    self.start();
+                    self.out.push_str(open);
+
+                    let multiline = self.multiline;
+                    self.multiline = false;
+                    self.stream(group.stream(), false);
+                    self.multiline = multiline;
+
+                    self.out.push_str(close);
+                    if attribute && self.multiline {
+                        self.newline = true;
+                    }
+                    attribute = false;
+                    joint = false;
+                    operand = true;
+                    fn_name = false;
+                }
+                TokenTree::Ident(ident) => {
+                    let ident = ident.to_string();
+                    self.word(&ident);
+                    joint = false;
+                    // Type names start in upper case, the rest are values
+                    operand = !fn_name
+                        && !ident.starts_with(char::is_uppercase)
+                        && !matches!(ident.as_str(), "impl" | "for");
+                    fn_name = ident == "fn";
+                }
+                TokenTree::Literal(lit) => {
+                    self.word(&lit.to_string());
+                    joint = false;
+                    operand = true;
+                    fn_name = false;
+                }
+                TokenTree::Punct(punct) => {
+                    let c = punct.as_char();
+                    comparison = match c {
+                        // `<` continues `<<` or follows an operand
+                        '<' if (joint && comparison) || (!joint && operand) => true,
+                        '<' => {
+                            angles += 1;
+                            false
+                        }
+                        '>' if joint && self.out.ends_with(['-', '=']) => false,
+                        '>' if angles > 0 => {
+                            angles -= 1;
+                            false
+                        }
+                        '>' => true,
+                        _ => false,
+                    };
+                    operand = c == '?';
+                    fn_name = false;
+                    let spaced = matches!(c, '=' | '+' | '|')
+                        || (c == '>' && self.out.ends_with('-'))
+                        || comparison;
+
+                    self.start();
+                    if !joint && (spaced || (c == '-' && punct.spacing() == Spacing::Joint)) {
+                        if !self.out.is_empty() && !self.out.ends_with([' ', '\n']) {
+                            self.out.push(' ');
+                        }
+                    } else if c == '\'' && needs_space(&self.out) {
+                        self.out.push(' ');
+                    }
+
+                    self.out.push(c);
+
+                    attribute = c == '#' || (attribute && c == '!');
+                    joint = punct.spacing() == Spacing::Joint;
+                    if self.multiline && statements && !joint && matches!(c, ';' | ',') {
+                        self.newline = true;
+                    } else if !joint
+                        && (spaced || matches!(c, ',' | ';' | ':') && !self.out.ends_with("::"))
+                    {
+                        self.out.push(' ');
+                    }
+                }
+            }
+        }
+
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+    }
+}
+
+/// Returns whether a word printed after `out` must be separated by a space.
+fn needs_space(out: &str) -> bool {
+    out.ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '"' || c == '>')
+}
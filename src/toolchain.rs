@@ -0,0 +1,81 @@
+use core::fmt;
+
+/// Defines the Rust edition the generated code targets.
+///
+/// The edition and the [`RustVersion`] set on a [`Scope`](crate::Scope),
+/// [`Library`](crate::Library) or [`Formatter`](crate::Formatter) select
+/// between constructs that only some toolchains accept.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edition {
+    /// Rust 2015, where trait objects may omit `dyn`
+    Rust2015,
+    /// Rust 2018
+    Rust2018,
+    /// Rust 2021
+    Rust2021,
+    /// Rust 2024
+    #[default]
+    Rust2024,
+}
+
+impl Edition {
+    /// Gets the year of the edition, as written in `Cargo.toml`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Edition::Rust2015 => "2015",
+            Edition::Rust2018 => "2018",
+            Edition::Rust2021 => "2021",
+            Edition::Rust2024 => "2024",
+        }
+    }
+}
+
+impl fmt::Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Defines the oldest Rust version the generated code must compile with,
+/// e.g. the `rust-version` of `Cargo.toml`.
+///
+/// Without a version, the code may use any stable construct.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RustVersion {
+    major: u32,
+    minor: u32,
+}
+
+impl RustVersion {
+    /// `async fn` in traits and `impl Trait` in their return types.
+    pub(crate) const ASYNC_FN_IN_TRAIT: RustVersion = RustVersion::new(1, 75);
+
+    /// `let ... else` statements.
+    pub(crate) const LET_ELSE: RustVersion = RustVersion::new(1, 65);
+
+    /// The `dyn` keyword of trait objects.
+    pub(crate) const DYN: RustVersion = RustVersion::new(1, 27);
+
+    /// Creates a version, e.g. `RustVersion::new(1, 70)` for Rust 1.70.
+    pub const fn new(major: u32, minor: u32) -> Self {
+        RustVersion { major, minor }
+    }
+
+    /// Gets the major version.
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// Gets the minor version.
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+}
+
+impl fmt::Display for RustVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
@@ -1,25 +1,39 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
+
+use thiserror::Error;
 
 use crate::associated_const::AssociatedConst;
 use crate::associated_type::AssociatedType;
+use crate::async_style::AsyncTraitStyle;
+use crate::attribute::Attribute;
 use crate::bound::Bound;
 use crate::doc::Doc;
-use crate::formatter::{Formatter, fmt_bound_rhs};
+use crate::formatter::{Formatter, fmt_bound_rhs, fmt_bounds_terminated};
 use crate::function::Function;
 use crate::generic_parameter::GenericParameter;
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::scope::Scope;
 use crate::r#type::Type;
 use crate::type_def::TypeDef;
 use crate::visibility::Vis;
 
 /// Defines a [trait](https://doc.rust-lang.org/book/ch10-02-traits.html).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Trait {
     type_def: TypeDef,
     parents: Vec<Type>,
     associated_consts: Vec<AssociatedConst>,
-    attributes: Vec<String>,
+    attributes: Vec<Attribute>,
     associated_types: Vec<AssociatedType>,
     functions: Vec<Function>,
+    async_style: AsyncTraitStyle,
+    r#unsafe: bool,
+    auto: bool,
+    alias: bool,
 }
 
 impl Trait {
@@ -32,6 +46,10 @@ impl Trait {
             attributes: Vec::new(),
             associated_types: Vec::new(),
             functions: Vec::new(),
+            async_style: AsyncTraitStyle::default(),
+            r#unsafe: false,
+            auto: false,
+            alias: false,
         }
     }
 
@@ -42,7 +60,7 @@ impl Trait {
 
     /// Sets the name of the trait.
     pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
-        self.type_def.ty_mut().set_name(name);
+        self.type_def.ty_mut().set_name(name.into());
         self
     }
 
@@ -58,7 +76,7 @@ impl Trait {
     }
 
     /// Gets the trait visibility.
-    pub fn vis(&mut self) -> &Vis {
+    pub fn vis(&self) -> &Vis {
         self.type_def.vis()
     }
 
@@ -80,14 +98,14 @@ impl Trait {
     }
 
     /// Gets the attributes.
-    pub fn attributes(&self) -> &[String] {
+    pub fn attributes(&self) -> &[Attribute] {
         &self.attributes
     }
 
     /// Sets the attributes.
     pub fn set_attributes<S>(&mut self, attributes: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.attributes = attributes.into_iter().map(Into::into).collect();
         self
@@ -96,25 +114,25 @@ impl Trait {
     /// Sets the attributes.
     pub fn with_attributes<S>(mut self, attributes: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_attributes(attributes);
         self
     }
 
     /// Gets a mutable reference to the attributes.
-    pub fn attributes_mut(&mut self) -> &mut Vec<String> {
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.attributes
     }
 
     /// Pushes an attribute.
-    pub fn push_attribute(&mut self, attr: impl Into<String>) -> &mut Self {
+    pub fn push_attribute(&mut self, attr: impl Into<Attribute>) -> &mut Self {
         self.attributes.push(attr.into());
         self
     }
 
     /// Pushes an attribute.
-    pub fn with_attribute(&mut self, attr: impl Into<String>) -> &mut Self {
+    pub fn with_attribute(mut self, attr: impl Into<Attribute>) -> Self {
         self.push_attribute(attr);
         self
     }
@@ -134,7 +152,7 @@ impl Trait {
     }
 
     /// Sets the generics.
-    pub fn with_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
+    pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
     {
@@ -200,14 +218,14 @@ impl Trait {
     }
 
     /// Gets the macros.
-    pub fn macros(&self) -> &[String] {
+    pub fn macros(&self) -> &[Attribute] {
         self.type_def.macros()
     }
 
     /// Sets the macros.
     pub fn set_macros<S>(&mut self, macros: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.type_def.set_macros(macros);
         self
@@ -216,25 +234,28 @@ impl Trait {
     /// Sets the macros.
     pub fn with_macros<S>(mut self, macros: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_macros(macros);
         self
     }
 
     /// Gets a mutable reference to the macros.
-    pub fn macros_mut(&mut self) -> &mut Vec<String> {
+    pub fn macros_mut(&mut self) -> &mut Vec<Attribute> {
         self.type_def.macros_mut()
     }
 
-    /// Pushes a macro to the trait def (e.g. `"#[async_trait]"`).
-    pub fn push_macro(&mut self, r#macro: impl Into<String>) -> &mut Self {
+    /// Pushes an attribute macro to the trait def (e.g. `"#[async_trait]"`).
+    ///
+    /// Macros are written after the other attributes, in the order they are
+    /// pushed; duplicates are skipped.
+    pub fn push_macro(&mut self, r#macro: impl Into<Attribute>) -> &mut Self {
         self.type_def.push_macro(r#macro.into());
         self
     }
 
-    /// Pushes a macro to the trait def (e.g. `"#[async_trait]"`).
-    pub fn with_macro(mut self, r#macro: impl Into<String>) -> Self {
+    /// Pushes an attribute macro to the trait def (e.g. `"#[async_trait]"`).
+    pub fn with_macro(mut self, r#macro: impl Into<Attribute>) -> Self {
         self.push_macro(r#macro);
         self
     }
@@ -274,13 +295,45 @@ impl Trait {
     }
 
     /// Pushes a parent trait.
-    pub fn with_parent(&mut self, parent: impl Into<Type>) -> &mut Self {
+    pub fn with_parent(mut self, parent: impl Into<Type>) -> Self {
         self.push_parent(parent);
         self
     }
 
+    /// Seals the trait, so it can only be implemented by the given types.
+    ///
+    /// This pushes `sealed::Sealed` as a parent trait and adds the private
+    /// `sealed` module to `scope`, the scope the trait is pushed to. The
+    /// module declares the `Sealed` trait and implements it for each of
+    /// `types`, which are resolved from the parent module. Traits sealed in
+    /// the same scope share the module, so each type is only implemented
+    /// once.
+    pub fn seal<T>(&mut self, scope: &mut Scope, types: impl IntoIterator<Item = T>) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.push_parent("sealed::Sealed");
+
+        let module = scope.get_or_new_module("sealed");
+        module.push_import("super", "*", Vis::Private);
+        if module.get_trait("Sealed").is_none() {
+            module.new_trait("Sealed").set_vis(Vis::Pub);
+        }
+        for ty in types {
+            let ty = ty.into();
+            let sealed = module.scope().items().iter().any(|item| {
+                matches!(item, Item::Impl(v) if *v.target() == ty
+                    && v.impl_trait().is_some_and(|t| t.name() == "Sealed"))
+            });
+            if !sealed {
+                module.push_impl(Impl::new(ty).with_impl_trait("Sealed"));
+            }
+        }
+        self
+    }
+
     /// Gets the trait documentation.
-    pub fn doc(&mut self) -> Option<&Doc> {
+    pub fn doc(&self) -> Option<&Doc> {
         self.type_def.doc()
     }
 
@@ -318,6 +371,7 @@ impl Trait {
         C: Into<AssociatedConst>,
     {
         self.associated_consts = associated_consts.into_iter().map(Into::into).collect();
+        self.assert_items_allowed();
         self
     }
 
@@ -339,11 +393,16 @@ impl Trait {
     }
 
     /// Pushes an associated const.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait is a [trait alias](#method.set_alias).
     pub fn push_associated_const(
         &mut self,
         associated_const: impl Into<AssociatedConst>,
     ) -> &mut Self {
         self.associated_consts.push(associated_const.into());
+        self.assert_items_allowed();
         self
     }
 
@@ -367,6 +426,7 @@ impl Trait {
         T: Into<AssociatedType>,
     {
         self.associated_types = associated_types.into_iter().map(Into::into).collect();
+        self.assert_items_allowed();
         self
     }
 
@@ -388,19 +448,21 @@ impl Trait {
     }
 
     /// Pushes an associated type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait is a [trait alias](#method.set_alias).
     pub fn push_associated_type(
         &mut self,
         associated_type: impl Into<AssociatedType>,
     ) -> &mut Self {
         self.associated_types.push(associated_type.into());
+        self.assert_items_allowed();
         self
     }
 
     /// Pushes an associated type.
-    pub fn with_associated_type(
-        &mut self,
-        associated_type: impl Into<AssociatedType>,
-    ) -> &mut Self {
+    pub fn with_associated_type(mut self, associated_type: impl Into<AssociatedType>) -> Self {
         self.push_associated_type(associated_type);
         self
     }
@@ -416,6 +478,7 @@ impl Trait {
         F: Into<Function>,
     {
         self.functions = functions.into_iter().map(Into::into).collect();
+        self.assert_items_allowed();
         self
     }
 
@@ -434,8 +497,13 @@ impl Trait {
     }
 
     /// Pushes a function definition.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trait is a [trait alias](#method.set_alias).
     pub fn push_function(&mut self, function: impl Into<Function>) -> &mut Self {
         self.functions.push(function.into());
+        self.assert_items_allowed();
         self
     }
 
@@ -445,13 +513,233 @@ impl Trait {
         self
     }
 
+    /// Gets the `#[must_use]` attribute of the trait: `None` if it is not
+    /// `#[must_use]`, otherwise the optional reason.
+    pub fn must_use(&self) -> Option<Option<&str>> {
+        self.type_def.must_use()
+    }
+
+    /// Marks the trait as `#[must_use]`, with an optional reason, e.g.
+    /// `#[must_use = "reason"]`.
+    pub fn set_must_use(&mut self, reason: Option<&str>) -> &mut Self {
+        self.type_def.set_must_use(reason);
+        self
+    }
+
+    /// Marks the trait as `#[must_use]`, with an optional reason.
+    pub fn with_must_use(mut self, reason: Option<&str>) -> Self {
+        self.set_must_use(reason);
+        self
+    }
+
+    /// Gets a mutable reference to the `#[must_use]` attribute of the trait.
+    pub fn must_use_mut(&mut self) -> &mut Option<Option<String>> {
+        self.type_def.must_use_mut()
+    }
+
+    /// Gets whether the trait is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.type_def.is_compact()
+    }
+
+    /// Sets whether the trait is rendered without a blank line before it.
+    ///
+    /// Use it to keep related marker traits together.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.type_def.set_compact(compact);
+        self
+    }
+
+    /// Sets whether the trait is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the trait is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        self.type_def.compact_mut()
+    }
+
+    /// Gets where the trait was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.type_def.provenance()
+    }
+
+    /// Sets where the trait was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.type_def.set_provenance(provenance);
+        self
+    }
+
+    /// Sets where the trait was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the trait was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        self.type_def.provenance_mut()
+    }
+
+    /// Gets how the async functions of the trait are emitted.
+    pub fn async_style(&self) -> AsyncTraitStyle {
+        self.async_style
+    }
+
+    /// Sets how the async functions of the trait are emitted.
+    ///
+    /// Impl blocks of the trait should use the same style.
+    pub fn set_async_style(&mut self, async_style: AsyncTraitStyle) -> &mut Self {
+        self.async_style = async_style;
+        self
+    }
+
+    /// Sets how the async functions of the trait are emitted.
+    pub fn with_async_style(mut self, async_style: AsyncTraitStyle) -> Self {
+        self.set_async_style(async_style);
+        self
+    }
+
+    /// Gets a mutable reference to how the async functions of the trait are
+    /// emitted.
+    pub fn async_style_mut(&mut self) -> &mut AsyncTraitStyle {
+        &mut self.async_style
+    }
+
+    /// Gets whether the trait is an `unsafe trait`.
+    pub fn is_unsafe(&self) -> bool {
+        self.r#unsafe
+    }
+
+    /// Sets whether the trait is an `unsafe trait`.
+    ///
+    /// Impl blocks of an unsafe trait need [`Impl::set_unsafe`](crate::Impl::set_unsafe).
+    pub fn set_unsafe(&mut self, r#unsafe: bool) -> &mut Self {
+        self.r#unsafe = r#unsafe;
+        self
+    }
+
+    /// Sets whether the trait is an `unsafe trait`.
+    pub fn with_unsafe(mut self, r#unsafe: bool) -> Self {
+        self.set_unsafe(r#unsafe);
+        self
+    }
+
+    /// Gets a mutable reference to whether the trait is an `unsafe trait`.
+    pub fn unsafe_mut(&mut self) -> &mut bool {
+        &mut self.r#unsafe
+    }
+
+    /// Gets whether the trait is an `auto trait`.
+    pub fn is_auto(&self) -> bool {
+        self.auto
+    }
+
+    /// Sets whether the trait is an `auto trait`.
+    ///
+    /// Auto traits are unstable and need the `auto_traits` feature.
+    pub fn set_auto(&mut self, auto: bool) -> &mut Self {
+        self.auto = auto;
+        self
+    }
+
+    /// Sets whether the trait is an `auto trait`.
+    pub fn with_auto(mut self, auto: bool) -> Self {
+        self.set_auto(auto);
+        self
+    }
+
+    /// Gets a mutable reference to whether the trait is an `auto trait`.
+    pub fn auto_mut(&mut self) -> &mut bool {
+        &mut self.auto
+    }
+
+    /// Gets whether the trait is a trait alias.
+    pub fn is_alias(&self) -> bool {
+        self.alias
+    }
+
+    /// Sets whether the trait is a trait alias, e.g.
+    /// `trait Alias = Foo + Send;`, standing for its parent traits.
+    ///
+    /// Trait aliases are unstable and need the `trait_alias` feature.
+    ///
+    /// # Panics
+    ///
+    /// Trait aliases cannot have associated items or functions, so this
+    /// panics if the trait has any, and pushing one to an alias panics as
+    /// well. See [`try_set_alias`](#method.try_set_alias) for a fallible
+    /// version.
+    pub fn set_alias(&mut self, alias: bool) -> &mut Self {
+        if let Err(e) = self.try_set_alias(alias) {
+            panic!("{}", e);
+        }
+        self
+    }
+
+    /// Sets whether the trait is a trait alias, or returns an error if the
+    /// trait has associated items or functions.
+    pub fn try_set_alias(&mut self, alias: bool) -> Result<&mut Self, TraitAliasError> {
+        if alias && self.has_items() {
+            return Err(TraitAliasError {
+                name: self.name().to_string(),
+            });
+        }
+        self.alias = alias;
+        Ok(self)
+    }
+
+    fn has_items(&self) -> bool {
+        !self.associated_consts.is_empty()
+            || !self.associated_types.is_empty()
+            || !self.functions.is_empty()
+    }
+
+    fn assert_items_allowed(&self) {
+        if self.alias && self.has_items() {
+            panic!(
+                "{}",
+                TraitAliasError {
+                    name: self.name().to_string(),
+                }
+            );
+        }
+    }
+
+    /// Sets whether the trait is a trait alias.
+    pub fn with_alias(mut self, alias: bool) -> Self {
+        self.set_alias(alias);
+        self
+    }
+
+    /// Gets a mutable reference to whether the trait is a trait alias.
+    pub fn alias_mut(&mut self) -> &mut bool {
+        &mut self.alias
+    }
+
     /// Formats the trait using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for attr in &self.attributes {
             writeln!(fmt, "#[{}]", attr)?;
         }
 
-        self.type_def.fmt_head("trait", &self.parents, fmt)?;
+        if let Some(attr) = self.async_style.attribute() {
+            writeln!(fmt, "{}", attr)?;
+        }
+
+        if self.alias {
+            return self.fmt_alias(fmt);
+        }
+
+        let keyword = match (self.r#unsafe, self.auto) {
+            (true, true) => "unsafe auto trait",
+            (true, false) => "unsafe trait",
+            (false, true) => "auto trait",
+            (false, false) => "trait",
+        };
+        self.type_def.fmt_head(keyword, &self.parents, fmt)?;
 
         fmt.block(|fmt| {
             let assoc_csts = &self.associated_consts;
@@ -460,7 +748,9 @@ impl Trait {
             // Format associated consts
             if !assoc_csts.is_empty() {
                 for cst in assoc_csts {
-                    writeln!(fmt, "const {}: {};", cst.name(), cst.ty())?;
+                    write!(fmt, "const {}: ", cst.name())?;
+                    cst.ty().fmt(fmt)?;
+                    writeln!(fmt, ";")?;
                 }
             }
 
@@ -483,11 +773,50 @@ impl Trait {
                 if i != 0 || !assoc_tys.is_empty() || !assoc_csts.is_empty() {
                     writeln!(fmt)?;
                 }
+                fmt.fmt_provenance("fn", Some(func.name()), func.provenance())?;
 
-                func.fmt(true, fmt)?;
+                let style = fmt.async_style(self.async_style);
+                func.desugar_async_with(style, fmt).fmt(true, fmt)?;
             }
 
             Ok(())
         })
     }
+
+    /// Formats the trait as a trait alias.
+    ///
+    /// Items can only have been added through the `_mut` accessors; they
+    /// have no place in an alias and are not written.
+    fn fmt_alias(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.type_def.fmt_head_without_bounds("trait", &[], fmt)?;
+        write!(fmt, " = ")?;
+        for (i, ty) in self.parents.iter().enumerate() {
+            if i != 0 {
+                write!(fmt, " + ")?;
+            }
+            ty.fmt(fmt)?;
+        }
+
+        if self.type_def.bounds().is_empty() {
+            writeln!(fmt, ";")
+        } else {
+            fmt_bounds_terminated(self.type_def.bounds(), ";", fmt)
+        }
+    }
+}
+
+/// Error returned when a trait alias would have associated items or
+/// functions.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+#[error("trait alias `{name}` cannot have items")]
+pub struct TraitAliasError {
+    /// Name of the trait
+    name: String,
+}
+
+impl TraitAliasError {
+    /// Gets the name of the trait.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
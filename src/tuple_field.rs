@@ -0,0 +1,181 @@
+use crate::attribute::{Attribute, push_unique};
+use crate::doc::Doc;
+use crate::prelude::*;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// Defines a field of a tuple struct or tuple variant.
+///
+/// Any type converts into a tuple field, so `"String"` can be used where a
+/// private field without attributes is wanted.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TupleField {
+    /// Field type
+    ty: Type,
+
+    /// Field documentation
+    doc: Option<Doc>,
+
+    /// Field annotations, e.g. `#[serde(default)]`
+    annotations: Vec<Attribute>,
+
+    /// The visibility of the field
+    vis: Vis,
+
+    /// Whether the value of the field must not be printed
+    sensitive: bool,
+}
+
+impl<T: Into<Type>> From<T> for TupleField {
+    fn from(ty: T) -> Self {
+        TupleField::new(ty)
+    }
+}
+
+impl TupleField {
+    /// Creates a private tuple field of the provided type.
+    pub fn new(ty: impl Into<Type>) -> Self {
+        TupleField {
+            ty: ty.into(),
+            doc: None,
+            annotations: Vec::new(),
+            vis: Vis::Private,
+            sensitive: false,
+        }
+    }
+
+    /// Gets the type of the field.
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+
+    /// Sets the type of the field.
+    pub fn set_ty(&mut self, ty: impl Into<Type>) -> &mut Self {
+        self.ty = ty.into();
+        self
+    }
+
+    /// Sets the type of the field.
+    pub fn with_ty(mut self, ty: impl Into<Type>) -> Self {
+        self.set_ty(ty);
+        self
+    }
+
+    /// Gets a mutable reference to the type of the field.
+    pub fn ty_mut(&mut self) -> &mut Type {
+        &mut self.ty
+    }
+
+    /// Gets the documentation for the field.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// Sets the field documentation.
+    pub fn set_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the field documentation.
+    pub fn with_doc(mut self, doc: impl Into<String>) -> Self {
+        self.set_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the field documentation.
+    pub fn doc_mut(&mut self) -> Option<&mut Doc> {
+        self.doc.as_mut()
+    }
+
+    /// Gets the annotations for the field.
+    pub fn annotations(&self) -> &[Attribute] {
+        &self.annotations
+    }
+
+    /// Sets the annotations for the field, skipping duplicates.
+    pub fn set_annotations<S>(&mut self, annotations: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: Into<Attribute>,
+    {
+        self.annotations.clear();
+        for annotation in annotations {
+            self.push_annotation(annotation);
+        }
+        self
+    }
+
+    /// Sets the annotations for the field, skipping duplicates.
+    pub fn with_annotations<S>(mut self, annotations: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<Attribute>,
+    {
+        self.set_annotations(annotations);
+        self
+    }
+
+    /// Gets a mutable reference to the annotations for the field.
+    pub fn annotations_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.annotations
+    }
+
+    /// Pushes a single annotation, e.g. `#[serde(default)]` or a
+    /// [`SerdeAttr`](crate::SerdeAttr). An annotation equal to an existing
+    /// one has no effect.
+    pub fn push_annotation(&mut self, annotation: impl Into<Attribute>) -> &mut Self {
+        push_unique(&mut self.annotations, annotation.into());
+        self
+    }
+
+    /// Pushes a single annotation, e.g. `#[serde(default)]` or a
+    /// [`SerdeAttr`](crate::SerdeAttr).
+    pub fn with_annotation(mut self, annotation: impl Into<Attribute>) -> Self {
+        self.push_annotation(annotation);
+        self
+    }
+
+    /// Gets the visibility of the field.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility of the field.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility of the field.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets a mutable reference to the visibility of the field.
+    pub fn vis_mut(&mut self) -> &mut Vis {
+        &mut self.vis
+    }
+    /// Returns whether the field is sensitive.
+    pub fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Sets whether the field is sensitive, e.g. a secret key.
+    ///
+    /// The value of a sensitive field is printed as `"***"` by the `Debug`
+    /// impl generated with [`Struct::redacted_debug_impl`](crate::Struct::redacted_debug_impl).
+    pub fn set_sensitive(&mut self, sensitive: bool) -> &mut Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Sets whether the field is sensitive.
+    pub fn with_sensitive(mut self, sensitive: bool) -> Self {
+        self.set_sensitive(sensitive);
+        self
+    }
+}
@@ -1,44 +1,402 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
-use crate::formatter::Formatter;
+use crate::formatter::{Formatter, fmt_generic_list};
 use crate::generic_parameter::GenericParameter;
+use crate::prelude::*;
+use crate::symbol::Symbol;
+use crate::toolchain::{Edition, RustVersion};
 
 /// Defines a type.
+///
+/// Most types are a path with generics, e.g. `Vec<T>`. References, slices,
+/// arrays, tuples, `impl Trait`, `dyn Trait` and function pointers can be
+/// built from other types with the matching constructors, e.g.
+/// `Type::reference("[u8]")`. They are named as written, e.g. `&[u8]`, and
+/// have no generics of their own. Wrappers such as [`Type::option`] keep their
+/// type arguments as types too, so visitors can reach them.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Type {
-    name: String,
+    name: Symbol,
     generics: Vec<GenericParameter>,
+    kind: TypeKind,
+}
+
+/// Defines the shape of a [`Type`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TypeKind {
+    /// A path with generics, e.g. `Vec<T>`.
+    Path,
+    /// A path with type arguments that are types themselves, e.g.
+    /// `Option<T>` built by [`Type::option`]. The path is the type's name.
+    Applied(Vec<Type>),
+    /// A reference, e.g. `&'a mut T`.
+    Reference {
+        /// The lifetime, including the leading `'`
+        lifetime: Option<String>,
+        /// Whether the reference is mutable
+        mutable: bool,
+        /// The referenced type
+        ty: Box<Type>,
+    },
+    /// A slice, e.g. `[T]`.
+    Slice(Box<Type>),
+    /// An array, e.g. `[T; 4]`.
+    Array(Box<Type>, String),
+    /// A tuple, e.g. `(A, B)`.
+    Tuple(Vec<Type>),
+    /// An `impl Trait` type with its bounds, e.g. `impl Iterator<Item = T> + 'a`.
+    ImplTrait(Vec<Type>),
+    /// A trait object with its bounds, e.g. `dyn Fn(A) -> B + Send`.
+    DynTrait(Vec<Type>),
+    /// A function pointer, e.g. `fn(i32) -> i32`.
+    FnPtr {
+        /// The argument types
+        args: Vec<Type>,
+        /// The return type, `None` for `()`
+        ret: Option<Box<Type>>,
+    },
 }
 
 impl Type {
     /// Creates a new type with the given name.
-    pub fn new(name: impl Into<String>) -> Self {
+    pub fn new(name: impl Into<Symbol>) -> Self {
         Type {
             name: name.into(),
             generics: Vec::new(),
+            kind: TypeKind::Path,
+        }
+    }
+
+    fn from_kind(kind: TypeKind) -> Self {
+        let mut ret = Type::new("");
+        ret.set_kind(kind);
+        ret
+    }
+
+    /// Names a type that is not a path after the way it is written, so that
+    /// e.g. impl blocks for `&'a str` can be found by the name of their
+    /// target.
+    pub(crate) fn refresh_name(&mut self) {
+        if !self.is_path() {
+            self.name = Symbol::from(self.to_source());
+        }
+    }
+
+    /// Returns whether the type is a path, with or without type arguments.
+    fn is_path(&self) -> bool {
+        matches!(self.kind, TypeKind::Path | TypeKind::Applied(_))
+    }
+
+    /// Creates a path type with the given type arguments.
+    fn applied<T>(name: &'static str, args: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::new(name).with_kind(TypeKind::Applied(
+            args.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Creates a shared reference to a type, e.g. `&T`.
+    pub fn reference(ty: impl Into<Type>) -> Self {
+        Type::from_kind(TypeKind::Reference {
+            lifetime: None,
+            mutable: false,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Creates a mutable reference to a type, e.g. `&mut T`.
+    pub fn reference_mut(ty: impl Into<Type>) -> Self {
+        Type::from_kind(TypeKind::Reference {
+            lifetime: None,
+            mutable: true,
+            ty: Box::new(ty.into()),
+        })
+    }
+
+    /// Creates a slice of a type, e.g. `[T]`.
+    pub fn slice(ty: impl Into<Type>) -> Self {
+        Type::from_kind(TypeKind::Slice(Box::new(ty.into())))
+    }
+
+    /// Creates an array of a type, e.g. `[T; 4]`.
+    pub fn array(ty: impl Into<Type>, len: impl Into<String>) -> Self {
+        Type::from_kind(TypeKind::Array(Box::new(ty.into()), len.into()))
+    }
+
+    /// Creates a tuple of types, e.g. `(A, B)`.
+    ///
+    /// An empty tuple is the unit type `()`.
+    pub fn tuple<T>(types: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(TypeKind::Tuple(types.into_iter().map(Into::into).collect()))
+    }
+
+    /// Creates an `impl Trait` type from its bounds, e.g. `impl Read + Send`.
+    pub fn impl_trait<T>(bounds: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(TypeKind::ImplTrait(
+            bounds.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Creates a trait object type from its bounds, e.g. `dyn Error + Send`.
+    pub fn dyn_trait<T>(bounds: impl IntoIterator<Item = T>) -> Self
+    where
+        T: Into<Type>,
+    {
+        Type::from_kind(TypeKind::DynTrait(
+            bounds.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Creates a function pointer type, e.g. `fn(i32) -> i32`.
+    ///
+    /// A return type of `()` is left out.
+    pub fn fn_ptr<T>(args: impl IntoIterator<Item = T>, ret: impl Into<Type>) -> Self
+    where
+        T: Into<Type>,
+    {
+        let ret = ret.into();
+        let ret = (!ret.is_unit()).then(|| Box::new(ret));
+        Type::from_kind(TypeKind::FnPtr {
+            args: args.into_iter().map(Into::into).collect(),
+            ret,
+        })
+    }
+
+    /// Creates an `Option` of a type.
+    pub fn option(inner: impl Into<Type>) -> Self {
+        Type::applied("Option", [inner])
+    }
+
+    /// Creates a `Result` of an ok and an error type.
+    pub fn result(ok: impl Into<Type>, err: impl Into<Type>) -> Self {
+        Type::applied("Result", [ok.into(), err.into()])
+    }
+
+    /// Creates a `Vec` of a type.
+    pub fn vec(inner: impl Into<Type>) -> Self {
+        Type::applied("Vec", [inner])
+    }
+
+    /// Creates a `Box` of a type.
+    pub fn boxed(inner: impl Into<Type>) -> Self {
+        Type::applied("Box", [inner])
+    }
+
+    /// Creates an `Arc` of a type, named by its `alloc` path so that it
+    /// needs no import and also compiles in `no_std` crates, which need an
+    /// `extern crate alloc;` like any crate naming `alloc`.
+    pub fn arc(inner: impl Into<Type>) -> Self {
+        Type::applied("alloc::sync::Arc", [inner])
+    }
+
+    /// Creates a `Pin` of a type, named by its `core` path so that it needs
+    /// no import and also compiles in `no_std` crates.
+    pub fn pin(inner: impl Into<Type>) -> Self {
+        Type::applied("core::pin::Pin", [inner])
+    }
+
+    /// Creates a path type from its segments, e.g. `["std", "fmt", "Debug"]`
+    /// for `std::fmt::Debug`.
+    pub fn path<S>(segments: impl IntoIterator<Item = S>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let mut ret = Type::new("");
+        ret.set_segments(segments);
+        ret
+    }
+
+    /// Gets the segments of the path of the type.
+    ///
+    /// Types that are not paths have no segments.
+    pub fn segments(&self) -> Vec<&str> {
+        match self.kind {
+            TypeKind::Path | TypeKind::Applied(_) if !self.name.is_empty() => {
+                self.name.split("::").collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Sets the segments of the path of the type.
+    pub fn set_segments<S>(&mut self, segments: impl IntoIterator<Item = S>) -> &mut Self
+    where
+        S: AsRef<str>,
+    {
+        let segments: Vec<S> = segments.into_iter().collect();
+        let segments: Vec<&str> = segments.iter().map(AsRef::as_ref).collect();
+        self.set_name(segments.join("::"))
+    }
+
+    /// Sets the segments of the path of the type.
+    pub fn with_segments<S>(mut self, segments: impl IntoIterator<Item = S>) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.set_segments(segments);
+        self
+    }
+
+    /// Pushes a segment to the end of the path of the type.
+    pub fn push_segment(&mut self, segment: impl AsRef<str>) -> &mut Self {
+        let name = match self.name.as_str() {
+            "" => segment.as_ref().to_string(),
+            name => format!("{}::{}", name, segment.as_ref()),
+        };
+        self.set_name(name)
+    }
+
+    /// Removes the leading segments of the path if they match `prefix`, e.g.
+    /// when `std::collections` is imported, and returns whether they did.
+    ///
+    /// The last segment is never removed.
+    pub fn strip_prefix<S>(&mut self, prefix: impl IntoIterator<Item = S>) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let prefix: Vec<S> = prefix.into_iter().collect();
+        let segments = self.segments();
+        let matches = prefix.len() < segments.len()
+            && prefix.iter().zip(&segments).all(|(p, s)| p.as_ref() == *s);
+        if matches {
+            let rest = segments[prefix.len()..].join("::");
+            self.set_name(rest);
+        }
+        matches
+    }
+
+    /// Returns the type as written in an expression, where generics need a
+    /// turbofish, e.g. `HashMap::<K, V>` for a call like `HashMap::<K, V>::new()`.
+    pub fn turbofish(&self) -> String {
+        let mut ret = String::new();
+        let mut fmt = Formatter::new(&mut ret);
+        if self.kind == TypeKind::Path && !self.generics.is_empty() {
+            write!(fmt, "{}::", self.name).unwrap();
+            Type::fmt_slice(&self.generics, &mut fmt).unwrap();
+        } else if let TypeKind::Applied(args) = &self.kind {
+            write!(fmt, "{}::", self.name).unwrap();
+            fmt_generic_list(args, &mut fmt, |ty, fmt| ty.fmt(fmt)).unwrap();
+        } else {
+            self.fmt(&mut fmt).unwrap();
+        }
+        ret
+    }
+
+    /// Gets the shape of the type.
+    pub fn kind(&self) -> &TypeKind {
+        &self.kind
+    }
+
+    /// Sets the shape of the type.
+    ///
+    /// A type that is not a path is renamed after the way it is written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type has generics and the shape is not a path.
+    pub fn set_kind(&mut self, kind: TypeKind) -> &mut Self {
+        assert!(
+            kind == TypeKind::Path || self.generics.is_empty(),
+            "only path types have generics"
+        );
+        self.kind = kind;
+        self.refresh_name();
+        self
+    }
+
+    /// Sets the shape of the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type has generics and the shape is not a path.
+    pub fn with_kind(mut self, kind: TypeKind) -> Self {
+        self.set_kind(kind);
+        self
+    }
+
+    /// Gets a mutable reference to the shape of the type.
+    ///
+    /// Unlike [`set_kind`](#method.set_kind), this does not rename the type
+    /// after its new shape.
+    pub fn kind_mut(&mut self) -> &mut TypeKind {
+        &mut self.kind
+    }
+
+    /// Sets the lifetime of a reference, e.g. `'a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a reference.
+    pub fn set_lifetime(&mut self, lifetime: impl Into<String>) -> &mut Self {
+        let TypeKind::Reference { lifetime: l, .. } = &mut self.kind else {
+            panic!("only references have a lifetime");
+        };
+        let lifetime = lifetime.into();
+        *l = Some(if lifetime.starts_with('\'') {
+            lifetime
+        } else {
+            format!("'{}", lifetime)
+        });
+        self.refresh_name();
+        self
+    }
+
+    /// Sets the lifetime of a reference, e.g. `'a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a reference.
+    pub fn with_lifetime(mut self, lifetime: impl Into<String>) -> Self {
+        self.set_lifetime(lifetime);
+        self
+    }
+
+    /// Returns whether the type is the unit type `()`.
+    pub fn is_unit(&self) -> bool {
+        match &self.kind {
+            TypeKind::Path => self.name.as_str() == "()",
+            TypeKind::Tuple(types) => types.is_empty(),
+            _ => false,
         }
     }
 
     /// Gets the name of the type.
+    ///
+    /// Types that are not paths are named as written, e.g. `&'a str`.
     pub fn name(&self) -> &str {
         &self.name
     }
 
     /// Sets the name of the type.
-    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+    pub fn set_name(&mut self, name: impl Into<Symbol>) -> &mut Self {
         self.name = name.into();
         self
     }
 
     /// Sets the name of the type.
-    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+    pub fn with_name(mut self, name: impl Into<Symbol>) -> Self {
         self.set_name(name);
         self
     }
 
     /// Gets a mutable reference to the name of the type.
     pub fn name_mut(&mut self) -> &mut String {
-        &mut self.name
+        self.name.to_mut()
+    }
+
+    /// Gets the name of the type as a symbol.
+    pub fn symbol(&self) -> &Symbol {
+        &self.name
     }
 
     /// Returns the generics for the type.
@@ -47,15 +405,27 @@ impl Type {
     }
 
     /// Sets the generics for the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if generics are given and the type is not a path, e.g. a
+    /// reference.
     pub fn set_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
     where
         G: Into<GenericParameter>,
     {
-        self.generics = generics.into_iter().map(Into::into).collect();
+        self.generics.clear();
+        for generic in generics {
+            self.push_generic(generic);
+        }
         self
     }
 
     /// Sets the generics for the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if generics are given and the type is not a path.
     pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
@@ -70,46 +440,151 @@ impl Type {
     }
 
     /// Pushes a generic to the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a path, e.g. a reference, whose generics
+    /// would not be written.
     pub fn push_generic(&mut self, generic: impl Into<GenericParameter>) -> &mut Self {
+        assert!(self.kind == TypeKind::Path, "only path types have generics");
         self.generics.push(generic.into());
         self
     }
 
     /// Pushes a generic to the type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type is not a path.
     pub fn with_generic(mut self, generic: impl Into<GenericParameter>) -> Self {
         self.push_generic(generic);
         self
     }
 
-    /// Formats the type using the given formatter.
-    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        write!(fmt, "{}", self.name)?;
-        Type::fmt_slice(&self.generics, fmt)
+    /// Returns the size in bytes of a primitive type on a 64-bit target.
+    ///
+    /// Returns `None` for non-primitive types, or types with generics.
+    pub fn primitive_size(&self) -> Option<usize> {
+        if !self.generics.is_empty() {
+            return None;
+        }
+
+        let size = match self.name.as_str() {
+            "()" => 0,
+            "bool" | "u8" | "i8" => 1,
+            "u16" | "i16" => 2,
+            "u32" | "i32" | "f32" | "char" => 4,
+            "u64" | "i64" | "f64" | "usize" | "isize" => 8,
+            "u128" | "i128" => 16,
+            "String" => 24,
+            _ => return None,
+        };
+
+        Some(size)
     }
 
-    fn fmt_slice(generics: &[GenericParameter], fmt: &mut Formatter<'_>) -> fmt::Result {
-        if !generics.is_empty() {
-            write!(fmt, "<")?;
+    /// Formats the type using the given formatter.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            TypeKind::Path => {
+                write!(fmt, "{}", self.name)?;
+                Type::fmt_slice(&self.generics, fmt)
+            }
+            TypeKind::Applied(args) => {
+                write!(fmt, "{}", self.name)?;
+                fmt_generic_list(args, fmt, |ty, fmt| ty.fmt(fmt))
+            }
+            TypeKind::Reference {
+                lifetime,
+                mutable,
+                ty,
+            } => {
+                write!(fmt, "&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(fmt, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    write!(fmt, "mut ")?;
+                }
 
-            for (i, g) in generics.iter().enumerate() {
-                if i != 0 {
-                    write!(fmt, ", ")?
+                // `&dyn A + B` is ambiguous, so several bounds need parentheses
+                match &ty.kind {
+                    TypeKind::ImplTrait(bounds) | TypeKind::DynTrait(bounds)
+                        if bounds.len() > 1 =>
+                    {
+                        write!(fmt, "(")?;
+                        ty.fmt(fmt)?;
+                        write!(fmt, ")")
+                    }
+                    _ => ty.fmt(fmt),
+                }
+            }
+            TypeKind::Slice(ty) => {
+                write!(fmt, "[")?;
+                ty.fmt(fmt)?;
+                write!(fmt, "]")
+            }
+            TypeKind::Array(ty, len) => {
+                write!(fmt, "[")?;
+                ty.fmt(fmt)?;
+                write!(fmt, "; {}]", len)
+            }
+            TypeKind::Tuple(types) => {
+                write!(fmt, "(")?;
+                Type::fmt_list(types, ", ", fmt)?;
+                if types.len() == 1 {
+                    write!(fmt, ",")?;
                 }
-                g.fmt(fmt)?;
+                write!(fmt, ")")
             }
+            TypeKind::ImplTrait(bounds) => {
+                write!(fmt, "impl ")?;
+                Type::fmt_list(bounds, " + ", fmt)
+            }
+            TypeKind::DynTrait(bounds) => {
+                // Bare trait objects are only allowed in the 2015 edition
+                if fmt.edition() != Edition::Rust2015 || fmt.supports(RustVersion::DYN) {
+                    write!(fmt, "dyn ")?;
+                }
+                Type::fmt_list(bounds, " + ", fmt)
+            }
+            TypeKind::FnPtr { args, ret } => {
+                write!(fmt, "fn(")?;
+                Type::fmt_list(args, ", ", fmt)?;
+                write!(fmt, ")")?;
+                if let Some(ret) = ret {
+                    write!(fmt, " -> ")?;
+                    ret.fmt(fmt)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn to_source(&self) -> String {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        ret
+    }
 
-            write!(fmt, ">")?;
+    fn fmt_list(types: &[Type], separator: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for (i, ty) in types.iter().enumerate() {
+            if i != 0 {
+                write!(fmt, "{}", separator)?;
+            }
+            ty.fmt(fmt)?;
         }
 
         Ok(())
     }
+
+    fn fmt_slice(generics: &[GenericParameter], fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt_generic_list(generics, fmt, |g, fmt| g.fmt(fmt))
+    }
 }
 
-impl<S: Into<String>> From<S> for Type {
+impl<S: Into<Symbol>> From<S> for Type {
     fn from(src: S) -> Self {
-        Type {
-            name: src.into(),
-            generics: Vec::new(),
-        }
+        Type::new(src)
     }
 }
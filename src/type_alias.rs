@@ -1,16 +1,21 @@
 use core::fmt;
-use std::fmt::Write;
+use core::fmt::Write;
 
 use crate::bound::Bound;
 use crate::doc::Doc;
+use crate::formatter::fmt_bounds;
 use crate::generic_parameter::GenericParameter;
 use crate::lint::Lint;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::repr::Repr;
 use crate::type_def::TypeDef;
 use crate::visibility::Vis;
 use crate::{Formatter, Type};
 
 /// Defines a [type alias](https://doc.rust-lang.org/reference/items/type-aliases.html).
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeAlias {
     type_def: TypeDef,
     ty: Type,
@@ -32,7 +37,7 @@ impl TypeAlias {
 
     /// Sets the alias name.
     pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
-        self.type_def.ty_mut().set_name(name);
+        self.type_def.ty_mut().set_name(name.into());
         self
     }
 
@@ -114,7 +119,7 @@ impl TypeAlias {
     }
 
     /// Sets the generics.
-    pub fn with_generics<G>(&mut self, generics: impl IntoIterator<Item = G>) -> &mut Self
+    pub fn with_generics<G>(mut self, generics: impl IntoIterator<Item = G>) -> Self
     where
         G: Into<GenericParameter>,
     {
@@ -134,7 +139,7 @@ impl TypeAlias {
     }
 
     /// Pushes a generic.
-    pub fn with_generic(&mut self, generic: impl Into<String>) -> &mut Self {
+    pub fn with_generic(mut self, generic: impl Into<String>) -> Self {
         self.push_generic(generic);
         self
     }
@@ -287,24 +292,24 @@ impl TypeAlias {
         self
     }
 
-    /// Gets the representation.
-    pub fn repr(&self) -> Option<&String> {
+    /// Gets the representation hints.
+    pub fn repr(&self) -> &[Repr] {
         self.type_def.repr()
     }
 
-    /// Sets the representation.
-    pub fn set_repr<S>(&mut self, repr: impl Into<Option<S>>) -> &mut Self
+    /// Sets the representation hints.
+    pub fn set_repr<R>(&mut self, repr: impl IntoIterator<Item = R>) -> &mut Self
     where
-        S: Into<String>,
+        R: Into<Repr>,
     {
         self.type_def.set_repr(repr);
         self
     }
 
-    /// Sets the representation.
-    pub fn with_repr<S>(mut self, repr: impl Into<Option<S>>) -> Self
+    /// Sets the representation hints.
+    pub fn with_repr<R>(mut self, repr: impl IntoIterator<Item = R>) -> Self
     where
-        S: Into<String>,
+        R: Into<Repr>,
     {
         self.set_repr(repr);
         self
@@ -332,12 +337,66 @@ impl TypeAlias {
         &mut self.ty
     }
 
+    /// Gets whether the type alias is rendered without a blank line before it.
+    pub fn is_compact(&self) -> bool {
+        self.type_def.is_compact()
+    }
+
+    /// Sets whether the type alias is rendered without a blank line before it.
+    ///
+    /// Use it to keep a run of related aliases together, e.g. the
+    /// `Result` and id aliases of a module.
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.type_def.set_compact(compact);
+        self
+    }
+
+    /// Sets whether the type alias is rendered without a blank line before it.
+    pub fn with_compact(mut self, compact: bool) -> Self {
+        self.set_compact(compact);
+        self
+    }
+
+    /// Gets a mutable reference to whether the type alias is compact.
+    pub fn compact_mut(&mut self) -> &mut bool {
+        self.type_def.compact_mut()
+    }
+
+    /// Gets where the type alias was generated from.
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.type_def.provenance()
+    }
+
+    /// Sets where the type alias was generated from, e.g. a schema file and line.
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.type_def.set_provenance(provenance);
+        self
+    }
+
+    /// Sets where the type alias was generated from, e.g. a schema file and line.
+    pub fn with_provenance(mut self, provenance: impl Into<Provenance>) -> Self {
+        self.set_provenance(provenance);
+        self
+    }
+
+    /// Gets a mutable reference to where the type alias was generated from.
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        self.type_def.provenance_mut()
+    }
+
     /// Formats the type alias using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        self.type_def.fmt_head("type", &[], fmt)?;
-        write!(fmt, " = ")?;
+        self.type_def.fmt_head_without_bounds("type", &[], fmt)?;
+
+        // The `where` clause of a type alias goes before the aliased type,
+        // which starts its own line after it
+        if self.bounds().is_empty() {
+            write!(fmt, " = ")?;
+        } else {
+            fmt_bounds(self.bounds(), fmt)?;
+            write!(fmt, "= ")?;
+        }
         self.ty.fmt(fmt)?;
-        write!(fmt, ";")?;
-        Ok(())
+        writeln!(fmt, ";")
     }
 }
@@ -1,24 +1,37 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::attribute::{Attribute, push_unique};
 use crate::bound::Bound;
+use crate::comment::Comment;
+use crate::derive::DeriveOrder;
 use crate::doc::Doc;
-use crate::formatter::{Formatter, fmt_bounds};
+use crate::formatter::{Formatter, fmt_bounds, fmt_list, fmt_must_use};
 use crate::lint::Lint;
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::repr::Repr;
 use crate::r#type::Type;
 use crate::visibility::Vis;
 
 /// Defines a type definition.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeDef {
     ty: Type,
     vis: Vis,
+    comment: Option<Comment>,
     doc: Option<Doc>,
     derives: Vec<String>,
+    derive_order: DeriveOrder,
     lints: Vec<Lint>,
-    attributes: Vec<String>,
-    repr: Option<String>,
+    attributes: Vec<Attribute>,
+    repr: Vec<Repr>,
+    non_exhaustive: bool,
+    must_use: Option<Option<String>>,
     bounds: Vec<Bound>,
-    macros: Vec<String>,
+    macros: Vec<Attribute>,
+    compact: bool,
+    provenance: Option<Provenance>,
 }
 
 impl TypeDef {
@@ -27,13 +40,19 @@ impl TypeDef {
         TypeDef {
             ty: Type::new(name.into()),
             vis: Vis::Private,
+            comment: None,
             doc: None,
             derives: Vec::new(),
+            derive_order: DeriveOrder::default(),
             lints: Vec::new(),
             attributes: Vec::new(),
-            repr: None,
+            repr: Vec::new(),
+            non_exhaustive: false,
+            must_use: None,
             bounds: Vec::new(),
             macros: Vec::new(),
+            compact: false,
+            provenance: None,
         }
     }
 
@@ -89,6 +108,22 @@ impl TypeDef {
         self.doc.as_mut()
     }
 
+    pub fn comment(&self) -> Option<&Comment> {
+        self.comment.as_ref()
+    }
+
+    pub fn set_comment<C>(&mut self, comment: impl Into<Option<C>>) -> &mut Self
+    where
+        C: Into<Comment>,
+    {
+        self.comment = comment.into().map(Into::into);
+        self
+    }
+
+    pub fn comment_mut(&mut self) -> Option<&mut Comment> {
+        self.comment.as_mut()
+    }
+
     pub fn bounds(&self) -> &[Bound] {
         &self.bounds
     }
@@ -123,47 +158,53 @@ impl TypeDef {
         self
     }
 
-    pub fn macros(&self) -> &[String] {
+    pub fn macros(&self) -> &[Attribute] {
         &self.macros
     }
 
     pub fn set_macros<S>(&mut self, macros: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
-        self.macros = macros.into_iter().map(Into::into).collect();
+        self.macros.clear();
+        for r#macro in macros {
+            self.push_macro(r#macro);
+        }
         self
     }
 
     pub fn with_macros<S>(mut self, macros: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_macros(macros);
         self
     }
 
-    pub fn macros_mut(&mut self) -> &mut Vec<String> {
+    pub fn macros_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.macros
     }
 
-    pub fn push_macro(&mut self, r#macro: impl Into<String>) -> &mut Self {
-        self.macros.push(r#macro.into());
+    pub fn push_macro(&mut self, r#macro: impl Into<Attribute>) -> &mut Self {
+        let r#macro = r#macro.into();
+        if !self.attributes.contains(&r#macro) {
+            push_unique(&mut self.macros, r#macro);
+        }
         self
     }
 
-    pub fn with_macro(mut self, r#macro: impl Into<String>) -> Self {
+    pub fn with_macro(mut self, r#macro: impl Into<Attribute>) -> Self {
         self.push_macro(r#macro);
         self
     }
 
-    pub fn attributes(&self) -> &[String] {
+    pub fn attributes(&self) -> &[Attribute] {
         &self.attributes
     }
 
     pub fn set_attributes<S>(&mut self, attributes: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.attributes = attributes.into_iter().map(Into::into).collect();
         self
@@ -171,22 +212,22 @@ impl TypeDef {
 
     pub fn with_attributes<S>(mut self, attributes: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_attributes(attributes);
         self
     }
 
-    pub fn attributes_mut(&mut self) -> &mut Vec<String> {
+    pub fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.attributes
     }
 
-    pub fn push_attribute(&mut self, attribute: impl Into<String>) -> &mut Self {
+    pub fn push_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
         self.attributes.push(attribute.into());
         self
     }
 
-    pub fn with_attribute(mut self, attribute: impl Into<String>) -> Self {
+    pub fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
         self.push_attribute(attribute);
         self
     }
@@ -225,6 +266,20 @@ impl TypeDef {
         self
     }
 
+    pub fn derive_order(&self) -> DeriveOrder {
+        self.derive_order
+    }
+
+    pub fn set_derive_order(&mut self, derive_order: DeriveOrder) -> &mut Self {
+        self.derive_order = derive_order;
+        self
+    }
+
+    pub fn with_derive_order(mut self, derive_order: DeriveOrder) -> Self {
+        self.set_derive_order(derive_order);
+        self
+    }
+
     pub fn lints(&self) -> &[Lint] {
         &self.lints
     }
@@ -259,25 +314,95 @@ impl TypeDef {
         self
     }
 
-    pub fn repr(&self) -> Option<&String> {
-        self.repr.as_ref()
+    pub fn repr(&self) -> &[Repr] {
+        &self.repr
     }
 
-    pub fn set_repr<S>(&mut self, repr: impl Into<Option<S>>) -> &mut Self
+    pub fn set_repr<R>(&mut self, repr: impl IntoIterator<Item = R>) -> &mut Self
     where
-        S: Into<String>,
+        R: Into<Repr>,
     {
-        self.repr = repr.into().map(Into::into);
+        self.repr = repr.into_iter().map(Into::into).collect();
         self
     }
 
-    pub fn with_repr(mut self, repr: impl Into<Option<String>>) -> Self {
+    pub fn with_repr<R>(mut self, repr: impl IntoIterator<Item = R>) -> Self
+    where
+        R: Into<Repr>,
+    {
         self.set_repr(repr);
         self
     }
 
-    pub fn repr_mut(&mut self) -> Option<&mut String> {
-        self.repr.as_mut()
+    pub fn repr_mut(&mut self) -> &mut Vec<Repr> {
+        &mut self.repr
+    }
+
+    pub fn push_repr(&mut self, repr: impl Into<Repr>) -> &mut Self {
+        self.repr.push(repr.into());
+        self
+    }
+
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.non_exhaustive
+    }
+
+    pub fn set_non_exhaustive(&mut self, non_exhaustive: bool) -> &mut Self {
+        self.non_exhaustive = non_exhaustive;
+        self
+    }
+
+    pub fn with_non_exhaustive(mut self, non_exhaustive: bool) -> Self {
+        self.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    pub fn non_exhaustive_mut(&mut self) -> &mut bool {
+        &mut self.non_exhaustive
+    }
+
+    pub fn must_use(&self) -> Option<Option<&str>> {
+        self.must_use.as_ref().map(Option::as_deref)
+    }
+
+    pub fn set_must_use(&mut self, reason: Option<&str>) -> &mut Self {
+        self.must_use = Some(reason.map(String::from));
+        self
+    }
+
+    pub fn with_must_use(mut self, reason: Option<&str>) -> Self {
+        self.set_must_use(reason);
+        self
+    }
+
+    pub fn must_use_mut(&mut self) -> &mut Option<Option<String>> {
+        &mut self.must_use
+    }
+
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+
+    pub fn set_compact(&mut self, compact: bool) -> &mut Self {
+        self.compact = compact;
+        self
+    }
+
+    pub fn compact_mut(&mut self) -> &mut bool {
+        &mut self.compact
+    }
+
+    pub fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    pub fn set_provenance(&mut self, provenance: impl Into<Provenance>) -> &mut Self {
+        self.provenance = Some(provenance.into());
+        self
+    }
+
+    pub fn provenance_mut(&mut self) -> &mut Option<Provenance> {
+        &mut self.provenance
     }
 
     pub fn fmt_head(
@@ -286,6 +411,21 @@ impl TypeDef {
         parents: &[Type],
         fmt: &mut Formatter<'_>,
     ) -> fmt::Result {
+        self.fmt_head_without_bounds(keyword, parents, fmt)?;
+        fmt_bounds(&self.bounds, fmt)
+    }
+
+    /// Formats the head of the type up to its `where` clause, for items that
+    /// write the bounds after their definition, such as type aliases.
+    pub(crate) fn fmt_head_without_bounds(
+        &self,
+        keyword: &str,
+        parents: &[Type],
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        if let Some(ref comment) = self.comment {
+            comment.fmt(fmt)?;
+        }
         if let Some(ref doc) = self.doc {
             doc.fmt(fmt)?;
         }
@@ -293,6 +433,12 @@ impl TypeDef {
         self.fmt_lints(fmt)?;
         self.fmt_derive(fmt)?;
         self.fmt_repr(fmt)?;
+        if self.non_exhaustive {
+            writeln!(fmt, "#[non_exhaustive]")?;
+        }
+        if let Some(ref must_use) = self.must_use {
+            fmt_must_use(must_use.as_deref(), fmt)?;
+        }
         self.fmt_attributes(fmt)?;
         self.fmt_macros(fmt)?;
         self.vis.fmt(fmt)?;
@@ -312,8 +458,6 @@ impl TypeDef {
             }
         }
 
-        fmt_bounds(&self.bounds, fmt)?;
-
         Ok(())
     }
 
@@ -334,8 +478,15 @@ impl TypeDef {
     }
 
     fn fmt_repr(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if let Some(ref repr) = self.repr {
-            writeln!(fmt, "#[repr({})]", repr)?;
+        if !self.repr.is_empty() {
+            write!(fmt, "#[repr(")?;
+            for (i, hint) in self.repr.iter().enumerate() {
+                if i != 0 {
+                    write!(fmt, ", ")?;
+                }
+                write!(fmt, "{}", hint)?;
+            }
+            writeln!(fmt, ")]")?;
         }
 
         Ok(())
@@ -343,16 +494,11 @@ impl TypeDef {
 
     fn fmt_derive(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         if !self.derives.is_empty() {
-            write!(fmt, "#[derive(")?;
-
-            for (i, name) in self.derives.iter().enumerate() {
-                if i != 0 {
-                    write!(fmt, ", ")?
-                }
-                write!(fmt, "{}", name)?;
-            }
-
-            writeln!(fmt, ")]")?;
+            let derives = self.derive_order.apply(&self.derives);
+            fmt_list("#[derive(", &derives, ")]", fmt, |name, fmt| {
+                write!(fmt, "{}", name)
+            })?;
+            writeln!(fmt)?;
         }
 
         Ok(())
@@ -360,7 +506,7 @@ impl TypeDef {
 
     fn fmt_macros(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
         for m in self.macros.iter() {
-            writeln!(fmt, "{}", m)?;
+            writeln!(fmt, "#[{}]", m)?;
         }
         Ok(())
     }
@@ -0,0 +1,73 @@
+use crate::item::Item;
+use crate::prelude::*;
+use crate::scope::Scope;
+
+/// Maps type names to the modules defining them.
+///
+/// Used by [`Library`](crate::Library) to insert `use` statements for types
+/// defined in other files.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeRegistry {
+    /// Type names and their defining module paths, e.g. `crate::models`
+    types: IndexMap<String, String>,
+}
+
+impl TypeRegistry {
+    /// Creates a new, empty registry.
+    pub fn new() -> Self {
+        TypeRegistry::default()
+    }
+
+    /// Gets the registered types and their defining module paths.
+    pub fn types(&self) -> &IndexMap<String, String> {
+        &self.types
+    }
+
+    /// Gets a mutable reference to the registered types.
+    pub fn types_mut(&mut self) -> &mut IndexMap<String, String> {
+        &mut self.types
+    }
+
+    /// Registers a type as defined in the module with the given path.
+    ///
+    /// The path should be absolute, e.g. `crate::models`.
+    pub fn register(&mut self, name: impl Into<String>, module: impl Into<String>) -> &mut Self {
+        self.types.insert(name.into(), module.into());
+        self
+    }
+
+    /// Registers a type as defined in the module with the given path.
+    pub fn with(mut self, name: impl Into<String>, module: impl Into<String>) -> Self {
+        self.register(name, module);
+        self
+    }
+
+    /// Gets the path of the module defining the given type, if registered.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.types.get(name).map(String::as_str)
+    }
+
+    /// Registers the types defined in a scope rendered as module `module`,
+    /// including those of nested modules.
+    ///
+    /// Types that are already registered are left untouched.
+    pub fn register_scope(&mut self, scope: &Scope, module: &str) -> &mut Self {
+        for item in scope.items() {
+            match item {
+                Item::Module(v) => {
+                    self.register_scope(v.scope(), &format!("{}::{}", module, v.name()));
+                }
+                Item::Struct(_) | Item::Enum(_) | Item::Trait(_) | Item::TypeAlias(_) => {
+                    let name = item.name().unwrap();
+                    if !self.types.contains_key(name) {
+                        self.register(name, module);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self
+    }
+}
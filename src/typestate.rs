@@ -0,0 +1,322 @@
+use thiserror::Error;
+
+use crate::field::Field;
+use crate::function::{Function, SelfArg};
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::prelude::*;
+use crate::r#struct::Struct;
+use crate::r#type::Type;
+use crate::visibility::Vis;
+
+/// Defines a transition of a [`Typestate`] machine.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transition {
+    /// Name of the method performing the transition
+    method: String,
+
+    /// State the transition starts from
+    from: String,
+
+    /// State the transition leads to
+    to: String,
+
+    /// Documentation of the method
+    doc: Option<String>,
+}
+
+impl Transition {
+    /// Creates a transition performed by `method`, from one state to another.
+    pub fn new(method: impl Into<String>, from: impl Into<String>, to: impl Into<String>) -> Self {
+        Transition {
+            method: method.into(),
+            from: from.into(),
+            to: to.into(),
+            doc: None,
+        }
+    }
+
+    /// Gets the name of the method performing the transition.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Gets the state the transition starts from.
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    /// Gets the state the transition leads to.
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    /// Gets the documentation of the method.
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
+
+    /// Sets the documentation of the method.
+    pub fn set_doc(&mut self, doc: impl Into<String>) -> &mut Self {
+        self.doc = Some(doc.into());
+        self
+    }
+
+    /// Sets the documentation of the method.
+    pub fn with_doc(mut self, doc: impl Into<String>) -> Self {
+        self.set_doc(doc);
+        self
+    }
+}
+
+/// Generates a state machine using the typestate pattern.
+///
+/// Each state is a unit marker struct, and the machine is a struct generic
+/// over its state, so that calling a method in the wrong state fails to
+/// compile. Transitions consume the machine and return it in the new state,
+/// carrying its fields over. The first state is the initial one: the
+/// machine is created in it with `new`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Typestate {
+    /// Name of the machine struct
+    name: String,
+
+    /// Visibility of the generated items
+    vis: Vis,
+
+    /// Names of the states, the initial one first
+    states: Vec<String>,
+
+    /// Fields of the machine, kept across transitions
+    fields: Vec<Field>,
+
+    /// Transitions between the states
+    transitions: Vec<Transition>,
+}
+
+impl Typestate {
+    /// Creates a machine without states.
+    pub fn new(name: impl Into<String>) -> Self {
+        Typestate {
+            name: name.into(),
+            vis: Vis::Private,
+            states: Vec::new(),
+            fields: Vec::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Gets the name of the machine struct.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the visibility of the generated items.
+    pub fn vis(&self) -> &Vis {
+        &self.vis
+    }
+
+    /// Sets the visibility of the generated items.
+    pub fn set_vis(&mut self, vis: impl Into<Vis>) -> &mut Self {
+        self.vis = vis.into();
+        self
+    }
+
+    /// Sets the visibility of the generated items.
+    pub fn with_vis(mut self, vis: impl Into<Vis>) -> Self {
+        self.set_vis(vis);
+        self
+    }
+
+    /// Gets the names of the states, the initial one first.
+    pub fn states(&self) -> &[String] {
+        &self.states
+    }
+
+    /// Pushes a state. The first state pushed is the initial one.
+    pub fn push_state(&mut self, name: impl Into<String>) -> &mut Self {
+        self.states.push(name.into());
+        self
+    }
+
+    /// Pushes a state. The first state pushed is the initial one.
+    pub fn with_state(mut self, name: impl Into<String>) -> Self {
+        self.push_state(name);
+        self
+    }
+
+    /// Gets the fields of the machine.
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    /// Gets a mutable reference to the fields of the machine.
+    pub fn fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.fields
+    }
+
+    /// Pushes a field of the machine, kept across transitions.
+    pub fn push_field(&mut self, name: impl Into<String>, ty: impl Into<Type>) -> &mut Self {
+        self.fields.push(Field::new(name.into(), ty));
+        self
+    }
+
+    /// Pushes a field of the machine, kept across transitions.
+    pub fn with_field(mut self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+        self.push_field(name, ty);
+        self
+    }
+
+    /// Gets the transitions between the states.
+    pub fn transitions(&self) -> &[Transition] {
+        &self.transitions
+    }
+
+    /// Pushes a transition.
+    pub fn push_transition(&mut self, transition: impl Into<Transition>) -> &mut Self {
+        self.transitions.push(transition.into());
+        self
+    }
+
+    /// Pushes a transition.
+    pub fn with_transition(mut self, transition: impl Into<Transition>) -> Self {
+        self.push_transition(transition);
+        self
+    }
+
+    /// Generates the state markers, the machine struct, and an impl block
+    /// per state holding its transitions.
+    ///
+    /// Fails if there are no states, if a field is named `state`, if a
+    /// transition refers to an unknown state, or if a state has several
+    /// transitions with the same method.
+    pub fn generate(&self) -> Result<Vec<Item>, TypestateError> {
+        self.validate()?;
+
+        let mut items: Vec<Item> = Vec::new();
+        for state in &self.states {
+            items.push(
+                Struct::new(state.as_str())
+                    .with_vis(self.vis.clone())
+                    .into(),
+            );
+        }
+
+        let mut machine = Struct::new(self.name.as_str())
+            .with_vis(self.vis.clone())
+            .with_generic("S");
+        for field in &self.fields {
+            machine.push_named_field(field.clone());
+        }
+        machine.push_named_field(Field::new("state", "core::marker::PhantomData<S>"));
+        items.push(machine.into());
+
+        for (i, state) in self.states.iter().enumerate() {
+            let mut r#impl = Impl::new(self.machine_type(state));
+            if i == 0 {
+                r#impl.push_function(self.constructor());
+            }
+            for transition in self.transitions.iter().filter(|t| &t.from == state) {
+                r#impl.push_function(self.transition(transition));
+            }
+            if !r#impl.functions().is_empty() {
+                items.push(r#impl.into());
+            }
+        }
+
+        Ok(items)
+    }
+
+    /// Checks the states and transitions.
+    fn validate(&self) -> Result<(), TypestateError> {
+        if self.states.is_empty() {
+            return Err(TypestateError::NoStates);
+        }
+        if self.fields.iter().any(|f| f.name() == "state") {
+            return Err(TypestateError::StateField);
+        }
+
+        for (i, transition) in self.transitions.iter().enumerate() {
+            for state in [&transition.from, &transition.to] {
+                if !self.states.contains(state) {
+                    return Err(TypestateError::UnknownState(state.clone()));
+                }
+            }
+            let duplicate = self.transitions[..i]
+                .iter()
+                .any(|t| t.from == transition.from && t.method == transition.method);
+            if duplicate {
+                return Err(TypestateError::DuplicateTransition {
+                    state: transition.from.clone(),
+                    method: transition.method.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the machine struct in the given state, e.g. `Connection<Open>`.
+    fn machine_type(&self, state: &str) -> Type {
+        Type::new(self.name.as_str()).with_generic(state)
+    }
+
+    /// Generates `new`, creating the machine in the initial state.
+    fn constructor(&self) -> Function {
+        let mut function = Function::new("new")
+            .with_vis(self.vis.clone())
+            .with_ret("Self");
+        for field in &self.fields {
+            function.push_arg(field.name(), field.declared_ty());
+        }
+        function.push_line(format!("{} {{", self.name));
+        for field in &self.fields {
+            function.push_line(format!("    {},", field.name()));
+        }
+        function.push_line("    state: core::marker::PhantomData,");
+        function.push_line("}");
+        function
+    }
+
+    /// Generates the method performing a transition.
+    fn transition(&self, transition: &Transition) -> Function {
+        let mut function = Function::new(transition.method.as_str())
+            .with_vis(self.vis.clone())
+            .with_self_arg(SelfArg::WithSelf)
+            .with_ret(self.machine_type(&transition.to));
+        if let Some(doc) = &transition.doc {
+            function.set_doc(doc.as_str());
+        }
+        function.push_line(format!("{} {{", self.name));
+        for field in &self.fields {
+            function.push_line(format!("    {0}: self.{0},", field.name()));
+        }
+        function.push_line("    state: core::marker::PhantomData,");
+        function.push_line("}");
+        function
+    }
+}
+
+/// Error returned when a [`Typestate`] machine is inconsistent.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum TypestateError {
+    /// The machine has no states.
+    #[error("the machine has no states")]
+    NoStates,
+    /// A field is named `state`, like the field holding the state marker.
+    #[error("the field name `state` is reserved for the state marker")]
+    StateField,
+    /// A transition refers to a state that was not pushed.
+    #[error("unknown state: {0}")]
+    UnknownState(String),
+    /// A state has several transitions with the same method.
+    #[error("duplicate transition `{method}` from state {state}")]
+    DuplicateTransition {
+        /// State the transitions start from
+        state: String,
+        /// Name of the method
+        method: String,
+    },
+}
@@ -1,17 +1,26 @@
-use std::fmt::{self, Write};
+use core::fmt::{self, Write};
 
+use crate::attribute::{Attribute, push_unique};
+use crate::doc::Doc;
 use crate::field::Field;
 use crate::fields::Fields;
 use crate::formatter::Formatter;
+use crate::prelude::*;
+use crate::symbol::Symbol;
+use crate::tuple_field::TupleField;
 use crate::r#type::Type;
 
 /// Defines an [enum](https://doc.rust-lang.org/rust-by-example/custom_types/enum.html) variant.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Variant {
-    name: String,
+    name: Symbol,
     fields: Fields,
-    /// Annotations for field e.g., `#[serde(rename = "variant")]`.
-    annotations: Vec<String>,
+    non_exhaustive: bool,
+    /// Documentation of the variant.
+    doc: Option<Doc>,
+    /// Annotations for the variant, e.g. `#[serde(rename = "variant")]`.
+    annotations: Vec<Attribute>,
 }
 
 impl From<&str> for Variant {
@@ -20,12 +29,25 @@ impl From<&str> for Variant {
     }
 }
 
+impl From<Symbol> for Variant {
+    fn from(name: Symbol) -> Self {
+        Variant::new(name)
+    }
+}
+
 impl Variant {
     /// Creates a new enum variant with the given name.
-    pub fn new(name: impl Into<String>) -> Self {
+    ///
+    /// The name is used verbatim. Variants built from enumerated string
+    /// values, such as `"in-progress"` or `"1st"`, can be made valid with
+    /// [`to_camel_case`](crate::to_camel_case) and
+    /// [`sanitize_ident`](crate::sanitize_ident).
+    pub fn new(name: impl Into<Symbol>) -> Self {
         Variant {
             name: name.into(),
             fields: Fields::Empty,
+            non_exhaustive: false,
+            doc: None,
             annotations: Vec::new(),
         }
     }
@@ -36,20 +58,25 @@ impl Variant {
     }
 
     /// Sets the variant's name.
-    pub fn set_name(&mut self, name: impl Into<String>) -> &mut Self {
+    pub fn set_name(&mut self, name: impl Into<Symbol>) -> &mut Self {
         self.name = name.into();
         self
     }
 
     /// Sets the variant's name.
-    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+    pub fn with_name(mut self, name: impl Into<Symbol>) -> Self {
         self.set_name(name);
         self
     }
 
     /// Gets a mutable reference to the variant's name.
     pub fn name_mut(&mut self) -> &mut String {
-        &mut self.name
+        self.name.to_mut()
+    }
+
+    /// Gets the variant's name as a symbol.
+    pub fn symbol(&self) -> &Symbol {
+        &self.name
     }
 
     /// Gets the variant's fields.
@@ -74,41 +101,100 @@ impl Variant {
         &mut self.fields
     }
 
+    /// Returns whether the variant is `#[non_exhaustive]`.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.non_exhaustive
+    }
+
+    /// Sets whether the variant is `#[non_exhaustive]`.
+    pub fn set_non_exhaustive(&mut self, non_exhaustive: bool) -> &mut Self {
+        self.non_exhaustive = non_exhaustive;
+        self
+    }
+
+    /// Sets whether the variant is `#[non_exhaustive]`.
+    pub fn with_non_exhaustive(mut self, non_exhaustive: bool) -> Self {
+        self.set_non_exhaustive(non_exhaustive);
+        self
+    }
+
+    /// Gets a mutable reference to whether the variant is `#[non_exhaustive]`.
+    pub fn non_exhaustive_mut(&mut self) -> &mut bool {
+        &mut self.non_exhaustive
+    }
+
+    /// Gets the variant's documentation.
+    pub fn doc(&self) -> Option<&Doc> {
+        self.doc.as_ref()
+    }
+
+    /// Sets the variant's documentation.
+    pub fn set_doc<S>(&mut self, doc: impl Into<Option<S>>) -> &mut Self
+    where
+        S: Into<Doc>,
+    {
+        self.doc = doc.into().map(Into::into);
+        self
+    }
+
+    /// Sets the variant's documentation.
+    pub fn with_doc<S>(mut self, doc: impl Into<Option<S>>) -> Self
+    where
+        S: Into<Doc>,
+    {
+        self.set_doc(doc);
+        self
+    }
+
+    /// Gets a mutable reference to the variant's documentation.
+    pub fn doc_mut(&mut self) -> Option<&mut Doc> {
+        self.doc.as_mut()
+    }
+
     /// Gets the variant's annotations.
-    pub fn annotations(&self) -> &[String] {
+    pub fn annotations(&self) -> &[Attribute] {
         &self.annotations
     }
-    /// Sets the variant's annotations.
+
+    /// Sets the variant's annotations, skipping duplicates.
     pub fn set_annotations<S>(&mut self, annotations: impl IntoIterator<Item = S>) -> &mut Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
-        self.annotations = annotations.into_iter().map(Into::into).collect();
+        self.annotations.clear();
+        for annotation in annotations {
+            self.push_annotation(annotation);
+        }
         self
     }
 
-    /// Sets the variant's annotations.
+    /// Sets the variant's annotations, skipping duplicates.
     pub fn with_annotations<S>(mut self, annotations: impl IntoIterator<Item = S>) -> Self
     where
-        S: Into<String>,
+        S: Into<Attribute>,
     {
         self.set_annotations(annotations);
         self
     }
 
     /// Gets a mutable reference to the variant's annotations.
-    pub fn annotations_mut(&mut self) -> &mut Vec<String> {
+    pub fn annotations_mut(&mut self) -> &mut Vec<Attribute> {
         &mut self.annotations
     }
 
-    /// Pushes an annotation to the variant.
-    pub fn push_annotation(&mut self, annotation: impl Into<String>) -> &mut Self {
-        self.annotations.push(annotation.into());
+    /// Pushes an annotation to the variant, e.g. `#[serde(rename = "a")]` or
+    /// a [`SerdeAttr`](crate::SerdeAttr).
+    ///
+    /// Annotations are written in the order they are pushed; pushing one equal
+    /// to an existing annotation has no effect.
+    pub fn push_annotation(&mut self, annotation: impl Into<Attribute>) -> &mut Self {
+        push_unique(&mut self.annotations, annotation.into());
         self
     }
 
-    /// Pushes an annotation to the variant.
-    pub fn with_annotation(mut self, annotation: impl Into<String>) -> Self {
+    /// Pushes an annotation to the variant, e.g. `#[serde(rename = "a")]` or
+    /// a [`SerdeAttr`](crate::SerdeAttr).
+    pub fn with_annotation(mut self, annotation: impl Into<Attribute>) -> Self {
         self.push_annotation(annotation);
         self
     }
@@ -116,15 +202,15 @@ impl Variant {
     /// Pushes a named field to the variant.
     ///
     /// Panics if the fields are tuple-based rather than named.
-    pub fn push_named_field(&mut self, name: impl Into<String>, ty: impl Into<Type>) -> &mut Self {
-        self.fields.push_named(Field::new(name.into(), ty.into()));
+    pub fn push_named_field(&mut self, name: impl Into<Symbol>, ty: impl Into<Type>) -> &mut Self {
+        self.fields.push_named(Field::new(name, ty));
         self
     }
 
     /// Pushes a named field to the variant.
     ///
     /// Panics if the fields are tuple-based rather than named.
-    pub fn with_named_field(mut self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+    pub fn with_named_field(mut self, name: impl Into<Symbol>, ty: impl Into<Type>) -> Self {
         self.push_named_field(name, ty);
         self
     }
@@ -132,24 +218,29 @@ impl Variant {
     /// Pushes a tuple field to the variant.
     ///
     /// Panics if the fields are named rather than tuple-based.
-    pub fn push_tuple_field(&mut self, ty: impl Into<String>) -> &mut Self {
-        self.fields.push_tuple(ty.into());
+    pub fn push_tuple_field(&mut self, ty: impl Into<TupleField>) -> &mut Self {
+        self.fields.push_tuple(ty);
         self
     }
 
     /// Pushes a tuple field to the variant.
     ///
     /// Panics if the fields are named rather than tuple-based.
-    pub fn with_tuple_field(mut self, ty: impl Into<String>) -> Self {
+    pub fn with_tuple_field(mut self, ty: impl Into<TupleField>) -> Self {
         self.push_tuple_field(ty);
         self
     }
 
     /// Formats the variant using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref doc) = self.doc {
+            doc.fmt(fmt)?;
+        }
+        if self.non_exhaustive {
+            writeln!(fmt, "#[non_exhaustive]")?;
+        }
         for a in &self.annotations {
-            write!(fmt, "{}", a)?;
-            writeln!(fmt)?;
+            writeln!(fmt, "#[{}]", a)?;
         }
         write!(fmt, "{}", self.name)?;
         self.fields.fmt(fmt)?;
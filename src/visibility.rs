@@ -1,10 +1,15 @@
-use std::fmt;
-use std::fmt::Write;
+use core::fmt;
+use core::fmt::Write;
+use core::str::FromStr;
+
+use thiserror::Error;
 
 use crate::formatter::Formatter;
+use crate::prelude::*;
 
 /// Enum representing the [visibility](https://doc.rust-lang.org/reference/visibility-and-privacy.html) of an item.
 #[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Vis {
     /// The default private visiblity
     #[default]
@@ -17,6 +22,8 @@ pub enum Vis {
     PubSelf,
     /// Equivalent of `pub(super)`
     PubSuper,
+    /// Equivalent of `pub(in path)`, holding the path
+    PubIn(String),
     /// Custom visibility pub
     Custom(String),
 }
@@ -38,6 +45,9 @@ impl Vis {
             Vis::PubSuper => {
                 write!(fmt, "pub(super) ")?;
             }
+            Vis::PubIn(path) => {
+                write!(fmt, "pub(in {}) ", path)?;
+            }
             Vis::Custom(s) => {
                 write!(fmt, "{} ", s)?;
             }
@@ -45,3 +55,80 @@ impl Vis {
         Ok(())
     }
 }
+
+/// Error returned when a visibility cannot be parsed.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum VisError {
+    /// The string is not a visibility.
+    #[error("unknown visibility: {0}")]
+    Unknown(String),
+}
+
+impl fmt::Display for Vis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vis::Private => Ok(()),
+            Vis::Pub => write!(f, "pub"),
+            Vis::PubCrate => write!(f, "pub(crate)"),
+            Vis::PubSelf => write!(f, "pub(self)"),
+            Vis::PubSuper => write!(f, "pub(super)"),
+            Vis::PubIn(path) => write!(f, "pub(in {})", path),
+            Vis::Custom(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl FromStr for Vis {
+    type Err = VisError;
+
+    /// Parses a visibility as written in Rust, e.g. `pub(crate)` or
+    /// `pub(in crate::foo)`. An empty string is the private visibility.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(rest) = s.strip_prefix("pub") else {
+            return if s.is_empty() {
+                Ok(Vis::Private)
+            } else {
+                Err(VisError::Unknown(s.to_string()))
+            };
+        };
+
+        let rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(Vis::Pub);
+        }
+
+        let inner = rest
+            .strip_prefix('(')
+            .and_then(|r| r.strip_suffix(')'))
+            .map(str::trim)
+            .ok_or_else(|| VisError::Unknown(s.to_string()))?;
+
+        match inner {
+            "crate" => Ok(Vis::PubCrate),
+            "self" => Ok(Vis::PubSelf),
+            "super" => Ok(Vis::PubSuper),
+            _ => match inner.strip_prefix("in ") {
+                Some(path) if !path.trim().is_empty() => Ok(Vis::PubIn(path.trim().to_string())),
+                _ => Err(VisError::Unknown(s.to_string())),
+            },
+        }
+    }
+}
+
+impl From<&str> for Vis {
+    /// Converts a visibility as written in Rust, keeping unknown ones as
+    /// [`Vis::Custom`].
+    fn from(s: &str) -> Self {
+        s.parse()
+            .unwrap_or_else(|_| Vis::Custom(s.trim().to_string()))
+    }
+}
+
+impl From<String> for Vis {
+    /// Converts a visibility as written in Rust, keeping unknown ones as
+    /// [`Vis::Custom`].
+    fn from(s: String) -> Self {
+        Vis::from(s.as_str())
+    }
+}
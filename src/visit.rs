@@ -0,0 +1,696 @@
+use core::ops::Range;
+
+use crate::associated_const::AssociatedConst;
+use crate::associated_type::AssociatedType;
+use crate::bound::Bound;
+use crate::r#enum::Enum;
+use crate::field::Field;
+use crate::fields::Fields;
+use crate::function::Function;
+use crate::generic_parameter::GenericParameter;
+use crate::r#impl::Impl;
+use crate::item::Item;
+use crate::module::Module;
+use crate::prelude::*;
+use crate::scope::Scope;
+use crate::r#struct::Struct;
+use crate::r#trait::Trait;
+use crate::r#type::{Type, TypeKind};
+use crate::type_alias::TypeAlias;
+use crate::variant::Variant;
+
+/// Traverses the item tree of a [`Scope`].
+///
+/// Each method defaults to walking into the node's children through the
+/// matching `walk_*` function. Override a method to act on a kind of node,
+/// calling the `walk_*` function to keep descending.
+pub trait Visitor {
+    /// Visits a scope.
+    fn visit_scope(&mut self, scope: &Scope) {
+        walk_scope(self, scope);
+    }
+
+    /// Visits an item.
+    fn visit_item(&mut self, item: &Item) {
+        walk_item(self, item);
+    }
+
+    /// Visits a module.
+    fn visit_module(&mut self, module: &Module) {
+        walk_module(self, module);
+    }
+
+    /// Visits a struct.
+    fn visit_struct(&mut self, r#struct: &Struct) {
+        walk_struct(self, r#struct);
+    }
+
+    /// Visits an enum.
+    fn visit_enum(&mut self, r#enum: &Enum) {
+        walk_enum(self, r#enum);
+    }
+
+    /// Visits an enum variant.
+    fn visit_variant(&mut self, variant: &Variant) {
+        walk_variant(self, variant);
+    }
+
+    /// Visits a trait.
+    fn visit_trait(&mut self, r#trait: &Trait) {
+        walk_trait(self, r#trait);
+    }
+
+    /// Visits an `impl` block.
+    fn visit_impl(&mut self, r#impl: &Impl) {
+        walk_impl(self, r#impl);
+    }
+
+    /// Visits an associated type of a trait or `impl` block.
+    fn visit_associated_type(&mut self, associated_type: &AssociatedType) {
+        walk_associated_type(self, associated_type);
+    }
+
+    /// Visits an associated const of a trait or `impl` block.
+    fn visit_associated_const(&mut self, associated_const: &AssociatedConst) {
+        walk_associated_const(self, associated_const);
+    }
+
+    /// Visits a function.
+    fn visit_function(&mut self, function: &Function) {
+        walk_function(self, function);
+    }
+
+    /// Visits a type alias.
+    fn visit_type_alias(&mut self, type_alias: &TypeAlias) {
+        walk_type_alias(self, type_alias);
+    }
+
+    /// Visits the fields of a struct or variant.
+    fn visit_fields(&mut self, fields: &Fields) {
+        walk_fields(self, fields);
+    }
+
+    /// Visits a field or function argument.
+    fn visit_field(&mut self, field: &Field) {
+        walk_field(self, field);
+    }
+
+    /// Visits a type reference.
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+}
+
+/// Traverses the item tree of a [`Scope`], with mutable access.
+///
+/// Like [`Visitor`], each method defaults to walking into the node's children
+/// through the matching `walk_*_mut` function.
+pub trait VisitorMut {
+    /// Visits a scope.
+    fn visit_scope(&mut self, scope: &mut Scope) {
+        walk_scope_mut(self, scope);
+    }
+
+    /// Visits an item.
+    fn visit_item(&mut self, item: &mut Item) {
+        walk_item_mut(self, item);
+    }
+
+    /// Visits a module.
+    fn visit_module(&mut self, module: &mut Module) {
+        walk_module_mut(self, module);
+    }
+
+    /// Visits a struct.
+    fn visit_struct(&mut self, r#struct: &mut Struct) {
+        walk_struct_mut(self, r#struct);
+    }
+
+    /// Visits an enum.
+    fn visit_enum(&mut self, r#enum: &mut Enum) {
+        walk_enum_mut(self, r#enum);
+    }
+
+    /// Visits an enum variant.
+    fn visit_variant(&mut self, variant: &mut Variant) {
+        walk_variant_mut(self, variant);
+    }
+
+    /// Visits a trait.
+    fn visit_trait(&mut self, r#trait: &mut Trait) {
+        walk_trait_mut(self, r#trait);
+    }
+
+    /// Visits an `impl` block.
+    fn visit_impl(&mut self, r#impl: &mut Impl) {
+        walk_impl_mut(self, r#impl);
+    }
+
+    /// Visits an associated type of a trait or `impl` block.
+    fn visit_associated_type(&mut self, associated_type: &mut AssociatedType) {
+        walk_associated_type_mut(self, associated_type);
+    }
+
+    /// Visits an associated const of a trait or `impl` block.
+    fn visit_associated_const(&mut self, associated_const: &mut AssociatedConst) {
+        walk_associated_const_mut(self, associated_const);
+    }
+
+    /// Visits a function.
+    fn visit_function(&mut self, function: &mut Function) {
+        walk_function_mut(self, function);
+    }
+
+    /// Visits a type alias.
+    fn visit_type_alias(&mut self, type_alias: &mut TypeAlias) {
+        walk_type_alias_mut(self, type_alias);
+    }
+
+    /// Visits the fields of a struct or variant.
+    fn visit_fields(&mut self, fields: &mut Fields) {
+        walk_fields_mut(self, fields);
+    }
+
+    /// Visits a field or function argument.
+    fn visit_field(&mut self, field: &mut Field) {
+        walk_field_mut(self, field);
+    }
+
+    /// Visits a type reference.
+    fn visit_type(&mut self, ty: &mut Type) {
+        walk_type_mut(self, ty);
+    }
+}
+
+/// Walks the children of a scope.
+pub fn walk_scope<V: Visitor + ?Sized>(visitor: &mut V, scope: &Scope) {
+    for item in scope.items() {
+        visitor.visit_item(item);
+    }
+}
+
+/// Walks the children of an item.
+pub fn walk_item<V: Visitor + ?Sized>(visitor: &mut V, item: &Item) {
+    match item {
+        Item::Module(v) => visitor.visit_module(v),
+        Item::Struct(v) => visitor.visit_struct(v),
+        Item::Function(v) => visitor.visit_function(v),
+        Item::Trait(v) => visitor.visit_trait(v),
+        Item::Enum(v) => visitor.visit_enum(v),
+        Item::Impl(v) => visitor.visit_impl(v),
+        Item::TypeAlias(v) => visitor.visit_type_alias(v),
+        Item::Const(v) => visitor.visit_type(v.ty()),
+        Item::ModDecl(_) | Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => {}
+    }
+}
+
+/// Walks the children of a module.
+pub fn walk_module<V: Visitor + ?Sized>(visitor: &mut V, module: &Module) {
+    visitor.visit_scope(module.scope());
+}
+
+/// Walks the children of a struct.
+pub fn walk_struct<V: Visitor + ?Sized>(visitor: &mut V, r#struct: &Struct) {
+    walk_generic_bounds(visitor, r#struct.generics());
+    walk_bounds(visitor, r#struct.bounds());
+    visitor.visit_fields(r#struct.fields());
+    for test in r#struct.tests() {
+        visitor.visit_function(test);
+    }
+}
+
+/// Walks the children of an enum.
+pub fn walk_enum<V: Visitor + ?Sized>(visitor: &mut V, r#enum: &Enum) {
+    walk_generic_bounds(visitor, r#enum.generics());
+    walk_bounds(visitor, r#enum.bounds());
+    for variant in r#enum.variants() {
+        visitor.visit_variant(variant);
+    }
+    for test in r#enum.tests() {
+        visitor.visit_function(test);
+    }
+}
+
+/// Walks the children of an enum variant.
+pub fn walk_variant<V: Visitor + ?Sized>(visitor: &mut V, variant: &Variant) {
+    visitor.visit_fields(variant.fields());
+}
+
+/// Walks the children of a trait.
+pub fn walk_trait<V: Visitor + ?Sized>(visitor: &mut V, r#trait: &Trait) {
+    walk_generic_bounds(visitor, r#trait.generics());
+    walk_bounds(visitor, r#trait.bounds());
+    for parent in r#trait.parents() {
+        visitor.visit_type(parent);
+    }
+    for associated_const in r#trait.associated_consts() {
+        visitor.visit_associated_const(associated_const);
+    }
+    for associated_type in r#trait.associated_type() {
+        visitor.visit_associated_type(associated_type);
+    }
+    for function in r#trait.functions() {
+        visitor.visit_function(function);
+    }
+}
+
+/// Walks the children of an `impl` block.
+pub fn walk_impl<V: Visitor + ?Sized>(visitor: &mut V, r#impl: &Impl) {
+    walk_bounds(visitor, r#impl.bounds());
+    visitor.visit_type(r#impl.target());
+    if let Some(impl_trait) = r#impl.impl_trait() {
+        visitor.visit_type(impl_trait);
+    }
+    for associated_const in r#impl.associated_consts() {
+        visitor.visit_associated_const(associated_const);
+    }
+    for cst in r#impl.consts() {
+        visitor.visit_type(cst.ty());
+    }
+    for associated_type in r#impl.associated_type() {
+        visitor.visit_associated_type(associated_type);
+    }
+    for function in r#impl.functions() {
+        visitor.visit_function(function);
+    }
+}
+
+/// Walks the trait bounds and the concrete type of an associated type.
+pub fn walk_associated_type<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    associated_type: &AssociatedType,
+) {
+    for bound in associated_type.trait_bounds() {
+        walk_text(visitor, bound);
+    }
+    if let Some(ty) = associated_type.concrete_ty() {
+        visitor.visit_type(ty);
+    }
+}
+
+/// Walks the type of an associated const.
+pub fn walk_associated_const<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    associated_const: &AssociatedConst,
+) {
+    visitor.visit_type(associated_const.ty());
+}
+
+/// Walks the children of a function.
+pub fn walk_function<V: Visitor + ?Sized>(visitor: &mut V, function: &Function) {
+    walk_bounds(visitor, function.bounds());
+    for arg in function.args() {
+        visitor.visit_field(arg);
+    }
+    if let Some(ret) = function.ret() {
+        visitor.visit_type(ret);
+    }
+}
+
+/// Walks the children of a type alias.
+pub fn walk_type_alias<V: Visitor + ?Sized>(visitor: &mut V, type_alias: &TypeAlias) {
+    walk_generic_bounds(visitor, type_alias.generics());
+    walk_bounds(visitor, type_alias.bounds());
+    visitor.visit_type(type_alias.ty());
+}
+
+/// Walks the children of the fields of a struct or variant.
+pub fn walk_fields<V: Visitor + ?Sized>(visitor: &mut V, fields: &Fields) {
+    match fields {
+        Fields::Empty => {}
+        Fields::Tuple(fields) => {
+            for field in fields {
+                visitor.visit_type(field.ty());
+            }
+        }
+        Fields::Named(fields) => {
+            for field in fields {
+                visitor.visit_field(field);
+            }
+        }
+    }
+}
+
+/// Walks the children of a field or function argument.
+pub fn walk_field<V: Visitor + ?Sized>(visitor: &mut V, field: &Field) {
+    visitor.visit_type(field.ty());
+}
+
+/// Walks the types nested in a type: the type arguments of a path, whether
+/// written in its name or pushed as generics, and the types nested in a
+/// reference, slice, tuple, trait or function pointer type.
+pub fn walk_type<V: Visitor + ?Sized>(visitor: &mut V, ty: &Type) {
+    match ty.kind() {
+        TypeKind::Path => {
+            for arg in type_args(ty.name()) {
+                walk_text(visitor, &ty.name()[arg]);
+            }
+            walk_generics(visitor, ty.generics());
+        }
+        TypeKind::Reference { ty, .. } | TypeKind::Slice(ty) | TypeKind::Array(ty, _) => {
+            visitor.visit_type(ty);
+        }
+        TypeKind::Applied(types)
+        | TypeKind::Tuple(types)
+        | TypeKind::ImplTrait(types)
+        | TypeKind::DynTrait(types) => {
+            for ty in types {
+                visitor.visit_type(ty);
+            }
+        }
+        TypeKind::FnPtr { args, ret } => {
+            for ty in args {
+                visitor.visit_type(ty);
+            }
+            if let Some(ret) = ret {
+                visitor.visit_type(ret);
+            }
+        }
+    }
+}
+
+/// Walks the children of a scope.
+pub fn walk_scope_mut<V: VisitorMut + ?Sized>(visitor: &mut V, scope: &mut Scope) {
+    for item in scope.items_mut() {
+        visitor.visit_item(item);
+    }
+}
+
+/// Walks the children of an item.
+pub fn walk_item_mut<V: VisitorMut + ?Sized>(visitor: &mut V, item: &mut Item) {
+    match item {
+        Item::Module(v) => visitor.visit_module(v),
+        Item::Struct(v) => visitor.visit_struct(v),
+        Item::Function(v) => visitor.visit_function(v),
+        Item::Trait(v) => visitor.visit_trait(v),
+        Item::Enum(v) => visitor.visit_enum(v),
+        Item::Impl(v) => visitor.visit_impl(v),
+        Item::TypeAlias(v) => visitor.visit_type_alias(v),
+        Item::Const(v) => visitor.visit_type(v.ty_mut()),
+        Item::ModDecl(_) | Item::Raw(_) | Item::Comment(_) | Item::LineBreak(_) => {}
+    }
+}
+
+/// Walks the children of a module.
+pub fn walk_module_mut<V: VisitorMut + ?Sized>(visitor: &mut V, module: &mut Module) {
+    visitor.visit_scope(module.scope_mut());
+}
+
+/// Walks the children of a struct.
+pub fn walk_struct_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#struct: &mut Struct) {
+    walk_generic_bounds_mut(visitor, r#struct.generics_mut());
+    walk_bounds_mut(visitor, r#struct.bounds_mut());
+    visitor.visit_fields(r#struct.fields_mut());
+    for test in r#struct.tests_mut() {
+        visitor.visit_function(test);
+    }
+}
+
+/// Walks the children of an enum.
+pub fn walk_enum_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#enum: &mut Enum) {
+    walk_generic_bounds_mut(visitor, r#enum.generics_mut());
+    walk_bounds_mut(visitor, r#enum.bounds_mut());
+    for variant in r#enum.variants_mut() {
+        visitor.visit_variant(variant);
+    }
+    for test in r#enum.tests_mut() {
+        visitor.visit_function(test);
+    }
+}
+
+/// Walks the children of an enum variant.
+pub fn walk_variant_mut<V: VisitorMut + ?Sized>(visitor: &mut V, variant: &mut Variant) {
+    visitor.visit_fields(variant.fields_mut());
+}
+
+/// Walks the children of a trait.
+pub fn walk_trait_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#trait: &mut Trait) {
+    walk_generic_bounds_mut(visitor, r#trait.generics_mut());
+    walk_bounds_mut(visitor, r#trait.bounds_mut());
+    for parent in r#trait.parents_mut() {
+        visitor.visit_type(parent);
+    }
+    for associated_const in r#trait.associated_consts_mut() {
+        visitor.visit_associated_const(associated_const);
+    }
+    for associated_type in r#trait.associated_types_mut() {
+        visitor.visit_associated_type(associated_type);
+    }
+    for function in r#trait.functions_mut() {
+        visitor.visit_function(function);
+    }
+}
+
+/// Walks the children of an `impl` block.
+pub fn walk_impl_mut<V: VisitorMut + ?Sized>(visitor: &mut V, r#impl: &mut Impl) {
+    walk_bounds_mut(visitor, r#impl.bounds_mut());
+    visitor.visit_type(r#impl.target_mut());
+    if let Some(impl_trait) = r#impl.impl_trait_mut() {
+        visitor.visit_type(impl_trait);
+    }
+    for associated_const in r#impl.associated_consts_mut() {
+        visitor.visit_associated_const(associated_const);
+    }
+    for cst in r#impl.consts_mut() {
+        visitor.visit_type(cst.ty_mut());
+    }
+    for associated_type in r#impl.associated_types_mut() {
+        visitor.visit_associated_type(associated_type);
+    }
+    for function in r#impl.functions_mut() {
+        visitor.visit_function(function);
+    }
+}
+
+/// Walks the trait bounds and the concrete type of an associated type.
+pub fn walk_associated_type_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    associated_type: &mut AssociatedType,
+) {
+    for bound in associated_type.trait_bounds_mut() {
+        walk_text_mut(visitor, bound);
+    }
+    if let Some(ty) = associated_type.concrete_ty_mut() {
+        visitor.visit_type(ty);
+    }
+}
+
+/// Walks the type of an associated const.
+pub fn walk_associated_const_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    associated_const: &mut AssociatedConst,
+) {
+    visitor.visit_type(associated_const.ty_mut());
+}
+
+/// Walks the children of a function.
+pub fn walk_function_mut<V: VisitorMut + ?Sized>(visitor: &mut V, function: &mut Function) {
+    walk_bounds_mut(visitor, function.bounds_mut());
+    for arg in function.args_mut() {
+        visitor.visit_field(arg);
+    }
+    if let Some(ret) = function.ret_mut() {
+        visitor.visit_type(ret);
+    }
+}
+
+/// Walks the children of a type alias.
+pub fn walk_type_alias_mut<V: VisitorMut + ?Sized>(visitor: &mut V, type_alias: &mut TypeAlias) {
+    walk_generic_bounds_mut(visitor, type_alias.generics_mut());
+    walk_bounds_mut(visitor, type_alias.bounds_mut());
+    visitor.visit_type(type_alias.ty_mut());
+}
+
+/// Walks the children of the fields of a struct or variant.
+pub fn walk_fields_mut<V: VisitorMut + ?Sized>(visitor: &mut V, fields: &mut Fields) {
+    match fields {
+        Fields::Empty => {}
+        Fields::Tuple(fields) => {
+            for field in fields {
+                visitor.visit_type(field.ty_mut());
+            }
+        }
+        Fields::Named(fields) => {
+            for field in fields {
+                visitor.visit_field(field);
+            }
+        }
+    }
+}
+
+/// Walks the children of a field or function argument.
+pub fn walk_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut Field) {
+    visitor.visit_type(field.ty_mut());
+}
+
+/// Walks the types nested in a type, like [`walk_type`], then renames the
+/// type after them.
+pub fn walk_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ty: &mut Type) {
+    if let TypeKind::Path = ty.kind() {
+        let mut name = ty.name().to_string();
+        // Back to front, so that the ranges before a changed argument hold
+        for arg in type_args(ty.name()).into_iter().rev() {
+            let mut text = name[arg.clone()].to_string();
+            walk_text_mut(visitor, &mut text);
+            name.replace_range(arg, &text);
+        }
+        if name != ty.name() {
+            ty.set_name(name);
+        }
+        walk_generics_mut(visitor, ty.generics_mut());
+    }
+    match ty.kind_mut() {
+        TypeKind::Path => {}
+        TypeKind::Reference { ty, .. } | TypeKind::Slice(ty) | TypeKind::Array(ty, _) => {
+            visitor.visit_type(ty);
+        }
+        TypeKind::Applied(types)
+        | TypeKind::Tuple(types)
+        | TypeKind::ImplTrait(types)
+        | TypeKind::DynTrait(types) => {
+            for ty in types {
+                visitor.visit_type(ty);
+            }
+        }
+        TypeKind::FnPtr { args, ret } => {
+            for ty in args {
+                visitor.visit_type(ty);
+            }
+            if let Some(ret) = ret {
+                visitor.visit_type(ret);
+            }
+        }
+    }
+    ty.refresh_name();
+}
+
+/// Walks the type arguments pushed as generics of a path type, with their
+/// bounds.
+fn walk_generics<V: Visitor + ?Sized>(visitor: &mut V, generics: &[GenericParameter]) {
+    for generic in generics {
+        walk_text(visitor, generic.name());
+    }
+    walk_generic_bounds(visitor, generics);
+}
+
+fn walk_generics_mut<V: VisitorMut + ?Sized>(visitor: &mut V, generics: &mut [GenericParameter]) {
+    for generic in generics.iter_mut() {
+        walk_text_mut(visitor, generic.name_mut());
+    }
+    walk_generic_bounds_mut(visitor, generics);
+}
+
+/// Walks the trait bounds of generic parameters, but not the parameters
+/// themselves, which an item declares rather than refers to.
+fn walk_generic_bounds<V: Visitor + ?Sized>(visitor: &mut V, generics: &[GenericParameter]) {
+    for generic in generics {
+        for bound in generic.traits() {
+            walk_text(visitor, bound);
+        }
+    }
+}
+
+fn walk_generic_bounds_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    generics: &mut [GenericParameter],
+) {
+    for generic in generics {
+        for bound in generic.traits_mut() {
+            walk_text_mut(visitor, bound);
+        }
+    }
+}
+
+fn walk_bounds<V: Visitor + ?Sized>(visitor: &mut V, bounds: &[Bound]) {
+    for bound in bounds {
+        walk_text(visitor, bound.name());
+        for r#trait in bound.traits() {
+            walk_text(visitor, r#trait);
+        }
+    }
+}
+
+fn walk_bounds_mut<V: VisitorMut + ?Sized>(visitor: &mut V, bounds: &mut [Bound]) {
+    for bound in bounds {
+        walk_text_mut(visitor, bound.name_mut());
+        for r#trait in bound.traits_mut() {
+            walk_text_mut(visitor, r#trait);
+        }
+    }
+}
+
+/// Visits a type or trait bound written as text, e.g. a generic argument,
+/// as a path type. Lifetimes and const arguments are not types and are
+/// skipped.
+fn walk_text<V: Visitor + ?Sized>(visitor: &mut V, text: &str) {
+    if is_type_text(text) {
+        visitor.visit_type(&Type::new(text.trim()));
+    }
+}
+
+fn walk_text_mut<V: VisitorMut + ?Sized>(visitor: &mut V, text: &mut String) {
+    if is_type_text(text) {
+        let mut ty = Type::new(text.trim());
+        visitor.visit_type(&mut ty);
+        let source = ty.to_source();
+        if source != text.trim() {
+            *text = source;
+        }
+    }
+}
+
+fn is_type_text(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty() && !text.starts_with(|c: char| c == '\'' || c == '{' || c.is_ascii_digit())
+}
+
+/// Returns where the type arguments of a path written as text are, e.g.
+/// `Foo` and `Bar<Baz>` in `Vec<Foo, Bar<Baz>>`. Only the type of an
+/// associated type binding, e.g. `Foo` in `Iterator<Item = Foo>`, is kept.
+fn type_args(name: &str) -> Vec<Range<usize>> {
+    let mut ret = Vec::new();
+    let Some(open) = name.find('<').filter(|&i| i > 0 && name.ends_with('>')) else {
+        return ret;
+    };
+
+    let mut push = |range: Range<usize>| {
+        let arg = &name[range.clone()];
+        let start = match arg.find('=') {
+            Some(i)
+                if arg[..i]
+                    .trim()
+                    .chars()
+                    .all(|c| c == '_' || c.is_alphanumeric()) =>
+            {
+                i + 1
+            }
+            _ => 0,
+        };
+        let arg = &arg[start..];
+        let start = range.start + start + (arg.len() - arg.trim_start().len());
+        let end = range.end - (arg.len() - arg.trim_end().len());
+        if start < end {
+            ret.push(start..end);
+        }
+    };
+
+    let mut depth = 0;
+    let mut start = open + 1;
+    for (i, c) in name.char_indices().skip_while(|&(i, _)| i <= open) {
+        match c {
+            '>' if name[..i].ends_with('-') => {}
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                push(start..i);
+                start = i + 1;
+            }
+            '>' if depth == 0 => {
+                push(start..i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    ret
+}
@@ -0,0 +1,102 @@
+use core::fmt;
+use std::io;
+
+use crate::formatter::{Formatter, NewlineStyle};
+use crate::provenance::SourceMap;
+use crate::scope::Scope;
+
+impl Scope {
+    /// Writes the formatted scope to the given writer.
+    ///
+    /// The output is the same as `to_string`, but it is streamed to the
+    /// writer instead of being built up in memory first. Wrap files and
+    /// sockets in a `BufWriter`, as the code is written in small pieces.
+    pub fn write_to<W: io::Write>(&self, w: W) -> io::Result<()> {
+        self.write_with(w, NewlineStyle::Lf, false).map(drop)
+    }
+
+    /// Writes the formatted scope using the given line terminator, ending
+    /// the output with one if `trailing_newline` is set.
+    ///
+    /// Returns the provenance of the items written.
+    pub(crate) fn write_with<W: io::Write>(
+        &self,
+        w: W,
+        newline_style: NewlineStyle,
+        trailing_newline: bool,
+    ) -> io::Result<SourceMap> {
+        let mut sink = IoSink {
+            inner: w,
+            newline: newline_style.as_str(),
+            pending: false,
+            written: false,
+            error: None,
+        };
+
+        let mut fmt = Formatter::from_writer(&mut sink).with_newline_style(newline_style);
+        let res = self.fmt(&mut fmt);
+        let source_map = fmt.into_source_map();
+        let res = match res {
+            Ok(()) if trailing_newline => sink.flush_newline(),
+            res => res,
+        };
+
+        match res {
+            Ok(()) => Ok(source_map),
+            Err(fmt::Error) => Err(sink
+                .error
+                .unwrap_or_else(|| io::Error::other("formatter error"))),
+        }
+    }
+}
+
+/// Adapts an `io::Write` to the formatter, holding back the last newline so
+/// the output matches `Scope::to_string`.
+struct IoSink<W> {
+    inner: W,
+    newline: &'static str,
+    pending: bool,
+    written: bool,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> IoSink<W> {
+    fn write(&mut self, buf: &[u8]) -> fmt::Result {
+        self.inner.write_all(buf).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+
+    /// Ends non-empty output with a newline, writing the held back one if
+    /// any.
+    fn flush_newline(&mut self) -> fmt::Result {
+        if !self.pending && !self.written {
+            return Ok(());
+        }
+        self.pending = false;
+        self.write(self.newline.as_bytes())
+    }
+}
+
+impl<W: io::Write> fmt::Write for IoSink<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        let (s, newline) = match s.strip_suffix(self.newline) {
+            Some(s) => (s, true),
+            None => (s, false),
+        };
+
+        if self.pending {
+            self.write(self.newline.as_bytes())?;
+        }
+        self.write(s.as_bytes())?;
+
+        self.written |= !s.is_empty();
+        self.pending = newline;
+        Ok(())
+    }
+}
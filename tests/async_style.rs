@@ -0,0 +1,82 @@
+use simple_codegen::*;
+
+fn fetch() -> Function {
+    Function::new("fetch")
+        .with_async(true)
+        .with_self_arg(SelfArg::WithSelfRef)
+        .with_arg("id", "u64")
+        .with_ret("String")
+}
+
+#[test]
+fn async_trait_styles() {
+    let mut scope = Scope::new();
+    scope.new_trait("Native").push_function(fetch());
+    scope
+        .new_trait("Desugared")
+        .set_async_style(AsyncTraitStyle::ImplFuture)
+        .push_function(fetch());
+    scope
+        .new_trait("Boxed")
+        .set_async_style(AsyncTraitStyle::BoxFuture)
+        .push_function(fetch());
+    scope
+        .new_trait("Annotated")
+        .set_async_style(AsyncTraitStyle::AsyncTrait)
+        .push_function(fetch());
+
+    let expect = r#"
+trait Native {
+    async fn fetch(&self, id: u64) -> String;
+}
+
+trait Desugared {
+    fn fetch(&self, id: u64) -> impl std::future::Future<Output = String> + Send;
+}
+
+trait Boxed {
+    fn fetch(&self, id: u64) -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send + '_>>;
+}
+
+#[async_trait::async_trait]
+trait Annotated {
+    async fn fetch(&self, id: u64) -> String;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn async_impl_styles() {
+    let mut scope = Scope::new();
+    scope.push_impl(
+        Impl::new("Client")
+            .with_impl_trait("Boxed")
+            .with_async_style(AsyncTraitStyle::BoxFuture)
+            .with_function(fetch().with_line("self.get(id).await")),
+    );
+    scope.push_impl(
+        Impl::new("Client")
+            .with_impl_trait("Annotated")
+            .with_async_style(AsyncTraitStyle::AsyncTrait)
+            .with_function(fetch().with_line("self.get(id).await")),
+    );
+
+    let expect = r#"
+impl Boxed for Client {
+    fn fetch(&self, id: u64) -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send + '_>> {
+        Box::pin(async move {
+            self.get(id).await
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Annotated for Client {
+    async fn fetch(&self, id: u64) -> String {
+        self.get(id).await
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,130 @@
+use simple_codegen::*;
+
+#[test]
+fn attribute_from_str() {
+    let attr = Attribute::from("allow(dead_code)");
+    assert_eq!(attr.path(), "allow");
+    assert_eq!(attr.args(), &AttributeArgs::List("dead_code".to_string()));
+
+    let attr = Attribute::from("path=\"foo.rs\"");
+    assert_eq!(attr, Attribute::value("path", "\"foo.rs\""));
+    assert_eq!(attr.to_string(), "path = \"foo.rs\"");
+
+    assert_eq!(Attribute::from("test"), Attribute::new("test"));
+    assert_eq!(
+        Attribute::from(Cfg::feature("json")),
+        Attribute::list("cfg", "feature = \"json\"")
+    );
+}
+
+#[test]
+fn attribute_derive_helper() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Foo")
+        .push_derive("Deserialize")
+        .push_attribute(Attribute::derive_helper("serde", "rename_all", "camelCase"))
+        .push_attribute("non_exhaustive")
+        .push_named_field(Field::new("one", "usize"));
+
+    let expect = r#"
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+struct Foo {
+    one: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn typed_serde_attributes() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("User")
+        .push_derive("Deserialize")
+        .push_attribute(
+            SerdeAttr::new()
+                .with_rename_all(RenameRule::Camel)
+                .with_deny_unknown_fields(),
+        )
+        .push_named_field(
+            Field::new("display_name", "Option<String>").with_annotation(
+                SerdeAttr::new()
+                    .with_default()
+                    .with_skip_serializing_if("Option::is_none"),
+            ),
+        );
+    scope
+        .new_enum("Event")
+        .push_derive("Deserialize")
+        .push_attribute(SerdeAttr::new().with_tag("type").with_content("data"))
+        .push_variant(
+            Variant::new("Login").with_annotation(SerdeAttr::new().with_rename("login")),
+        );
+
+    let expect = r#"
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct User {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "data")]
+enum Event {
+    #[serde(rename = "login")]
+    Login,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn attribute_from_outer_source() {
+    assert_eq!(
+        Attribute::from("#[serde(rename = \"a]\")]"),
+        Attribute::list("serde", "rename = \"a]\"")
+    );
+    assert_eq!(Attribute::from("#[test]"), Attribute::new("test"));
+}
+
+#[test]
+fn annotations_and_macros_skip_duplicates() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("User")
+        .push_attribute("serde_as")
+        .push_macro("#[serde_as]")
+        .push_macro("#[derive_more::Display]")
+        .push_macro("derive_more::Display")
+        .push_named_field(
+            Field::new("id", "u64")
+                .with_annotation(SerdeAttr::new().with_default())
+                .with_annotation("#[serde(default)]"),
+        );
+    scope.new_enum("Kind").push_variant(
+        Variant::new("A")
+            .with_doc("The first kind.")
+            .with_annotation("#[serde(rename = \"a\")]")
+            .with_annotation("serde(rename = \"a\")"),
+    );
+
+    let expect = r#"
+#[serde_as]
+#[derive_more::Display]
+struct User {
+    #[serde(default)]
+    id: u64,
+}
+
+enum Kind {
+    /// The first kind.
+    #[serde(rename = "a")]
+    A,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,84 @@
+use simple_codegen::*;
+
+#[test]
+fn bitflags_struct() {
+    let flags = Bitflags::new("Perms", "u8")
+        .with_vis(Vis::Pub)
+        .with_flag("READ", "1 << 0")
+        .with_flag("WRITE", "1 << 1");
+
+    let mut scope = Scope::new();
+    for item in flags.generate().unwrap() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Perms(u8);
+
+impl Perms {
+    pub const READ: Self = Self(1 << 0);
+    pub const WRITE: Self = Self(1 << 1);
+
+    /// Returns a set without any flag.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the raw bits of the set.
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Creates a set from raw bits, keeping unknown bits.
+    pub fn from_bits_retain(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns whether all the flags of `other` are set.
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Sets the flags of `other`.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clears the flags of `other`.
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl std::ops::BitOr for Perms {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for Perms {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn bitflags_validation() {
+    let err = Bitflags::new("Perms", "f32").generate().unwrap_err();
+    assert_eq!(err, BitflagsError::UnsupportedRepr("f32".to_string()));
+
+    let err = Bitflags::new("Perms", "u8")
+        .with_flag("READ", "1")
+        .with_flag("READ", "2")
+        .generate()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "duplicate flag: READ");
+}
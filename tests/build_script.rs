@@ -0,0 +1,34 @@
+use simple_codegen::build_script::BuildScriptOutput;
+use simple_codegen::*;
+
+#[test]
+fn build_script_output_skips_unchanged_files() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_out_{}", std::process::id()));
+    let output = BuildScriptOutput::new(&root);
+
+    let mut library = Library::new("ignored");
+    library.registry_mut().register("Uuid", "uuid");
+    library
+        .new_file("models.rs")
+        .scope_mut()
+        .new_struct("User")
+        .push_named_field(Field::new("id", "Uuid"));
+
+    assert_eq!(output.write_library(&library).expect("write"), 1);
+    assert_eq!(output.write_library(&library).expect("write"), 0);
+
+    let expect = r#"
+use uuid::Uuid;
+
+struct User {
+    id: Uuid,
+}
+"#;
+    let contents = std::fs::read_to_string(root.join("models.rs")).expect("read");
+    assert_eq!(contents, &expect[1..]);
+
+    library.files_mut()[0].scope_mut().new_struct("Group");
+    assert_eq!(output.write_library(&library).expect("write"), 1);
+
+    std::fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,49 @@
+use simple_codegen::*;
+
+#[test]
+fn case_conversions() {
+    assert_eq!(to_snake_case("HTTPServer"), "http_server");
+    assert_eq!(to_snake_case("fooBarBaz"), "foo_bar_baz");
+    assert_eq!(to_snake_case("Content-Type"), "content_type");
+    assert_eq!(to_camel_case("http_server"), "HttpServer");
+    assert_eq!(to_camel_case("in-progress"), "InProgress");
+    assert_eq!(to_camel_case("v1beta"), "V1beta");
+    assert_eq!(to_screaming_snake_case("maxRetries"), "MAX_RETRIES");
+    assert_eq!(Case::Snake.apply("someValue2"), "some_value2");
+}
+
+#[test]
+fn struct_fields_auto_case() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Request")
+        .push_named_field_auto_case(Field::new("requestId", "u64"))
+        .push_named_field_auto_case(Field::new("Content-Type", "String"))
+        .push_named_field_auto_case(Field::new("type", "String"));
+
+    let expect = r#"
+struct Request {
+    request_id: u64,
+    content_type: String,
+    r#type: String,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn enum_variants_auto_case() {
+    let mut scope = Scope::new();
+    scope
+        .new_enum("Status")
+        .push_variant_auto_case("in-progress")
+        .push_variant_auto_case("DONE");
+
+    let expect = r#"
+enum Status {
+    InProgress,
+    Done,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
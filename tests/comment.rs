@@ -0,0 +1,67 @@
+use simple_codegen::*;
+
+#[test]
+fn comment_items() {
+    let mut scope = Scope::new();
+    scope.push_comment("Generated by build.rs.\n\nDo not edit.");
+    scope.new_struct("Foo");
+    scope
+        .new_module("inner")
+        .push_comment(Comment::block("Internal types"))
+        .new_struct("Bar");
+
+    let expect = r#"
+// Generated by build.rs.
+//
+// Do not edit.
+struct Foo;
+
+mod inner {
+    /* Internal types */
+    struct Bar;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn comment_in_body() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("run")
+        .push_comment("Retry once.")
+        .push_block(
+            Block::new()
+                .with_comment(Comment::block("first attempt\nsecond attempt"))
+                .with_line("attempt();"),
+        );
+
+    let expect = r#"
+fn run() {
+    // Retry once.
+    {
+        /*
+         * first attempt
+         * second attempt
+         */
+        attempt();
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn comment_edge_cases() {
+    let mut scope = Scope::new();
+    scope.push_comment("");
+    scope.push_comment(Comment::block("matches */ in paths"));
+    scope.new_struct("Foo");
+
+    let expect = r#"
+//
+// matches */ in paths
+struct Foo;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,126 @@
+use simple_codegen::*;
+
+fn api_v1() -> Scope {
+    let mut scope = Scope::new();
+
+    scope
+        .new_function("connect")
+        .set_vis(Vis::Pub)
+        .push_arg("url", "&str")
+        .set_ret("Client")
+        .push_line("todo!()");
+
+    scope
+        .new_function("helper")
+        .push_arg("x", "usize")
+        .push_line("todo!()");
+
+    scope.new_impl("Client").push_function(
+        Function::new("send")
+            .with_vis(Vis::Pub)
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("body", "Vec<u8>")
+            .with_line("todo!()"),
+    );
+
+    scope
+        .new_module("admin")
+        .set_vis(Vis::Pub)
+        .new_function("reset")
+        .set_vis(Vis::Pub)
+        .push_line("todo!()");
+
+    scope
+}
+
+#[test]
+fn snapshot_public_functions() {
+    let snapshot = ApiSnapshot::from_scope(&api_v1());
+
+    let paths: Vec<_> = snapshot.functions().iter().map(|f| f.path()).collect();
+    assert_eq!(paths, ["connect", "Client::send", "admin::reset"]);
+
+    let send = snapshot.get_function("Client::send").unwrap();
+    assert_eq!(send.receiver(), Some("&self"));
+    assert_eq!(send.args(), [("body".to_string(), "Vec<u8>".to_string())]);
+    assert_eq!(send.ret(), None);
+}
+
+#[test]
+fn snapshot_breaking_changes() {
+    let old = ApiSnapshot::from_scope(&api_v1());
+
+    let mut scope = api_v1();
+    scope
+        .get_function_mut("connect")
+        .unwrap()
+        .set_ret("Result<Client, Error>");
+    scope
+        .get_function_mut("connect")
+        .unwrap()
+        .push_arg("timeout", "u64");
+    scope
+        .get_module_mut("admin")
+        .unwrap()
+        .set_vis(Vis::PubCrate);
+    scope
+        .new_function("extra")
+        .set_vis(Vis::Pub)
+        .push_line("todo!()");
+
+    let changes = old.breaking_changes(&ApiSnapshot::from_scope(&scope));
+    let messages: Vec<_> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "argument `timeout` was added to `connect`",
+            "return type of `connect` changed from `Client` to `Result<Client, Error>`",
+            "`admin::reset` was removed",
+        ]
+    );
+
+    assert!(old.is_compatible(&ApiSnapshot::from_scope(&api_v1())));
+}
+
+#[test]
+fn snapshot_compares_args_by_position() {
+    let old = ApiSnapshot::from_scope(&api_v1());
+
+    let mut scope = api_v1();
+    scope
+        .get_function_mut("connect")
+        .unwrap()
+        .args_mut()
+        .insert(0, Field::new("timeout", "u64"));
+    scope.get_function_mut("connect").unwrap().args_mut()[1].set_name("address");
+    scope
+        .items_mut()
+        .retain(|item| !matches!(item, Item::Impl(_)));
+    scope.new_impl("Client").push_function(
+        Function::new("send")
+            .with_vis(Vis::Pub)
+            .with_self_arg(SelfArg::WithSelf)
+            .with_arg("body", "Vec<u8>")
+            .with_line("todo!()"),
+    );
+
+    let changes = old.breaking_changes(&ApiSnapshot::from_scope(&scope));
+    let messages: Vec<_> = changes.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "argument `url` of `connect` changed type from `&str` to `u64`",
+            "argument `address` was added to `connect`",
+            "receiver of `Client::send` changed from `&self` to `self`",
+        ]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn snapshot_json_roundtrip() {
+    let snapshot = ApiSnapshot::from_scope(&api_v1());
+    let json = serde_json::to_string(&snapshot).unwrap();
+    let loaded: ApiSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded, snapshot);
+}
@@ -0,0 +1,64 @@
+use simple_codegen::*;
+
+#[test]
+fn complexity_within_limits() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("small")
+        .push_arg("a", "u32")
+        .push_line("a + 1");
+
+    assert_eq!(ComplexityLimits::new().check(&scope), vec![]);
+}
+
+#[test]
+fn complexity_issues() {
+    let mut scope = Scope::new();
+
+    let mut function = Function::new("build");
+    function
+        .set_self_arg(SelfArg::WithSelfRef)
+        .push_arg("a", "u32")
+        .push_arg("b", "u32")
+        .push_line("let x = a;")
+        .push_block(
+            Block::new()
+                .with_line("let y = b;")
+                .with_block(Block::new().with_line("x + y")),
+        );
+    scope
+        .new_module("models")
+        .new_impl("User")
+        .push_function(function);
+
+    let limits = ComplexityLimits::new()
+        .with_max_args(2)
+        .with_max_lines(2)
+        .with_max_depth(1);
+    let issues = limits.check(&scope);
+
+    assert_eq!(
+        issues,
+        vec![
+            ComplexityIssue::TooManyArgs {
+                path: "models::User::build".to_string(),
+                count: 3,
+                limit: 2,
+            },
+            ComplexityIssue::TooManyLines {
+                path: "models::User::build".to_string(),
+                count: 3,
+                limit: 2,
+            },
+            ComplexityIssue::TooDeep {
+                path: "models::User::build".to_string(),
+                depth: 2,
+                limit: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        issues[0].to_string(),
+        "`models::User::build` takes 3 arguments, more than 2"
+    );
+}
@@ -53,8 +53,7 @@ where T: Clone,
 {
     VariantA {
         test: String,
-    }
-    ,
+    },
     VariantB(usize),
     VariantC(T),
 }"#;
@@ -62,6 +61,35 @@ where T: Clone,
     assert_eq!(scope.to_string(), expect.trim_start());
 }
 
+#[test]
+fn enum_with_spaced_variants() {
+    let mut scope = Scope::new();
+    scope
+        .new_enum("Shape")
+        .set_spaced_variants(true)
+        .push_variant(
+            Variant::new("Rect")
+                .with_named_field("width", "f64")
+                .with_named_field("height", "f64"),
+        )
+        .push_variant(Variant::new("Circle").with_tuple_field("f64"))
+        .push_variant(Variant::new("Empty"));
+
+    let expect = r#"
+enum Shape {
+    Rect {
+        width: f64,
+        height: f64,
+    },
+
+    Circle(f64),
+
+    Empty,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
 #[test]
 fn enum_with_repr() {
     let mut scope = Scope::new();
@@ -103,3 +131,27 @@ enum IpAddrKind {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn enum_with_non_exhaustive_and_must_use() {
+    let mut scope = Scope::new();
+    scope
+        .new_enum("Error")
+        .push_derive("Debug")
+        .set_non_exhaustive(true)
+        .set_must_use(Some("errors should be \"handled\""))
+        .push_variant("Io")
+        .push_variant(Variant::new("Parse").with_non_exhaustive(true));
+
+    let expect = r#"
+#[derive(Debug)]
+#[non_exhaustive]
+#[must_use = "errors should be \"handled\""]
+enum Error {
+    Io,
+    #[non_exhaustive]
+    Parse,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
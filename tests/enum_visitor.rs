@@ -0,0 +1,80 @@
+use simple_codegen::*;
+
+#[test]
+fn enum_visitor_trait_and_accept() {
+    let expr = Enum::new("Expr")
+        .with_vis(Vis::Pub)
+        .with_variant(Variant::new("Lit").with_tuple_field("i64"))
+        .with_variant(
+            Variant::new("BinaryOp")
+                .with_named_field("lhs", "Box<Expr>")
+                .with_named_field("rhs", "Box<Expr>"),
+        )
+        .with_variant("Nil");
+
+    let mut scope = Scope::new();
+    for item in EnumVisitor::new(expr).generate() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+pub trait ExprVisitor {
+    type Output;
+
+    fn visit_lit(&mut self, f0: &i64) -> Self::Output;
+
+    fn visit_binary_op(&mut self, lhs: &Box<Expr>, rhs: &Box<Expr>) -> Self::Output;
+
+    fn visit_nil(&mut self) -> Self::Output;
+}
+
+impl Expr {
+    pub fn accept<V: ExprVisitor>(&self, __visitor: &mut V) -> V::Output {
+        match self {
+            Expr::Lit(f0) => __visitor.visit_lit(f0),
+            Expr::BinaryOp { lhs, rhs } => __visitor.visit_binary_op(lhs, rhs),
+            Expr::Nil => __visitor.visit_nil(),
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_visitor_generic_enum() {
+    let tree = Enum::new("Tree")
+        .with_generic("V")
+        .with_bound(Bound::new("V", ["Clone"]))
+        .with_variant(Variant::new("Leaf").with_named_field("visitor", "V"))
+        .with_variant(Variant::new("Node").with_tuple_field("Vec<Tree<V>>"));
+
+    let mut scope = Scope::new();
+    for item in EnumVisitor::new(tree).generate() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+trait TreeVisitor<V>
+where V: Clone,
+{
+    type Output;
+
+    fn visit_leaf(&mut self, visitor: &V) -> Self::Output;
+
+    fn visit_node(&mut self, f0: &Vec<Tree<V>>) -> Self::Output;
+}
+
+impl<V> Tree<V>
+where V: Clone,
+{
+    pub fn accept<__V: TreeVisitor<V>>(&self, __visitor: &mut __V) -> __V::Output {
+        match self {
+            Tree::Leaf { visitor } => __visitor.visit_leaf(visitor),
+            Tree::Node(f0) => __visitor.visit_node(f0),
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,71 @@
+use simple_codegen::*;
+
+#[test]
+fn error_enum_with_wrapped_sources() {
+    let error = ErrorEnum::new("ApiError")
+        .with_vis(Vis::Pub)
+        .with_variant("NotFound", "resource not found")
+        .with_wrapping_variant("Io", "i/o error: {source}", "std::io::Error")
+        .with_wrapping_variant("Json", "invalid payload", "serde_json::Error");
+
+    let mut scope = Scope::new();
+    for item in error.generate().unwrap() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound,
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "resource not found"),
+            Self::Io(source) => write!(f, "i/o error: {source}"),
+            Self::Json(_) => write!(f, "invalid payload"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(source) => Some(source),
+            Self::Json(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ApiError {
+    fn from(source: std::io::Error) -> Self {
+        Self::Io(source)
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json(source)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn error_enum_rejects_duplicate_sources() {
+    let err = ErrorEnum::new("ApiError")
+        .with_wrapping_variant("Read", "read failed", "std::io::Error")
+        .with_wrapping_variant("Write", "write failed", "std::io::Error")
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ErrorEnumError::DuplicateSource("std::io::Error".to_string())
+    );
+    assert_eq!(err.to_string(), "several variants wrap `std::io::Error`");
+}
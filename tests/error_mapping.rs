@@ -0,0 +1,58 @@
+use simple_codegen::*;
+
+fn io_error() -> Enum {
+    Enum::new("IoError")
+        .with_variant("NotFound")
+        .with_variant(Variant::new("Denied").with_tuple_field("String"))
+        .with_variant(Variant::new("Other").with_named_field("code", "i32"))
+}
+
+#[test]
+fn error_mapping_exhaustive() {
+    let mapping = ErrorMapping::new(io_error(), "AppError")
+        .with_arm("NotFound", "AppError::Missing")
+        .with_arm("Denied", "AppError::Forbidden(f0)")
+        .with_default("AppError::Unknown");
+
+    assert_eq!(mapping.unmapped(), ["Other"]);
+
+    let mut scope = Scope::new();
+    scope.push_impl(mapping.generate().unwrap());
+
+    let expect = r#"
+impl From<IoError> for AppError {
+    #[allow(unused_variables)]
+    fn from(value: IoError) -> Self {
+        match value {
+            IoError::NotFound => AppError::Missing,
+            IoError::Denied(f0) => AppError::Forbidden(f0),
+            IoError::Other { .. } => AppError::Unknown,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn error_mapping_validation() {
+    let err = ErrorMapping::new(io_error(), "AppError")
+        .with_arm("NotFound", "AppError::Missing")
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ErrorMappingError::Unmapped(vec!["Denied".to_string(), "Other".to_string()])
+    );
+    assert_eq!(err.to_string(), "unmapped source variants: Denied, Other");
+
+    let err = ErrorMapping::new(io_error(), "AppError")
+        .with_arm("Gone", "AppError::Missing")
+        .with_default("AppError::Unknown")
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ErrorMappingError::UnknownVariants(vec!["Gone".to_string()])
+    );
+}
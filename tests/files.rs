@@ -0,0 +1,477 @@
+use simple_codegen::*;
+
+fn scope_with_consts(count: usize) -> Scope {
+    let mut scope = Scope::new();
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+    for i in 0..count {
+        scope.raw(format!("pub const VALUE_{}: usize = {};", i, i));
+    }
+    scope
+}
+
+#[test]
+fn library_new_file() {
+    let mut library = Library::new("out");
+    library
+        .new_file("src/lib.rs")
+        .scope_mut()
+        .new_module("foo")
+        .set_vis(Vis::Pub);
+
+    let file = library.get_file("src/lib.rs").expect("file");
+    assert_eq!(file.render(), "pub mod foo;");
+}
+
+#[test]
+fn library_paginated_scope() {
+    let mut library = Library::new("out");
+    library.push_scope_paginated("src/table", scope_with_consts(5), 8);
+
+    let paths: Vec<_> = library
+        .files()
+        .iter()
+        .map(|f| f.path().to_path_buf())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "src/table/part_0.rs",
+            "src/table/part_1.rs",
+            "src/table/mod.rs"
+        ]
+        .map(std::path::PathBuf::from)
+    );
+
+    let expect = r#"
+use std::fmt::Debug;
+use super::*;
+
+pub const VALUE_0: usize = 0;
+
+pub const VALUE_1: usize = 1;
+
+pub const VALUE_2: usize = 2;"#;
+
+    assert_eq!(library.files()[0].render(), expect.trim_start());
+
+    let expect = r#"
+pub use part_0::*;
+pub use part_1::*;
+
+mod part_0;
+mod part_1;"#;
+
+    assert_eq!(library.files()[2].render(), expect.trim_start());
+}
+
+#[test]
+fn library_paginated_scope_counts_written_lines() {
+    let mut scope = Scope::new();
+    for i in 0..4 {
+        scope.push_const(
+            ConstItem::new(format!("VALUE_{}", i), "usize", i.to_string()).with_compact(true),
+        );
+    }
+    scope
+        .new_struct("Row")
+        .push_named_field(Field::new("id", "usize"));
+    scope.new_impl("Row").push_function(
+        Function::new("id")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_ret("usize")
+            .with_line("self.id"),
+    );
+
+    let mut library = Library::new("out");
+    library.push_scope_paginated("src/table", scope, 6);
+
+    let expect = r#"
+use super::*;
+
+pub(super) const VALUE_0: usize = 0;
+pub(super) const VALUE_1: usize = 1;
+pub(super) const VALUE_2: usize = 2;
+pub(super) const VALUE_3: usize = 3;"#;
+    assert_eq!(library.files()[0].render(), expect.trim_start());
+
+    let expect = r#"
+use super::*;
+
+pub(super) struct Row {
+    pub(super) id: usize,
+}"#;
+    assert_eq!(library.files()[1].render(), expect.trim_start());
+
+    let expect = r#"
+use super::*;
+
+impl Row {
+    pub(super) fn id(&self) -> usize {
+        self.id
+    }
+}"#;
+    assert_eq!(library.files()[2].render(), expect.trim_start());
+    for file in &library.files()[..2] {
+        assert!(file.render().lines().count() <= 6);
+    }
+}
+
+#[test]
+fn library_generate() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_files_{}", std::process::id()));
+    let mut library = Library::new(&root);
+    library.push_scope_paginated("src/table", scope_with_consts(2), 100);
+    library.generate().into_result().expect("generate");
+
+    let contents = std::fs::read_to_string(root.join("src/table/mod.rs")).expect("read");
+    assert_eq!(contents, "pub use part_0::*;\n\nmod part_0;\n");
+    assert!(root.join("src/table/part_0.rs").exists());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn library_render_in_memory() {
+    let mut library = Library::new("does/not/exist");
+    library.registry_mut().register("Uuid", "uuid");
+    library
+        .new_file("src/models.rs")
+        .scope_mut()
+        .new_struct("User")
+        .push_named_field(Field::new("id", "Uuid"));
+    library
+        .new_file("src/lib.rs")
+        .scope_mut()
+        .push_mod_decl(ModDecl::new("models"));
+
+    let rendered = library.render();
+    let paths: Vec<_> = rendered.keys().collect();
+    assert_eq!(paths, ["src/lib.rs", "src/models.rs"]);
+    assert_eq!(
+        rendered[std::path::Path::new("src/lib.rs")],
+        "mod models;\n"
+    );
+    assert_eq!(
+        rendered[std::path::Path::new("src/models.rs")],
+        "use uuid::Uuid;\n\nstruct User {\n    id: Uuid,\n}\n"
+    );
+    assert!(!std::path::Path::new("does").exists());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn library_generate_parallel() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_par_{}", std::process::id()));
+    let mut library = Library::new(&root);
+    library.push_scope_paginated("src/table", scope_with_consts(40), 4);
+    // `blocked` is a file, so no directory can be created at its path.
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("blocked"), "").unwrap();
+    library.new_file("blocked/nested.rs");
+
+    let report = library.generate_parallel();
+    let expected: Vec<_> = library.files().iter().map(|f| f.path()).collect();
+    let mut reported: Vec<_> = report.written().iter().map(|p| p.as_path()).collect();
+    reported.push(&report.failures()[0].0);
+    assert_eq!(reported, expected);
+    assert_eq!(
+        library.render()[std::path::Path::new("src/table/part_3.rs")],
+        std::fs::read_to_string(root.join("src/table/part_3.rs")).unwrap()
+    );
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn library_generate_reports_failures() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_report_{}", std::process::id()));
+    let mut library = Library::new(&root);
+    library.new_file("src/lib.rs");
+    // `src/lib.rs` is a file, so no directory can be created at its path.
+    library.new_file("src/lib.rs/nested.rs");
+    library.new_file("src/util.rs");
+
+    let report = library.generate();
+    assert!(!report.is_success());
+    assert_eq!(
+        report.written(),
+        [
+            std::path::PathBuf::from("src/lib.rs"),
+            std::path::PathBuf::from("src/util.rs")
+        ]
+    );
+    assert_eq!(report.failures().len(), 1);
+    assert_eq!(
+        report.failures()[0].0,
+        std::path::Path::new("src/lib.rs/nested.rs")
+    );
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn file_generate_newline_style() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_crlf_{}", std::process::id()));
+
+    let mut file = File::new("lib.rs").with_newline_style(NewlineStyle::CrLf);
+    file.scope_mut()
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"));
+    file.generate(&root).expect("generate");
+
+    let contents = std::fs::read_to_string(root.join("lib.rs")).expect("read");
+    assert_eq!(contents, "struct Foo {\r\n    one: usize,\r\n}\r\n");
+
+    file.set_trailing_newline(false)
+        .generate(&root)
+        .expect("generate");
+    let contents = std::fs::read_to_string(root.join("lib.rs")).expect("read");
+    assert_eq!(contents, "struct Foo {\r\n    one: usize,\r\n}");
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn file_content_hash() {
+    let root = std::env::temp_dir().join(format!("simple_codegen_hash_{}", std::process::id()));
+
+    let mut file = File::new("src/lib.rs").with_content_hash(true);
+    file.scope_mut()
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"));
+    assert!(!file.is_up_to_date(&root));
+
+    file.generate(&root).expect("generate");
+    assert!(file.is_up_to_date(&root));
+
+    let expect = r#"
+// @generated-hash: 71df7e9cb9f56e1562461e27b1dc97e3c19c5f56784bf88268ec0bbe8c0df3d5
+struct Foo {
+    one: usize,
+}
+"#;
+    let contents = std::fs::read_to_string(root.join("src/lib.rs")).expect("read");
+    assert_eq!(contents, &expect[1..]);
+
+    file.scope_mut().new_struct("Bar");
+    assert!(!file.is_up_to_date(&root));
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn library_resolves_associated_item_imports() {
+    let mut library = Library::new("out");
+    library
+        .new_file("src/models.rs")
+        .scope_mut()
+        .new_struct("User")
+        .set_vis(Vis::Pub);
+    library
+        .new_file("src/users.rs")
+        .scope_mut()
+        .new_impl("Users")
+        .set_impl_trait("Iterator")
+        .push_associated_type(AssociatedType::new_with_concrete_ty("Item", "User"))
+        .push_function(
+            Function::new("next")
+                .with_self_arg(SelfArg::WithMutSelfRef)
+                .with_ret("Option<Self::Item>")
+                .with_line("None"),
+        );
+
+    let expect = r#"
+use crate::models::User;
+
+impl Iterator for Users {
+    type Item = User;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        None
+    }
+}"#;
+
+    let file = library.get_file("src/users.rs").unwrap();
+    assert_eq!(library.resolve_imports(file).render(), &expect[1..]);
+}
+
+#[test]
+fn library_resolve_imports_skips_literals_and_comments() {
+    let mut library = Library::new("out");
+    library
+        .new_file("src/models.rs")
+        .scope_mut()
+        .new_struct("Foo")
+        .set_vis(Vis::Pub);
+    library
+        .new_file("src/api.rs")
+        .scope_mut()
+        .new_function("describe")
+        .set_ret("&'static str")
+        .push_comment("Foo is not used here")
+        .push_line("let _ = 'F'; // Foo")
+        .push_line("/* Foo */ r#\"Foo\"#;")
+        .push_line("\"Foo\"");
+
+    let expect = r##"
+fn describe() -> &'static str {
+    // Foo is not used here
+    let _ = 'F'; // Foo
+    /* Foo */ r#"Foo"#;
+    "Foo"
+}"##;
+
+    let file = library.get_file("src/api.rs").unwrap();
+    assert_eq!(library.resolve_imports(file).render(), &expect[1..]);
+}
+
+#[test]
+fn library_resolves_cross_file_imports() {
+    let mut library = Library::new("out");
+    library.registry_mut().register("Uuid", "uuid");
+
+    let models = library.new_file("src/models/mod.rs").scope_mut();
+    models
+        .new_struct("User")
+        .set_vis(Vis::Pub)
+        .push_derive("Default")
+        .push_named_field(Field::new("id", "Uuid"));
+    models
+        .new_module("group")
+        .set_vis(Vis::Pub)
+        .new_struct("Group")
+        .set_vis(Vis::Pub)
+        .push_named_field(Field::new("owner", "User"));
+
+    let api = library.new_file("src/api.rs").scope_mut();
+    api.push_import("crate::models::group", "Group", Vis::Private);
+    api.new_function("get_user")
+        .set_ret("Option<User>")
+        .push_arg("group", "&Group")
+        .push_line("let _ = crate::models::User::default();")
+        .push_line("None");
+
+    let expect = r#"
+use crate::models::group::Group;
+use crate::models::User;
+
+fn get_user(group: &Group) -> Option<User> {
+    let _ = crate::models::User::default();
+    None
+}"#;
+
+    let file = library.get_file("src/api.rs").unwrap();
+    assert_eq!(file.module_path(), "crate::api");
+    assert_eq!(library.resolve_imports(file).render(), &expect[1..]);
+
+    let expect = r#"
+use uuid::Uuid;
+
+#[derive(Default)]
+pub struct User {
+    id: Uuid,
+}
+
+pub mod group {
+    use crate::models::User;
+
+    pub struct Group {
+        owner: User,
+    }
+}"#;
+
+    let file = library.get_file("src/models/mod.rs").unwrap();
+    assert_eq!(file.module_path(), "crate::models");
+    assert_eq!(library.resolve_imports(file).render(), &expect[1..]);
+}
+
+#[test]
+fn library_feature_requirements() {
+    let mut library = Library::new("out");
+
+    let lib = library.new_file("src/lib.rs").scope_mut();
+    lib.new_struct("Json").push_attribute(Cfg::feature("json"));
+    lib.new_function("encode")
+        .push_attribute(Cfg::all([
+            Cfg::feature("json"),
+            Cfg::not(Cfg::feature("std")),
+        ]))
+        .push_line("todo!()");
+    lib.new_module("unix").push_attribute(Cfg::raw("unix"));
+    lib.new_function("simd")
+        .push_attribute("cfg(target_feature = \"avx2\")")
+        .push_attribute("cfg_attr(feature = \"inline\", inline)")
+        .push_line("todo!()");
+    lib.new_impl("Json").push_function(
+        Function::new("parse")
+            .with_cfg(Cfg::feature("parse"))
+            .with_line("todo!()"),
+    );
+    lib.push_item(ModDecl::new("extra").with_cfg(Cfg::feature("extra")));
+
+    assert_eq!(
+        library
+            .referenced_features()
+            .into_iter()
+            .collect::<Vec<_>>(),
+        ["extra", "inline", "json", "parse", "std"]
+    );
+
+    library
+        .push_feature("json")
+        .push_feature("extra")
+        .push_feature("inline")
+        .push_feature("parse");
+    library.set_manifest(Manifest::new("lib"));
+    let err = library.resolved_manifest().unwrap_err();
+    assert_eq!(err, FeatureError::Undeclared(vec!["std".to_string()]));
+    assert_eq!(err.to_string(), "features referenced but not declared: std");
+    assert!(
+        !library
+            .render()
+            .contains_key(std::path::Path::new("Cargo.toml"))
+    );
+
+    library.push_feature("std");
+    assert!(library.check_features().is_ok());
+    assert!(library.resolved_manifest().unwrap().is_some());
+}
+
+#[test]
+fn library_benches_and_examples() {
+    let mut library = Library::new("out");
+    library
+        .push_bench(File::new("parse.rs"))
+        .push_bench(File::new("benches/render/main.rs"))
+        .push_bench(File::new("render/util.rs"))
+        .push_example(File::new("basic.rs"));
+
+    let paths: Vec<_> = library.files().iter().map(|f| f.path()).collect();
+    assert_eq!(
+        paths,
+        [
+            "benches/parse.rs",
+            "benches/render/main.rs",
+            "benches/render/util.rs",
+            "examples/basic.rs"
+        ]
+        .map(std::path::Path::new)
+    );
+    assert_eq!(library.benches(), ["parse", "render"]);
+    assert_eq!(library.examples(), ["basic"]);
+
+    let expect = r#"
+[[bench]]
+name = "parse"
+harness = false
+
+[[bench]]
+name = "render"
+harness = false
+
+[[example]]
+name = "basic"
+"#;
+
+    assert_eq!(library.targets_table(), expect.trim_start());
+}
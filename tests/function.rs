@@ -68,3 +68,342 @@ where T: Clone,
 
     assert_eq!(scope.to_string(), expect.trim_start());
 }
+
+#[test]
+fn function_with_self_lifetime() {
+    let mut r#trait = Trait::new("Store");
+    r#trait
+        .push_function(
+            Function::new("get")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret(Type::new("Option").with_generic("&str"))
+                .with_self_lifetime("a"),
+        )
+        .push_function(
+            Function::new("entry")
+                .with_generic("'b")
+                .with_self_arg(SelfArg::WithMutSelfRef)
+                .with_arg("key", "&'b str")
+                .with_ret("&mut Vec<&'b str>")
+                .with_self_lifetime("'a"),
+        );
+
+    let mut scope = Scope::new();
+    scope.push_trait(r#trait);
+
+    let expect = r#"
+trait Store {
+    fn get<'a>(&'a self) -> Option<&'a str>;
+
+    fn entry<'a, 'b>(&'a mut self, key: &'b str) -> &'a mut Vec<&'b str>;
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn function_with_typed_self() {
+    let mut r#trait = Trait::new("Task");
+    r#trait
+        .push_function(
+            Function::new("poll")
+                .with_self_arg(SelfArg::typed(Type::new("Pin").with_generic("&mut Self")))
+                .with_ret("bool"),
+        )
+        .push_function(
+            Function::new("spawn")
+                .with_self_arg(SelfArg::typed(Type::arc("Self")))
+                .with_arg("name", "String"),
+        );
+
+    let mut scope = Scope::new();
+    scope.push_trait(r#trait);
+
+    let expect = r#"
+trait Task {
+    fn poll(self: Pin<&mut Self>) -> bool;
+
+    fn spawn(self: alloc::sync::Arc<Self>, name: String);
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn call_with_turbofish() {
+    let function = Function::new("convert")
+        .with_generic("'a")
+        .with_generic("T: Clone")
+        .with_generic("const N: usize")
+        .with_arg("value", "&'a [T; N]");
+
+    assert_eq!(function.turbofish(), "convert::<T, N>");
+    assert_eq!(function.call(["input"]), "convert::<T, N>(input)");
+    assert_eq!(
+        function.call_with(["u8", "4"], ["&bytes"]),
+        "convert::<u8, 4>(&bytes)"
+    );
+
+    let method = Function::new("len").with_self_arg(SelfArg::WithSelfRef);
+    assert_eq!(method.call(Vec::<String>::new()), "self.len()");
+}
+
+#[test]
+fn function_with_cfg() {
+    let mut r#trait = Trait::new("Store");
+    r#trait.push_function(
+        Function::new("dump")
+            .with_cfg(Cfg::feature("debug"))
+            .with_attribute("must_use")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_ret("String"),
+    );
+
+    let mut r#impl = Impl::new("Memory").with_impl_trait("Store");
+    r#impl.push_function(r#trait.functions()[0].clone().with_line("String::new()"));
+
+    let mut scope = Scope::new();
+    scope.push_trait(r#trait).push_impl(r#impl);
+
+    let expect = r#"
+trait Store {
+    #[cfg(feature = "debug")]
+    #[must_use]
+    fn dump(&self) -> String;
+}
+
+impl Store for Memory {
+    #[cfg(feature = "debug")]
+    #[must_use]
+    fn dump(&self) -> String {
+        String::new()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_with_raw_indented_snippet() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("run")
+        .push_line("let x = 1;")
+        .push_block(Block::new().with_raw_indented(
+            "
+            if x > 0 {
+                println!(\"positive\");
+            }
+            ",
+        ));
+
+    let expect = r#"
+fn run() {
+    let x = 1;
+    {
+        if x > 0 {
+            println!("positive");
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_with_must_use() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("len")
+        .set_must_use(None)
+        .set_ret("usize")
+        .push_line("0");
+
+    let expect = r#"
+#[must_use]
+fn len() -> usize {
+    0
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_with_result_and_option_returns() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("parse")
+        .push_arg("s", "&str")
+        .set_ret_result("u32", "ParseIntError")
+        .push_line("s.parse()");
+    scope
+        .new_function("first")
+        .push_arg("v", "&[u32]")
+        .set_ret_option("u32")
+        .push_line("v.first().copied()");
+
+    let expect = r#"
+fn parse(s: &str) -> Result<u32, ParseIntError> {
+    s.parse()
+}
+
+fn first(v: &[u32]) -> Option<u32> {
+    v.first().copied()
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn scope_default_error_is_inherited_by_modules() {
+    let mut scope = Scope::new();
+    scope.new_module("admin");
+    scope.set_default_error("ApiError");
+    scope
+        .new_fallible_function("ping", "()")
+        .unwrap()
+        .push_line("Ok(())");
+    scope
+        .new_module("users")
+        .new_fallible_function("get_user", "User")
+        .unwrap()
+        .push_arg("id", "u64")
+        .push_line("todo!()");
+    scope.push_module(Module::new("jobs"));
+    for name in ["admin", "jobs"] {
+        scope
+            .get_module_mut(name)
+            .unwrap()
+            .new_fallible_function("run", "()")
+            .unwrap()
+            .push_line("Ok(())");
+    }
+
+    let expect = r#"
+mod admin {
+    fn run() -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+fn ping() -> Result<(), ApiError> {
+    Ok(())
+}
+
+mod users {
+    fn get_user(id: u64) -> Result<User, ApiError> {
+        todo!()
+    }
+}
+
+mod jobs {
+    fn run() -> Result<(), ApiError> {
+        Ok(())
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+
+    let err = Scope::new()
+        .new_fallible_function("ping", "()")
+        .unwrap_err();
+    assert_eq!(err.to_string(), "the scope has no default error type");
+}
+
+#[test]
+fn function_with_bound_on_generic_type() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("dump")
+        .push_generic("T")
+        .push_arg("v", "Vec<T>")
+        .push_bound(Bound::for_type(Type::vec("T"), ["Debug"]))
+        .push_line("println!(\"{:?}\", v);");
+
+    let expect = r#"
+fn dump<T>(v: Vec<T>)
+where Vec<T>: Debug,
+{
+    println!("{:?}", v);
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn function_with_args_wrapped_past_max_width() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Server")
+        .set_derives(["Clone", "Copy", "PartialEq", "Eq", "Hash", "Debug"]);
+    scope.new_impl("Server").push_function(
+        Function::new("listen")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("address", "std::net::SocketAddr")
+            .with_arg("backlog", "u32")
+            .with_ret("std::io::Result<()>")
+            .with_line("Ok(())"),
+    );
+    scope.new_function("stop").push_line("todo!()");
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_max_width(40))
+        .unwrap();
+
+    let expect = r#"
+#[derive(
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Debug,
+)]
+struct Server;
+
+impl Server {
+    fn listen(
+        &self,
+        address: std::net::SocketAddr,
+        backlog: u32,
+    ) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn stop() {
+    todo!()
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn function_with_attributed_statements() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("setup")
+        .push_attributed_line("allow(unused_variables)", "let config = load();")
+        .push_attributed_block(
+            Attribute::list("cfg", "unix"),
+            Block::new().with_attributed_line(
+                Attribute::list("cfg", "debug_assertions"),
+                "eprintln!(\"installing handlers\");",
+            ),
+        );
+
+    let expect = r#"
+fn setup() {
+    #[allow(unused_variables)]
+    let config = load();
+    #[cfg(unix)]
+    {
+        #[cfg(debug_assertions)]
+        eprintln!("installing handlers");
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "golden")]
+
+use simple_codegen::testing::{bless_golden, check_golden};
+use simple_codegen::*;
+
+#[test]
+fn golden_file_roundtrip() {
+    let path = std::env::temp_dir()
+        .join(format!("simple_codegen_golden_{}", std::process::id()))
+        .join("foo.rs");
+
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"));
+
+    assert!(check_golden(scope.to_string(), &path).is_err());
+
+    bless_golden(scope.to_string(), &path).unwrap();
+    assert_golden!(scope, &path);
+
+    scope
+        .get_struct_mut("Foo")
+        .unwrap()
+        .push_named_field(Field::new("two", "String"));
+    let err = check_golden(scope.to_string(), &path).unwrap_err();
+    assert!(err.contains("+    two: String,"), "{}", err);
+
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
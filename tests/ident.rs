@@ -0,0 +1,57 @@
+use simple_codegen::*;
+
+#[test]
+fn keywords_are_detected() {
+    assert!(is_keyword("type"));
+    assert!(is_keyword("async"));
+    assert!(is_keyword("gen"));
+    assert!(!is_keyword("types"));
+}
+
+#[test]
+fn valid_idents() {
+    assert!(is_valid_ident("foo_bar"));
+    assert!(is_valid_ident("_foo"));
+    assert!(is_valid_ident("r#type"));
+    assert!(!is_valid_ident("type"));
+    assert!(!is_valid_ident("r#self"));
+    assert!(!is_valid_ident("1foo"));
+    assert!(!is_valid_ident("foo-bar"));
+    assert!(!is_valid_ident("_"));
+    assert!(!is_valid_ident(""));
+}
+
+#[test]
+fn escape_keywords() {
+    assert_eq!(escape_ident("match"), "r#match");
+    assert_eq!(escape_ident("self"), "self_");
+    assert_eq!(escape_ident("Self"), "Self_");
+    assert_eq!(escape_ident("value"), "value");
+}
+
+#[test]
+fn sanitize_external_names() {
+    assert_eq!(sanitize_ident("type"), "r#type");
+    assert_eq!(sanitize_ident("content-type"), "content_type");
+    assert_eq!(sanitize_ident("2fa"), "_2fa");
+    assert_eq!(sanitize_ident("a.b c"), "a_b_c");
+    assert_eq!(sanitize_ident("r#type"), "r#type");
+    assert_eq!(sanitize_ident(""), "_unnamed");
+}
+
+#[test]
+fn sanitized_field_names() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Response")
+        .push_named_field(Field::new(sanitize_ident("type"), "String"))
+        .push_named_field(Field::new(sanitize_ident("content-length"), "usize"));
+
+    let expect = r#"
+struct Response {
+    r#type: String,
+    content_length: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
@@ -60,9 +60,9 @@ impl MyStruct {
 #[test]
 fn impl_with_associated_types() {
     let mut scope = Scope::new();
-    scope.new_impl("MyStruct").push_associated_type(
-        AssociatedType::new("MY_TYPE").with_concrete_ty("usize", Vec::<String>::new()),
-    );
+    scope
+        .new_impl("MyStruct")
+        .push_associated_type(AssociatedType::new("MY_TYPE").with_concrete_ty("usize"));
     let expect = r#"
 impl MyStruct {
     type MY_TYPE = usize;
@@ -101,6 +101,27 @@ impl MyStruct {
     assert_eq!(scope.to_string(), expect.trim_start());
 }
 
+#[test]
+fn impl_with_doc_lints_and_attributes() {
+    let mut scope = Scope::new();
+    scope
+        .new_impl("MyStruct")
+        .set_doc("Conversions.")
+        .push_lint(Lint::allow("clippy::needless_lifetimes"))
+        .push_attribute("automatically_derived")
+        .push_macro("#[async_trait::async_trait]");
+
+    let expect = r#"
+/// Conversions.
+#[allow(clippy::needless_lifetimes)]
+#[automatically_derived]
+#[async_trait::async_trait]
+impl MyStruct {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
 #[test]
 fn impl_with_single_function() {
     let mut scope = Scope::new();
@@ -149,3 +170,216 @@ impl MyStruct {
 
     assert_eq!(scope.to_string(), expect.trim_start());
 }
+
+#[test]
+fn remote_orphan_rule() {
+    let r#impl = Impl::new_remote("chrono::DateTime<Utc>")
+        .unwrap()
+        .with_impl_trait("serde::Serialize");
+    assert_eq!(r#impl.origin(), Some("chrono"));
+
+    let err = r#impl.check_orphan_rule().unwrap_err();
+    assert_eq!(err.trait_origin, "serde");
+    assert_eq!(err.origin, "chrono");
+    assert_eq!(err.target, "chrono::DateTime<Utc>");
+
+    let mut scope = Scope::new();
+    let uuid = Impl::new_remote("uuid::Uuid").unwrap();
+    scope.push_impl(uuid.clone().with_impl_trait("Clone"));
+    scope.push_impl(
+        uuid.clone()
+            .with_impl_trait(Type::new("From").with_generic("MyId")),
+    );
+    scope.push_impl(
+        uuid.clone()
+            .with_impl_trait(Type::new("From").with_generic("&'a MyId")),
+    );
+    scope.push_impl(
+        uuid.clone()
+            .with_impl_trait(Type::new("From").with_generic("&'a str")),
+    );
+    scope.push_impl(uuid.with_impl_trait("crate::Identify"));
+    scope.push_impl(Impl::new("uuid::Uuid").with_impl_trait("Clone"));
+
+    let errors = scope.check_orphan_rule();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        errors[0].to_string(),
+        "impl of foreign trait `Clone` from `std` for `uuid::Uuid` from `uuid` breaks the orphan rule"
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "impl of foreign trait `From<&'a str>` from `std` for `uuid::Uuid` from `uuid` breaks the orphan rule"
+    );
+
+    let err = Impl::new_remote("Uuid").unwrap_err();
+    assert_eq!(err.target(), "Uuid");
+    assert_eq!(
+        err.to_string(),
+        "remote impl target `Uuid` has no crate path"
+    );
+}
+
+#[test]
+fn impl_with_multiline_where_clause() {
+    let mut scope = Scope::new();
+    scope.push_impl(
+        Impl::new(Type::new("Cache").with_generic("K").with_generic("V"))
+            .with_generic("K")
+            .with_generic("V")
+            .with_bound(Bound::new("K", ["Hash", "Eq"]))
+            .with_bound(Bound::new("V", ["Clone"]))
+            .with_function(
+                Function::new("clear")
+                    .with_self_arg(SelfArg::WithMutSelfRef)
+                    .with_line("self.map.clear();"),
+            ),
+    );
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_where_style(WhereStyle::Multiline))
+        .unwrap();
+
+    let expect = r#"
+impl<K, V> Cache<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn impl_with_documented_consts() {
+    let mut scope = Scope::new();
+    scope
+        .new_impl("Limits")
+        .push_const(
+            ConstItem::new("MAX_LEN", "usize", "1_000_000")
+                .with_vis(Vis::Pub)
+                .with_doc("The longest accepted input.")
+                .with_lint(Lint::allow("clippy::unreadable_literal")),
+        )
+        .push_const(ConstItem::new("MIN_LEN", "usize", "1").with_compact(true));
+
+    let expect = r#"
+impl Limits {
+    /// The longest accepted input.
+    #[allow(clippy::unreadable_literal)]
+    pub const MAX_LEN: usize = 1_000_000;
+    const MIN_LEN: usize = 1;
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn impl_rejects_static() {
+    let mut imp = Impl::new("Limits");
+    let err = imp
+        .try_push_const(ConstItem::new_static("HITS", "usize", "0"))
+        .unwrap_err();
+
+    assert_eq!(err.name(), "HITS");
+    assert!(imp.consts().is_empty());
+}
+
+#[test]
+fn module_with_static() {
+    let mut scope = Scope::new();
+    let module = scope.new_module("counters");
+    module
+        .new_static("HITS", "AtomicUsize", "AtomicUsize::new(0)")
+        .set_vis(Vis::PubCrate);
+    module
+        .new_static("BUF", "[u8; 4]", "[0; 4]")
+        .set_kind(ConstKind::StaticMut);
+
+    let expect = r#"
+mod counters {
+    pub(crate) static HITS: AtomicUsize = AtomicUsize::new(0);
+
+    static mut BUF: [u8; 4] = [0; 4];
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn blanket_impls() {
+    let mut scope = Scope::new();
+    scope.push_impl(
+        Impl::new_blanket("ToJson", "T: serde::Serialize + ?Sized").with_function(
+            Function::new("to_json")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret("String")
+                .with_line("serde_json::to_string(self).unwrap()"),
+        ),
+    );
+    scope.push_impl(Impl::new_blanket_for(
+        "Marker",
+        "Wrapper<'a, T, N>",
+        ["'a", "T: Clone", "const N: usize"],
+    ));
+
+    let expect = r#"
+impl<T: serde::Serialize + ?Sized> ToJson for T {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+impl<'a, T: Clone, const N: usize> Marker for Wrapper<'a, T, N> {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_with_undeclared_generic() {
+    let r#impl = Impl::new("Wrapper<'a, K, V>")
+        .with_generic("K")
+        .with_impl_trait("Clone");
+    let err = r#impl.check_generics().unwrap_err();
+
+    assert_eq!(
+        err,
+        ImplGenericsError::UndeclaredLifetime {
+            lifetime: "'a".to_string(),
+            target: "Wrapper<'a, K, V>".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "lifetime `'a` of impl target `Wrapper<'a, K, V>` is not declared on the impl"
+    );
+    assert!(
+        r#impl
+            .with_generics(["'a", "K", "V: Clone"])
+            .check_generics()
+            .is_ok()
+    );
+
+    let err = Impl::new("Wrapper<T>")
+        .with_generics(["T", "Item"])
+        .check_generics()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "generic `Item` is not used by impl target `Wrapper<T>` or its trait"
+    );
+    assert!(
+        Impl::new("Wrapper")
+            .with_generic("Item")
+            .with_impl_trait(Type::new("From").with_generic("Item"))
+            .check_generics()
+            .is_ok()
+    );
+}
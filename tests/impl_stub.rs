@@ -0,0 +1,92 @@
+use simple_codegen::*;
+
+fn store() -> Trait {
+    let mut r#trait = Trait::new("Store");
+    r#trait
+        .push_associated_type(AssociatedType::new("Item"))
+        .push_associated_type(AssociatedType::new("Error"))
+        .push_associated_const(AssociatedConst::new("NAME", "&'static str"))
+        .push_associated_const(AssociatedConst::new("LIMIT", "usize").with_concrete_value("10"))
+        .push_function(
+            Function::new("get")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_arg("key", "&str")
+                .with_ret("Option<Self::Item>"),
+        )
+        .push_function(
+            Function::new("len")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret("usize")
+                .with_line("0"),
+        );
+    r#trait
+}
+
+#[test]
+fn impl_stub() {
+    let r#impl = ImplStub::new(store(), "MemStore")
+        .with_type("Item", "String")
+        .with_const("NAME", "\"mem\"")
+        .generate()
+        .unwrap();
+
+    let mut scope = Scope::new();
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+impl Store for MemStore {
+    const NAME: &'static str = "mem";
+    type Item = String;
+    type Error = _;
+
+    fn get(&self, key: &str) -> Option<Self::Item> {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_stub_provided_and_validation() {
+    let r#impl = ImplStub::new(store(), "MemStore")
+        .with_const("NAME", "\"mem\"")
+        .with_body(["unimplemented!()"])
+        .with_provided(true)
+        .generate()
+        .unwrap();
+    let names: Vec<&str> = r#impl.functions().iter().map(|f| f.name()).collect();
+    assert_eq!(names, ["get", "len"]);
+    assert_eq!(r#impl.functions()[1].body().len(), 1);
+
+    let err = ImplStub::new(store(), "MemStore").generate().unwrap_err();
+    assert_eq!(err, ImplStubError::MissingConsts(vec!["NAME".to_string()]));
+
+    let err = ImplStub::new(store(), "MemStore")
+        .with_type("Missing", "()")
+        .generate()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unknown associated items: Missing");
+}
+
+#[test]
+fn impl_stub_emitted_as_macro_call() {
+    let r#trait = Trait::new("Handler")
+        .with_function(Function::new("handle").with_self_arg(SelfArg::WithSelfRef));
+    let stub = ImplStub::new(r#trait, "Foo");
+
+    let mut scope = Scope::new();
+    scope.push_item(Emission::Expanded.emit(&stub).unwrap());
+    scope.push_item(Emission::macro_call("impl_handler").emit(&stub).unwrap());
+
+    let expect = r#"
+impl Handler for Foo {
+    fn handle(&self) {
+        todo!()
+    }
+}
+
+impl_handler!(Foo);"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
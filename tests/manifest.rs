@@ -0,0 +1,142 @@
+use simple_codegen::*;
+
+#[test]
+fn manifest_render() {
+    let manifest = Manifest::new("widgets")
+        .with_version("0.2.0")
+        .with_edition(Edition::Rust2021)
+        .with_rust_version(RustVersion::new(1, 70))
+        .with_description("Generated \"widget\" bindings")
+        .with_license("MIT OR Apache-2.0")
+        .with_lib(CargoTarget::default().with_path("src/widgets.rs"))
+        .with_bin(CargoTarget::new("widgets-cli").with_path("src/main.rs"))
+        .with_feature("default", ["std"])
+        .with_feature("std", Vec::<String>::new())
+        .with_feature("serde", ["dep:serde"])
+        .with_dependency(Dependency::new("log", "0.4"))
+        .with_dependency(
+            Dependency::new("serde", "1.0")
+                .with_feature("derive")
+                .with_optional(true),
+        )
+        .with_dependency(Dependency::new("libm", "0.2").with_default_features(false))
+        .with_dependency(Dependency::new_path("widgets-sys", "../sys"))
+        .with_dev_dependency(Dependency::new("criterion", "0.5"));
+
+    let expect = r#"
+[package]
+name = "widgets"
+version = "0.2.0"
+edition = "2021"
+rust-version = "1.70"
+description = "Generated \"widget\" bindings"
+license = "MIT OR Apache-2.0"
+
+[lib]
+path = "src/widgets.rs"
+
+[[bin]]
+name = "widgets-cli"
+path = "src/main.rs"
+
+[features]
+default = ["std"]
+std = []
+serde = ["dep:serde"]
+
+[dependencies]
+log = "0.4"
+serde = { version = "1.0", features = ["derive"], optional = true }
+libm = { version = "0.2", default-features = false }
+widgets-sys = { path = "../sys" }
+
+[dev-dependencies]
+criterion = "0.5"
+"#;
+
+    assert_eq!(manifest.to_string(), &expect[1..]);
+}
+
+#[test]
+fn library_generates_manifest() {
+    let root =
+        std::env::temp_dir().join(format!("simple_codegen_manifest_{}", std::process::id()));
+    let mut library = Library::new(&root)
+        .with_edition(Edition::Rust2024)
+        .with_feature("std")
+        .with_manifest(Manifest::new("gen").with_feature("default", ["std"]));
+    library
+        .new_file("src/lib.rs")
+        .scope_mut()
+        .new_struct("Unit");
+
+    let expect = r#"
+[package]
+name = "gen"
+version = "0.1.0"
+edition = "2024"
+
+[features]
+default = ["std"]
+std = []
+"#;
+
+    let rendered = library.render();
+    assert_eq!(rendered[std::path::Path::new("Cargo.toml")], &expect[1..]);
+
+    let report = library.generate();
+    let written: Vec<_> = report
+        .written()
+        .iter()
+        .map(|path| path.to_str().unwrap())
+        .collect();
+    assert_eq!(written, ["src/lib.rs", "Cargo.toml"]);
+    let contents = std::fs::read_to_string(root.join("Cargo.toml")).expect("read");
+    assert_eq!(contents, &expect[1..]);
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn library_resolves_manifest_targets_and_optional_dependencies() {
+    let library = Library::new("gen")
+        .with_feature("serde")
+        .with_feature("std")
+        .with_bench(File::new("parse.rs"))
+        .with_example(File::new("basic.rs"))
+        .with_example(File::new("custom.rs"))
+        .with_manifest(
+            Manifest::new("gen")
+                .with_example(CargoTarget::new("custom").with_path("examples/custom.rs"))
+                .with_dependency(Dependency::new("serde", "1.0").with_optional(true)),
+        );
+
+    let expect = r#"
+[package]
+name = "gen"
+version = "0.1.0"
+
+[[example]]
+name = "custom"
+path = "examples/custom.rs"
+
+[[example]]
+name = "basic"
+
+[[bench]]
+name = "parse"
+harness = false
+
+[features]
+serde = ["dep:serde"]
+std = []
+
+[dependencies]
+serde = { version = "1.0", optional = true }
+"#;
+
+    assert_eq!(
+        library.resolved_manifest().unwrap().unwrap().to_string(),
+        &expect[1..]
+    );
+}
@@ -157,3 +157,85 @@ mod foo {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn mod_decls() {
+    let mut scope = Scope::new();
+    scope
+        .new_mod_decl("models")
+        .set_vis(Vis::Pub)
+        .set_doc("Generated models.");
+    scope
+        .new_mod_decl("imp")
+        .set_cfg(Cfg::feature("serde"))
+        .set_path("serde_impl.rs")
+        .push_attribute("allow(dead_code)");
+    scope.push_mod_decl(ModDecl::new("util").with_compact(true));
+
+    let expect = r#"
+/// Generated models.
+pub mod models;
+
+#[cfg(feature = "serde")]
+#[path = "serde_impl.rs"]
+#[allow(dead_code)]
+mod imp;
+mod util;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn module_path() {
+    let mut scope = Scope::new();
+    scope
+        .get_or_new_module_path(["api", "v1"])
+        .new_struct("User");
+    scope
+        .get_or_new_module("api")
+        .get_or_new_module_path(vec![String::from("v1"), String::from("admin")])
+        .new_struct("Role");
+
+    let expect = r#"
+mod api {
+    mod v1 {
+        struct User;
+
+        mod admin {
+            struct Role;
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_modules_from_package() {
+    let mut scope = Scope::new();
+    scope
+        .get_or_new_package("com.acme.billing.v1")
+        .new_struct("Invoice");
+    scope
+        .get_or_new_package("com.acme.Shipping.type")
+        .new_struct("Parcel");
+
+    let expect = r#"
+pub mod com {
+    pub mod acme {
+        pub mod billing {
+            pub mod v1 {
+                struct Invoice;
+            }
+        }
+
+        pub mod shipping {
+            pub mod r#type {
+                struct Parcel;
+            }
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
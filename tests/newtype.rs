@@ -0,0 +1,91 @@
+use simple_codegen::*;
+
+#[test]
+fn newtype_with_operators() {
+    let meters = Newtype::new("Meters", "f64")
+        .with_vis(Vis::Pub)
+        .with_derive("Copy")
+        .with_impl(NewtypeImpl::From)
+        .with_impl(NewtypeImpl::Display)
+        .with_impl(NewtypeImpl::Add);
+
+    let mut scope = Scope::new();
+    for item in meters.generate() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+#[derive(Clone, PartialEq, Debug, Copy)]
+pub struct Meters(f64);
+
+impl From<f64> for Meters {
+    fn from(value: f64) -> Self {
+        Self(value)
+    }
+}
+
+impl std::fmt::Display for Meters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::ops::Add for Meters {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn newtype_transparent_with_deref() {
+    let id = Newtype::new("UserId", "String")
+        .with_derives(["Clone", "Debug"])
+        .with_impl(NewtypeImpl::SerdeTransparent)
+        .with_impl(NewtypeImpl::Deref)
+        .with_impl(NewtypeImpl::Into);
+
+    let mut scope = Scope::new();
+    for item in id.generate() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+struct UserId(String);
+
+impl std::ops::Deref for UserId {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<UserId> for String {
+    fn from(value: UserId) -> Self {
+        value.0
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn newtype_default_derives() {
+    let mut scope = Scope::new();
+    for item in Newtype::new("Name", "String").generate() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+#[derive(Clone, PartialEq, Debug)]
+struct Name(String);"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,364 @@
+#![cfg(feature = "syn")]
+
+use simple_codegen::*;
+
+const SOURCE: &str = r#"
+use std::collections::{HashMap, BTreeMap as Map};
+
+/// A user.
+#[derive(Debug, Clone)]
+pub struct User<'a, T: Clone> {
+    /// The name.
+    pub name: &'a str,
+    #[serde(default)]
+    tags: Vec<T>,
+}
+
+pub enum Kind {
+    /// Unit.
+    A,
+    B(u32, String),
+}
+
+impl<'a, T: Clone> User<'a, T> {
+    pub fn name(&self) -> &str {
+        if self.name.is_empty() {
+            return "anonymous";
+        }
+        self.name
+    }
+}
+
+pub trait Named: Send {
+    fn name(&self) -> String;
+}
+
+const MAX: usize = 10;
+
+pub(crate) mod inner {
+    pub type Id = u64;
+}
+"#;
+
+#[test]
+fn scope_from_source() {
+    let scope = Scope::from_source(SOURCE).unwrap();
+
+    let expect = r#"
+use std::collections::{HashMap, BTreeMap as Map};
+
+/// A user.
+#[derive(Debug, Clone)]
+pub struct User<'a, T: Clone> {
+    /// The name.
+    pub name: &'a str,
+    #[serde(default)]
+    tags: Vec<T>,
+}
+
+pub enum Kind {
+    /// Unit.
+    A,
+    B(u32, String),
+}
+
+impl<'a, T: Clone> User<'a, T> {
+    pub fn name(&self) -> &str {
+        if self.name.is_empty() {
+            return "anonymous";
+        }
+        self.name
+    }
+}
+
+pub trait Named: Send {
+    fn name(&self) -> String;
+}
+
+const MAX: usize = 10;
+
+pub(crate) mod inner {
+    pub type Id = u64;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_from_source_read_modify_write() {
+    let mut scope = Scope::from_source("pub enum Kind {\n    A,\n}\n").unwrap();
+    scope.get_enum_mut("Kind").unwrap().push_variant("B");
+
+    assert_eq!(scope.to_string(), "pub enum Kind {\n    A,\n    B,\n}");
+}
+
+#[test]
+fn scope_from_source_error() {
+    assert!(Scope::from_source("struct {").is_err());
+}
+
+#[test]
+fn scope_from_source_typed_self() {
+    let source = "impl Fut {\n    fn poll(self: Pin<&mut Self>) {\n        todo!()\n    }\n}";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), source);
+}
+
+#[test]
+fn scope_from_source_mod_decl() {
+    let source = "/// Docs.\n#[cfg(test)]\n#[path = \"tests/mod.rs\"]\npub mod tests;";
+    let scope = Scope::from_source(source).unwrap();
+
+    let decl = scope.items()[0].as_mod_decl().unwrap();
+    assert_eq!(decl.path(), Some("tests/mod.rs"));
+    assert_eq!(decl.cfg().unwrap().to_string(), "test");
+    assert_eq!(scope.to_string(), source);
+
+    let source = "#[path = \"imp.rs\"]\n#[cfg(unix)]\n#[allow(dead_code)]\nmod imp;";
+    let scope = Scope::from_source(source).unwrap();
+
+    let decl = scope.items()[0].as_mod_decl().unwrap();
+    assert_eq!(decl.path(), Some("imp.rs"));
+    assert_eq!(decl.cfg(), None);
+    assert_eq!(scope.to_string(), source);
+
+    let source = "#[allow(dead_code)]\n#[path = \"imp.rs\"]\nmod imp;";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.items()[0].as_mod_decl().unwrap().path(), None);
+    assert_eq!(scope.to_string(), source);
+}
+
+#[test]
+fn scope_from_source_with_restricted_vis() {
+    let scope = Scope::from_source("pub(in crate::model) struct Foo;").unwrap();
+
+    assert_eq!(
+        scope.get_struct("Foo").unwrap().vis(),
+        &Vis::PubIn("crate::model".to_string())
+    );
+}
+
+#[test]
+fn scope_from_source_fn_qualifiers() {
+    let source = "pub const unsafe fn get(ptr: *const u8) -> u8 {\n    *ptr\n}";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), source);
+}
+
+#[test]
+fn scope_from_source_inner_attributes() {
+    let source = "//! Crate docs.\n#![allow(dead_code)]\n\nstruct Foo;";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), source);
+}
+
+#[test]
+fn scope_from_source_unsupported() {
+    let err = Scope::from_source("struct X;\nimpl !Send for X {}").unwrap_err();
+    assert_eq!(err.to_string(), "negative impls are not supported");
+
+    let err = Scope::from_source("enum E {\n    A = 1,\n}").unwrap_err();
+    assert_eq!(err.to_string(), "enum discriminants are not supported");
+
+    let err = Scope::from_source("trait T {\n    m!();\n}").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "trait items other than functions, types and consts are not supported"
+    );
+}
+
+#[test]
+fn scope_from_source_unsafe_impl() {
+    let source = "pub unsafe trait Zeroable {}\n\nstruct X;\n\nunsafe impl Send for X {}";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert!(scope.get_trait("Zeroable").unwrap().is_unsafe());
+    assert_eq!(
+        scope.to_string(),
+        "pub unsafe trait Zeroable {\n}\n\nstruct X;\n\nunsafe impl Send for X {\n}"
+    );
+}
+
+#[test]
+fn scope_from_source_consts() {
+    let source = r#"
+/// The longest accepted input.
+pub const MAX_LEN: usize = 1_000_000;
+
+static mut BUF: [u8; 4] = [0; 4];
+
+impl Limits {
+    pub(crate) const MIN_LEN: usize = 1;
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(
+        scope.items()[1].as_const().unwrap().kind(),
+        ConstKind::StaticMut
+    );
+    assert_eq!(scope.to_string(), &source[1..]);
+}
+
+#[test]
+fn scope_from_source_associated_items() {
+    let source = r#"
+trait Source {
+    const LEN: usize;
+    type Item: Clone;
+
+    fn next(&mut self) -> Self::Item;
+}
+
+impl Source for Bytes {
+    type Item = u8;
+
+    fn next(&mut self) -> Self::Item {
+        todo!()
+    }
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), &source[1..]);
+}
+
+#[test]
+fn scope_from_source_cfg_gated_modules() {
+    let source = "#[cfg(unix)]\nmod imp {}\n\n#[cfg(windows)]\nmod imp {}";
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.items().len(), 2);
+    assert!(scope.items().iter().all(|item| item.name() == Some("imp")));
+}
+
+#[test]
+fn scope_from_source_split_derives() {
+    let source = r#"
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+#[repr(C)]
+#[repr(align(8))]
+struct Foo {
+    a: u8,
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+
+    let expect = r#"
+#[derive(Debug, Clone, PartialEq)]
+#[repr(C, align(8))]
+struct Foo {
+    a: u8,
+}"#;
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_from_source_variant_named_fields() {
+    let source = r#"
+enum Event {
+    Click {
+        /// Horizontal position.
+        #[serde(rename = "posX")]
+        x: u32,
+    },
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), &source[1..]);
+}
+
+#[test]
+fn scope_from_source_generic_defaults_and_lifetime_bounds() {
+    let source = r#"
+struct Foo<'a, 'b, T: Clone = u32>
+where 'a: 'b,
+      T: 'a,
+{
+    a: &'a T,
+    b: &'b T,
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+
+    assert_eq!(scope.to_string(), &source[1..]);
+}
+
+#[test]
+fn scope_from_source_single_segment_imports() {
+    let scope = Scope::from_source("use foo;").unwrap();
+    assert_eq!(scope.to_string(), "use foo;\n");
+
+    let scope = Scope::from_source("use foo::{self};").unwrap();
+    assert_eq!(scope.to_string(), "use foo;\n");
+
+    let scope = Scope::from_source("use foo::bar::{self, Baz};").unwrap();
+    assert_eq!(scope.to_string(), "use foo::bar::{self, Baz};\n");
+}
+
+#[test]
+fn scope_from_source_empty_fields() {
+    let source = r#"
+struct Unit;
+
+struct Braced {}
+
+struct Paren();
+
+enum Shape {
+    Unit,
+    Braced {},
+    Paren(),
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+    assert_eq!(scope.to_string(), &source[1..]);
+}
+
+#[test]
+fn scope_from_source_use_paths() {
+    let scope = Scope::from_source("use ::serde::Serialize;").unwrap();
+    assert_eq!(scope.to_string(), "use ::serde::Serialize;\n");
+
+    let scope = Scope::from_source("use foo::bar::{self as baz};").unwrap();
+    assert_eq!(scope.to_string(), "use foo::bar as baz;\n");
+
+    let scope = Scope::from_source("use foo::{self};").unwrap();
+    assert_eq!(scope.imports()["foo"]["self"].line(), "foo");
+
+    let err = Scope::from_source("use ::serde;").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "single-segment imports starting with `::` are not supported"
+    );
+}
+
+#[test]
+fn scope_from_source_arg_attributes() {
+    let source = r#"
+fn open(#[cfg(unix)] fd: i32, path: &str) {
+    todo!()
+}"#;
+    let scope = Scope::from_source(source).unwrap();
+    assert_eq!(scope.to_string(), &source[1..]);
+
+    let err = Scope::from_source("impl Foo { fn f(#[allow(unused)] self) {} }").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "attributes on `self` arguments are not supported"
+    );
+}
+
+#[test]
+fn scope_from_source_multi_line_literals() {
+    let source = "fn f() -> &'static str {\n    \"a  \n  b\"\n}";
+    let err = Scope::from_source(source).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "literals spanning several lines in function bodies are not supported"
+    );
+
+    let source = "fn f() -> &'static str {\n    \"a  b\"\n}";
+    let scope = Scope::from_source(source).unwrap();
+    assert_eq!(scope.to_string(), source);
+}
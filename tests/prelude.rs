@@ -0,0 +1,66 @@
+use simple_codegen::*;
+
+fn api() -> Scope {
+    let mut scope = Scope::new();
+    scope.new_struct("Client").set_vis(Vis::Pub);
+    scope.new_struct("Internal");
+    scope.new_trait("Service").set_vis(Vis::Pub);
+
+    let models = scope.new_module("models").set_vis(Vis::Pub).scope_mut();
+    models.new_struct("User").set_vis(Vis::Pub);
+    models.new_struct("UserBuilder").set_vis(Vis::Pub);
+    models.new_enum("Role").set_vis(Vis::Pub);
+    models
+        .new_struct("Metrics")
+        .set_vis(Vis::Pub)
+        .push_attribute(Cfg::feature("metrics").to_attribute());
+    models
+        .new_module("mock")
+        .set_vis(Vis::Pub)
+        .push_attribute("cfg(test)")
+        .scope_mut()
+        .new_struct("MockUser")
+        .set_vis(Vis::Pub);
+    models
+        .new_module("detail")
+        .scope_mut()
+        .new_struct("Hidden")
+        .set_vis(Vis::Pub);
+
+    scope
+}
+
+#[test]
+fn prelude_reexports_public_types() {
+    let mut scope = api();
+    scope.push_prelude(&PreludeModule::new());
+
+    let mut prelude = Scope::new();
+    prelude.push_module(scope.get_module("prelude").unwrap().clone());
+    let expect = r#"
+pub mod prelude {
+    pub use super::{Client, Service};
+    pub use super::models::{User, UserBuilder, Role};
+}"#;
+
+    assert_eq!(prelude.to_string(), &expect[1..]);
+}
+
+#[test]
+fn prelude_include_and_exclude_patterns() {
+    let mut prelude = Scope::new();
+    prelude.push_module(
+        PreludeModule::new()
+            .with_name("exports")
+            .with_include("models::*")
+            .with_exclude("*Builder")
+            .generate(&api()),
+    );
+
+    let expect = r#"
+pub mod exports {
+    pub use super::models::{User, Role};
+}"#;
+
+    assert_eq!(prelude.to_string(), &expect[1..]);
+}
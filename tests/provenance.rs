@@ -0,0 +1,120 @@
+use simple_codegen::*;
+
+fn scope() -> Scope {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("User")
+        .set_provenance(Provenance::at("schema.json", 3))
+        .push_named_field(Field::new("id", "u64"));
+    scope
+        .new_module("admin")
+        .new_enum("Role")
+        .set_provenance("roles.json")
+        .push_variant(Variant::new("Owner"));
+    scope
+}
+
+#[test]
+fn provenance_comments() {
+    let scope = scope();
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_provenance_comments(true))
+        .unwrap();
+
+    let expect = r#"
+// generated from schema.json:3
+struct User {
+    id: u64,
+}
+
+mod admin {
+    // generated from roles.json
+    enum Role {
+        Owner,
+    }
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn file_source_map() {
+    let file = File::new("src/models.rs")
+        .with_scope(scope())
+        .with_content_hash(true);
+
+    let source_map = file.source_map();
+    let lines: Vec<_> = source_map.mappings().iter().map(|m| m.line()).collect();
+    assert_eq!(lines, [2, 7]);
+
+    assert_eq!(
+        source_map.to_json(),
+        concat!(
+            r#"{"file":"src/models.rs","mappings":["#,
+            r#"{"line":2,"kind":"struct","name":"User","source":"schema.json","source_line":3},"#,
+            r#"{"line":7,"kind":"enum","name":"Role","source":"roles.json"}]}"#,
+        )
+    );
+}
+
+#[test]
+fn nested_function_provenance() {
+    let mut scope = Scope::new();
+    scope.new_impl("User").push_function(
+        Function::new("from_row")
+            .with_provenance(Provenance::at("queries.sql", 12))
+            .with_line("todo!()"),
+    );
+    scope.new_trait("Store").push_function(
+        Function::new("load")
+            .with_provenance("store.json")
+            .with_ret("User"),
+    );
+
+    let mut ret = String::new();
+    let mut fmt = Formatter::new(&mut ret).with_provenance_comments(true);
+    scope.fmt(&mut fmt).unwrap();
+    let lines: Vec<_> = fmt
+        .source_map()
+        .mappings()
+        .iter()
+        .map(|m| (m.line(), m.kind().to_string()))
+        .collect();
+
+    let expect = r#"
+impl User {
+    // generated from queries.sql:12
+    fn from_row() {
+        todo!()
+    }
+}
+
+trait Store {
+    // generated from store.json
+    fn load() -> User;
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+    assert_eq!(lines, [(2, "fn".to_string()), (9, "fn".to_string())]);
+}
+
+#[test]
+fn library_source_maps() {
+    let mut library = Library::new("out");
+    library.registry_mut().register("Uuid", "uuid");
+    library
+        .new_file("src/models.rs")
+        .scope_mut()
+        .new_struct("User")
+        .set_provenance("schema.json")
+        .push_named_field(Field::new("id", "Uuid"));
+
+    let source_maps = library.source_maps();
+    let source_map = &source_maps[std::path::Path::new("src/models.rs")];
+    assert_eq!(source_map.file(), Some("src/models.rs"));
+    assert_eq!(source_map.mappings()[0].line(), 3);
+}
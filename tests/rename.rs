@@ -0,0 +1,163 @@
+use simple_codegen::*;
+
+fn sample() -> Scope {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("User")
+        .push_generic("T")
+        .push_bound(Bound::new("T", ["Store"]))
+        .push_named_field(Field::new("group", "Option<Group>"))
+        .push_named_field(Field::new("store", "T"));
+
+    scope.new_enum("Group").push_variant("Admin");
+    scope.new_trait("Store");
+
+    scope.new_impl("User").push_function(
+        Function::new("group")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_ret("&Group")
+            .with_line("todo!()"),
+    );
+
+    scope
+}
+
+#[test]
+fn rename_with_prefix() {
+    let mut scope = sample();
+    TypeRenamer::new()
+        .with_prefix("Api")
+        .with_name("Option", "Maybe")
+        .apply(&mut scope);
+
+    let expect = r#"
+struct ApiUser<T>
+where T: ApiStore,
+{
+    group: Maybe<ApiGroup>,
+    store: T,
+}
+
+enum ApiGroup {
+    Admin,
+}
+
+trait ApiStore {
+}
+
+impl ApiUser {
+    fn group(&self) -> &ApiGroup {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn rename_with_name_map() {
+    let mut scope = sample();
+    TypeRenamer::new()
+        .with_suffix("V2")
+        .with_name("Group", "Team")
+        .apply(&mut scope);
+
+    assert!(scope.get_struct("UserV2").is_some());
+    assert!(scope.get_enum("Team").is_some());
+    assert!(scope.get_trait("StoreV2").is_some());
+    assert!(scope.get_impl_for("UserV2").is_some());
+}
+
+#[test]
+fn rename_bounds_once() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("User")
+        .push_generic(GenericParameter::new("T").with_trait("Into<Group>"))
+        .push_bound(Bound::new("Vec<Group>", ["From<Team>"]))
+        .push_named_field(Field::new("groups", "Vec<Group>"));
+
+    TypeRenamer::new()
+        .with_name("Group", "Team")
+        .with_name("Team", "Squad")
+        .apply(&mut scope);
+
+    let expect = r#"
+struct User<T: Into<Team>>
+where Vec<Team>: From<Squad>,
+{
+    groups: Vec<Team>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn rename_keeps_foreign_paths() {
+    let mut scope = Scope::new();
+    scope.new_module("models").new_struct("Group");
+    scope
+        .new_struct("User")
+        .push_named_field(Field::new("group", "models::Group"))
+        .push_named_field(Field::new("local", "crate::models::Group"))
+        .push_named_field(Field::new("other", "other::Group"));
+
+    TypeRenamer::new().with_prefix("Api").apply(&mut scope);
+
+    let expect = r#"
+mod models {
+    struct ApiGroup;
+}
+
+struct ApiUser {
+    group: models::ApiGroup,
+    local: crate::models::ApiGroup,
+    other: other::Group,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn rename_associated_items_and_bodies() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo");
+    scope
+        .new_trait("Make")
+        .push_associated_type(AssociatedType::new_with_bounds("Output", ["Make"]));
+    scope
+        .new_impl("Foo")
+        .set_impl_trait("Make")
+        .push_associated_type(AssociatedType::new_with_concrete_ty("Output", "Foo"))
+        .push_associated_const(
+            AssociatedConst::new("DEFAULT", "Option<Foo>").with_concrete_value("None"),
+        )
+        .push_function(
+            Function::new("make")
+                .with_ret("Self::Output")
+                .with_line("let _ = other::Foo::new();")
+                .with_line("Foo::new()"),
+        );
+
+    TypeRenamer::new().with_prefix("Api").apply(&mut scope);
+
+    let expect = r#"
+struct ApiFoo;
+
+trait ApiMake {
+    type Output: ApiMake;
+}
+
+impl ApiMake for ApiFoo {
+    const DEFAULT: Option<ApiFoo> = None;
+    type Output = ApiFoo;
+
+    fn make() -> Self::Output {
+        let _ = other::Foo::new();
+        ApiFoo::new()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -47,6 +47,25 @@ use baz::Baz;
     assert_eq!(scope.to_string(), expect.trim_start());
 }
 
+#[test]
+fn scope_with_batch_imports() {
+    let mut scope = Scope::new();
+    scope.push_imports(
+        [
+            ("std::collections", vec!["HashMap", "HashSet"]),
+            ("std::fmt", vec!["Display"]),
+        ],
+        Vis::Pub,
+    );
+
+    let expect = r#"
+pub use std::collections::{HashMap, HashSet};
+pub use std::fmt::Display;
+"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
 #[test]
 fn scope_with_overlapping_import_paths() {
     let mut scope = Scope::new();
@@ -76,6 +95,24 @@ fn scope_with_repeated_new_module() {
     scope.to_string();
 }
 
+#[test]
+fn scope_with_repeated_try_new_module() {
+    let mut scope = Scope::new();
+    scope.try_new_module("foo").unwrap().new_struct("Foo");
+
+    let err = scope.try_new_module("foo").unwrap_err();
+    assert_eq!(
+        err,
+        DuplicateModuleError {
+            name: "foo".to_string()
+        }
+    );
+    assert_eq!(err.to_string(), "module `foo` is already defined");
+    assert!(scope.try_push_module(Module::new("foo")).is_err());
+    assert!(scope.try_push_module(Module::new("bar")).is_ok());
+    assert_eq!(scope.items().len(), 2);
+}
+
 #[test]
 fn scope_with_modules_and_one_line_break() {
     let mut scope = Scope::new();
@@ -165,3 +202,702 @@ struct Bar {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn scope_amend_pushed_items() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Foo");
+    scope.new_enum("Kind");
+    scope.new_impl("Foo");
+    scope.new_function("run").push_line("todo!()");
+
+    scope
+        .get_struct_mut("Foo")
+        .unwrap()
+        .push_named_field(Field::new("kind", "Kind"));
+    scope.get_enum_mut("Kind").unwrap().push_variant("A");
+    scope
+        .get_impl_for_mut("Foo")
+        .unwrap()
+        .push_function(Function::new("new").with_ret("Self").with_line("todo!()"));
+    scope.get_function_mut("run").unwrap().set_ret("usize");
+
+    assert!(scope.get_struct("Kind").is_none());
+    assert!(scope.get_trait("Foo").is_none());
+    assert!(scope.get_impl_for("Kind").is_none());
+
+    let expect = r#"
+struct Foo {
+    kind: Kind,
+}
+
+enum Kind {
+    A,
+}
+
+impl Foo {
+    fn new() -> Self {
+        todo!()
+    }
+}
+
+fn run() -> usize {
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_sort_items() {
+    let mut scope = Scope::new();
+
+    scope.push_import("std::fmt", "Display", Vis::Private);
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+    scope.push_import("std::collections", "HashMap", Vis::Private);
+    scope.new_function("run").push_line("todo!()");
+    scope.new_struct("Foo");
+    scope.new_impl("Bar");
+    scope.new_struct("Bar");
+    scope.new_module("util").new_struct("Z");
+    scope.get_module_mut("util").unwrap().new_struct("A");
+
+    scope.sort_items(ItemOrder::KindThenName).sort_imports();
+
+    let expect = r#"
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+mod util {
+    struct A;
+
+    struct Z;
+}
+
+struct Bar;
+
+struct Foo;
+
+impl Bar {
+}
+
+fn run() {
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.sort_items(ItemOrder::Name);
+    let names: Vec<_> = scope.items().iter().map(|item| item.name()).collect();
+    assert_eq!(
+        names,
+        [
+            Some("Bar"),
+            Some("Bar"),
+            Some("Foo"),
+            Some("run"),
+            Some("util")
+        ]
+    );
+
+    scope.sort_items_by(|a, b| b.name().cmp(&a.name()));
+    assert_eq!(scope.items()[0].name(), Some("util"));
+}
+
+#[test]
+fn scope_sort_items_keeps_comments() {
+    let mut scope = Scope::new();
+    scope.new_struct("Zed");
+    scope.push_comment("The first struct.");
+    scope.new_struct("Alpha");
+    scope.raw("// trailing");
+
+    scope.sort_items(ItemOrder::Name);
+
+    let expect = r#"
+// The first struct.
+struct Alpha;
+
+struct Zed;
+
+// trailing"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_serde_with() {
+    let mut scope = Scope::new();
+
+    let mut field = Field::new("at", "Timestamp");
+    scope.new_serde_with(&mut field, "timestamp_serde");
+    scope.new_struct("Event").push_named_field(field);
+
+    let expect = r#"
+mod timestamp_serde {
+    pub fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer,
+    {
+        todo!()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where D: serde::Deserializer<'de>,
+    {
+        todo!()
+    }
+}
+
+struct Event {
+    #[serde(with = "timestamp_serde")]
+    at: Timestamp,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_serde_with_optional_field() {
+    let mut scope = Scope::new();
+
+    let mut field = Field::new("at", "Timestamp").with_optional(true);
+    scope.new_serde_with(&mut field, "timestamp_serde");
+    scope.new_struct("Event").push_named_field(field);
+
+    let expect = r#"
+mod timestamp_serde {
+    pub fn serialize<S>(value: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+    where S: serde::Serializer,
+    {
+        todo!()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+    where D: serde::Deserializer<'de>,
+    {
+        todo!()
+    }
+}
+
+struct Event {
+    #[serde(with = "timestamp_serde")]
+    at: Option<Timestamp>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_remove_and_replace_items() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Foo");
+    scope.new_struct("Bar");
+    scope.new_enum("Kind").push_variant("A");
+    scope.new_function("run").push_line("todo!()");
+    scope.raw("// generated");
+
+    let old = scope.replace_struct(
+        "Foo",
+        Struct::new("Foo").with_named_field(Field::new("one", "usize")),
+    );
+    assert_eq!(old, Some(Struct::new("Foo")));
+    assert!(
+        scope
+            .replace_struct("Missing", Struct::new("Missing"))
+            .is_none()
+    );
+
+    let removed = scope.remove_item("run").unwrap();
+    assert_eq!(removed.as_function().map(|f| f.name()), Some("run"));
+    assert!(scope.remove_item("run").is_none());
+
+    scope.retain_items(|item| item.as_struct().is_none_or(|s| s.name() != "Bar"));
+
+    let expect = r#"
+struct Foo {
+    one: usize,
+}
+
+enum Kind {
+    A,
+}
+
+// generated"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_merge() {
+    let mut scope = Scope::new();
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+    scope.new_struct("Foo");
+    scope.new_module("models").new_struct("User");
+
+    let mut other = Scope::new();
+    other.push_import("std::fmt", "Debug", Vis::Private);
+    other.push_import("std::fmt", "Display", Vis::Private);
+    other.new_struct("Bar");
+    other.new_impl("Foo");
+    other.new_module("models").new_struct("Group");
+
+    scope.merge(other).unwrap();
+
+    let expect = r#"
+use std::fmt::{Debug, Display};
+
+struct Foo;
+
+mod models {
+    struct User;
+
+    struct Group;
+}
+
+struct Bar;
+
+impl Foo {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_merge_conflicts() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo");
+    scope.new_module("models").new_struct("User");
+
+    let mut other = Scope::new();
+    other
+        .new_module("models")
+        .new_struct("User")
+        .push_named_field(Field::new("id", "u64"));
+
+    let err = scope.clone().merge(other.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::Conflict {
+            kind: "struct",
+            name: "models::User".to_string(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "struct `models::User` is defined in both scopes"
+    );
+
+    let mut kept = scope.clone();
+    kept.merge_with(other.clone(), MergeStrategy::KeepExisting)
+        .unwrap();
+    assert_eq!(kept, scope);
+
+    scope.merge_with(other, MergeStrategy::Replace).unwrap();
+    let user = scope
+        .get_module("models")
+        .unwrap()
+        .get_struct("User")
+        .unwrap();
+    assert!(matches!(user.fields(), Fields::Named(_)));
+}
+
+#[test]
+fn scope_merge_namespaces() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo");
+    scope
+        .new_struct("Config")
+        .push_named_field(Field::new("path", "String"));
+
+    let mut other = Scope::new();
+    other.new_enum("Foo").push_variant("A");
+    let err = scope.clone().merge(other).unwrap_err();
+    assert_eq!(
+        err,
+        MergeError::Conflict {
+            kind: "enum",
+            name: "Foo".to_string(),
+        }
+    );
+
+    let mut other = Scope::new();
+    other.new_function("Foo");
+    let err = scope.clone().merge(other).unwrap_err();
+    assert_eq!(err.to_string(), "fn `Foo` is defined in both scopes");
+
+    let mut other = Scope::new();
+    other.new_function("Config");
+    scope.merge(other).unwrap();
+    assert!(scope.get_function("Config").is_some());
+}
+
+#[test]
+fn scope_merge_settings() {
+    let mut scope = Scope::new().with_attribute("allow(dead_code)");
+
+    let other = Scope::new()
+        .with_inner_doc("Generated models.")
+        .with_attribute("allow(dead_code)")
+        .with_attribute("deny(missing_docs)")
+        .with_default_error("Error")
+        .with_edition(Edition::Rust2018);
+    scope.merge(other).unwrap();
+
+    assert_eq!(scope.attributes().len(), 2);
+    assert_eq!(scope.default_error().unwrap().name(), "Error");
+    assert_eq!(scope.edition(), Some(Edition::Rust2018));
+    assert!(scope.inner_doc().is_some());
+}
+
+#[test]
+fn scope_replace_in_bodies() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("OldName")
+        .push_named_field(Field::new("old", "OldName"));
+    scope
+        .new_function("old")
+        .set_ret("OldName")
+        .push_line("let old = OldName::default();")
+        .push_block(Block::new().with_line("old.check(old)"));
+    scope
+        .new_module("nested")
+        .new_impl("OldName")
+        .push_function(Function::new("get").with_line("old"));
+    scope.raw("const OLD: &str = \"old\";");
+    scope.push_comment("the old name");
+    scope
+        .new_function("commented")
+        .push_comment("keep old")
+        .push_line("old");
+
+    assert_eq!(scope.replace_in_bodies("old", "new"), 6);
+
+    let expect = r#"
+struct OldName {
+    old: OldName,
+}
+
+fn old() -> OldName {
+    let new = OldName::default();
+    {
+        new.check(new)
+    }
+}
+
+mod nested {
+    impl OldName {
+        fn get() {
+            new
+        }
+    }
+}
+
+const OLD: &str = "new";
+
+// the old name
+fn commented() {
+    // keep old
+    new
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_write_to() {
+    let mut scope = Scope::new();
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+    scope
+        .new_module("inner")
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"));
+    scope.new_function("bar").push_line("let x = 1;");
+
+    let mut out = Vec::new();
+    scope.write_to(&mut out).unwrap();
+
+    assert_eq!(String::from_utf8(out).unwrap(), scope.to_string());
+}
+
+#[test]
+fn scope_insert_items() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo");
+    scope.new_struct("Bar");
+
+    scope
+        .insert_item_after("Foo", Impl::new("Foo"))
+        .unwrap()
+        .insert_item_before("Foo", Function::new("helper").with_line("()"))
+        .unwrap()
+        .insert_item(0, "// generated".to_string());
+
+    assert!(
+        scope
+            .insert_item_before("Missing", Struct::new("Baz"))
+            .is_none()
+    );
+
+    let names: Vec<_> = scope.items().iter().map(Item::name).collect();
+    assert_eq!(
+        names,
+        [None, Some("helper"), Some("Foo"), Some("Foo"), Some("Bar")]
+    );
+    assert_eq!(scope.item_index("Bar"), Some(4));
+}
+
+#[test]
+fn compact_items() {
+    let mut scope = Scope::new();
+    scope.new_type_alias("Id", "u64");
+    scope.new_type_alias("Name", "String").set_compact(true);
+    scope.new_type_alias("Email", "String").set_compact(true);
+    scope.push_const(ConstItem::new("MIN_LEN", "usize", "1"));
+    scope.push_const(ConstItem::new("MAX_LEN", "usize", "64").with_compact(true));
+    scope.new_struct("User");
+
+    let expect = r#"
+type Id = u64;
+type Name = String;
+type Email = String;
+
+const MIN_LEN: usize = 1;
+const MAX_LEN: usize = 64;
+
+struct User;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scopes_dedup_by_hash() {
+    use std::collections::HashSet;
+
+    let build = |imports: [(&str, &str); 2]| {
+        let mut scope = Scope::new();
+        for (path, ty) in imports {
+            scope.push_import(path, ty, Vis::Private);
+        }
+        scope
+            .new_struct("Foo")
+            .push_derive("Debug")
+            .push_named_field(Field::new("one", "usize"));
+        scope.new_trait("Bar").push_function(Function::new("bar"));
+        scope
+    };
+
+    let a = build([("std::fmt", "Debug"), ("std::rc", "Rc")]);
+    let b = build([("std::rc", "Rc"), ("std::fmt", "Debug")]);
+    assert_eq!(a, b);
+
+    let scopes: HashSet<Scope> = [a, b, Scope::new()].into_iter().collect();
+    assert_eq!(scopes.len(), 2);
+}
+
+#[test]
+fn scope_with_item_capacity() {
+    let mut scope = Scope::with_item_capacity(2);
+
+    scope
+        .new_struct("Foo")
+        .set_fields(Fields::with_capacity(2).with_named(Field::new("one", "usize")));
+    scope
+        .new_struct("Bar")
+        .set_fields(Fields::tuple_with_capacity(1));
+
+    let expect = r#"
+struct Foo {
+    one: usize,
+}
+
+struct Bar();"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_organize_impls() {
+    let mut scope = Scope::new();
+    scope.new_impl("Foo").set_impl_trait("Clone");
+    scope.new_impl("Bar");
+    scope.new_impl("Foo");
+    scope.new_struct("Foo");
+    scope.new_enum("Bar").push_variant("A");
+    scope.new_impl("External");
+
+    scope.organize();
+
+    let names: Vec<_> = scope
+        .items()
+        .iter()
+        .map(|item| match item.as_impl() {
+            Some(v) => match v.impl_trait() {
+                Some(t) => format!("impl {} for {}", t.name(), v.target().name()),
+                None => format!("impl {}", v.target().name()),
+            },
+            None => item.name().unwrap().to_string(),
+        })
+        .collect();
+
+    assert_eq!(
+        names,
+        [
+            "Foo",
+            "impl Foo",
+            "impl Clone for Foo",
+            "Bar",
+            "impl Bar",
+            "impl External",
+        ]
+    );
+}
+
+#[test]
+fn scope_with_blank_line_policy() {
+    let mut scope = Scope::new();
+    scope.push_import("crate::model", "User", Vis::Private);
+    scope.push_import("serde", "Serialize", Vis::Private);
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+    scope.raw("const A: u8 = 1;");
+    scope.raw("const B: u8 = 2;");
+    scope.new_function("a").push_line("A");
+    scope.new_function("b").push_line("B");
+
+    let blank_lines = BlankLines::new()
+        .with_between_kind(ItemKind::Raw, 0)
+        .with_between_kind(ItemKind::Function, 2)
+        .with_grouped_imports(true);
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_blank_lines(blank_lines))
+        .unwrap();
+
+    let expect = r#"
+use std::fmt::Debug;
+
+use serde::Serialize;
+
+use crate::model::User;
+
+const A: u8 = 1;
+const B: u8 = 2;
+
+fn a() {
+    A
+}
+
+
+fn b() {
+    B
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn scope_with_sorted_import_groups() {
+    let mut scope = Scope::new();
+    scope.push_import("super", "Config", Vis::Private);
+    scope.push_import("std::io", "Write", Vis::Private);
+    scope.push_import("serde", "Serialize", Vis::Private);
+    scope.push_import("std::fmt", "Display", Vis::Private);
+    scope.push_import("std::fmt", "self", Vis::Private);
+    scope.push_import("serde", "Deserialize", Vis::Private);
+    scope.push_import("crate::model", "User", Vis::Private);
+    scope.push_import("core::mem", "swap", Vis::Private);
+
+    assert_eq!(ImportGroup::of("alloc::vec"), ImportGroup::Std);
+    assert_eq!(ImportGroup::of("::indexmap"), ImportGroup::External);
+    assert_eq!(ImportGroup::of("self::inner"), ImportGroup::Crate);
+
+    let mut ret = String::new();
+    scope
+        .fmt(
+            &mut Formatter::new(&mut ret)
+                .with_blank_lines(BlankLines::new().with_grouped_imports(true)),
+        )
+        .unwrap();
+
+    let expect = r#"
+use core::mem::swap;
+use std::fmt::{self, Display};
+use std::io::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::User;
+use super::Config;
+
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn scope_check_diagnostics() {
+    let mut scope = Scope::new();
+    scope.new_struct("Config");
+    scope.new_enum("Config");
+    scope.new_function("Config").push_line("todo!()");
+    scope
+        .new_trait("Store")
+        .push_function(Function::new("get").with_vis(Vis::Pub));
+    let module = scope.new_module("codes");
+    module.new_enum("Empty").push_repr("u8");
+    module
+        .new_enum("Status")
+        .push_variant("Ok")
+        .push_variant("Ok");
+
+    let diagnostics = scope.check();
+    assert_eq!(
+        diagnostics,
+        vec![
+            Diagnostic::DuplicateName {
+                module: String::new(),
+                kind: "enum",
+                name: "Config".to_string(),
+            },
+            Diagnostic::DuplicateName {
+                module: String::new(),
+                kind: "fn",
+                name: "Config".to_string(),
+            },
+            Diagnostic::TraitFunctionWithVis {
+                module: String::new(),
+                trait_name: "Store".to_string(),
+                function: "get".to_string(),
+            },
+            Diagnostic::EmptyEnumWithRepr {
+                module: "codes".to_string(),
+                name: "Empty".to_string(),
+            },
+            Diagnostic::DuplicateVariant {
+                module: "codes".to_string(),
+                enum_name: "Status".to_string(),
+                variant: "Ok".to_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        diagnostics[4].to_string(),
+        "duplicate variant `Ok` in enum `Status` in module `codes`"
+    );
+    assert!(Scope::new().check().is_empty());
+}
+
+#[test]
+fn scope_imports_use_fnv_hasher() {
+    type Map<V> = indexmap::IndexMap<String, V, FnvBuildHasher>;
+
+    let mut scope = Scope::new();
+    scope.push_import("std::fmt", "Debug", Vis::Private);
+
+    let imports: &Map<Map<Import>> = scope.imports();
+    assert_eq!(imports["std::fmt"]["Debug"].line(), "std::fmt::Debug");
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "serde")]
+
+use simple_codegen::*;
+
+#[test]
+fn scope_json_roundtrip() {
+    let mut scope = Scope::new();
+
+    scope.push_import("std::collections", "HashMap", Vis::Private);
+    scope
+        .new_struct("Foo")
+        .push_derive("Debug")
+        .push_named_field(Field::new("map", "HashMap<String, u32>"));
+    scope.new_enum("Kind").push_variant("A");
+    scope
+        .new_impl("Foo")
+        .push_function(Function::new("new").with_ret("Self").with_line("todo!()"));
+    scope
+        .new_module("inner")
+        .set_vis(Vis::PubCrate)
+        .new_trait("Bar");
+    scope.raw("// end");
+
+    let json = serde_json::to_string(&scope).unwrap();
+    let loaded: Scope = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded, scope);
+    assert_eq!(loaded.to_string(), scope.to_string());
+}
+
+fn roundtrip<T>(value: &T)
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + core::fmt::Debug,
+{
+    let json = serde_json::to_string(value).unwrap();
+    let loaded: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(&loaded, value);
+}
+
+#[test]
+fn generators_json_roundtrip() {
+    let color = Enum::new("Color").with_variant("Red");
+
+    roundtrip(&TargetOsVariants::new().with_target("linux"));
+    roundtrip(&TypeRenamer::new().with_prefix("Api").with_name("A", "B"));
+    roundtrip(&Template::new(Struct::new("Foo")));
+    roundtrip(&Newtype::new("UserId", "u64").with_impl(NewtypeImpl::Deref));
+    roundtrip(&Bitflags::new("Flags", "u8").with_flag("A", "1"));
+    roundtrip(&ErrorEnum::new("Error").with_variant("Io", "io error"));
+    roundtrip(&ErrorVariant::new("Parse", "parse error"));
+    roundtrip(
+        &Typestate::new("Door")
+            .with_state("Open")
+            .with_transition(Transition::new("close", "Open", "Open")),
+    );
+    roundtrip(&EnumVisitor::new(color.clone()));
+    roundtrip(&ImplStub::new(Trait::new("Greet"), "Foo"));
+    roundtrip(&StructConversion::new(Struct::new("A"), Struct::new("B")));
+    roundtrip(&ErrorMapping::new(color, "Error").with_arm("Red", "Error::Red"));
+    roundtrip(&PreludeModule::new().with_include("*"));
+    roundtrip(&ItemOrder::KindThenName);
+    roundtrip(&MergeStrategy::Replace);
+    roundtrip(&Case::ScreamingSnake);
+}
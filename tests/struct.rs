@@ -244,3 +244,556 @@ struct Foo {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn struct_optimize_layout() {
+    let mut scope = Scope::new();
+    let reordered = scope
+        .new_struct("Packet")
+        .push_named_field(Field::new("flag", "bool"))
+        .push_named_field(Field::new("id", "u64"))
+        .push_named_field(Field::new("extra", "Extra"))
+        .push_named_field(Field::new("len", "u16"))
+        .optimize_layout(Type::primitive_size);
+
+    assert!(reordered);
+
+    let expect = r#"
+// Fields reordered by descending size to minimize padding.
+struct Packet {
+    id: u64,
+    len: u16,
+    flag: bool,
+    extra: Extra,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn struct_optimize_layout_skips_repr_c() {
+    let mut scope = Scope::new();
+    let reordered = scope
+        .new_struct("Packet")
+        .set_repr(Some(String::from("C")))
+        .push_named_field(Field::new("flag", "bool"))
+        .push_named_field(Field::new("id", "u64"))
+        .optimize_layout(Type::primitive_size);
+
+    assert!(!reordered);
+
+    let expect = r#"
+#[repr(C)]
+struct Packet {
+    flag: bool,
+    id: u64,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn struct_optimize_layout_skips_repr_attributes() {
+    for repr in [
+        "repr(C)",
+        "repr(packed)",
+        "repr(transparent)",
+        "repr(C, align(8))",
+    ] {
+        let mut r#struct = Struct::new("Packet")
+            .with_attribute(repr)
+            .with_named_field(Field::new("flag", "bool"))
+            .with_named_field(Field::new("id", "u64"));
+
+        assert!(!r#struct.optimize_layout(Type::primitive_size), "{}", repr);
+        assert_eq!(r#struct.fields().len(), 2);
+        let Fields::Named(fields) = r#struct.fields() else {
+            unreachable!();
+        };
+        assert_eq!(fields[0].name(), "flag");
+    }
+}
+
+#[test]
+fn struct_optimize_layout_keeps_comment() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Packet")
+        .set_comment("Wire format.")
+        .push_named_field(Field::new("flag", "bool"))
+        .push_named_field(Field::new("id", "u64"))
+        .optimize_layout(Type::primitive_size);
+
+    let expect = r#"
+// Wire format.
+// Fields reordered by descending size to minimize padding.
+struct Packet {
+    id: u64,
+    flag: bool,
+}"#;
+
+    assert_eq!(scope.to_string(), expect.trim_start());
+}
+
+#[test]
+fn struct_with_attached_tests() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"))
+        .push_test(Function::new("foo_default").with_line("let _ = Foo { one: 0 };"));
+
+    scope.new_enum("Kind").push_variant("A").push_test(
+        Function::new("kind_debug")
+            .with_attribute("test")
+            .with_line("assert_eq!(format!(\"{:?}\", Kind::A), \"A\");"),
+    );
+
+    let expect = r#"
+struct Foo {
+    one: usize,
+}
+
+enum Kind {
+    A,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foo_default() {
+        let _ = Foo { one: 0 };
+    }
+
+    #[test]
+    fn kind_debug() {
+        assert_eq!(format!("{:?}", Kind::A), "A");
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_attached_tests_and_tests_module() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .push_test(Function::new("foo_new").with_line("let _ = Foo;"));
+
+    scope
+        .new_module("tests")
+        .push_attribute(Cfg::raw("test"))
+        .push_function(
+            Function::new("existing")
+                .with_attribute("test")
+                .with_line("assert!(true);"),
+        );
+
+    let expect = r#"
+struct Foo;
+
+#[cfg(test)] 
+mod tests {
+    use super::*;
+
+    #[test]
+    fn existing() {
+        assert!(true);
+    }
+
+    #[test]
+    fn foo_new() {
+        let _ = Foo;
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_derive_enum() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Foo")
+        .push_derive(Derive::Debug)
+        .push_derive(Derive::Clone)
+        .push_derive(Derive::Serialize)
+        .push_derive(Derive::custom("Builder"))
+        .push_named_field(Field::new("one", "usize"));
+
+    let expect = r#"
+#[derive(Debug, Clone, serde::Serialize, Builder)]
+struct Foo {
+    one: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn derive_order() {
+    let derives = ["Hash", "Debug", "MyDerive", "Clone", "Debug"];
+
+    let mut scope = Scope::new();
+    scope.new_struct("Deduped").set_derives(derives);
+    scope
+        .new_struct("Canonical")
+        .set_derives(derives)
+        .set_derive_order(DeriveOrder::Canonical);
+    scope
+        .new_struct("Exact")
+        .set_derives(derives)
+        .set_derive_order(DeriveOrder::Exact);
+
+    let expect = r#"
+#[derive(Hash, Debug, MyDerive, Clone)]
+struct Deduped;
+
+#[derive(Debug, Clone, Hash, MyDerive)]
+struct Canonical;
+
+#[derive(Hash, Debug, MyDerive, Clone, Debug)]
+struct Exact;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn optional_fields() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Config")
+        .push_named_field(Field::new("name", "String"))
+        .push_named_field(Field::new("port", "u16").with_optional(true))
+        .push_named_field(Field::new("host", "Option<String>").with_optional(true));
+
+    let expect = r#"
+struct Config {
+    name: String,
+    port: Option<u16>,
+    host: Option<String>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_combined_repr() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Header")
+        .set_repr([Repr::C, Repr::Packed(Some(4))])
+        .push_named_field(Field::new("len", "u32"));
+    scope
+        .new_enum("Kind")
+        .push_repr(IntType::U8)
+        .push_variant(Variant::new("A"));
+
+    let expect = r#"
+#[repr(C, packed(4))]
+struct Header {
+    len: u32,
+}
+
+#[repr(u8)]
+enum Kind {
+    A,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    assert_eq!("align(8)".parse::<Repr>(), Ok(Repr::Align(8)));
+    assert_eq!(
+        Repr::validate(&[Repr::Transparent, Repr::C], "struct"),
+        Err(ReprError::Conflict(Repr::Transparent, Repr::C))
+    );
+    assert_eq!(
+        Repr::validate(&[Repr::Int(IntType::U8)], "struct"),
+        Err(ReprError::Unsupported(
+            Repr::Int(IntType::U8),
+            "struct".to_string()
+        ))
+    );
+}
+
+#[test]
+fn struct_with_invalid_repr() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo").push_repr("u8");
+    scope.new_struct("Bar").push_repr("simd");
+
+    let expect = r#"
+#[repr(u8)]
+struct Foo;
+
+#[repr(simd)]
+struct Bar;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(
+        scope.get_struct("Bar").unwrap().repr(),
+        &[Repr::Custom("simd".to_string())]
+    );
+    assert_eq!(
+        "simd".parse::<Repr>(),
+        Err(ReprError::Unknown("simd".to_string()))
+    );
+    assert_eq!(
+        scope.check(),
+        vec![Diagnostic::InvalidRepr {
+            module: String::new(),
+            name: "Foo".to_string(),
+            error: ReprError::Unsupported(Repr::Int(IntType::U8), "struct".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn tuple_struct_with_field_vis_and_annotations() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Wrapper")
+        .set_vis(Vis::Pub)
+        .push_tuple_field(TupleField::new("String").with_vis(Vis::Pub))
+        .push_tuple_field(
+            TupleField::new("u32")
+                .with_annotation("#[serde(default)]")
+                .with_annotation(SerdeAttr::new().with_default()),
+        );
+    scope
+        .new_struct("Documented")
+        .push_tuple_field(TupleField::new("u64").with_doc("The raw id"));
+
+    let expect = r#"
+pub struct Wrapper(pub String, #[serde(default)] u32);
+
+struct Documented(
+    /// The raw id
+    u64,
+);"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_emptied_fields() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Named")
+        .set_fields(Fields::Named(Vec::new()));
+    scope
+        .new_struct("Tuple")
+        .set_fields(Fields::Tuple(Vec::new()));
+    scope.new_struct("Next");
+
+    let expect = r#"
+struct Named {}
+
+struct Tuple();
+
+struct Next;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_phantom_data() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Handle")
+        .push_generic("'a")
+        .push_generic("T")
+        .push_generic("Id")
+        .push_named_field(Field::new("id", "Id"))
+        .insert_phantom_data("_marker");
+    scope
+        .new_struct("Meters")
+        .push_generic("Unit")
+        .push_tuple_field("f64")
+        .insert_phantom_data("_unit");
+    scope
+        .new_struct("Used")
+        .push_generic("T")
+        .push_named_field(Field::new("items", "Vec<T>"))
+        .insert_phantom_data("_marker");
+
+    let expect = r#"
+struct Handle<'a, T, Id> {
+    id: Id,
+    _marker: std::marker::PhantomData<(&'a (), T)>,
+}
+
+struct Meters<Unit>(f64, std::marker::PhantomData<Unit>);
+
+struct Used<T> {
+    items: Vec<T>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_phantom_data_of_bounded_generics() {
+    let mut scope = Scope::new();
+    let r#struct = scope
+        .new_struct("Cache")
+        .push_generic("K: Clone")
+        .push_generic("'a: 'static")
+        .push_generic("T: Clone")
+        .push_named_field(Field::new("keys", "Vec<K>"));
+    assert_eq!(r#struct.unused_generics(), ["'a", "T"]);
+    r#struct.insert_phantom_data("_marker");
+
+    let expect = r#"
+struct Cache<K: Clone, 'a: 'static, T: Clone> {
+    keys: Vec<K>,
+    _marker: std::marker::PhantomData<(&'a (), T)>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_redacted_debug() {
+    let mut credentials = Struct::new("Credentials");
+    credentials
+        .push_named_field(Field::new("user", "String"))
+        .push_named_field(Field::new("password", "String").with_sensitive(true));
+
+    let mut scope = Scope::new();
+    scope.push_impl(credentials.redacted_debug_impl());
+
+    let expect = r#"
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user", &self.user)
+            .field("password", &"***")
+            .finish()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn tuple_struct_with_redacted_debug() {
+    let token = Struct::new("Token")
+        .with_tuple_field("String")
+        .with_tuple_field(TupleField::new("String").with_sensitive(true));
+
+    let mut scope = Scope::new();
+    scope.push_impl(token.redacted_debug_impl());
+
+    let expect = r#"
+impl std::fmt::Debug for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Token")
+            .field(&self.0)
+            .field(&"***")
+            .finish()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn generic_struct_with_redacted_debug() {
+    let secret = Struct::new("Secret")
+        .with_generic("'a")
+        .with_generic("T: Clone")
+        .with_named_field(Field::new("id", "&'a str"))
+        .with_named_field(Field::new("value", "T").with_sensitive(true));
+
+    let mut scope = Scope::new();
+    scope.push_impl(secret.redacted_debug_impl());
+
+    let expect = r#"
+impl<'a, T: Clone> std::fmt::Debug for Secret<'a, T>
+where T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Secret")
+            .field("id", &self.id)
+            .field("value", &"***")
+            .finish()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_getter_trait() {
+    let mut plugin = Struct::new("Plugin");
+    plugin
+        .set_vis(Vis::Pub)
+        .push_named_field(Field::new("name", "String").with_doc("Name of the plugin."))
+        .push_named_field(Field::new("priority", "u32").with_optional(true));
+
+    let (r#trait, r#impl) = plugin.getter_trait("PluginInfo");
+
+    let mut scope = Scope::new();
+    scope.push_trait(r#trait);
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+pub trait PluginInfo {
+    /// Name of the plugin.
+    fn name(&self) -> &String;
+
+    fn priority(&self) -> &Option<u32>;
+}
+
+impl PluginInfo for Plugin {
+    fn name(&self) -> &String {
+        &self.name
+    }
+
+    fn priority(&self) -> &Option<u32> {
+        &self.priority
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn generic_tuple_struct_getter_trait() {
+    let wrapper = Struct::new("Wrapper")
+        .with_generic("T: Clone")
+        .with_tuple_field(TupleField::new("T").with_doc("The wrapped value."))
+        .with_tuple_field("usize");
+
+    let (r#trait, r#impl) = wrapper.getter_trait("Get");
+
+    let mut scope = Scope::new();
+    scope.push_trait(r#trait);
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+trait Get<T: Clone> {
+    /// The wrapped value.
+    fn _0(&self) -> &T;
+
+    fn _1(&self) -> &usize;
+}
+
+impl<T: Clone> Get<T> for Wrapper<T> {
+    fn _0(&self) -> &T {
+        &self.0
+    }
+
+    fn _1(&self) -> &usize {
+        &self.1
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,119 @@
+use simple_codegen::*;
+
+fn dto() -> Struct {
+    Struct::new("UserDto")
+        .with_named_field(Field::new("id", "u64"))
+        .with_named_field(Field::new("full_name", "String"))
+        .with_named_field(Field::new("age", "u32"))
+}
+
+fn user() -> Struct {
+    Struct::new("User")
+        .with_named_field(Field::new("id", "u64"))
+        .with_named_field(Field::new("name", "Name"))
+        .with_named_field(Field::new("age", "u8"))
+        .with_named_field(Field::new("active", "bool"))
+}
+
+#[test]
+fn struct_conversion_from() {
+    let r#impl = StructConversion::new(dto(), user())
+        .with_rename("name", "full_name")
+        .with_expr("active", "true")
+        .generate()
+        .unwrap();
+
+    let mut scope = Scope::new();
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+impl From<UserDto> for User {
+    fn from(value: UserDto) -> Self {
+        Self {
+            id: value.id,
+            name: value.full_name.into(),
+            age: value.age.into(),
+            active: true,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_conversion_try_from() {
+    let r#impl = StructConversion::new(dto(), user())
+        .with_rename("name", "full_name")
+        .with_expr("active", "true")
+        .with_error("ConversionError")
+        .generate()
+        .unwrap();
+
+    let mut scope = Scope::new();
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+impl TryFrom<UserDto> for User {
+    type Error = ConversionError;
+
+    fn try_from(value: UserDto) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            name: value.full_name.try_into()?,
+            age: value.age.try_into()?,
+            active: true,
+        })
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_conversion_validation() {
+    let err = StructConversion::new(dto(), user()).generate().unwrap_err();
+    assert_eq!(
+        err,
+        StructConversionError::Unmapped(vec!["name".to_string(), "active".to_string()])
+    );
+
+    let err = StructConversion::new(dto(), user())
+        .with_rename("name", "missing")
+        .with_expr("other", "1")
+        .generate()
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unknown mapped fields: name, other");
+
+    let tuple = Struct::new("Pair").with_tuple_field("u64");
+    let err = StructConversion::new(tuple, user()).generate().unwrap_err();
+    assert_eq!(err, StructConversionError::TupleFields("Pair".to_string()));
+    assert_eq!(
+        err.to_string(),
+        "struct conversions need named fields: Pair"
+    );
+}
+
+#[test]
+fn optional_target_fields() {
+    let source = Struct::new("Row").with_named_field(Field::new("id", "u64"));
+    let target = Struct::new("User")
+        .with_named_field(Field::new("id", "u64"))
+        .with_named_field(Field::new("email", "String").with_optional(true));
+
+    let r#impl = StructConversion::new(source, target).generate().unwrap();
+    let mut scope = Scope::new();
+    scope.push_impl(r#impl);
+
+    let expect = r#"
+impl From<Row> for User {
+    fn from(value: Row) -> Self {
+        Self {
+            id: value.id,
+            email: None,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,56 @@
+use simple_codegen::*;
+
+#[test]
+fn interner_shares_names() {
+    let mut interner = Interner::new();
+
+    let a = Field::new(interner.intern("id"), Type::new(interner.intern("u64")));
+    let b = Field::new(interner.intern("id"), Type::new(interner.intern("u64")));
+
+    assert_eq!(interner.len(), 2);
+    assert!(a.symbol().is_shared());
+    assert_eq!(a, b);
+    assert_eq!(a, Field::new("id", "u64"));
+    assert_eq!(a.ty().name(), "u64");
+}
+
+#[test]
+fn symbol_name_mut_copies_shared_name() {
+    let mut interner = Interner::new();
+    let mut ty = Type::new(interner.intern("Foo"));
+    let other = Type::new(interner.intern("Foo"));
+
+    ty.name_mut().push_str("Bar");
+
+    assert_eq!(ty.name(), "FooBar");
+    assert!(!ty.symbol().is_shared());
+    assert_eq!(other.name(), "Foo");
+}
+
+#[test]
+fn interner_shares_variant_names() {
+    let mut interner = Interner::new();
+    let mut scope = Scope::new();
+
+    scope
+        .new_enum("Color")
+        .push_variant(Variant::new(interner.intern("Red")))
+        .push_variant(
+            Variant::new(interner.intern("Rgb"))
+                .with_tuple_field(Type::new(interner.intern("u8")))
+                .with_tuple_field(Type::new(interner.intern("u8"))),
+        );
+
+    let color = scope.items()[0].as_enum().unwrap();
+    assert!(color.variants()[0].symbol().is_shared());
+    assert_eq!(color.variants()[1].name(), "Rgb");
+    assert_eq!(interner.len(), 3);
+
+    let expect = r#"
+enum Color {
+    Red,
+    Rgb(u8, u8),
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,133 @@
+use simple_codegen::*;
+
+#[test]
+fn target_os_function() {
+    let function = Function::new("page_size")
+        .with_vis(Vis::Pub)
+        .with_ret("usize")
+        .with_line("4096");
+
+    let variants = TargetOsVariants::new()
+        .with_override(
+            "linux",
+            "page_size",
+            ["unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }"],
+        )
+        .with_target("macos")
+        .with_fallback(true);
+
+    let mut scope = Scope::new();
+    for function in variants.generate_function(&function) {
+        scope.push_function(function);
+    }
+
+    let expect = r#"
+#[cfg(target_os = "linux")]
+pub fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+#[cfg(target_os = "macos")]
+pub fn page_size() -> usize {
+    4096
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn page_size() -> usize {
+    4096
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn target_os_impl() {
+    let mut r#impl = Impl::new("Shim");
+    r#impl
+        .push_function(Function::new("open").with_line("todo!()"))
+        .push_function(Function::new("close").with_line("todo!()"));
+
+    let variants = TargetOsVariants::new()
+        .with_override("windows", "open", ["win::open()"])
+        .with_override("linux", "close", ["unix::close()"]);
+
+    let mut scope = Scope::new();
+    for r#impl in variants.generate_impl(&r#impl) {
+        scope.push_impl(r#impl);
+    }
+
+    let expect = r#"
+#[cfg(target_os = "windows")]
+impl Shim {
+    fn open() {
+        win::open()
+    }
+
+    fn close() {
+        todo!()
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Shim {
+    fn open() {
+        todo!()
+    }
+
+    fn close() {
+        unix::close()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "override of unknown function `missing`")]
+fn target_os_unknown_function() {
+    TargetOsVariants::new()
+        .with_override("linux", "missing", ["()"])
+        .generate_function(&Function::new("present"));
+}
+
+#[test]
+fn target_os_try_generate_unknown_function() {
+    let variants = TargetOsVariants::new().with_override("linux", "missing", ["()"]);
+
+    let err = variants
+        .try_generate_function(&Function::new("present"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        UnknownOverrideError {
+            function: "missing".to_string(),
+            os: "linux".to_string(),
+        }
+    );
+
+    let r#impl = Impl::new("Shim").with_function(Function::new("open").with_line("todo!()"));
+    let err = variants.try_generate_impl(&r#impl).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "override of unknown function `missing` for target OS `linux`"
+    );
+}
+
+#[test]
+fn target_os_keeps_existing_cfg() {
+    let function = Function::new("page_size")
+        .with_cfg(Cfg::feature("sys"))
+        .with_ret("usize")
+        .with_line("4096");
+
+    let variants = TargetOsVariants::new().with_target("linux");
+    let variants = variants.generate_function(&function);
+
+    assert_eq!(
+        variants[0].cfg(),
+        Some(&Cfg::all([
+            Cfg::raw("target_os = \"linux\""),
+            Cfg::feature("sys")
+        ]))
+    );
+}
@@ -0,0 +1,101 @@
+use simple_codegen::*;
+
+#[test]
+fn template_struct() {
+    let mut r#struct = Struct::new("Entity");
+    r#struct
+        .push_derive("Debug")
+        .push_named_field(Field::new("id", "EntityId"))
+        .push_named_field(Field::new("parent", "Option<Box<Entity>>"));
+    let template = Template::new(r#struct);
+
+    let mut scope = Scope::new();
+    for name in ["User", "Group"] {
+        scope.push_struct(template.instantiate([
+            ("Entity", name.to_string()),
+            ("EntityId", format!("{}Id", name)),
+        ]));
+    }
+
+    let expect = r#"
+#[derive(Debug)]
+struct User {
+    id: UserId,
+    parent: Option<Box<User>>,
+}
+
+#[derive(Debug)]
+struct Group {
+    id: GroupId,
+    parent: Option<Box<Group>>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(template.item().name(), "Entity");
+}
+
+#[test]
+fn template_impl() {
+    let mut r#impl = Impl::new("Entity");
+    r#impl.push_function(
+        Function::new("find_entity")
+            .with_arg("entity_id", "Key")
+            .with_ret("Option<Entity>")
+            .with_comment("Look up the Entity by id")
+            .with_line("self.entities.get(&entity_id).map(Entity::clone)"),
+    );
+    let template = Template::new(r#impl);
+
+    let mut scope = Scope::new();
+    scope.push_impl(template.instantiate([
+        ("Entity", "User"),
+        ("find_entity", "find_user"),
+        ("entity_id", "user_id"),
+    ]));
+
+    let expect = r#"
+impl User {
+    fn find_user(user_id: Key) -> Option<User> {
+        // Look up the Entity by id
+        self.entities.get(&user_id).map(User::clone)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_with_string_template() {
+    let mut scope = Scope::new();
+    scope.new_struct("Config");
+    scope
+        .push_template(
+            r#"impl {{ ty }} {
+    pub fn describe(&self) -> String {
+        format!("\{{}}: {}", "{{ty}}", {{version}})
+    }
+}"#,
+            [("ty", "Config"), ("version", "2"), ("unused", "")],
+        )
+        .unwrap();
+
+    let expect = r#"
+struct Config;
+
+impl Config {
+    pub fn describe(&self) -> String {
+        format!("{{}}: {}", "Config", 2)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    let err = Scope::new()
+        .push_template("type {{name}} = {{ty}};", [("name", "Id")])
+        .unwrap_err();
+    assert_eq!(err, TemplateError::UnknownPlaceholder("ty".to_string()));
+    let err = Scope::new()
+        .push_template("fn {{name() {}", [("name", "f")])
+        .unwrap_err();
+    assert_eq!(err.to_string(), "unclosed placeholder at offset 3");
+}
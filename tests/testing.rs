@@ -0,0 +1,61 @@
+use simple_codegen::testing::{compare, diff, normalize};
+use simple_codegen::*;
+
+#[test]
+fn normalize_whitespace() {
+    assert_eq!(
+        normalize("\n\nfn foo() {  \n    bar();\n}\n\n  \n"),
+        "fn foo() {\n    bar();\n}"
+    );
+}
+
+#[test]
+fn assert_code_eq_ignores_surrounding_whitespace() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Foo")
+        .push_named_field(Field::new("one", "usize"));
+
+    assert_code_eq!(
+        scope.to_string(),
+        r#"
+struct Foo {
+    one: usize,
+}
+"#
+    );
+}
+
+#[test]
+fn compare_reports_line_diff() {
+    let err = compare(
+        "struct Foo {\n    one: u32,\n}",
+        "struct Foo {\n    one: usize,\n}",
+    )
+    .unwrap_err();
+
+    assert_eq!(err, " struct Foo {\n-    one: usize,\n+    one: u32,\n }\n");
+    assert_eq!(diff("a\nb", "b\nc"), "-a\n b\n+c\n");
+    assert_eq!(diff("a\nb\nx", "c\nd\nx"), "-a\n-b\n+c\n+d\n x\n");
+}
+
+#[test]
+fn diff_large_input_with_one_changed_line() {
+    let old: Vec<String> = (0..100_000).map(|i| format!("line {}", i)).collect();
+    let mut new = old.clone();
+    new[50_000] = "changed".to_string();
+
+    let diff = diff(&old.join("\n"), &new.join("\n"));
+    let lines: Vec<&str> = diff.lines().collect();
+    assert_eq!(lines.len(), 100_001);
+    assert_eq!(
+        lines[49_999..50_003],
+        [" line 49999", "-line 50000", "+changed", " line 50001"]
+    );
+}
+
+#[test]
+#[should_panic(expected = "generated code does not match expected code")]
+fn assert_code_eq_panics_on_mismatch() {
+    assert_code_eq!("struct Foo;", "struct Bar;");
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "proc-macro2")]
+
+use proc_macro2::TokenStream;
+use simple_codegen::*;
+
+#[test]
+fn scope_to_token_stream() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .push_derive("Debug")
+        .push_named_field(Field::new("one", "Vec<usize>"));
+
+    let expect: TokenStream = "#[derive(Debug)] struct Foo { one: Vec<usize>, }"
+        .parse()
+        .unwrap();
+
+    assert_eq!(
+        scope.to_token_stream().unwrap().to_string(),
+        expect.to_string()
+    );
+    assert_eq!(
+        scope.items()[0].to_token_stream().unwrap().to_string(),
+        expect.to_string()
+    );
+}
+
+#[test]
+fn scope_to_token_stream_unbalanced() {
+    let mut scope = Scope::new();
+    scope.new_function("broken").push_line("if x {");
+    scope.raw("struct Raw(");
+
+    assert!(scope.to_token_stream().is_err());
+    assert!(scope.items()[0].to_token_stream().is_err());
+    assert!(scope.items()[1].to_token_stream().is_err());
+}
+
+#[test]
+fn push_tokens() {
+    let tokens: TokenStream = r#"
+        #[inline]
+        pub fn foo(x: &mut Vec<u32>) -> Option<u32> {
+            if x.is_empty() { return None; } else { x.push(1); }
+            let y = match x.len() { 1 => Some(1), _ => None };
+            y
+        }
+        impl Bar for Baz {}
+    "#
+    .parse()
+    .unwrap();
+
+    let mut scope = Scope::new();
+    scope.new_module("inner").scope_mut().push_tokens(tokens);
+
+    let expect = r#"
+mod inner {
+    #[inline]
+    pub fn foo(x: &mut Vec<u32>) -> Option<u32> {
+        if x.is_empty() {
+            return None;
+        } else {
+            x.push(1);
+        }
+        let y = match x.len() {
+            1 => Some(1),
+            _ => None
+        };
+        y
+    }
+    impl Bar for Baz {}
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn push_tokens_comparisons() {
+    let tokens: TokenStream = r#"
+        fn cmp<T: PartialOrd>(a: T, b: T, v: Vec<Vec<u8>>) -> bool {
+            let n = v.iter().count::<Vec<usize>>();
+            a > b || a < b || n >= 1 || n <= 2 || 1 << 2 > 8 >> 1 || v.len() > 0
+        }
+    "#
+    .parse()
+    .unwrap();
+
+    let mut scope = Scope::new();
+    scope.push_tokens(tokens);
+
+    let expect = r#"
+fn cmp<T: PartialOrd>(a: T, b: T, v: Vec<Vec<u8>>) -> bool {
+    let n = v.iter().count::<Vec<usize>>();
+    a > b || a < b || n >= 1 || n <= 2 || 1 << 2 > 8 >> 1 || v.len() > 0
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,124 @@
+use simple_codegen::*;
+
+#[test]
+fn old_rust_version_boxes_async_trait_methods() {
+    let mut scope = Scope::new().with_rust_version(RustVersion::new(1, 70));
+    scope.new_trait("Store").push_function(
+        Function::new("load")
+            .with_async(true)
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_ret("u64"),
+    );
+
+    let expect = r#"
+trait Store {
+    fn load(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = u64> + Send + '_>>;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn boxed_async_bodies_follow_rust_version() {
+    let mut scope = Scope::new().with_rust_version(RustVersion::new(1, 60));
+    scope
+        .new_impl("Cache")
+        .set_impl_trait("Store")
+        .push_function(
+            Function::new("load")
+                .with_async(true)
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret("u64")
+                .with_let_else("Some(v)", "self.value", "return 0")
+                .with_line("v"),
+        );
+
+    let expect = r#"
+impl Store for Cache {
+    fn load(&self) -> std::pin::Pin<Box<dyn std::future::Future<Output = u64> + Send + '_>> {
+        Box::pin(async move {
+            let v = match self.value {
+                Some(v) => v,
+                _ => return 0,
+            };
+            v
+        })
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn let_else_depends_on_rust_version() {
+    let func = Function::new("first")
+        .with_ret("Option<u32>")
+        .with_let_else("Some(x)", "map.get(&0)", "return None")
+        .with_line("Some(*x)");
+
+    let mut scope = Scope::new();
+    scope.push_function(func.clone());
+
+    let expect = r#"
+fn first() -> Option<u32> {
+    let Some(x) = map.get(&0) else {
+        return None;
+    };
+    Some(*x)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.set_rust_version(RustVersion::new(1, 60));
+
+    let expect = r#"
+fn first() -> Option<u32> {
+    let x = match map.get(&0) {
+        Some(x) => x,
+        _ => return None,
+    };
+    Some(*x)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn rust_2015_omits_dyn_before_1_27() {
+    let mut scope = Scope::new()
+        .with_edition(Edition::Rust2015)
+        .with_rust_version(RustVersion::new(1, 26));
+    scope.new_type_alias("Handler", Type::dyn_trait(["Fn()"]));
+
+    assert_eq!(scope.to_string(), "type Handler = Fn();");
+
+    scope.set_rust_version(None);
+
+    assert_eq!(scope.to_string(), "type Handler = dyn Fn();");
+}
+
+#[test]
+fn library_toolchain_applies_to_files() {
+    let mut lib = Library::new("out").with_rust_version(RustVersion::new(1, 60));
+    lib.new_file("src/lib.rs").scope_mut().push_function(
+        Function::new("get")
+            .with_ret("u32")
+            .with_let_else("Ok(v)", "parse()", "return 0")
+            .with_line("v"),
+    );
+
+    let expect = r#"
+fn get() -> u32 {
+    let v = match parse() {
+        Ok(v) => v,
+        _ => return 0,
+    };
+    v
+}
+"#;
+
+    assert_eq!(
+        lib.render()[std::path::Path::new("src/lib.rs")],
+        &expect[1..]
+    );
+}
@@ -24,6 +24,19 @@ pub trait MyTrait {
     assert_eq!(scope.to_string(), expect.trim_start());
 }
 
+#[test]
+fn trait_accessors_take_shared_references() {
+    let r#trait = Trait::new("MyTrait")
+        .with_vis(Vis::Pub)
+        .with_doc("Does things.")
+        .with_parent("Clone");
+
+    let r#trait = &r#trait;
+    assert_eq!(r#trait.vis(), &Vis::Pub);
+    assert_eq!(r#trait.doc().unwrap().as_inner(), "Does things.");
+    assert_eq!(r#trait.parents()[0].name(), "Clone");
+}
+
 #[test]
 fn trait_with_attributes() {
     let mut scope = Scope::new();
@@ -146,3 +159,141 @@ trait MyTrait {
 
     assert_eq!(scope.to_string(), expect.trim_start());
 }
+
+#[test]
+fn unsafe_and_auto_traits() {
+    let mut scope = Scope::new();
+    scope
+        .new_trait("RawBuffer")
+        .set_vis(Vis::Pub)
+        .set_unsafe(true)
+        .push_function(
+            Function::new("as_ptr")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret("*const u8"),
+        );
+    scope.new_trait("Marker").set_auto(true);
+    scope.push_impl(
+        Impl::new("Buffer")
+            .with_impl_trait("RawBuffer")
+            .with_unsafe(true)
+            .with_function(
+                Function::new("as_ptr")
+                    .with_self_arg(SelfArg::WithSelfRef)
+                    .with_ret("*const u8")
+                    .with_line("self.data.as_ptr()"),
+            ),
+    );
+
+    let expect = r#"
+pub unsafe trait RawBuffer {
+    fn as_ptr(&self) -> *const u8;
+}
+
+auto trait Marker {
+}
+
+unsafe impl RawBuffer for Buffer {
+    fn as_ptr(&self) -> *const u8 {
+        self.data.as_ptr()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_method_with_self_sized_bound() {
+    let mut scope = Scope::new();
+    scope
+        .new_trait("Shape")
+        .push_function(
+            Function::new("area")
+                .with_self_arg(SelfArg::WithSelfRef)
+                .with_ret("f64"),
+        )
+        .push_function(
+            Function::new("boxed")
+                .with_self_arg(SelfArg::WithSelf)
+                .with_ret("Box<dyn Shape>")
+                .with_bound(Bound::self_sized()),
+        );
+
+    let expect = r#"
+trait Shape {
+    fn area(&self) -> f64;
+
+    fn boxed(self) -> Box<dyn Shape>
+    where
+        Self: Sized;
+}"#;
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_where_style(WhereStyle::Multiline))
+        .unwrap();
+    assert_eq!(ret.trim_end(), &expect[1..]);
+}
+
+#[test]
+fn sealed_trait() {
+    let mut scope = Scope::new();
+    let mut backend = Trait::new("Backend").with_vis(Vis::Pub);
+    backend.seal(&mut scope, ["Memory", "Disk"]);
+    let mut cache = Trait::new("Cache").with_vis(Vis::Pub);
+    cache.seal(&mut scope, ["Memory"]);
+    scope.push_trait(backend).push_trait(cache);
+
+    let expect = r#"
+mod sealed {
+    use super::*;
+
+    pub trait Sealed {
+    }
+
+    impl Sealed for Memory {
+    }
+
+    impl Sealed for Disk {
+    }
+}
+
+pub trait Backend: sealed::Sealed {
+}
+
+pub trait Cache: sealed::Sealed {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_alias() {
+    let mut scope = Scope::new();
+    scope
+        .new_trait("Service")
+        .set_vis(Vis::Pub)
+        .push_generic("R")
+        .push_parent("Handler<R>")
+        .push_parent("Send")
+        .push_parent("'static")
+        .push_bound(Bound::new("R", ["Request"]))
+        .set_alias(true);
+
+    let expect = r#"
+pub trait Service<R> = Handler<R> + Send + 'static
+where R: Request;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_alias_rejects_items() {
+    let mut service = Trait::new("Service");
+    service.push_function(Function::new("call").with_self_arg(SelfArg::WithSelfRef));
+
+    let err = service.try_set_alias(true).unwrap_err();
+    assert_eq!(err.name(), "Service");
+    assert_eq!(err.to_string(), "trait alias `Service` cannot have items");
+    assert!(!service.is_alias());
+}
@@ -0,0 +1,162 @@
+use simple_codegen::*;
+
+fn render(ty: &Type) -> String {
+    let mut ret = String::new();
+    ty.fmt(&mut Formatter::new(&mut ret)).unwrap();
+    ret
+}
+
+#[test]
+fn structured_types() {
+    let bytes = Type::reference_mut(Type::slice("u8")).with_lifetime("a");
+    assert_eq!(render(&bytes), "&'a mut [u8]");
+
+    let iter = Type::impl_trait([Type::new("Iterator").with_generic("Item = T")]);
+    assert_eq!(render(&iter), "impl Iterator<Item = T>");
+
+    let callback = Type::reference(Type::dyn_trait(["Fn(A) -> B", "Send"]));
+    assert_eq!(render(&callback), "&(dyn Fn(A) -> B + Send)");
+
+    assert_eq!(render(&Type::tuple(["A", "B"])), "(A, B)");
+    assert_eq!(render(&Type::tuple(["A"])), "(A,)");
+    assert_eq!(render(&Type::array("u8", "4")), "[u8; 4]");
+    assert_eq!(render(&Type::fn_ptr(["i32"], "i32")), "fn(i32) -> i32");
+    assert_eq!(render(&Type::fn_ptr(Vec::<Type>::new(), "()")), "fn()");
+}
+
+#[test]
+fn structured_types_in_items() {
+    let mut scope = Scope::new();
+    scope
+        .new_function("parse")
+        .push_arg("input", Type::reference(Type::slice("u8")))
+        .set_ret(Type::tuple([
+            Type::new("Vec").with_generic("Token"),
+            Type::new("usize"),
+        ]))
+        .push_line("todo!()");
+    TypeRenamer::new()
+        .with_name("Token", "Lexeme")
+        .apply(&mut scope);
+
+    let expect = r#"
+fn parse(input: &[u8]) -> (Vec<Lexeme>, usize) {
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn path_types() {
+    let mut map = Type::path(["std", "collections", "HashMap"]).with_generics(["K", "V"]);
+    assert_eq!(map.segments(), ["std", "collections", "HashMap"]);
+    assert_eq!(map.turbofish(), "std::collections::HashMap::<K, V>");
+
+    assert!(!map.strip_prefix(["std", "fmt"]));
+    assert!(map.strip_prefix(["std", "collections"]));
+    assert_eq!(render(&map), "HashMap<K, V>");
+    assert_eq!(map.turbofish(), "HashMap::<K, V>");
+
+    let mut ty = Type::path(["crate"]);
+    ty.push_segment("models").push_segment("User");
+    assert_eq!(ty.name(), "crate::models::User");
+    assert!(!ty.strip_prefix(["crate", "models", "User"]));
+}
+
+#[test]
+fn wrapper_types() {
+    let user = Type::new("User");
+    assert_eq!(render(&Type::option(user.clone())), "Option<User>");
+    assert_eq!(
+        render(&Type::vec(Type::boxed("dyn Error"))),
+        "Vec<Box<dyn Error>>"
+    );
+    assert_eq!(
+        render(&Type::result(Type::arc(user), "Error")),
+        "Result<alloc::sync::Arc<User>, Error>"
+    );
+}
+
+#[test]
+fn wrapper_types_keep_inner_types() {
+    let ty = Type::result(Type::vec("User"), "Error");
+    let TypeKind::Applied(args) = ty.kind() else {
+        panic!("expected type arguments");
+    };
+    assert_eq!(args[0].kind(), &TypeKind::Applied(vec![Type::new("User")]));
+    assert_eq!(args[1].name(), "Error");
+    assert_eq!(ty.turbofish(), "Result::<Vec<User>, Error>");
+
+    let mut scope = Scope::new();
+    scope.new_struct("User");
+    scope
+        .new_struct("Team")
+        .push_named_field(Field::new("lead", Type::option(Type::arc("User"))));
+    TypeRenamer::new().with_prefix("Api").apply(&mut scope);
+
+    let expect = r#"
+struct ApiUser;
+
+struct ApiTeam {
+    lead: Option<alloc::sync::Arc<ApiUser>>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn type_wraps_long_generics() {
+    let mut scope = Scope::new();
+    scope.new_struct("Registry").push_named_field(Field::new(
+        "handlers",
+        Type::new("HashMap")
+            .with_generic("RequestDiscriminant")
+            .with_generic("Vec<BoxedRequestHandler>"),
+    ));
+    scope
+        .new_function("short")
+        .set_ret(Type::new("Option").with_generic("u8"))
+        .push_line("None");
+
+    let mut ret = String::new();
+    scope
+        .fmt(&mut Formatter::new(&mut ret).with_max_width(40))
+        .unwrap();
+
+    let expect = r#"
+struct Registry {
+    handlers: HashMap<
+        RequestDiscriminant,
+        Vec<BoxedRequestHandler>,
+    >,
+}
+
+fn short() -> Option<u8> {
+    None
+}
+"#;
+
+    assert_eq!(ret, &expect[1..]);
+}
+
+#[test]
+fn structured_type_names() {
+    assert_eq!(Type::reference("str").with_lifetime("a").name(), "&'a str");
+    assert_eq!(Type::tuple(["A", "B"]).name(), "(A, B)");
+    assert!(Type::slice("u8").segments().is_empty());
+
+    let mut scope = Scope::new();
+    scope
+        .new_impl(Type::reference("str").with_lifetime("a"))
+        .push_generic("'a")
+        .set_impl_trait("Parse");
+
+    assert!(scope.get_impl_for("&'a str").is_some());
+}
+
+#[test]
+#[should_panic(expected = "only path types have generics")]
+fn structured_type_rejects_generics() {
+    Type::reference("str").push_generic("T");
+}
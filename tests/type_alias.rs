@@ -10,3 +10,33 @@ fn type_alias_basic() {
 
     assert_eq!(scope.to_string(), expect);
 }
+
+#[test]
+fn type_alias_with_lifetime_and_bounds() {
+    let mut scope = Scope::new();
+    scope
+        .new_type_alias(
+            "BoxFuture",
+            Type::pin(Type::boxed(Type::dyn_trait([
+                "Future<Output = T>",
+                "Send",
+                "'a",
+            ]))),
+        )
+        .set_vis(Vis::Pub)
+        .push_generic("'a")
+        .push_generic("T");
+    scope
+        .new_type_alias("Shared", Type::arc("T"))
+        .push_generic("T")
+        .push_bound(Bound::new("T", ["Send", "Sync"]));
+
+    let expect = r#"
+pub type BoxFuture<'a, T> = core::pin::Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+type Shared<T>
+where T: Send + Sync,
+= alloc::sync::Arc<T>;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
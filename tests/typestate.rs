@@ -0,0 +1,120 @@
+use simple_codegen::*;
+
+#[test]
+fn typestate_machine() {
+    let machine = Typestate::new("Connection")
+        .with_vis(Vis::Pub)
+        .with_state("Closed")
+        .with_state("Open")
+        .with_field("addr", "String")
+        .with_transition(
+            Transition::new("open", "Closed", "Open").with_doc("Opens the connection."),
+        )
+        .with_transition(Transition::new("close", "Open", "Closed"));
+
+    let mut scope = Scope::new();
+    for item in machine.generate().unwrap() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+pub struct Closed;
+
+pub struct Open;
+
+pub struct Connection<S> {
+    addr: String,
+    state: core::marker::PhantomData<S>,
+}
+
+impl Connection<Closed> {
+    pub fn new(addr: String) -> Self {
+        Connection {
+            addr,
+            state: core::marker::PhantomData,
+        }
+    }
+
+    /// Opens the connection.
+    pub fn open(self) -> Connection<Open> {
+        Connection {
+            addr: self.addr,
+            state: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Connection<Open> {
+    pub fn close(self) -> Connection<Closed> {
+        Connection {
+            addr: self.addr,
+            state: core::marker::PhantomData,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn typestate_optional_field_constructor() {
+    let mut machine = Typestate::new("Job")
+        .with_state("Queued")
+        .with_field("retries", "u8");
+    machine.fields_mut()[0].set_optional(true);
+
+    let mut scope = Scope::new();
+    for item in machine.generate().unwrap() {
+        scope.push_item(item);
+    }
+
+    let expect = r#"
+struct Queued;
+
+struct Job<S> {
+    retries: Option<u8>,
+    state: core::marker::PhantomData<S>,
+}
+
+impl Job<Queued> {
+    fn new(retries: Option<u8>) -> Self {
+        Job {
+            retries,
+            state: core::marker::PhantomData,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn typestate_validation() {
+    let err = Typestate::new("Machine").generate().unwrap_err();
+    assert_eq!(err, TypestateError::NoStates);
+
+    let err = Typestate::new("Machine")
+        .with_state("Idle")
+        .with_field("state", "u8")
+        .generate()
+        .unwrap_err();
+    assert_eq!(err, TypestateError::StateField);
+
+    let err = Typestate::new("Machine")
+        .with_state("Idle")
+        .with_transition(Transition::new("start", "Idle", "Running"))
+        .generate()
+        .unwrap_err();
+    assert_eq!(err, TypestateError::UnknownState("Running".to_string()));
+
+    let err = Typestate::new("Machine")
+        .with_state("Idle")
+        .with_transition(Transition::new("reset", "Idle", "Idle"))
+        .with_transition(Transition::new("reset", "Idle", "Idle"))
+        .generate()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "duplicate transition `reset` from state Idle"
+    );
+}
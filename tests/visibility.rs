@@ -0,0 +1,50 @@
+use simple_codegen::*;
+
+#[test]
+fn vis_from_str() {
+    assert_eq!("".parse::<Vis>(), Ok(Vis::Private));
+    assert_eq!("pub".parse::<Vis>(), Ok(Vis::Pub));
+    assert_eq!("pub(crate)".parse::<Vis>(), Ok(Vis::PubCrate));
+    assert_eq!("pub( self )".parse::<Vis>(), Ok(Vis::PubSelf));
+    assert_eq!("pub(super)".parse::<Vis>(), Ok(Vis::PubSuper));
+    assert_eq!(
+        "pub(in crate::foo)".parse::<Vis>(),
+        Ok(Vis::PubIn("crate::foo".to_string()))
+    );
+    assert_eq!(
+        "public".parse::<Vis>(),
+        Err(VisError::Unknown("public".to_string()))
+    );
+    assert!("pub(in)".parse::<Vis>().is_err());
+}
+
+#[test]
+fn vis_round_trips_through_display() {
+    for s in [
+        "pub",
+        "pub(crate)",
+        "pub(self)",
+        "pub(super)",
+        "pub(in crate::foo)",
+    ] {
+        assert_eq!(Vis::from(s).to_string(), s);
+    }
+}
+
+#[test]
+fn struct_with_vis_from_str() {
+    let mut scope = Scope::new();
+    scope.new_struct("Foo").set_vis("pub(in crate::model)");
+
+    assert_eq!(scope.to_string(), "pub(in crate::model) struct Foo;");
+}
+
+#[test]
+fn vis_from_unknown_str_is_custom() {
+    assert_eq!(Vis::from("crate"), Vis::Custom("crate".to_string()));
+
+    let mut scope = Scope::new();
+    scope.new_struct("Foo").set_vis(String::from("crate"));
+
+    assert_eq!(scope.to_string(), "crate struct Foo;");
+}
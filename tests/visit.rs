@@ -0,0 +1,191 @@
+use simple_codegen::*;
+
+fn sample() -> Scope {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .push_named_field(Field::new("bar", "Bar"))
+        .push_named_field(Field::new("count", "usize"));
+
+    scope
+        .new_module("inner")
+        .new_enum("Bar")
+        .push_variant(Variant::new("Tuple").with_tuple_field("String"));
+
+    scope.new_impl("Foo").push_function(
+        Function::new("get")
+            .with_self_arg(SelfArg::WithSelfRef)
+            .with_arg("key", "u32")
+            .with_ret("Option<Bar>")
+            .with_line("None"),
+    );
+
+    scope
+}
+
+#[derive(Default)]
+struct Collector {
+    structs: Vec<String>,
+    types: Vec<String>,
+}
+
+impl Visitor for Collector {
+    fn visit_struct(&mut self, r#struct: &Struct) {
+        self.structs.push(r#struct.name().to_string());
+        walk_struct(self, r#struct);
+    }
+
+    fn visit_enum(&mut self, r#enum: &Enum) {
+        self.structs.push(r#enum.name().to_string());
+        walk_enum(self, r#enum);
+    }
+
+    fn visit_type(&mut self, ty: &Type) {
+        self.types.push(ty.name().to_string());
+    }
+}
+
+#[test]
+fn visitor_walks_item_tree() {
+    let mut collector = Collector::default();
+    collector.visit_scope(&sample());
+
+    assert_eq!(collector.structs, ["Foo", "Bar"]);
+    assert_eq!(
+        collector.types,
+        ["Bar", "usize", "String", "Foo", "u32", "Option<Bar>"]
+    );
+}
+
+#[test]
+fn visitor_walks_associated_items() {
+    let mut scope = Scope::new();
+    scope
+        .new_trait("Source")
+        .push_associated_const(AssociatedConst::new("LEN", "usize"))
+        .push_associated_type(AssociatedType::new("Item"));
+    scope
+        .new_impl("Bytes")
+        .set_impl_trait("Source")
+        .push_associated_const(AssociatedConst::new("LEN", "Len").with_concrete_value("Len(0)"))
+        .push_associated_type(AssociatedType::new_with_concrete_ty("Item", "Byte"));
+
+    let mut collector = Collector::default();
+    collector.visit_scope(&scope);
+
+    assert_eq!(collector.types, ["usize", "Bytes", "Source", "Len", "Byte"]);
+}
+
+#[derive(Default)]
+struct TypeNames(Vec<String>);
+
+impl Visitor for TypeNames {
+    fn visit_type(&mut self, ty: &Type) {
+        self.0.push(ty.name().to_string());
+        walk_type(self, ty);
+    }
+}
+
+#[test]
+fn visitor_walks_path_generics_and_bounds() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Wrapper")
+        .push_generic(GenericParameter::new("T").with_trait("Into<Inner>"))
+        .push_bound(Bound::new("T", ["Clone"]))
+        .push_named_field(Field::new("items", "Vec<Item, &'a str>"))
+        .push_named_field(Field::new("iter", "Box<dyn Iterator<Item = Entry>>"))
+        .push_named_field(Field::new("map", Type::new("Map").with_generic("Key")));
+
+    let mut names = TypeNames::default();
+    names.visit_scope(&scope);
+
+    assert_eq!(
+        names.0,
+        [
+            "Into<Inner>",
+            "Inner",
+            "T",
+            "Clone",
+            "Vec<Item, &'a str>",
+            "Item",
+            "&'a str",
+            "Box<dyn Iterator<Item = Entry>>",
+            "dyn Iterator<Item = Entry>",
+            "Entry",
+            "Map",
+            "Key",
+        ]
+    );
+}
+
+struct RenameItem;
+
+impl VisitorMut for RenameItem {
+    fn visit_type(&mut self, ty: &mut Type) {
+        if ty.name() == "Item" {
+            ty.set_name("Element");
+        }
+        walk_type_mut(self, ty);
+    }
+}
+
+#[test]
+fn visitor_mut_rewrites_path_generics_and_bounds() {
+    let mut scope = Scope::new();
+    scope
+        .new_struct("Wrapper")
+        .push_bound(Bound::new("Vec<Item>", ["From<Item>"]))
+        .push_named_field(Field::new("items", "HashMap<Key, Vec<Item>>"));
+    RenameItem.visit_scope(&mut scope);
+
+    let expect = r#"
+struct Wrapper
+where Vec<Element>: From<Element>,
+{
+    items: HashMap<Key, Vec<Element>>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+struct DeriveDebug;
+
+impl VisitorMut for DeriveDebug {
+    fn visit_struct(&mut self, r#struct: &mut Struct) {
+        r#struct.push_derive("Debug");
+    }
+
+    fn visit_enum(&mut self, r#enum: &mut Enum) {
+        r#enum.push_derive("Debug");
+    }
+}
+
+#[test]
+fn visitor_mut_injects_attributes() {
+    let mut scope = sample();
+    DeriveDebug.visit_scope(&mut scope);
+
+    let expect = r#"
+#[derive(Debug)]
+struct Foo {
+    bar: Bar,
+    count: usize,
+}
+
+mod inner {
+    #[derive(Debug)]
+    enum Bar {
+        Tuple(String),
+    }
+}
+
+impl Foo {
+    fn get(&self, key: u32) -> Option<Bar> {
+        None
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}